@@ -0,0 +1,204 @@
+use actuate::{composer::Composer, prelude::*};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use std::{cell::RefCell, hint::black_box, rc::Rc};
+
+type Signals = Rc<RefCell<Vec<Signal<i32>>>>;
+
+/// Leaf composable with its own signal, registered in `signals` on its first composition.
+#[derive(Data)]
+struct Leaf {
+    signals: Signals,
+}
+
+impl Compose for Leaf {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let signal = use_signal(&cx, || 0);
+        use_ref(&cx, || cx.me().signals.borrow_mut().push(signal.clone()));
+
+        black_box(signal.get());
+    }
+}
+
+/// Chain of `depth` nested composables, ending with a [`Leaf`].
+#[derive(Data)]
+struct Deep {
+    depth: usize,
+    signals: Signals,
+}
+
+impl Compose for Deep {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let signals = cx.me().signals.clone();
+        if cx.me().depth == 0 {
+            (None, Some(Leaf { signals }))
+        } else {
+            let deep = Deep {
+                depth: cx.me().depth - 1,
+                signals,
+            };
+            (Some(DynCompose::new(deep)), None)
+        }
+    }
+}
+
+/// Composable with `width` sibling [`Leaf`]s.
+#[derive(Data)]
+struct Wide {
+    width: usize,
+    signals: Signals,
+}
+
+impl Compose for Wide {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        (0..cx.me().width)
+            .map(|_| Leaf {
+                signals: cx.me().signals.clone(),
+            })
+            .collect::<Vec<_>>()
+    }
+}
+
+/// Keyed row of a [`List`].
+#[derive(Data)]
+struct Row {
+    key: usize,
+}
+
+impl Compose for Row {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let key = use_ref(&cx, || cx.me().key);
+        black_box(*key);
+    }
+}
+
+/// List of keyed rows, in the order of `keys`.
+#[derive(Data)]
+struct List {
+    keys: Rc<RefCell<Vec<usize>>>,
+}
+
+impl Compose for List {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.set_changed();
+
+        cx.me()
+            .keys
+            .borrow()
+            .iter()
+            .map(|&key| DynCompose::keyed(key, Row { key }))
+            .collect::<Vec<_>>()
+    }
+}
+
+fn compose_deep(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compose_deep");
+    for depth in [10, 100, 1000] {
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &depth, |b, &depth| {
+            b.iter_batched(
+                || {
+                    Composer::new(Deep {
+                        depth,
+                        signals: Signals::default(),
+                    })
+                },
+                |mut composer| {
+                    composer.compose();
+                    composer
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn compose_wide(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compose_wide");
+    for width in [10, 100, 1000] {
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |b, &width| {
+            b.iter_batched(
+                || {
+                    Composer::new(Wide {
+                        width,
+                        signals: Signals::default(),
+                    })
+                },
+                |mut composer| {
+                    composer.compose();
+                    composer
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+/// Update one leaf, and recompose.
+///
+/// Only the updated leaf runs its `compose` function,
+/// so this should stay flat as the tree grows (apart from visiting each container on the path).
+fn update_leaf(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_leaf");
+    for size in [10, 100, 1000] {
+        let signals = Signals::default();
+        let mut composer = Composer::new(Deep {
+            depth: size,
+            signals: signals.clone(),
+        });
+        composer.compose();
+
+        group.bench_with_input(BenchmarkId::new("deep", size), &size, |b, _| {
+            let signal = signals.borrow()[0].clone();
+            b.iter(|| {
+                signal.update(|x| *x += 1);
+                composer.compose();
+            })
+        });
+
+        let signals = Signals::default();
+        let mut composer = Composer::new(Wide {
+            width: size,
+            signals: signals.clone(),
+        });
+        composer.compose();
+
+        group.bench_with_input(BenchmarkId::new("wide", size), &size, |b, _| {
+            let signal = signals.borrow()[size / 2].clone();
+            b.iter(|| {
+                signal.update(|x| *x += 1);
+                composer.compose();
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Reverse a keyed list, and recompose.
+///
+/// Lists are composed by position, so each row with a changed key is reset.
+fn reorder_keyed_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reorder_keyed_list");
+    for len in [10, 100, 1000] {
+        let keys = Rc::new(RefCell::new((0..len).collect::<Vec<_>>()));
+        let mut composer = Composer::new(List { keys: keys.clone() });
+        composer.compose();
+
+        group.bench_with_input(BenchmarkId::from_parameter(len), &len, |b, _| {
+            b.iter(|| {
+                keys.borrow_mut().reverse();
+                composer.compose();
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    compose_deep,
+    compose_wide,
+    update_leaf,
+    reorder_keyed_list
+);
+criterion_main!(benches);
@@ -1,9 +1,6 @@
 use actuate::prelude::*;
 use taffy::{Size, Style};
-use vello::{
-    kurbo::{self, Affine},
-    peniko::Fill,
-};
+use vello::kurbo::{self, Affine};
 
 #[derive(Data)]
 struct Circle {
@@ -17,13 +14,11 @@ impl Compose for Circle {
                 size: Size::from_lengths(100., 100.),
                 ..Default::default()
             },
-            move |_layout, scene| {
-                scene.fill(
-                    Fill::NonZero,
+            move |_layout, renderer| {
+                renderer.fill(
                     Affine::IDENTITY,
-                    cx.me().color,
-                    None,
-                    &kurbo::Circle::new((50.0, 50.0), 50.0),
+                    cx.me().color.into(),
+                    kurbo::Circle::new((50.0, 50.0), 50.0).into(),
                 );
             },
         )
@@ -44,7 +39,8 @@ impl Compose for App {
                     color: Color::YELLOW,
                 },
             ),
-            background_color: Color::BLACK,
+            background_color: Some(Color::BLACK),
+            frame_rate: None,
         }
     }
 }
@@ -12,8 +12,9 @@ struct BreedList;
 
 impl Compose for BreedList {
     fn compose(cx: Scope<Self>) -> impl Compose {
-        let breeds = use_mut(&cx, Vec::new);
+        let breeds = use_signal(&cx, Vec::new);
 
+        let task_breeds = breeds.clone();
         use_task(&cx, move || async move {
             let json: Response = reqwest::get("https://dog.ceo/api/breeds/list/all")
                 .await
@@ -23,11 +24,11 @@ impl Compose for BreedList {
                 .unwrap();
 
             for (name, _) in json.message {
-                Mut::update(breeds, |breeds| breeds.push(name));
+                task_breeds.update(|breeds| breeds.push(name));
             }
         });
 
-        Window::new(compose::from_iter(breeds, |breed| Text::new(breed)))
+        Window::new(compose::from_iter(breeds.get(), Text::new))
     }
 }
 
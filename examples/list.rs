@@ -18,7 +18,7 @@ impl Compose for List {
             ))
             .color(Color::WHITE)
             .background_color(Color::BLACK),
-            compose::from_iter(items, |label| Text::new(label)),
+            compose::from_iter(items, Text::new),
         ))
         .font_size(40.)
     }
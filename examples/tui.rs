@@ -0,0 +1,28 @@
+use actuate::{prelude::*, tui};
+
+#[derive(Data)]
+struct Fruits;
+
+impl Compose for Fruits {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let selected = use_mut(&cx, || Some(0));
+        let fruits = ["Apples", "Oranges", "Pears"];
+
+        tui::Flex::column((
+            tui::Block::new(
+                tui::List::new(fruits)
+                    .with_selected(*selected)
+                    .on_select(move |idx| Mut::set(selected, Some(idx))),
+            )
+            .with_title("Fruits"),
+            tui::Text::new(match *selected {
+                Some(idx) => format!("Picked {}. Press Ctrl+C to quit.", fruits[idx]),
+                None => String::from("Press Ctrl+C to quit."),
+            }),
+        ))
+    }
+}
+
+fn main() {
+    tui::run(Fruits).unwrap();
+}
@@ -1,15 +1,23 @@
-use proc_macro::TokenStream;
+use proc_macro::{Span, TokenStream};
 use quote::{format_ident, quote, ToTokens};
 use syn::{
-    parse_macro_input, parse_quote, punctuated::Punctuated, token::Comma, Data, DeriveInput,
-    GenericParam,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    token::Comma,
+    Data, DeriveInput, Expr, Fields, FnArg, GenericParam, Ident, Index, ItemFn, Pat, Token,
 };
 
-#[proc_macro_derive(Data)]
+#[proc_macro_derive(Data, attributes(actuate))]
 pub fn data(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let ident = &input.ident;
 
+    let is_memo = match is_memo(&input) {
+        Ok(is_memo) => is_memo,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     let generics = &input.generics;
 
     let generic_params: Punctuated<_, Comma> = generics
@@ -19,15 +27,24 @@ pub fn data(input: TokenStream) -> TokenStream {
             GenericParam::Lifetime(lifetime_param) => lifetime_param.to_token_stream(),
             GenericParam::Type(type_param) => {
                 let ident = &type_param.ident;
+                let bounds = &type_param.bounds;
 
-                let mut bounds = type_param.bounds.clone();
-                bounds.push(parse_quote!(Data));
+                if bounds.is_empty() {
+                    ident.to_token_stream()
+                } else {
+                    quote! {
+                        #ident: #bounds
+                    }
+                }
+            }
+            GenericParam::Const(const_param) => {
+                let ident = &const_param.ident;
+                let ty = &const_param.ty;
 
                 quote! {
-                    #ident: #bounds
+                    const #ident: #ty
                 }
             }
-            GenericParam::Const(const_param) => const_param.to_token_stream(),
         })
         .collect();
 
@@ -37,31 +54,435 @@ pub fn data(input: TokenStream) -> TokenStream {
         .map(|param| match param {
             GenericParam::Lifetime(lifetime_param) => lifetime_param.to_token_stream(),
             GenericParam::Type(type_param) => type_param.ident.to_token_stream(),
-            GenericParam::Const(const_param) => const_param.to_token_stream(),
+            GenericParam::Const(const_param) => const_param.ident.to_token_stream(),
         })
         .collect();
 
-    let Data::Struct(input_struct) = input.data else {
-        todo!()
+    // Require each type parameter to be `Data`.
+    let mut where_clause = generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| parse_quote!(where));
+    for param in generics.type_params() {
+        let ident = &param.ident;
+        where_clause.predicates.push(parse_quote!(#ident: Data));
+    }
+
+    let checks: Vec<_> = match &input.data {
+        Data::Struct(input_struct) => input_struct
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let (field_name, field_member) = match &field.ident {
+                    Some(field_ident) => (field_ident.to_string(), field_ident.to_token_stream()),
+                    None => (idx.to_string(), Index::from(idx).to_token_stream()),
+                };
+
+                let check_ident = format_ident!("__check_{}_{}", ident, field_name);
+                quote! {
+                   #[doc(hidden)]
+                   #[allow(non_snake_case)]
+                   fn #check_ident <#generic_params> (t: #ident <#generic_ty_params>) #where_clause {
+                        (&&FieldWrap(t.#field_member)).check()
+                   }
+                }
+            })
+            .collect(),
+        Data::Enum(input_enum) => input_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let bindings: Vec<_> = (0..variant.fields.len())
+                    .map(|idx| format_ident!("__field_{}", idx))
+                    .collect();
+
+                let pattern = match &variant.fields {
+                    Fields::Named(fields) => {
+                        let field_idents = fields.named.iter().map(|field| &field.ident);
+                        quote!(#ident::#variant_ident { #( #field_idents: #bindings ),* })
+                    }
+                    Fields::Unnamed(_) => quote!(#ident::#variant_ident( #( #bindings ),* )),
+                    Fields::Unit => quote!(#ident::#variant_ident),
+                };
+
+                let check_ident = format_ident!("__check_{}_{}", ident, variant_ident);
+                quote! {
+                   #[doc(hidden)]
+                   #[allow(non_snake_case, irrefutable_let_patterns)]
+                   fn #check_ident <#generic_params> (t: #ident <#generic_ty_params>) #where_clause {
+                        if let #pattern = t {
+                            #( (&&FieldWrap(#bindings)).check(); )*
+                        }
+                   }
+                }
+            })
+            .collect(),
+        Data::Union(_) => {
+            return syn::Error::new_spanned(ident, "`Data` cannot be derived for unions")
+                .to_compile_error()
+                .into();
+        }
     };
 
-    let checks = input_struct.fields.iter().map(|field| {
-        let field_ident = field.ident.as_ref().unwrap();
-        let check_ident = format_ident!("__check_{}_{}", ident, field_ident);
-        quote! {
-           #[doc(hidden)]
-           #[allow(non_snake_case)]
-           fn #check_ident <#generic_params> (t: #ident <#generic_ty_params>) {
-                (&&FieldWrap(t.#field_ident)).check()
-           }
+    // Compare each field with `Memoize` for composables declared with `#[memo]`.
+    let memo_changed = match &input.data {
+        Data::Struct(input_struct) if is_memo => {
+            let memoized = input_struct.fields.iter().enumerate().rev().fold(
+                quote!(()),
+                |memoized, (idx, field)| {
+                    let member = match &field.ident {
+                        Some(field_ident) => field_ident.to_token_stream(),
+                        None => Index::from(idx).to_token_stream(),
+                    };
+
+                    quote! {
+                        (Memoize::memoized(::core::clone::Clone::clone(&self.#member)), #memoized)
+                    }
+                },
+            );
+
+            quote! {
+                fn memo_changed(
+                    &self,
+                    last: &mut MemoState,
+                ) -> bool {
+                    memo_changed(last, #memoized)
+                }
+            }
         }
-    });
+        _ => quote!(),
+    };
 
     let gen = quote! {
         #( #checks )*
 
         #[doc(hidden)]
-        unsafe impl <#generic_params> Data for #ident <#generic_ty_params> {}
+        unsafe impl <#generic_params> Data for #ident <#generic_ty_params> #where_clause {
+            #memo_changed
+        }
     };
     gen.into()
 }
+
+/// Returns `true` if `input` has the `#[actuate(memo)]` attribute added by `#[memo]`.
+fn is_memo(input: &DeriveInput) -> syn::Result<bool> {
+    let mut is_memo = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("actuate") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("memo") {
+                    is_memo = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported `actuate` attribute"))
+                }
+            })?;
+        }
+    }
+    Ok(is_memo)
+}
+
+/// Memoize a composable struct by its fields.
+///
+/// The composable is only re-composed from changes to its parent when any of its fields change,
+/// as if it were wrapped in `Memo::all` with each of its fields.
+/// Each field must implement `Clone` and `Memoize`.
+///
+/// This attribute must be placed before `#[derive(Data)]`.
+///
+/// ```ignore
+/// #[memo]
+/// #[derive(Data)]
+/// struct Label {
+///     text: String,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn memo(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    if !attrs.is_empty() {
+        return syn::Error::new(Span::call_site().into(), "`#[memo]` doesn't take arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut input = parse_macro_input!(input as DeriveInput);
+    if !matches!(input.data, Data::Struct(_)) {
+        return syn::Error::new_spanned(&input.ident, "`#[memo]` can only be used on structs")
+            .to_compile_error()
+            .into();
+    }
+
+    input.attrs.push(parse_quote!(#[actuate(memo)]));
+    input.to_token_stream().into()
+}
+
+/// Turn a function into a composable struct.
+///
+/// The first argument of the function is its `ScopeState`,
+/// and each other argument becomes a field of a struct named after the function in `UpperCamelCase`.
+/// The generated struct derives `Data` and implements `Compose` with the function's body,
+/// where each argument is a clone of its field.
+///
+/// ```ignore
+/// #[composable]
+/// fn counter(cx: ScopeState, start: i32) -> impl Compose {
+///     let count = use_mut(cx, || start);
+///     // ...
+/// }
+///
+/// // Compose with `Counter { start: 0 }`.
+/// ```
+#[proc_macro_attribute]
+pub fn composable(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    if !attrs.is_empty() {
+        return syn::Error::new(
+            Span::call_site().into(),
+            "`#[composable]` doesn't take arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let item = parse_macro_input!(input as ItemFn);
+    match composable_struct(item) {
+        Ok(tokens) => tokens,
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn composable_struct(item: ItemFn) -> syn::Result<TokenStream> {
+    let sig = &item.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`#[composable]` functions can't be async",
+        ));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    let cx_pat = match inputs.next() {
+        Some(FnArg::Typed(arg)) => &arg.pat,
+        Some(FnArg::Receiver(receiver)) => {
+            return Err(syn::Error::new_spanned(
+                receiver,
+                "`#[composable]` functions can't take `self`",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "`#[composable]` functions must take a `ScopeState` as their first argument",
+            ))
+        }
+    };
+
+    let mut field_idents = Vec::new();
+    let mut field_tys = Vec::new();
+    let mut bindings = Vec::new();
+    for arg in inputs {
+        let FnArg::Typed(arg) = arg else {
+            unreachable!()
+        };
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "`#[composable]` arguments must be identifiers",
+            ));
+        };
+
+        field_idents.push(&pat.ident);
+        field_tys.push(&arg.ty);
+        bindings.push(pat);
+    }
+
+    let vis = &item.vis;
+    let attrs = &item.attrs;
+    let block = &item.block;
+    let ident = format_ident!(
+        "{}",
+        upper_camel_case(&sig.ident.to_string()),
+        span = sig.ident.span()
+    );
+
+    let generics = &sig.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Require each type parameter to be `Data`, like the derived implementation of `Data`.
+    let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    for param in generics.type_params() {
+        let ident = &param.ident;
+        where_clause.predicates.push(parse_quote!(#ident: Data));
+    }
+
+    let def = if field_idents.is_empty() {
+        quote!(#vis struct #ident #generics #where_clause;)
+    } else {
+        quote! {
+            #vis struct #ident #generics #where_clause {
+                #( #vis #field_idents: #field_tys, )*
+            }
+        }
+    };
+
+    Ok(quote! {
+        #( #attrs )*
+        #[derive(Data)]
+        #def
+
+        impl #impl_generics Compose for #ident #ty_generics #where_clause {
+            fn compose(__scope: Scope<Self>) -> impl Compose {
+                #( let #bindings = ::core::clone::Clone::clone(&__scope.me().#field_idents); )*
+                let #cx_pat = __scope.state();
+                #block
+            }
+        }
+    }
+    .into())
+}
+
+/// Convert a `snake_case` identifier to `UpperCamelCase`.
+fn upper_camel_case(ident: &str) -> String {
+    ident
+        .trim_start_matches("r#")
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Binding to clone with `clone!` or `callback!`, optionally renamed with `as`.
+struct CloneBinding {
+    ident: Ident,
+    rename: Option<Ident>,
+}
+
+impl Parse for CloneBinding {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let rename = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self { ident, rename })
+    }
+}
+
+impl CloneBinding {
+    fn to_let(&self) -> impl ToTokens {
+        let ident = &self.ident;
+        let rename = self.rename.as_ref().unwrap_or(ident);
+        quote! {
+            let #rename = ::core::clone::Clone::clone(&#ident);
+        }
+    }
+}
+
+/// Parse bindings separated by commas, up to and including `=>`.
+fn parse_clone_bindings(input: ParseStream) -> syn::Result<Vec<CloneBinding>> {
+    let mut bindings = Vec::new();
+    while !input.peek(Token![=>]) {
+        bindings.push(input.parse()?);
+
+        if !input.peek(Token![=>]) {
+            input.parse::<Token![,]>()?;
+        }
+    }
+    input.parse::<Token![=>]>()?;
+
+    Ok(bindings)
+}
+
+struct CloneInput {
+    bindings: Vec<CloneBinding>,
+    body: Expr,
+}
+
+impl Parse for CloneInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            bindings: parse_clone_bindings(input)?,
+            body: input.parse()?,
+        })
+    }
+}
+
+/// Clone each binding before an expression, such as a `move` closure.
+///
+/// Each binding is cloned with its own name, or renamed with `as`.
+///
+/// ```ignore
+/// let on_click = clone!(count, name as label => move || println!("{label}: {count:?}"));
+/// ```
+#[proc_macro]
+pub fn clone(input: TokenStream) -> TokenStream {
+    let CloneInput { bindings, body } = parse_macro_input!(input as CloneInput);
+    let bindings = bindings.iter().map(CloneBinding::to_let);
+
+    quote! {
+        {
+            #( #bindings )*
+            #body
+        }
+    }
+    .into()
+}
+
+struct CallbackInput {
+    cx: Expr,
+    bindings: Vec<CloneBinding>,
+    body: Expr,
+}
+
+impl Parse for CallbackInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let cx = input.parse()?;
+        let bindings = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Vec::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            parse_clone_bindings(input)?
+        };
+
+        Ok(Self {
+            cx,
+            bindings,
+            body: input.parse()?,
+        })
+    }
+}
+
+/// Use a callback with `use_callback`, cloning each binding into it first.
+///
+/// Bindings are cloned like with `clone!`, so state handles can be moved into the callback.
+///
+/// ```ignore
+/// let on_submit = callback!(cx, signal, name as label => move |value: i32| {
+///     println!("{label}: {value}");
+///     signal.set(value);
+/// });
+/// ```
+#[proc_macro]
+pub fn callback(input: TokenStream) -> TokenStream {
+    let CallbackInput { cx, bindings, body } = parse_macro_input!(input as CallbackInput);
+    let bindings = bindings.iter().map(CloneBinding::to_let);
+
+    quote! {
+        {
+            #( #bindings )*
+            use_callback(#cx, #body)
+        }
+    }
+    .into()
+}
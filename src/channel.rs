@@ -0,0 +1,151 @@
+use crate::{use_local_task, use_mut, Mut, Ref, ScopeState};
+use futures_core::Stream;
+use std::{future::Future, pin::pin};
+use tokio::sync::{broadcast, mpsc, watch};
+
+/// Receiving half of a channel.
+///
+/// This can be used with [`use_channel`].
+pub trait Channel {
+    /// Type of items received from this channel.
+    type Item;
+
+    /// Receive the next item from this channel, or `None` once the channel is closed.
+    fn recv(&mut self) -> impl Future<Output = Option<Self::Item>>;
+}
+
+impl<T> Channel for mpsc::Receiver<T> {
+    type Item = T;
+
+    fn recv(&mut self) -> impl Future<Output = Option<Self::Item>> {
+        mpsc::Receiver::recv(self)
+    }
+}
+
+impl<T> Channel for mpsc::UnboundedReceiver<T> {
+    type Item = T;
+
+    fn recv(&mut self) -> impl Future<Output = Option<Self::Item>> {
+        mpsc::UnboundedReceiver::recv(self)
+    }
+}
+
+/// Items sent while this receiver lags behind the channel's capacity are skipped.
+impl<T: Clone> Channel for broadcast::Receiver<T> {
+    type Item = T;
+
+    async fn recv(&mut self) -> Option<Self::Item> {
+        loop {
+            match broadcast::Receiver::recv(self).await {
+                Ok(item) => break Some(item),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break None,
+            }
+        }
+    }
+}
+
+/// Each change to the watched value is received as a clone of the new value.
+impl<T: Clone> Channel for watch::Receiver<T> {
+    type Item = T;
+
+    async fn recv(&mut self) -> Option<Self::Item> {
+        self.changed().await.ok()?;
+        Some(self.borrow_and_update().clone())
+    }
+}
+
+/// Use the latest item received from a [`Channel`], or `None` if no item was received yet.
+///
+/// `make_receiver` will only be called once to create the receiver.
+/// Each received item is set as a state update on the composer's thread, triggering an update to this composable.
+/// The receiver is dropped with this composable, unsubscribing from the channel.
+pub fn use_channel<'a, C>(
+    cx: ScopeState<'a>,
+    make_receiver: impl FnOnce() -> C + 'a,
+) -> Ref<'a, Option<C::Item>>
+where
+    C: Channel + 'a,
+    C::Item: 'static,
+{
+    let item = use_mut(cx, || None);
+
+    use_local_task(cx, move || async move {
+        let mut receiver = make_receiver();
+        while let Some(next) = receiver.recv().await {
+            Mut::set(item, Some(next));
+        }
+    });
+
+    Mut::as_ref(item)
+}
+
+/// Use the latest item produced by a [`Stream`], or `None` if no item was produced yet.
+///
+/// `make_stream` will only be called once to create the stream.
+/// Each item is set as a state update on the composer's thread, triggering an update to this composable.
+/// The stream is dropped with this composable.
+pub fn use_stream<'a, S>(
+    cx: ScopeState<'a>,
+    make_stream: impl FnOnce() -> S + 'a,
+) -> Ref<'a, Option<S::Item>>
+where
+    S: Stream + 'a,
+    S::Item: 'static,
+{
+    let item = use_mut(cx, || None);
+
+    use_local_task(cx, move || async move {
+        let mut stream = pin!(make_stream());
+        while let Some(next) = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+            Mut::set(item, Some(next));
+        }
+    });
+
+    Mut::as_ref(item)
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn it_receives_channel_items() {
+        #[derive(Data)]
+        struct A {
+            rx: Rc<RefCell<Option<tokio::sync::mpsc::UnboundedReceiver<i32>>>>,
+            values: Rc<RefCell<Vec<Option<i32>>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let rx = cx.me().rx.clone();
+                let item = use_channel(&cx, move || rx.borrow_mut().take().unwrap());
+                cx.me().values.borrow_mut().push(*item);
+            }
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            rx: Rc::new(RefCell::new(Some(rx))),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [None]);
+
+        tx.send(1).unwrap();
+        composer.compose();
+        assert_eq!(*values.borrow(), [None, Some(1)]);
+
+        tx.send(2).unwrap();
+        tx.send(3).unwrap();
+        composer.compose();
+        assert_eq!(*values.borrow(), [None, Some(1), Some(3)]);
+
+        drop(composer);
+        assert!(tx.is_closed());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{use_ref, ScopeState};
+use std::{cell::RefCell, rc::Rc};
+use thiserror::Error;
+
+/// Content of the clipboard.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ClipboardContent {
+    /// Plain text.
+    Text(String),
+}
+
+impl From<String> for ClipboardContent {
+    fn from(value: String) -> Self {
+        ClipboardContent::Text(value)
+    }
+}
+
+impl From<&str> for ClipboardContent {
+    fn from(value: &str) -> Self {
+        ClipboardContent::Text(value.to_owned())
+    }
+}
+
+/// Error from a [`Clipboard`].
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    /// The clipboard doesn't contain content in the requested format.
+    #[error("Clipboard content not available in the requested format")]
+    ContentNotAvailable,
+    /// Error from the platform clipboard.
+    #[error(transparent)]
+    Platform(#[from] arboard::Error),
+}
+
+/// Handle to the platform clipboard.
+///
+/// This can be created with [`use_clipboard`].
+#[derive(Clone, Default)]
+pub struct Clipboard {
+    /// Platform clipboard, connected on first use.
+    inner: Rc<RefCell<Option<arboard::Clipboard>>>,
+}
+
+impl Clipboard {
+    fn with_inner<R>(
+        &self,
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<R, arboard::Error>,
+    ) -> Result<R, ClipboardError> {
+        let mut inner = self.inner.borrow_mut();
+        let inner = match &mut *inner {
+            Some(inner) => inner,
+            none => none.insert(arboard::Clipboard::new()?),
+        };
+
+        f(inner).map_err(|error| match error {
+            arboard::Error::ContentNotAvailable => ClipboardError::ContentNotAvailable,
+            error => ClipboardError::Platform(error),
+        })
+    }
+
+    /// Get the current content of the clipboard.
+    pub fn get(&self) -> Result<ClipboardContent, ClipboardError> {
+        self.get_text().map(ClipboardContent::Text)
+    }
+
+    /// Set the content of the clipboard.
+    pub fn set(&self, content: impl Into<ClipboardContent>) -> Result<(), ClipboardError> {
+        match content.into() {
+            ClipboardContent::Text(text) => self.set_text(text),
+        }
+    }
+
+    /// Get the current text content of the clipboard.
+    pub fn get_text(&self) -> Result<String, ClipboardError> {
+        self.with_inner(|inner| inner.get_text())
+    }
+
+    /// Set the text content of the clipboard.
+    pub fn set_text(&self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        let text = text.into();
+        self.with_inner(|inner| inner.set_text(text))
+    }
+}
+
+/// Use a handle to the platform clipboard.
+///
+/// The returned [`Clipboard`] can be cloned into event callbacks to copy and paste content.
+pub fn use_clipboard(cx: ScopeState<'_>) -> &Clipboard {
+    use_ref(cx, Clipboard::default)
+}
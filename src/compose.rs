@@ -1,10 +1,13 @@
-use crate::{prelude::*, Memoize, ScopeData};
-use std::{
+use crate::{composer, prelude::*, Memoize, ScopeData};
+use alloc::{borrow::Cow, boxed::Box, format, rc::Rc, vec::Vec};
+use core::{
     any::TypeId,
-    borrow::Cow,
     cell::{RefCell, UnsafeCell},
+    error::Error,
+    hash::{BuildHasher, Hash},
     mem,
 };
+use foldhash::fast::FixedState;
 
 /// A composable function.
 ///
@@ -31,7 +34,7 @@ pub trait Compose: Data {
 
     #[doc(hidden)]
     fn name() -> Option<Cow<'static, str>> {
-        let name = std::any::type_name::<Self>();
+        let name = core::any::type_name::<Self>();
         Some(
             name.split('<')
                 .next()
@@ -63,28 +66,176 @@ impl<C: Compose> Compose for Option<C> {
         cx.is_container.set(true);
 
         let state_cell: &RefCell<Option<ScopeData>> = use_ref(&cx, || RefCell::new(None));
-        let mut state_cell = state_cell.borrow_mut();
 
         if let Some(content) = &*cx.me() {
-            if let Some(state) = &*state_cell {
-                state.is_parent_changed.set(cx.is_parent_changed.get());
-                unsafe {
-                    content.any_compose(state);
-                }
-            } else {
-                let mut state = ScopeData::default();
-                state.contexts = cx.contexts.clone();
-                *state_cell = Some(state);
-                unsafe {
-                    content.any_compose(state_cell.as_ref().unwrap());
+            compose_optional(&cx, state_cell, content);
+        } else {
+            // Dropping the content's state runs its `use_drop` handlers.
+            // This state is dropped in place, as child scopes point to it.
+            *state_cell.borrow_mut() = None;
+        }
+    }
+}
+
+impl<C, E> Compose for Result<C, E>
+where
+    C: Compose,
+    E: Error + 'static,
+{
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let catch_cx = use_context::<CatchContext>(&cx);
+
+        let state_cell: &RefCell<Option<ScopeData>> = use_ref(&cx, || RefCell::new(None));
+
+        match &*cx.me() {
+            Ok(content) => compose_optional(&cx, state_cell, content),
+            Err(error) => {
+                *state_cell.borrow_mut() = None;
+
+                match catch_cx {
+                    Ok(catch_cx) => (catch_cx.f.borrow())(error),
+                    Err(_) => panic!("Uncaught error in composable: {}", error),
                 }
             }
-        } else {
-            *state_cell = None;
         }
     }
 }
 
+impl<C: Compose> Compose for Vec<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let states = use_ref(&cx, RefCell::default);
+        compose_slice(&cx, states, &cx.me());
+    }
+}
+
+impl<C: Compose, const N: usize> Compose for [C; N] {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let states = use_ref(&cx, RefCell::default);
+        compose_slice(&cx, states, &*cx.me());
+    }
+}
+
+/// Compose each item of `items` in the child scope at its index in `states`.
+///
+/// Scopes are created for new items, and dropped for removed items (running their `use_drop` handlers).
+fn compose_slice<C: Compose>(cx: ScopeState, states: &RefCell<Vec<Box<ScopeData>>>, items: &[C]) {
+    let mut states = states.borrow_mut();
+
+    // Each scope is boxed so it stays in place as the list grows, since child scopes point to it.
+    states.truncate(items.len());
+    while states.len() < items.len() {
+        states.push(Box::default());
+    }
+
+    for (item, state) in items.iter().zip(states.iter()) {
+        state.inherit_contexts(cx);
+
+        state.is_parent_changed.set(cx.is_parent_changed.get());
+
+        unsafe { item.any_compose(state) }
+    }
+}
+
+/// Compose `content` in the child scope stored in `state_cell`, creating it if it doesn't exist.
+fn compose_optional(
+    cx: ScopeState,
+    state_cell: &RefCell<Option<ScopeData>>,
+    content: &impl Compose,
+) {
+    let mut state_cell = state_cell.borrow_mut();
+    let state = state_cell.get_or_insert_with(ScopeData::default);
+
+    state.inherit_contexts(cx);
+
+    state.is_parent_changed.set(cx.is_parent_changed.get());
+
+    unsafe { content.any_compose(state) }
+}
+
+type CatchFn<'a> = Rc<dyn Fn(&dyn Error) + 'a>;
+
+#[derive(Clone)]
+struct CatchContext {
+    f: Rc<RefCell<CatchFn<'static>>>,
+}
+
+/// Create a composable that catches errors from its content.
+///
+/// Errors returned as `Err` from a [`Result`] composable are passed to `on_error`.
+pub fn catch<'a, C: Compose>(on_error: impl Fn(&dyn Error) + 'a, content: C) -> Catch<'a, C> {
+    Catch {
+        f: Rc::new(on_error),
+        content,
+    }
+}
+
+/// Error catching composable.
+///
+/// For more see [`catch`].
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Catch<'a, C> {
+    f: CatchFn<'a>,
+    content: C,
+}
+
+unsafe impl<C: Data> Data for Catch<'_, C> {}
+
+impl<C: Compose> Compose for Catch<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let f: CatchFn<'_> = cx.me().f.clone();
+
+        // Safety: `f` is only called by the content of this composable, which is dropped before `f`'s lifetime ends.
+        let f: CatchFn<'static> = unsafe { mem::transmute(f) };
+
+        let f_cell = use_ref(&cx, || Rc::new(RefCell::new(f.clone())));
+        *f_cell.borrow_mut() = f;
+
+        use_provider(&cx, || CatchContext { f: f_cell.clone() });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Composable that provides a context value of type `T` to its content.
+///
+/// The value is available to [`use_context`] in each child of this composable,
+/// and replacing it with a different value recomposes each child that used it (see [`use_memo_provider`]).
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct ContextProvider<T, C> {
+    context: T,
+    content: C,
+}
+
+impl<T, C> ContextProvider<T, C> {
+    /// Create a new composable that provides `value` to `content`.
+    pub fn new(value: T, content: C) -> Self {
+        Self {
+            context: value,
+            content,
+        }
+    }
+}
+
+unsafe impl<T: 'static, C: Data> Data for ContextProvider<T, C> {}
+
+impl<T, C> Compose for ContextProvider<T, C>
+where
+    T: Clone + PartialEq + 'static,
+    C: Compose,
+{
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        use_memo_provider(&cx, cx.me().context.clone(), || cx.me().context.clone());
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
 /// Create a composable from an iterator.
 pub fn from_iter<'a, I, C>(
     iter: I,
@@ -189,13 +340,7 @@ where
             let state: &ItemState<Item> =
                 unsafe { mem::transmute(state.boxed.as_deref().unwrap()) };
 
-            *state.scope.contexts.borrow_mut() = cx.contexts.borrow().clone();
-            state
-                .scope
-                .contexts
-                .borrow_mut()
-                .values
-                .extend(cx.child_contexts.borrow().values.clone());
+            state.scope.inherit_contexts(&cx);
 
             state
                 .scope
@@ -213,20 +358,59 @@ where
 /// The content of the memoized composable is only re-composed when the dependency changes.
 ///
 /// Children of this `Memo` may still be re-composed if their state has changed.
+///
+/// To memoize a composable by all of its fields, see [`memo`](crate::data::memo).
 #[derive(Data)]
 #[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
-pub struct Memo<T, C> {
+pub struct Memo<T, C>
+where
+    T: 'static,
+{
     dependency: T,
+    eq: fn(&T, &T) -> bool,
     content: C,
 }
 
-impl<T, C> Memo<T, C> {
+impl<T, C> Memo<T, C>
+where
+    T: 'static,
+{
     /// Create a new memoized composable.
     ///
     /// `content` is only re-composed when `dependency` is changed.
-    pub fn new(dependency: impl Memoize<Value = T>, content: C) -> Self {
+    pub fn new(dependency: impl Memoize<Value = T>, content: C) -> Self
+    where
+        T: PartialEq,
+    {
         Self {
             dependency: dependency.memoized(),
+            eq: T::eq,
+            content,
+        }
+    }
+
+    /// Create a new memoized composable from multiple dependencies.
+    ///
+    /// `content` is only re-composed when any of the `dependencies` are changed.
+    pub fn all<D>(dependencies: D, content: C) -> Self
+    where
+        D: Dependencies<Value = T>,
+        T: PartialEq,
+    {
+        Self {
+            dependency: dependencies.memoized(),
+            eq: T::eq,
+            content,
+        }
+    }
+
+    /// Create a new memoized composable with a custom comparison function.
+    ///
+    /// `content` is only re-composed when `eq` returns `false` for the last and current `dependency`.
+    pub fn by(eq: fn(&T, &T) -> bool, dependency: T, content: C) -> Self {
+        Self {
+            dependency,
+            eq,
             content,
         }
     }
@@ -234,23 +418,34 @@ impl<T, C> Memo<T, C> {
 
 impl<T, C> Compose for Memo<T, C>
 where
-    T: Clone + Data + PartialEq + 'static,
+    T: Clone + Data + 'static,
     C: Compose,
 {
     fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
         let last = use_ref(&cx, RefCell::default);
         let mut last = last.borrow_mut();
-        if let Some(last) = &mut *last {
-            if cx.me().dependency != *last {
+        let is_changed = if let Some(last) = &mut *last {
+            if !(cx.me().eq)(&cx.me().dependency, last) {
                 *last = cx.me().dependency.clone();
-                cx.is_parent_changed.set(true);
+                true
+            } else {
+                false
             }
         } else {
             *last = Some(cx.me().dependency.clone());
-            cx.is_parent_changed.set(true);
-        }
+            true
+        };
 
-        Ref::map(cx.me(), |me| &me.content)
+        let state = use_ref(&cx, ScopeData::default);
+
+        state.inherit_contexts(&cx);
+
+        // Only propagate parent changes when the dependency has changed.
+        state.is_parent_changed.set(is_changed);
+
+        unsafe { cx.me().content.any_compose(state) }
     }
 
     fn name() -> Option<Cow<'static, str>> {
@@ -262,10 +457,46 @@ where
     }
 }
 
+/// Multiple dependencies for [`Memo::all`].
+///
+/// This is implemented for tuples of values implementing [`Memoize`].
+pub trait Dependencies {
+    /// Inner values to store and compare.
+    type Value: PartialEq + 'static;
+
+    /// Return the inner values for memoization.
+    fn memoized(self) -> Self::Value;
+}
+
+macro_rules! impl_dependencies {
+    ($($t:tt : $idx:tt),*) => {
+        impl<$($t: Memoize),*> Dependencies for ($($t,)*) {
+            type Value = ($($t::Value,)*);
+
+            fn memoized(self) -> Self::Value {
+                ($(self.$idx.memoized(),)*)
+            }
+        }
+    };
+}
+
+impl_dependencies!(T1:0);
+impl_dependencies!(T1:0, T2:1);
+impl_dependencies!(T1:0, T2:1, T3:2);
+impl_dependencies!(T1:0, T2:1, T3:2, T4:3);
+impl_dependencies!(T1:0, T2:1, T3:2, T4:3, T5:4);
+impl_dependencies!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5);
+impl_dependencies!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6);
+impl_dependencies!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7);
+
 /// Dynamically-typed composable.
+///
+/// The content's state is preserved while its type (and key, if created with [`DynCompose::keyed`]) stays the same.
+/// Otherwise, the last content's state is dropped (running its [`use_drop`] handlers) before composing the new content.
 #[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
 pub struct DynCompose<'a> {
     compose: UnsafeCell<Option<Box<dyn AnyCompose + 'a>>>,
+    key: Option<u64>,
 }
 
 impl<'a> DynCompose<'a> {
@@ -273,6 +504,17 @@ impl<'a> DynCompose<'a> {
     pub fn new(content: impl Compose + 'a) -> Self {
         Self {
             compose: UnsafeCell::new(Some(Box::new(content))),
+            key: None,
+        }
+    }
+
+    /// Create a new dynamically-typed composable identified by `key`.
+    ///
+    /// Changing `key` resets the content's state, even if its type stays the same.
+    pub fn keyed(key: impl Hash, content: impl Compose + 'a) -> Self {
+        Self {
+            compose: UnsafeCell::new(Some(Box::new(content))),
+            key: Some(FixedState::default().hash_one(key)),
         }
     }
 }
@@ -280,51 +522,56 @@ impl<'a> DynCompose<'a> {
 struct DynComposeState {
     compose: Box<dyn AnyCompose>,
     data_id: TypeId,
+    key: Option<u64>,
 }
 
 impl Compose for DynCompose<'_> {
     fn compose(cx: Scope<Self>) -> impl Compose {
         cx.is_container.set(true);
 
+        // The content's scope is stored before its composable, so it's dropped first.
+        let state_cell: &RefCell<Option<ScopeData>> = use_ref(&cx, || RefCell::new(None));
+
         let cell: &UnsafeCell<Option<DynComposeState>> = use_ref(&cx, || UnsafeCell::new(None));
         let cell = unsafe { &mut *cell.get() };
 
         let inner = unsafe { &mut *cx.me().compose.get() };
 
-        let child_state = use_ref(&cx, ScopeData::default);
-
-        *child_state.contexts.borrow_mut() = cx.contexts.borrow().clone();
-        child_state
-            .contexts
-            .borrow_mut()
-            .values
-            .extend(cx.child_contexts.borrow().values.clone());
-
-        child_state
-            .is_parent_changed
-            .set(cx.is_parent_changed.get());
-
         if let Some(any_compose) = inner.take() {
             let mut compose: Box<dyn AnyCompose> = unsafe { mem::transmute(any_compose) };
+            let key = cx.me().key;
 
-            if let Some(state) = cell {
-                if state.data_id != compose.data_id() {
-                    todo!()
-                }
-
-                let ptr = (*state.compose).as_ptr_mut();
+            match cell {
+                Some(state) if state.data_id == compose.data_id() && state.key == key => {
+                    let ptr = (*state.compose).as_ptr_mut();
 
-                unsafe {
-                    compose.reborrow(ptr);
+                    unsafe {
+                        compose.reborrow(ptr);
+                    }
+                }
+                _ => {
+                    // Drop the last content's state in place (running its `use_drop` handlers),
+                    // and then the last content itself.
+                    *state_cell.borrow_mut() = None;
+
+                    *cell = Some(DynComposeState {
+                        data_id: compose.data_id(),
+                        key,
+                        compose,
+                    });
                 }
-            } else {
-                *cell = Some(DynComposeState {
-                    data_id: compose.data_id(),
-                    compose,
-                })
             }
         }
 
+        let mut state_cell = state_cell.borrow_mut();
+        let child_state = state_cell.get_or_insert_with(ScopeData::default);
+
+        child_state.inherit_contexts(&cx);
+
+        child_state
+            .is_parent_changed
+            .set(cx.is_parent_changed.get());
+
         unsafe { cell.as_mut().unwrap().compose.any_compose(child_state) }
     }
 }
@@ -342,12 +589,7 @@ macro_rules! impl_tuples {
                         ScopeData::default()
                     });
 
-                    *state.contexts.borrow_mut() = cx.contexts.borrow().clone();
-                    state
-                        .contexts
-                        .borrow_mut()
-                        .values
-                        .extend(cx.child_contexts.borrow().values.clone());
+                    state.inherit_contexts(&cx);
 
                     state.is_parent_changed.set(cx.is_parent_changed.get());
 
@@ -366,6 +608,14 @@ impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4);
 impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5);
 impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6);
 impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9, T11:10);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9, T11:10, T12:11);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9, T11:10, T12:11, T13:12);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9, T11:10, T12:11, T13:12, T14:13);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9, T11:10, T12:11, T13:12, T14:13, T15:14);
+impl_tuples!(T1:0, T2:1, T3:2, T4:3, T5:4, T6:5, T7:6, T8:7, T9:8, T10:9, T11:10, T12:11, T13:12, T14:13, T15:14, T16:15);
 
 pub(crate) trait AnyCompose {
     fn data_id(&self) -> TypeId;
@@ -391,16 +641,43 @@ where
     }
 
     unsafe fn reborrow(&mut self, ptr: *mut ()) {
-        std::ptr::swap(self, ptr as _);
+        core::ptr::swap(self, ptr as _);
     }
 
     unsafe fn any_compose(&self, state: &ScopeData) {
+        let _guard = composer::ScopeGuard::enter(state);
+
+        // Skip this scope if neither it, its ancestors, or its descendants have changed.
+        let is_child_changed = state.is_child_changed.take();
+        let is_initial = unsafe { (*state.hooks.get()).is_empty() };
+        let is_reloading = composer::is_reloading();
+        if !is_initial
+            && !is_reloading
+            && !is_child_changed
+            && !state.is_changed.get()
+            && !state.is_parent_changed.get()
+        {
+            return;
+        }
+
         // Reset the hook index.
         state.hook_idx.set(0);
 
         // Increment the scope's current generation.
         state.generation.set(state.generation.get() + 1);
 
+        // Span covering this composable and its content.
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+            "compose",
+            name = C::name().as_deref().unwrap_or("<anonymous>"),
+            generation = state.generation.get(),
+            is_changed = state.is_changed.get(),
+            is_parent_changed = state.is_parent_changed.get(),
+            is_child_changed,
+        )
+        .entered();
+
         // Transmute the lifetime of `&Self`, `&ScopeData`, and the `Scope` containing both to the same`'a`.
         // Safety: `self` and `state` are guranteed to have the same lifetime..
         let state: ScopeState = unsafe { mem::transmute(state) };
@@ -415,18 +692,32 @@ where
         // Scope for this composable's content.
         let child_state = use_ref(&cx, ScopeData::default);
 
+        // Ignore parent changes if this composable's memoized fields are unchanged.
+        if !Data::memo_changed(self, &mut cx.memoized.borrow_mut()) {
+            cx.is_parent_changed.set(false);
+        }
+
         if cell.is_none()
             || cx.is_changed.take()
             || cx.is_parent_changed.get()
             || cx.is_container.get()
+            || is_reloading
         {
+            composer::push_frame(C::name, cx.state);
             let child = C::compose(cx);
+            composer::pop_frame();
 
             cx.is_parent_changed.set(false);
             if cx.state.is_empty.take() {
                 return;
             }
 
+            #[cfg(feature = "devtools")]
+            {
+                cx.name.set(Some(C::name));
+                crate::devtools::emit(cx.state);
+            }
+
             #[cfg(feature = "tracing")]
             if !cx.is_container.get() {
                 if let Some(name) = C::name() {
@@ -434,12 +725,7 @@ where
                 }
             }
 
-            *child_state.contexts.borrow_mut() = cx.contexts.borrow().clone();
-            child_state
-                .contexts
-                .borrow_mut()
-                .values
-                .extend(cx.child_contexts.borrow().values.clone());
+            child_state.inherit_contexts(&cx);
 
             child_state.is_parent_changed.set(true);
 
@@ -460,3 +746,412 @@ where
         (*child).any_compose(child_state);
     }
 }
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{composer::Composer, prelude::*};
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    #[test]
+    fn it_skips_memoized_recomposes() {
+        #[derive(Data)]
+        struct B {
+            x: Rc<Cell<i32>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.me().x.set(cx.me().x.get() + 1);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+            y: Rc<Cell<i32>>,
+            z: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let count = use_mut(&cx, || 0);
+                let z = cx.me().z.get();
+
+                (
+                    Memo::all(
+                        (count, z),
+                        B {
+                            x: cx.me().x.clone(),
+                        },
+                    ),
+                    // Only compare the parity of `z`.
+                    Memo::by(
+                        |a, b| a % 2 == b % 2,
+                        z,
+                        B {
+                            x: cx.me().y.clone(),
+                        },
+                    ),
+                )
+            }
+        }
+
+        let x = Rc::new(Cell::new(0));
+        let y = Rc::new(Cell::new(0));
+        let z = Rc::new(Cell::new(0));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            y: y.clone(),
+            z: z.clone(),
+        });
+
+        composer.compose();
+        assert_eq!((x.get(), y.get()), (1, 1));
+
+        composer.compose();
+        assert_eq!((x.get(), y.get()), (1, 1));
+
+        z.set(2);
+        composer.compose();
+        assert_eq!((x.get(), y.get()), (2, 1));
+
+        z.set(3);
+        composer.compose();
+        assert_eq!((x.get(), y.get()), (3, 2));
+    }
+
+    #[test]
+    fn it_resets_dyn_compose_on_changed_type_or_key() {
+        #[derive(Data)]
+        struct B {
+            drops: Rc<Cell<i32>>,
+            states: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let state = use_ref(&cx, || Cell::new(0));
+                state.set(state.get() + 1);
+                cx.me().states.borrow_mut().push(state.get());
+
+                let drops = cx.me().drops.clone();
+                use_drop(&cx, move || drops.set(drops.get() + 1));
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            key: Rc<Cell<i32>>,
+            drops: Rc<Cell<i32>>,
+            states: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let content = B {
+                    drops: cx.me().drops.clone(),
+                    states: cx.me().states.clone(),
+                };
+
+                match cx.me().key.get() {
+                    // Different call sites with the same type and key share state.
+                    0 => DynCompose::keyed(0, content),
+                    1 => DynCompose::keyed(0, content),
+                    2 => DynCompose::keyed(2, content),
+                    _ => DynCompose::new(()),
+                }
+            }
+        }
+
+        let key = Rc::new(Cell::new(0));
+        let drops = Rc::new(Cell::new(0));
+        let states = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            key: key.clone(),
+            drops: drops.clone(),
+            states: states.clone(),
+        });
+
+        composer.compose();
+        key.set(1);
+        composer.compose();
+        assert_eq!(*states.borrow(), [1, 2]);
+        assert_eq!(drops.get(), 0);
+
+        key.set(2);
+        composer.compose();
+        assert_eq!(*states.borrow(), [1, 2, 1]);
+        assert_eq!(drops.get(), 1);
+
+        key.set(3);
+        composer.compose();
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn it_composes_vecs_with_positional_scopes() {
+        #[derive(Data)]
+        struct B {
+            x: i32,
+            counts: Rc<RefCell<Vec<(i32, i32)>>>,
+            drops: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let count = use_ref(&cx, || Cell::new(0));
+                count.set(count.get() + 1);
+                cx.me().counts.borrow_mut().push((cx.me().x, count.get()));
+
+                let x = cx.me().x;
+                let drops = cx.me().drops.clone();
+                use_drop(&cx, move || drops.borrow_mut().push(x));
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            len: Rc<Cell<i32>>,
+            counts: Rc<RefCell<Vec<(i32, i32)>>>,
+            drops: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                (0..cx.me().len.get())
+                    .map(|x| B {
+                        x,
+                        counts: cx.me().counts.clone(),
+                        drops: cx.me().drops.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            }
+        }
+
+        let len = Rc::new(Cell::new(2));
+        let counts = Rc::new(RefCell::new(Vec::new()));
+        let drops = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            len: len.clone(),
+            counts: counts.clone(),
+            drops: drops.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*counts.borrow(), [(0, 1), (1, 1)]);
+
+        len.set(3);
+        counts.borrow_mut().clear();
+        composer.compose();
+        assert_eq!(*counts.borrow(), [(0, 2), (1, 2), (2, 1)]);
+        assert!(drops.borrow().is_empty());
+
+        len.set(1);
+        counts.borrow_mut().clear();
+        composer.compose();
+        assert_eq!(*counts.borrow(), [(0, 3)]);
+        assert_eq!(*drops.borrow(), [1, 2]);
+    }
+
+    #[test]
+    fn it_composes_tuples_with_independent_scopes() {
+        #[derive(Data)]
+        struct B {
+            n: i32,
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let n = use_ref(&cx, || cx.me().n);
+                cx.me().values.borrow_mut().push(*n);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let b = |n| B {
+                    n,
+                    values: cx.me().values.clone(),
+                };
+
+                (
+                    b(0),
+                    b(1),
+                    b(2),
+                    b(3),
+                    b(4),
+                    b(5),
+                    b(6),
+                    b(7),
+                    b(8),
+                    b(9),
+                    b(10),
+                    b(11),
+                    b(12),
+                    b(13),
+                    b(14),
+                    b(15),
+                )
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*values.borrow(), (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn it_drops_optional_content() {
+        #[derive(Data)]
+        struct B {
+            is_dropped: Rc<Cell<bool>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let is_dropped = cx.me().is_dropped.clone();
+                use_drop(&cx, move || is_dropped.set(true));
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            is_shown: Rc<Cell<bool>>,
+            is_dropped: Rc<Cell<bool>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                if cx.me().is_shown.get() {
+                    Some(B {
+                        is_dropped: cx.me().is_dropped.clone(),
+                    })
+                } else {
+                    None
+                }
+            }
+        }
+
+        let is_shown = Rc::new(Cell::new(true));
+        let is_dropped = Rc::new(Cell::new(false));
+        let mut composer = Composer::new(A {
+            is_shown: is_shown.clone(),
+            is_dropped: is_dropped.clone(),
+        });
+
+        composer.compose();
+        assert!(!is_dropped.get());
+
+        is_shown.set(false);
+        composer.compose();
+        assert!(is_dropped.get());
+    }
+
+    #[test]
+    fn it_catches_errors() {
+        #[derive(Debug)]
+        struct Error;
+
+        impl std::fmt::Display for Error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("error")
+            }
+        }
+
+        impl std::error::Error for Error {}
+
+        #[derive(Data)]
+        struct A {
+            errors: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let errors = cx.me().errors.clone();
+                compose::catch(
+                    move |error| errors.borrow_mut().push(error.to_string()),
+                    Err::<(), _>(Error),
+                )
+            }
+        }
+
+        let errors = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            errors: errors.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*errors.borrow(), ["error"]);
+    }
+
+    #[test]
+    fn it_catches_panics() {
+        #[derive(Data)]
+        struct B {
+            is_failing: Rc<Cell<bool>>,
+            x: Rc<Cell<i32>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                if cx.me().is_failing.get() {
+                    panic!("failed");
+                }
+
+                cx.me().x.set(cx.me().x.get() + 1);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            is_failing: Rc<Cell<bool>>,
+            x: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                B {
+                    is_failing: cx.me().is_failing.clone(),
+                    x: cx.me().x.clone(),
+                }
+            }
+        }
+
+        let is_failing = Rc::new(Cell::new(true));
+        let x = Rc::new(Cell::new(0));
+        let mut composer = Composer::new(A {
+            is_failing: is_failing.clone(),
+            x: x.clone(),
+        });
+
+        let error = composer.try_compose().unwrap_err();
+        assert_eq!(error.name.as_deref(), Some("B"));
+        assert_eq!(error.message, "failed");
+        assert_eq!(x.get(), 0);
+
+        is_failing.set(false);
+        composer.try_compose().unwrap();
+        assert_eq!(x.get(), 1);
+    }
+}
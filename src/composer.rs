@@ -1,47 +1,261 @@
-use crate::{prelude::*, ScopeData};
+use crate::{
+    prelude::*,
+    sync::{self, Mutex, RwLock, RwLockWriteGuard},
+    ScopeData,
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+    sync::Arc,
+    task::Wake,
+    vec::Vec,
+};
 use compose::AnyCompose;
-use slotmap::{DefaultKey, SlotMap};
-use std::{
-    cell::RefCell,
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
     future::Future,
+    marker::PhantomData,
+    mem,
     pin::Pin,
-    rc::Rc,
-    sync::{mpsc, Arc},
-    task::{Context, Wake, Waker},
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Waker},
+};
+use hashbrown::HashSet;
+use slotmap::{DefaultKey, SlotMap};
+#[cfg(feature = "std")]
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
 };
-use tokio::sync::{RwLock, RwLockWriteGuard};
+#[cfg(feature = "std")]
+use thiserror::Error;
+
+#[cfg(not(feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(not(feature = "std"))))]
+pub use crate::sync::assume_single_threaded;
+
+/// Priority of an [`Update`].
+///
+/// Queued updates are applied in order of priority.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Updates from user input, applied before any other update.
+    Input,
+    /// Updates from animations.
+    Animation,
+    /// Default priority.
+    #[default]
+    Normal,
+    /// Low-priority updates, deferred until no other updates are queued.
+    Idle,
+}
 
-/// An update to apply to a composable.
+impl Priority {
+    const ALL: [Self; 4] = [
+        Priority::Input,
+        Priority::Animation,
+        Priority::Normal,
+        Priority::Idle,
+    ];
+}
+
+/// An update sent to the [`Updater`] of a composer when updates are queued.
+///
+/// This is applied on the composer's thread before it composes again,
+/// and can be sent to that thread (for example, with a user event on the system event loop).
 pub struct Update {
-    pub(crate) f: Box<dyn FnOnce()>,
+    pub(crate) f: Box<dyn FnOnce() + Send>,
+
+    pub(crate) priority: Priority,
 }
 
 impl Update {
+    pub(crate) fn new(f: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            f: Box::new(f),
+            priority: Priority::Normal,
+        }
+    }
+
+    /// Get the priority of this update.
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
     /// Apply this update.
+    pub fn apply(self) {
+        (self.f)();
+    }
+}
+
+/// Update queued for a composer, applied on its thread with the next flush.
+///
+/// Updates sent from other threads with an [`UpdateSender`] are queued with `F = dyn FnOnce() + Send`.
+struct QueuedUpdate<F: ?Sized = dyn FnOnce()> {
+    f: Box<F>,
+
+    /// ID of the scope this update belongs to, or `None` if it doesn't belong to a scope.
     ///
-    /// # Safety
-    /// The caller must ensure the composable triggering this update still exists.
-    pub unsafe fn apply(self) {
+    /// The update is skipped if this scope is dropped before it's applied.
+    scope: Option<u64>,
+
+    /// `true` if the scope of this update is marked as changed after it's applied.
+    is_changed: bool,
+
+    priority: Priority,
+}
+
+impl QueuedUpdate {
+    fn new(priority: Priority, f: impl FnOnce() + 'static) -> Self {
+        Self {
+            f: Box::new(f),
+            scope: None,
+            is_changed: false,
+            priority,
+        }
+    }
+
+    /// Apply this update, returning `false` if it was skipped because its scope was dropped.
+    fn try_apply(self, registry: &RefCell<ScopeRegistry>) -> bool {
+        if let Some(id) = self.scope {
+            if !registry.borrow().scopes.contains_key(&id) {
+                return false;
+            }
+        }
+
         (self.f)();
+        true
+    }
+}
+
+impl QueuedUpdate<dyn FnOnce() + Send> {
+    fn new_send(priority: Priority, f: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            f: Box::new(f),
+            scope: None,
+            is_changed: false,
+            priority,
+        }
+    }
+}
+
+impl From<QueuedUpdate<dyn FnOnce() + Send>> for QueuedUpdate {
+    fn from(update: QueuedUpdate<dyn FnOnce() + Send>) -> Self {
+        Self {
+            f: update.f,
+            scope: update.scope,
+            is_changed: update.is_changed,
+            priority: update.priority,
+        }
+    }
+}
+
+/// Scopes of a [`Runtime`] that can receive updates.
+///
+/// Scopes are registered when they're first updated, and removed when they're dropped (or reset),
+/// so updates queued for a dropped scope are skipped instead of accessing its freed state.
+#[derive(Default)]
+struct ScopeRegistry {
+    /// Registered scopes, by ID.
+    scopes: BTreeMap<u64, *const ScopeData<'static>>,
+
+    /// Next ID to register a scope with.
+    next_id: u64,
+}
+
+/// Get the ID of `scope` for updates, registering it with its runtime if needed.
+///
+/// This must be called on the composer's thread.
+pub(crate) fn scope_id(scope: &ScopeData) -> u64 {
+    if let Some(id) = scope.id.get() {
+        return id;
+    }
+
+    let mut registry = scope.runtime().scopes.borrow_mut();
+    let id = registry.next_id;
+    registry.next_id += 1;
+    registry.scopes.insert(id, scope as *const ScopeData as _);
+
+    scope.id.set(Some(id));
+    id
+}
+
+/// Remove `scope` from the registry of its runtime, skipping its pending updates.
+pub(crate) fn unregister_scope(scope: &ScopeData) {
+    if let Some(id) = scope.id.take() {
+        // Scopes are only registered once their runtime is set.
+        if let Some(rt) = scope.rt.get() {
+            rt.scopes.borrow_mut().scopes.remove(&id);
+        }
     }
 }
 
 type RuntimeFuture = Pin<Box<dyn Future<Output = ()>>>;
 
+/// Queue of local tasks to poll on the next composition.
+type TaskQueue = Arc<Mutex<VecDeque<DefaultKey>>>;
+
+/// Queued updates for each priority.
+type Lanes = [Vec<QueuedUpdate>; Priority::ALL.len()];
+
+/// Updates sent to a composer from other threads with an [`UpdateSender`].
+///
+/// Other threads only push to this inbox, and the composer's thread drains it with each flush.
+#[derive(Default)]
+struct Inbox {
+    updates: Mutex<Vec<QueuedUpdate<dyn FnOnce() + Send>>>,
+
+    /// `true` if the composer's updater was woken since the last flush.
+    is_woken: AtomicBool,
+}
+
+impl Inbox {
+    /// Wake the composer with `updater`, unless it was already woken since the last flush.
+    fn wake(&self, updater: &dyn Updater, priority: Priority) {
+        if !self.is_woken.swap(true, Ordering::SeqCst) {
+            updater.update(Update {
+                priority,
+                ..Update::new(|| {})
+            });
+        }
+    }
+}
+
 /// Runtime for a [`Composer`].
 #[derive(Clone)]
 pub struct Runtime {
-    /// Updater for this runtime.
+    /// Updater for this runtime, woken when updates are queued.
     pub(crate) updater: Arc<dyn Updater>,
 
+    /// Updates queued on this runtime, applied together in one pass.
+    queue: Rc<Mutex<Lanes>>,
+
+    /// Updates sent to this runtime from other threads.
+    inbox: Arc<Inbox>,
+
+    /// Scopes of this runtime that can receive updates.
+    scopes: Rc<RefCell<ScopeRegistry>>,
+
     /// Local task stored on this runtime.
     pub(crate) tasks: Rc<RefCell<SlotMap<DefaultKey, RuntimeFuture>>>,
 
-    /// Waker for local tasks.
-    pub(crate) task_tx: mpsc::Sender<DefaultKey>,
+    /// Local tasks that were woken.
+    pub(crate) task_queue: TaskQueue,
 
     /// Update lock for shared tasks.
     pub(crate) lock: Arc<RwLock<()>>,
+
+    /// Subscribers to devtools events.
+    #[cfg(feature = "devtools")]
+    pub(crate) subscribers: Rc<RefCell<Vec<crate::devtools::SubscriberFn>>>,
+
+    /// Restorable state of this runtime's composer.
+    #[cfg(feature = "snapshot")]
+    pub(crate) snapshot: Rc<RefCell<crate::snapshot::SnapshotState>>,
 }
 
 impl Runtime {
@@ -50,82 +264,378 @@ impl Runtime {
     /// # Panics
     /// Panics if called outside of a runtime.
     pub fn current() -> Self {
-        RUNTIME.with(|runtime| {
-            runtime
-                .borrow()
-                .as_ref()
-                .expect("Runtime::current() called outside of a runtime")
-                .clone()
-        })
+        Self::try_current().expect("Runtime::current() called outside of a runtime")
     }
 
-    /// Enter this runtime, making it available to [`Runtime::current`].
-    pub fn enter(&self) {
-        RUNTIME.with(|runtime| {
-            *runtime.borrow_mut() = Some(self.clone());
-        });
+    /// Get the current [`Runtime`], or `None` if called outside of a runtime.
+    pub fn try_current() -> Option<Self> {
+        RUNTIME.with(|runtime| runtime.borrow().last().cloned())
+    }
+
+    /// Enter this runtime, making it available to [`Runtime::current`] until the returned guard is dropped.
+    ///
+    /// Runtimes can be nested (for example, to compose a [`Composer`] inside another),
+    /// and the last runtime is restored once the guard of the inner runtime is dropped.
+    pub fn enter(&self) -> RuntimeGuard {
+        RUNTIME.with(|runtime| runtime.borrow_mut().push(self.clone()));
+
+        RuntimeGuard {
+            _marker: PhantomData,
+        }
     }
 
-    /// Queue an update to run after [`Composer::compose`].
+    /// Queue an update to run before the next [`Composer::compose`].
     pub fn update(&self, f: impl FnOnce() + 'static) {
-        self.updater.update(Update { f: Box::new(f) });
+        self.update_with_priority(Priority::Normal, f)
+    }
+
+    /// Queue an update with the given `priority` to run before the next [`Composer::compose`].
+    ///
+    /// Updates with a higher priority are applied first,
+    /// and [`Priority::Idle`] updates are deferred until no other updates are queued.
+    pub fn update_with_priority(&self, priority: Priority, f: impl FnOnce() + 'static) {
+        self.push(QueuedUpdate::new(priority, f));
+    }
+
+    /// Queue an update to run before the next [`Composer::compose`] if `scope` still exists,
+    /// marking `scope` as changed once applied if `is_changed` is `true`.
+    ///
+    /// Updates queued for the same scope before the next composition only mark that scope once.
+    pub(crate) fn update_scope(
+        &self,
+        scope: &ScopeData,
+        is_changed: bool,
+        f: impl FnOnce() + 'static,
+    ) {
+        self.push(QueuedUpdate {
+            scope: Some(scope_id(scope)),
+            is_changed,
+            ..QueuedUpdate::new(Priority::Normal, f)
+        });
+    }
+
+    fn push(&self, update: QueuedUpdate) {
+        let priority = update.priority;
+        sync::lock(&self.queue)[priority as usize].push(update);
+        self.inbox.wake(&*self.updater, priority);
+    }
+
+    /// Get an [`UpdateSender`] to queue updates to this runtime from other threads.
+    pub fn update_sender(&self) -> UpdateSender {
+        UpdateSender {
+            updater: self.updater.clone(),
+            inbox: self.inbox.clone(),
+        }
+    }
+
+    /// Queue the local task `key` to be polled on the next composition.
+    pub(crate) fn wake_task(&self, key: DefaultKey) {
+        sync::lock(&self.task_queue).push_back(key);
+    }
+
+    /// Apply each queued update in order of priority (including updates sent from other threads,
+    /// and updates queued while flushing), then mark each updated scope as changed once.
+    ///
+    /// Idle updates are only applied if no other updates were queued,
+    /// and the updater is woken again while they're pending.
+    ///
+    /// This must be called on the composer's thread, while holding the update lock.
+    fn flush(&self) {
+        // Clear the flag before draining, so updates sent during this flush wake the composer again.
+        self.inbox.is_woken.store(false, Ordering::SeqCst);
+
+        let mut scopes = HashSet::new();
+        let mut is_busy = false;
+
+        let is_idle_pending = loop {
+            let updates = {
+                let mut lanes = sync::lock(&self.queue);
+                for update in mem::take(&mut *sync::lock(&self.inbox.updates)) {
+                    lanes[update.priority as usize].push(update.into());
+                }
+
+                let (idle, lanes) = lanes.split_last_mut().unwrap();
+                if let Some(lane) = lanes.iter_mut().find(|lane| !lane.is_empty()) {
+                    is_busy = true;
+                    mem::take(lane)
+                } else if !is_busy && !idle.is_empty() {
+                    mem::take(idle)
+                } else {
+                    break !idle.is_empty();
+                }
+            };
+
+            for update in updates {
+                let scope = update.scope.filter(|_| update.is_changed);
+                if update.try_apply(&self.scopes) {
+                    scopes.extend(scope);
+                }
+            }
+        };
+
+        let registry = self.scopes.borrow();
+        for id in scopes {
+            if let Some(scope) = registry.scopes.get(&id) {
+                // Safety: Registered scopes exist until they're removed when dropped,
+                // and this runtime's scopes are only accessed on the composer's thread.
+                unsafe { (**scope).set_changed() }
+            }
+        }
+        drop(registry);
+
+        if is_idle_pending {
+            self.inbox.wake(&*self.updater, Priority::Idle);
+        }
     }
 }
 
-thread_local! {
-    static RUNTIME: RefCell<Option<Runtime>> = const { RefCell::new(None) };
+/// Handle to queue updates to a [`Composer`] from any thread.
+///
+/// Updates are pushed to a channel drained by the composer's thread at the start of its next
+/// [`Composer::compose`], and the composer's [`Updater`] is woken
+/// (for example, to send a user event to the system event loop).
+///
+/// This can be created with [`Runtime::update_sender`] or [`Composer::update_sender`].
+#[derive(Clone)]
+pub struct UpdateSender {
+    updater: Arc<dyn Updater>,
+    inbox: Arc<Inbox>,
 }
 
-/// Updater for a [`Composer`].
-pub trait Updater: Send + Sync {
-    /// Update the content of a [`Composer`].
-    fn update(&self, update: Update);
+impl UpdateSender {
+    /// Queue an update to run on the composer's thread.
+    pub fn send(&self, f: impl FnOnce() + Send + 'static) {
+        self.send_with_priority(Priority::Normal, f)
+    }
+
+    /// Queue an update with the given `priority` to run on the composer's thread.
+    pub fn send_with_priority(&self, priority: Priority, f: impl FnOnce() + Send + 'static) {
+        self.push(QueuedUpdate::new_send(priority, f));
+    }
+
+    /// Queue an update to run on the composer's thread if the scope with `id` still exists,
+    /// marking that scope as changed once applied if `is_changed` is `true`.
+    pub(crate) fn send_scope(&self, id: u64, is_changed: bool, f: impl FnOnce() + Send + 'static) {
+        self.push(QueuedUpdate {
+            scope: Some(id),
+            is_changed,
+            ..QueuedUpdate::new_send(Priority::Normal, f)
+        });
+    }
+
+    fn push(&self, update: QueuedUpdate<dyn FnOnce() + Send>) {
+        let priority = update.priority;
+        sync::lock(&self.inbox.updates).push(update);
+        self.inbox.wake(&*self.updater, priority);
+    }
+
+    /// Wake the composer, composing any content that changed since its last composition.
+    pub fn wake(&self) {
+        self.inbox.wake(&*self.updater, Priority::Normal);
+    }
 }
 
-#[cfg(feature = "rt")]
-struct DefaultUpdater;
+impl fmt::Debug for UpdateSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateSender").finish_non_exhaustive()
+    }
+}
 
-#[cfg(feature = "rt")]
-impl Updater for DefaultUpdater {
-    fn update(&self, update: Update) {
-        unsafe {
-            update.apply();
+/// Guard for an entered [`Runtime`].
+///
+/// This can be created with [`Runtime::enter`].
+#[must_use = "The runtime is exited once this guard is dropped"]
+pub struct RuntimeGuard {
+    /// Marker to keep this guard on the thread that entered its runtime.
+    _marker: PhantomData<*const ()>,
+}
+
+impl Drop for RuntimeGuard {
+    fn drop(&mut self) {
+        RUNTIME.with(|runtime| runtime.borrow_mut().pop());
+    }
+}
+
+impl fmt::Debug for RuntimeGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuntimeGuard").finish_non_exhaustive()
+    }
+}
+
+sync::local! {
+    /// Stack of entered runtimes, with the current runtime last.
+    static RUNTIME: RefCell<Vec<Runtime>> = const { RefCell::new(Vec::new()) };
+
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+
+    static CURRENT_SCOPE: Cell<Option<NonNull<ScopeData<'static>>>> = const { Cell::new(None) };
+
+    #[cfg(feature = "hot-reload")]
+    static IS_RELOADING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns `true` if every composable is being recomposed after a hot-reload.
+pub(crate) fn is_reloading() -> bool {
+    #[cfg(feature = "hot-reload")]
+    return IS_RELOADING.get();
+
+    #[cfg(not(feature = "hot-reload"))]
+    false
+}
+
+/// Payload used to unwind from a composable whose hooks changed since its last composition.
+#[cfg(feature = "hot-reload")]
+struct StaleHook;
+
+/// Unwind from the current composable if the hook `any` is not of type `T`.
+///
+/// The composer then resets the state of this composable, and composes it again.
+#[cfg(feature = "hot-reload")]
+pub(crate) fn check_hook<T: 'static>(any: &dyn Any) {
+    if !any.is::<T>() {
+        panic::resume_unwind(Box::new(StaleHook));
+    }
+}
+
+/// Function returning the name of a composable.
+pub(crate) type NameFn = fn() -> Option<Cow<'static, str>>;
+
+/// Composable currently running its `compose` function.
+struct Frame {
+    name: NameFn,
+
+    /// Scope of this composable, marked as changed if it panics (which can only be caught with `std`).
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    scope: *const ScopeData<'static>,
+}
+
+/// Push a frame for a composable that's about to run its `compose` function.
+pub(crate) fn push_frame(name: NameFn, scope: &ScopeData) {
+    let scope = scope as *const ScopeData as _;
+    FRAMES.with(|frames| frames.borrow_mut().push(Frame { name, scope }));
+}
+
+/// Get the names of the composables currently running their `compose` function, starting from the root.
+pub(crate) fn frame_names() -> Vec<Cow<'static, str>> {
+    FRAMES.with(|frames| {
+        frames
+            .borrow()
+            .iter()
+            .filter_map(|frame| (frame.name)())
+            .collect()
+    })
+}
+
+/// Pop the frame of the last composable to run its `compose` function.
+pub(crate) fn pop_frame() {
+    FRAMES.with(|frames| frames.borrow_mut().pop());
+}
+
+/// Guard for the scope currently being composed.
+///
+/// Scopes entered while this guard is alive are set as children of its scope.
+pub(crate) struct ScopeGuard {
+    parent: Option<NonNull<ScopeData<'static>>>,
+}
+
+impl ScopeGuard {
+    /// Enter `scope`, setting its parent to the scope currently being composed.
+    pub(crate) fn enter(scope: &ScopeData) -> Self {
+        scope.rt.get_or_init(Runtime::current);
+
+        let ptr = NonNull::from(scope).cast();
+        let parent = CURRENT_SCOPE.with(|current| current.replace(Some(ptr)));
+        let _last_parent = scope.parent.replace(parent);
+
+        #[cfg(feature = "devtools")]
+        if _last_parent.is_none() {
+            if let Some(parent) = parent {
+                // Safety: Parent scopes outlive their children.
+                unsafe { parent.as_ref() }.children.borrow_mut().push(ptr);
+            }
         }
+
+        Self { parent }
     }
 }
 
-struct UpdateWrapper<U> {
-    updater: U,
-    lock: Arc<RwLock<()>>,
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        CURRENT_SCOPE.with(|current| current.set(self.parent));
+    }
 }
 
-impl<U: Updater> Updater for UpdateWrapper<U> {
-    fn update(&self, update: Update) {
-        let lock = self.lock.clone();
-        self.updater.update(Update {
-            f: Box::new(move || {
-                let _guard = lock.blocking_write();
-                unsafe { update.apply() }
-            }),
-        });
+/// Error for a composable that panicked in [`Composer::try_compose`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Error)]
+#[error("Composable `{}` panicked: {message}", name.as_deref().unwrap_or("<unknown>"))]
+pub struct ComposeError {
+    /// Name of the composable that panicked, if known.
+    pub name: Option<Cow<'static, str>>,
+
+    /// Names of the container composables the panicking composable is nested in, starting from the root.
+    pub path: Vec<Cow<'static, str>>,
+
+    /// Panic message.
+    pub message: String,
+}
+
+#[cfg(feature = "std")]
+impl ComposeError {
+    fn new(frames: Vec<Frame>, payload: Box<dyn Any + Send>) -> Self {
+        let mut names: Vec<_> = frames.iter().filter_map(|frame| (frame.name)()).collect();
+        let name = names.pop();
+
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            String::from("Box<dyn Any>")
+        };
+
+        Self {
+            name,
+            path: names,
+            message,
+        }
     }
 }
 
+/// Updater for a [`Composer`].
+///
+/// Queued updates are applied on the composer's thread at the start of [`Composer::compose`].
+/// The updater is called (possibly from another thread) when updates are queued,
+/// and should wake the composer's thread to apply the given [`Update`] and compose again
+/// (for example, by sending it to the system event loop).
+pub trait Updater: Send + Sync {
+    /// Wake the [`Composer`] to apply `update` on its thread and compose its content.
+    fn update(&self, update: Update);
+}
+
+/// Updater for a [`Composer`] that's composed manually.
+///
+/// Queued updates are applied with the next call to [`Composer::compose`].
+#[cfg(feature = "rt")]
+struct DefaultUpdater;
+
+#[cfg(feature = "rt")]
+impl Updater for DefaultUpdater {
+    fn update(&self, _update: Update) {}
+}
+
 struct TaskWaker {
     key: DefaultKey,
-    updater: Arc<dyn Updater>,
-    tx: mpsc::Sender<DefaultKey>,
+    sender: UpdateSender,
+    queue: TaskQueue,
 }
 
 impl Wake for TaskWaker {
     fn wake(self: Arc<Self>) {
-        let key = self.key;
-        let pending = self.tx.clone();
-        self.updater.update(Update {
-            f: Box::new(move || {
-                pending.send(key).unwrap();
-            }),
-        });
+        // Tasks can be woken after their composer is dropped (and are then never polled).
+        sync::lock(&self.queue).push_back(self.key);
+        self.sender.wake();
     }
 }
 
@@ -134,7 +644,7 @@ pub struct Composer {
     compose: Box<dyn AnyCompose>,
     scope_state: Box<ScopeData<'static>>,
     rt: Runtime,
-    task_rx: mpsc::Receiver<DefaultKey>,
+    is_paused: bool,
 }
 
 impl Composer {
@@ -147,90 +657,336 @@ impl Composer {
 
     /// Create a new [`Composer`] with the given content, updater, and task executor.
     pub fn with_updater(content: impl Compose + 'static, updater: impl Updater + 'static) -> Self {
-        let lock = Arc::new(RwLock::new(()));
-        let updater = Arc::new(UpdateWrapper {
-            updater,
-            lock: lock.clone(),
-        });
-        let (task_tx, task_rx) = mpsc::channel();
-
         let scope_data = ScopeData::default();
         Self {
             compose: Box::new(content),
             scope_state: Box::new(scope_data),
             rt: Runtime {
-                updater: updater.clone(),
+                updater: Arc::new(updater),
+                queue: Rc::default(),
+                inbox: Arc::default(),
+                scopes: Rc::default(),
                 tasks: Rc::new(RefCell::new(SlotMap::new())),
-                task_tx,
-                lock,
+                task_queue: TaskQueue::default(),
+                lock: Arc::new(RwLock::new(())),
+                #[cfg(feature = "devtools")]
+                subscribers: Rc::default(),
+                #[cfg(feature = "snapshot")]
+                snapshot: Rc::default(),
             },
-            task_rx,
+            is_paused: false,
         }
     }
 
-    /// Compose the content of this composer.
+    /// Get the [`Runtime`] of this composer.
+    ///
+    /// This can be entered to handle events for this composer's content outside of [`Composer::compose`].
+    pub fn runtime(&self) -> &Runtime {
+        &self.rt
+    }
+
+    /// Get an [`UpdateSender`] to queue updates to this composer from other threads.
+    pub fn update_sender(&self) -> UpdateSender {
+        self.rt.update_sender()
+    }
+
+    /// Pause this composer, skipping compositions until [`Composer::resume`] is called.
+    ///
+    /// Updates are still applied while paused, and composed once this composer is resumed.
+    pub fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    /// Resume this composer after [`Composer::pause`].
+    pub fn resume(&mut self) {
+        self.is_paused = false;
+    }
+
+    /// Returns `true` if this composer is paused.
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Take a snapshot of the restorable state in this composer.
+    ///
+    /// This contains the current value of each composed [`use_restorable`](crate::snapshot::use_restorable) hook.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn snapshot(&self) -> crate::snapshot::Snapshot {
+        self.rt.snapshot.borrow().snapshot()
+    }
+
+    /// Restore the state in `snapshot` into this composer.
+    ///
+    /// Each value is restored into the [`use_restorable`](crate::snapshot::use_restorable) hook with its key
+    /// the next time it's composed.
+    /// Restoring before the first composition restores the initial state of a freshly built tree.
+    #[cfg(feature = "snapshot")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn restore(&mut self, snapshot: crate::snapshot::Snapshot) {
+        self.rt.snapshot.borrow_mut().restore(snapshot);
+    }
+
+    /// Apply any queued updates, then compose the content of this composer.
+    ///
+    /// This holds the update lock (see [`Composer::lock`]) until it returns,
+    /// so tasks from [`use_task`](crate::use_task) aren't polled on other threads while the content changes.
     pub fn compose(&mut self) {
+        let _rt = self.rt.enter();
+        let _guard = sync::write(&self.rt.lock);
+
+        if self.is_paused {
+            self.rt.flush();
+            return;
+        }
+
         #[cfg(feature = "tracing")]
         tracing::trace!("Composer::compose");
 
-        self.rt.enter();
+        loop {
+            // Release the queue before polling, as tasks can be woken while they're polled.
+            let Some(key) = sync::lock(&self.rt.task_queue).pop_front() else {
+                break;
+            };
 
-        while let Ok(key) = self.task_rx.try_recv() {
             let waker = Waker::from(Arc::new(TaskWaker {
                 key,
-                updater: Runtime::current().updater.clone(),
-                tx: self.rt.task_tx.clone(),
+                sender: self.rt.update_sender(),
+                queue: self.rt.task_queue.clone(),
             }));
             let mut cx = Context::from_waker(&waker);
 
+            // Tasks can be woken after their scope is dropped and they're removed.
             let mut tasks = self.rt.tasks.borrow_mut();
-            let task = tasks.get_mut(key).unwrap();
-            let _ = task.as_mut().poll(&mut cx);
+            if let Some(task) = tasks.get_mut(key) {
+                let _ = task.as_mut().poll(&mut cx);
+            }
         }
 
+        // Apply updates queued by tasks, composables, and other threads since the last composition.
+        self.rt.flush();
+
+        #[cfg(not(feature = "hot-reload"))]
         // Safety: `self.compose` is guaranteed to live as long as `self.scope_state`.
-        unsafe { self.compose.any_compose(&self.scope_state) }
-    }
+        unsafe {
+            self.compose.any_compose(&self.scope_state)
+        }
 
-    /// Lock updates to the content of this composer.
-    pub fn lock(&self) -> RwLockWriteGuard<()> {
-        self.rt.lock.blocking_write()
-    }
-}
+        #[cfg(feature = "hot-reload")]
+        loop {
+            FRAMES.with(|frames| frames.borrow_mut().clear());
 
-#[cfg(all(test, feature = "rt"))]
-mod tests {
-    use crate::{composer::Composer, prelude::*};
-    use std::{
-        cell::{Cell, RefCell},
-        rc::Rc,
-    };
+            // Safety: `self.compose` is guaranteed to live as long as `self.scope_state`.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+                self.compose.any_compose(&self.scope_state)
+            }));
 
-    #[derive(Data)]
-    struct Counter {
-        x: Rc<Cell<i32>>,
-    }
+            match result {
+                Ok(()) => break,
+                Err(payload) if payload.is::<StaleHook>() => {
+                    let frames = FRAMES.with(|frames| frames.take());
 
-    impl Compose for Counter {
-        fn compose(cx: Scope<Self>) -> impl Compose {
-            cx.me().x.set(cx.me().x.get() + 1);
+                    for frame in &frames {
+                        // Safety: the scopes of composables that were running are still owned by their parents.
+                        unsafe { (*frame.scope).set_changed() };
+                    }
 
-            cx.set_changed();
+                    // Reset the composable that used a hook of a different type, and retry.
+                    if let Some(frame) = frames.last() {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(
+                            "Resetting composable with changed hooks: {:?}",
+                            (frame.name)()
+                        );
+
+                        // Safety: this scope is no longer being composed.
+                        unsafe { (*frame.scope).reset() };
+                    }
+                }
+                Err(payload) => panic::resume_unwind(payload),
+            }
         }
     }
 
-    #[derive(Data)]
-    struct NonUpdateCounter {
-        x: Rc<Cell<i32>>,
+    /// Replace the content of this composer, and compose it immediately.
+    ///
+    /// If `content` has the same type as the current content, the existing state of each composable is re-used
+    /// (composables whose hooks changed type are reset).
+    /// Otherwise, all state is dropped.
+    #[cfg(feature = "hot-reload")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reload")))]
+    pub fn replace(&mut self, content: impl Compose + 'static) {
+        let content: Box<dyn AnyCompose> = Box::new(content);
+
+        if content.data_id() != self.compose.data_id() {
+            // Drop the state of the last content before the content it borrows.
+            let _rt = self.rt.enter();
+            *self.scope_state = ScopeData::default();
+        }
+
+        // Keep the last content alive until its state is re-composed with the new content.
+        let _last = mem::replace(&mut self.compose, content);
+        self.reload();
     }
 
-    impl Compose for NonUpdateCounter {
-        fn compose(cx: Scope<Self>) -> impl Compose {
-            cx.me().x.set(cx.me().x.get() + 1);
+    /// Compose every composable in this composer, even if it hasn't changed.
+    ///
+    /// This can be called after patching composable functions with a hot-reloading tool.
+    /// Composables whose hooks changed type since their last composition have their state reset.
+    #[cfg(feature = "hot-reload")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hot-reload")))]
+    pub fn reload(&mut self) {
+        IS_RELOADING.set(true);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.compose()));
+        IS_RELOADING.set(false);
+
+        if let Err(payload) = result {
+            panic::resume_unwind(payload);
         }
     }
 
-    #[test]
+    /// Compose the content of this composer, catching any panics from composables.
+    ///
+    /// If a composable panics, the returned [`ComposeError`] contains the name of that composable.
+    /// The composables that were running are marked as changed,
+    /// so the next call to [`Composer::compose`] (or [`Composer::try_compose`]) will retry them.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn try_compose(&mut self) -> Result<(), ComposeError> {
+        FRAMES.with(|frames| frames.borrow_mut().clear());
+
+        panic::catch_unwind(AssertUnwindSafe(|| self.compose())).map_err(|payload| {
+            let frames = FRAMES.with(|frames| frames.take());
+
+            for frame in &frames {
+                // Safety: the scopes of composables that were running are still owned by their parents.
+                unsafe { (*frame.scope).set_changed() };
+            }
+
+            ComposeError::new(frames, payload)
+        })
+    }
+
+    /// Get a snapshot of the tree of scopes in this composer.
+    #[cfg(feature = "devtools")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "devtools")))]
+    pub fn tree(&self) -> crate::devtools::ScopeNode {
+        crate::devtools::ScopeNode::new(&self.scope_state)
+    }
+
+    /// Subscribe to devtools events from this composer.
+    #[cfg(feature = "devtools")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "devtools")))]
+    pub fn subscribe(&self, f: impl FnMut(&crate::devtools::DevtoolsEvent) + 'static) {
+        self.rt.subscribers.borrow_mut().push(Box::new(f));
+    }
+
+    /// Lock updates to the content of this composer.
+    ///
+    /// While the returned guard is held, this composer can't compose
+    /// and tasks from [`use_task`](crate::use_task) aren't polled.
+    pub fn lock(&self) -> RwLockWriteGuard<'_, ()> {
+        sync::write(&self.rt.lock)
+    }
+}
+
+impl Drop for Composer {
+    fn drop(&mut self) {
+        // Drop the content's state in this composer's runtime, before the content it borrows.
+        let _rt = self.rt.enter();
+        *self.scope_state = ScopeData::default();
+    }
+}
+
+#[cfg(all(test, any(feature = "http", feature = "rt")))]
+pub(crate) mod testing {
+    use super::{Update, Updater};
+    use std::sync::mpsc;
+
+    /// Updater that sends updates to a channel, to be applied by the test.
+    ///
+    /// Updates sent after the receiver is dropped are ignored.
+    pub(crate) struct ChannelUpdater(mpsc::Sender<Update>);
+
+    impl ChannelUpdater {
+        /// Create a new updater and the receiver of its updates.
+        pub(crate) fn new() -> (Self, mpsc::Receiver<Update>) {
+            let (tx, rx) = mpsc::channel();
+            (Self(tx), rx)
+        }
+    }
+
+    impl Updater for ChannelUpdater {
+        fn update(&self, update: Update) {
+            let _ = self.0.send(update);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{
+        composer::{Composer, Priority, Runtime, Update, Updater},
+        prelude::*,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        mem,
+        rc::Rc,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    /// Updater that stores updates until they're manually applied.
+    #[derive(Clone, Default)]
+    struct DeferredUpdater {
+        updates: Arc<Mutex<Vec<Update>>>,
+    }
+
+    impl DeferredUpdater {
+        fn apply(&self) -> usize {
+            let updates = mem::take(&mut *self.updates.lock().unwrap());
+            let len = updates.len();
+
+            for update in updates {
+                update.apply();
+            }
+
+            len
+        }
+    }
+
+    impl Updater for DeferredUpdater {
+        fn update(&self, update: Update) {
+            self.updates.lock().unwrap().push(update);
+        }
+    }
+
+    #[derive(Data)]
+    struct Counter {
+        x: Rc<Cell<i32>>,
+    }
+
+    impl Compose for Counter {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            cx.me().x.set(cx.me().x.get() + 1);
+
+            cx.set_changed();
+        }
+    }
+
+    #[derive(Data)]
+    struct NonUpdateCounter {
+        x: Rc<Cell<i32>>,
+    }
+
+    impl Compose for NonUpdateCounter {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            cx.me().x.set(cx.me().x.get() + 1);
+        }
+    }
+
+    #[test]
     fn it_composes() {
         #[derive(Data)]
         struct Wrap {
@@ -339,4 +1095,559 @@ mod tests {
         compsoer.compose();
         assert_eq!(*x.borrow(), 1);
     }
+
+    #[test]
+    fn it_skips_compositions_while_paused() {
+        let x = Rc::new(Cell::new(0));
+        let mut composer = Composer::new(Counter { x: x.clone() });
+
+        composer.pause();
+        composer.compose();
+        assert_eq!(x.get(), 0);
+
+        composer.resume();
+        composer.compose();
+        assert_eq!(x.get(), 1);
+    }
+
+    #[test]
+    fn it_skips_updates_to_dropped_scopes() {
+        #[derive(Data)]
+        struct B {
+            signal: Rc<RefCell<Option<Signal<i32>>>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, || 0);
+                *cx.me().signal.borrow_mut() = Some(signal);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            is_shown: Rc<Cell<bool>>,
+            signal: Rc<RefCell<Option<Signal<i32>>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                cx.me().is_shown.get().then(|| B {
+                    signal: cx.me().signal.clone(),
+                })
+            }
+        }
+
+        let is_shown = Rc::new(Cell::new(true));
+        let signal = Rc::new(RefCell::new(None));
+        let mut composer = Composer::new(A {
+            is_shown: is_shown.clone(),
+            signal: signal.clone(),
+        });
+
+        composer.compose();
+        is_shown.set(false);
+        composer.compose();
+
+        // The scope owning this signal is dropped, so this update must be skipped.
+        let is_updated = Arc::new(Mutex::new(false));
+        let thread_is_updated = is_updated.clone();
+        signal
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .update(move |_| *thread_is_updated.lock().unwrap() = true);
+
+        composer.compose();
+        assert!(!*is_updated.lock().unwrap());
+    }
+
+    #[test]
+    fn it_keeps_scopes_per_composer() {
+        #[derive(Data)]
+        struct A {
+            is_shown: Rc<Cell<bool>>,
+            signal: Rc<RefCell<Option<Signal<i32>>>>,
+            count: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                cx.me().is_shown.get().then(|| B {
+                    signal: cx.me().signal.clone(),
+                    count: cx.me().count.clone(),
+                })
+            }
+        }
+
+        #[derive(Data)]
+        struct B {
+            signal: Rc<RefCell<Option<Signal<i32>>>>,
+            count: Rc<Cell<i32>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, || 0);
+                cx.me().count.set(*signal.lock());
+                *cx.me().signal.borrow_mut() = Some(signal);
+            }
+        }
+
+        let make_composer = || {
+            let is_shown = Rc::new(Cell::new(true));
+            let signal = Rc::new(RefCell::new(None));
+            let count = Rc::new(Cell::new(0));
+            let composer = Composer::new(A {
+                is_shown: is_shown.clone(),
+                signal: signal.clone(),
+                count: count.clone(),
+            });
+            (composer, is_shown, signal, count)
+        };
+        let (mut a, a_is_shown, _, _) = make_composer();
+        let (mut b, _, b_signal, b_count) = make_composer();
+
+        a.compose();
+        b.compose();
+
+        // Dropping a scope of one composer doesn't skip updates to the scopes of another.
+        a_is_shown.set(false);
+        a.compose();
+
+        b_signal.borrow().as_ref().unwrap().set(1);
+        b.compose();
+        assert_eq!(b_count.get(), 1);
+    }
+
+    #[test]
+    fn it_skips_updates_after_dropping_composers() {
+        #[derive(Data)]
+        struct A {
+            is_updated: Rc<Cell<bool>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let value = use_mut(&cx, String::new);
+                let is_updated = cx.me().is_updated.clone();
+                Mut::update(value, move |value| {
+                    value.push_str("updated");
+                    is_updated.set(true);
+                });
+            }
+        }
+
+        let is_updated = Rc::new(Cell::new(false));
+        let updater = DeferredUpdater::default();
+        let mut composer = Composer::with_updater(
+            A {
+                is_updated: is_updated.clone(),
+            },
+            updater.clone(),
+        );
+
+        composer.compose();
+        drop(composer);
+
+        // The queued update points to the dropped value, and must be dropped with the composer.
+        updater.apply();
+        assert!(!is_updated.get());
+    }
+
+    #[test]
+    fn it_skips_unchanged_subtrees() {
+        #[derive(Data)]
+        struct B {
+            x: Rc<Cell<i32>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                // Containers are composed whenever their scope is visited.
+                cx.is_container.set(true);
+
+                cx.me().x.set(cx.me().x.get() + 1);
+            }
+        }
+
+        #[derive(Data)]
+        struct C {
+            y: Rc<Cell<i32>>,
+        }
+
+        impl Compose for C {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                cx.me().y.set(cx.me().y.get() + 1);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+            y: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                (
+                    B {
+                        x: cx.me().x.clone(),
+                    },
+                    C {
+                        y: cx.me().y.clone(),
+                    },
+                )
+            }
+        }
+
+        let x = Rc::new(Cell::new(0));
+        let y = Rc::new(Cell::new(0));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            y: y.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        composer.compose();
+        assert_eq!((x.get(), y.get()), (1, 3));
+    }
+
+    #[test]
+    fn it_only_recomposes_updated_leaves() {
+        #[derive(Data)]
+        struct Leaf {
+            idx: usize,
+            composes: Rc<RefCell<Vec<usize>>>,
+            signals: Rc<RefCell<Vec<Signal<i32>>>>,
+        }
+
+        impl Compose for Leaf {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, || 0);
+                use_ref(&cx, || cx.me().signals.borrow_mut().push(signal.clone()));
+
+                cx.me().composes.borrow_mut().push(cx.me().idx);
+            }
+        }
+
+        #[derive(Data)]
+        struct Branch {
+            depth: usize,
+            composes: Rc<RefCell<Vec<usize>>>,
+            signals: Rc<RefCell<Vec<Signal<i32>>>>,
+        }
+
+        impl Compose for Branch {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let depth = cx.me().depth;
+                cx.me().composes.borrow_mut().push(100 + depth);
+
+                let composes = &cx.me().composes;
+                let signals = &cx.me().signals;
+                if depth == 0 {
+                    let leaves = (0..3)
+                        .map(|idx| Leaf {
+                            idx,
+                            composes: composes.clone(),
+                            signals: signals.clone(),
+                        })
+                        .collect();
+                    (None, Some(leaves))
+                } else {
+                    let branch = Branch {
+                        depth: depth - 1,
+                        composes: composes.clone(),
+                        signals: signals.clone(),
+                    };
+                    (Some(DynCompose::new(branch)), None::<Vec<Leaf>>)
+                }
+            }
+        }
+
+        let composes = Rc::new(RefCell::new(Vec::new()));
+        let signals = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(Branch {
+            depth: 3,
+            composes: composes.clone(),
+            signals: signals.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*composes.borrow(), [103, 102, 101, 100, 0, 1, 2]);
+
+        composes.borrow_mut().clear();
+        signals.borrow()[1].set(1);
+        composer.compose();
+
+        // Ancestors and siblings of the updated leaf are skipped.
+        assert_eq!(*composes.borrow(), [1]);
+    }
+
+    #[test]
+    fn it_batches_updates() {
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let count = use_mut(&cx, || 0);
+                cx.me().x.set(*count);
+
+                if *count == 0 {
+                    for _ in 0..3 {
+                        Mut::update(count, |count| *count += 1);
+                    }
+                }
+            }
+        }
+
+        let updater = DeferredUpdater::default();
+        let x = Rc::new(Cell::new(0));
+        let mut composer = Composer::with_updater(A { x: x.clone() }, updater.clone());
+
+        composer.compose();
+        assert_eq!(x.get(), 0);
+
+        assert_eq!(updater.apply(), 1);
+
+        composer.compose();
+        assert_eq!(x.get(), 3);
+    }
+
+    #[test]
+    fn it_applies_updates_by_priority() {
+        #[derive(Data)]
+        struct A {
+            order: Rc<RefCell<Vec<Priority>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_ref(&cx, || {
+                    let rt = Runtime::current();
+                    for priority in [
+                        Priority::Idle,
+                        Priority::Normal,
+                        Priority::Animation,
+                        Priority::Input,
+                    ] {
+                        let order = cx.me().order.clone();
+                        rt.update_with_priority(priority, move || {
+                            order.borrow_mut().push(priority)
+                        });
+                    }
+                });
+            }
+        }
+
+        let updater = DeferredUpdater::default();
+        let order = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::with_updater(
+            A {
+                order: order.clone(),
+            },
+            updater.clone(),
+        );
+
+        composer.compose();
+        assert_eq!(updater.apply(), 1);
+        composer.compose();
+        assert_eq!(
+            *order.borrow(),
+            [Priority::Input, Priority::Animation, Priority::Normal]
+        );
+
+        // Idle updates are deferred to the next flush, and wake the composer again.
+        assert_eq!(updater.apply(), 1);
+        composer.compose();
+        assert_eq!(order.borrow().last(), Some(&Priority::Idle));
+    }
+
+    #[cfg(feature = "hot-reload")]
+    #[test]
+    fn it_reloads_changed_hooks() {
+        #[derive(Data)]
+        struct B {
+            is_patched: Rc<Cell<bool>>,
+            inits: Rc<Cell<i32>>,
+            composes: Rc<Cell<i32>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let init = || cx.me().inits.set(cx.me().inits.get() + 1);
+
+                // Simulate patching this function with a hook of a different type.
+                if cx.me().is_patched.get() {
+                    use_ref(&cx, || {
+                        init();
+                        String::new()
+                    });
+                } else {
+                    use_ref(&cx, || {
+                        init();
+                        0
+                    });
+                }
+
+                cx.me().composes.set(cx.me().composes.get() + 1);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            is_patched: Rc<Cell<bool>>,
+            inits: Rc<Cell<i32>>,
+            composes: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                B {
+                    is_patched: cx.me().is_patched.clone(),
+                    inits: cx.me().inits.clone(),
+                    composes: cx.me().composes.clone(),
+                }
+            }
+        }
+
+        let is_patched = Rc::new(Cell::new(false));
+        let inits = Rc::new(Cell::new(0));
+        let composes = Rc::new(Cell::new(0));
+        let make_content = || A {
+            is_patched: is_patched.clone(),
+            inits: inits.clone(),
+            composes: composes.clone(),
+        };
+
+        let mut composer = Composer::new(make_content());
+        composer.compose();
+        composer.compose();
+        assert_eq!((inits.get(), composes.get()), (1, 1));
+
+        composer.reload();
+        assert_eq!((inits.get(), composes.get()), (1, 2));
+
+        is_patched.set(true);
+        composer.reload();
+        assert_eq!((inits.get(), composes.get()), (2, 3));
+
+        composer.replace(make_content());
+        assert_eq!((inits.get(), composes.get()), (2, 4));
+    }
+
+    #[test]
+    fn it_sends_updates_from_other_threads() {
+        let updater = DeferredUpdater::default();
+        let mut composer = Composer::with_updater((), updater.clone());
+
+        let is_updated = Arc::new(Mutex::new(false));
+        let sender = composer.update_sender();
+        let thread_is_updated = is_updated.clone();
+        thread::spawn(move || sender.send(move || *thread_is_updated.lock().unwrap() = true))
+            .join()
+            .unwrap();
+
+        // The sending thread only wakes the composer's updater.
+        assert_eq!(updater.apply(), 1);
+        assert!(!*is_updated.lock().unwrap());
+
+        composer.compose();
+        assert!(*is_updated.lock().unwrap());
+    }
+
+    #[test]
+    fn it_applies_updates_from_other_threads_on_the_composer_thread() {
+        #[derive(Data)]
+        struct A {
+            signal: Rc<RefCell<Option<Signal<Vec<thread::ThreadId>>>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, Vec::new);
+                *cx.me().signal.borrow_mut() = Some(signal);
+            }
+        }
+
+        let signal = Rc::new(RefCell::new(None));
+        let mut composer = Composer::new(A {
+            signal: signal.clone(),
+        });
+        composer.compose();
+
+        let sender = composer.update_sender();
+        let thread_signal = signal.borrow().clone().unwrap();
+        let handle = thread::spawn(move || {
+            for _ in 0..100 {
+                thread_signal.update(|ids| ids.push(thread::current().id()));
+                sender.send(|| {});
+            }
+        });
+
+        // Compose while the other thread is sending updates.
+        while !handle.is_finished() {
+            composer.compose();
+        }
+        handle.join().unwrap();
+        composer.compose();
+
+        let ids = signal.borrow().as_ref().unwrap().get();
+        assert_eq!(ids, vec![thread::current().id(); 100]);
+    }
+
+    #[test]
+    fn it_routes_updates_from_nested_composers() {
+        #[derive(Data)]
+        struct Inner;
+
+        impl Compose for Inner {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let x = use_mut(&cx, || 0);
+                use_ref(&cx, || Mut::set(x, 1));
+            }
+        }
+
+        #[derive(Data)]
+        struct Outer {
+            inner_updater: DeferredUpdater,
+        }
+
+        impl Compose for Outer {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let inner = use_ref(&cx, || {
+                    RefCell::new(Composer::with_updater(Inner, cx.me().inner_updater.clone()))
+                });
+                inner.borrow_mut().compose();
+
+                // The outer runtime is current again once the inner composer is done.
+                Runtime::current().update(|| {});
+            }
+        }
+
+        let outer_updater = DeferredUpdater::default();
+        let inner_updater = DeferredUpdater::default();
+        let mut composer = Composer::with_updater(
+            Outer {
+                inner_updater: inner_updater.clone(),
+            },
+            outer_updater.clone(),
+        );
+
+        composer.compose();
+        assert!(Runtime::try_current().is_none());
+
+        assert_eq!(inner_updater.apply(), 1);
+        assert_eq!(outer_updater.apply(), 1);
+    }
 }
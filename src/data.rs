@@ -1,7 +1,8 @@
 use crate::prelude::*;
-use std::collections::HashMap;
+use alloc::{borrow, boxed::Box, string::String, vec::Vec};
+use core::any::Any;
 
-pub use actuate_macros::Data;
+pub use actuate_macros::{composable, memo, Data};
 
 /// Composable data.
 ///
@@ -18,6 +19,12 @@ pub unsafe trait Data: Sized {
         let x = ptr as *mut Self;
         *x = self;
     }
+
+    #[doc(hidden)]
+    fn memo_changed(&self, last: &mut MemoState) -> bool {
+        let _ = last;
+        true
+    }
 }
 
 macro_rules! impl_data_for_std {
@@ -51,19 +58,45 @@ impl_data_for_std!(
 
 unsafe impl Data for &str {}
 
+unsafe impl Data for borrow::Cow<'_, str> {}
+
+unsafe impl<T: Data> Data for &[T] {}
+
 unsafe impl<T: Data> Data for Vec<T> {}
 
-unsafe impl<T: Data, U: Data> Data for HashMap<T, U> {}
+unsafe impl<T: Data, const N: usize> Data for [T; N] {}
+
+#[cfg(feature = "std")]
+unsafe impl<T: Data, U: Data> Data for std::collections::HashMap<T, U> {}
+
+unsafe impl<T: Data, U: Data> Data for hashbrown::HashMap<T, U> {}
 
 unsafe impl<T: Data> Data for &T {}
 
 unsafe impl<T: Data> Data for Option<T> {}
 
+unsafe impl<T: Data, E: 'static> Data for Result<T, E> {}
+
 unsafe impl Data for DynCompose<'_> {}
 
 #[doc(hidden)]
 pub struct FieldWrap<T>(pub T);
 
+/// Last memoized fields of a composable, stored by [`memo_changed`].
+#[doc(hidden)]
+pub type MemoState = Option<Box<dyn Any>>;
+
+/// Compare `memoized` fields with the `last` memoized fields, storing them if they've changed.
+#[doc(hidden)]
+pub fn memo_changed<T: PartialEq + 'static>(last: &mut MemoState, memoized: T) -> bool {
+    if last.as_ref().and_then(|last| last.downcast_ref::<T>()) == Some(&memoized) {
+        return false;
+    }
+
+    *last = Some(Box::new(memoized));
+    true
+}
+
 #[doc(hidden)]
 pub unsafe trait StateField {
     fn check(&self) {
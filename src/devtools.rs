@@ -0,0 +1,157 @@
+use crate::{composer::Runtime, ScopeData};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
+/// Snapshot of a scope in a tree of composables.
+///
+/// This can be created with [`Composer::tree`](crate::composer::Composer::tree).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScopeNode {
+    /// Name of the composable in this scope, if it has been composed.
+    pub name: Option<Cow<'static, str>>,
+
+    /// Current generation of this scope, incremented each time this scope is visited.
+    pub generation: u64,
+
+    /// `true` if this scope is changed.
+    pub is_changed: bool,
+
+    /// `true` if an ancestor to this scope is changed.
+    pub is_parent_changed: bool,
+
+    /// `true` if a descendant of this scope is changed.
+    pub is_child_changed: bool,
+
+    /// `true` if this scope contains a container composable.
+    pub is_container: bool,
+
+    /// Number of hooks used by this scope.
+    pub hooks: usize,
+
+    /// Child scopes, in the order they were first composed.
+    pub children: Vec<ScopeNode>,
+}
+
+impl ScopeNode {
+    pub(crate) fn new(scope: &ScopeData) -> Self {
+        Self {
+            name: scope.name.get().and_then(|name| name()),
+            generation: scope.generation.get(),
+            is_changed: scope.is_changed.get(),
+            is_parent_changed: scope.is_parent_changed.get(),
+            is_child_changed: scope.is_child_changed.get(),
+            is_container: scope.is_container.get(),
+            // Safety: Hooks are only mutated while composing this scope.
+            hooks: unsafe { (*scope.hooks.get()).len() },
+            children: scope
+                .children
+                .borrow()
+                .iter()
+                // Safety: Children remove themselves from this list when dropped.
+                .map(|child| ScopeNode::new(unsafe { child.as_ref() }))
+                .collect(),
+        }
+    }
+}
+
+/// Event sent to devtools subscribers.
+///
+/// Subscribers can be added with [`Composer::subscribe`](crate::composer::Composer::subscribe).
+#[derive(Clone, Debug, PartialEq)]
+pub enum DevtoolsEvent {
+    /// A composable was composed.
+    Composed {
+        /// Name of the composable.
+        name: Option<Cow<'static, str>>,
+        /// Generation of the composable's scope.
+        generation: u64,
+    },
+}
+
+pub(crate) type SubscriberFn = Box<dyn FnMut(&DevtoolsEvent)>;
+
+/// Send a [`DevtoolsEvent::Composed`] event for `scope` to each subscriber of the current runtime.
+pub(crate) fn emit(scope: &ScopeData) {
+    let rt = Runtime::current();
+    let mut subscribers = rt.subscribers.borrow_mut();
+    if subscribers.is_empty() {
+        return;
+    }
+
+    let event = DevtoolsEvent::Composed {
+        name: scope.name.get().and_then(|name| name()),
+        generation: scope.generation.get(),
+    };
+
+    for f in subscribers.iter_mut() {
+        f(&event);
+    }
+}
+
+/// Get the path of composable names from the root to `scope`.
+#[cfg(feature = "ui")]
+pub(crate) fn path(scope: &ScopeData) -> Vec<Cow<'static, str>> {
+    let mut path = Vec::new();
+    if let Some(name) = scope.name.get().and_then(|name| name()) {
+        path.push(name);
+    }
+
+    let mut parent = scope.parent.get();
+    while let Some(scope) = parent {
+        // Safety: Parent scopes outlive their children.
+        let scope = unsafe { scope.as_ref() };
+        if let Some(name) = scope.name.get().and_then(|name| name()) {
+            path.push(name);
+        }
+        parent = scope.parent.get();
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use super::DevtoolsEvent;
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn it_inspects_trees() {
+        #[derive(Data)]
+        struct B;
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_ref(&cx, || ());
+            }
+        }
+
+        #[derive(Data)]
+        struct A;
+
+        impl Compose for A {
+            fn compose(_cx: Scope<Self>) -> impl Compose {
+                B
+            }
+        }
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A);
+
+        let events_clone = events.clone();
+        composer.subscribe(move |event| {
+            let DevtoolsEvent::Composed { name, .. } = event;
+            events_clone.borrow_mut().push(name.clone().unwrap());
+        });
+
+        composer.compose();
+        assert_eq!(*events.borrow(), ["A", "B"]);
+
+        let tree = composer.tree();
+        assert_eq!(tree.name.as_deref(), Some("A"));
+
+        let b = &tree.children[0];
+        assert_eq!(b.name.as_deref(), Some("B"));
+        assert_eq!(b.hooks, 3);
+    }
+}
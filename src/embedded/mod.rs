@@ -0,0 +1,574 @@
+use crate::{
+    composer::{Composer, Update, Updater},
+    prelude::*,
+};
+use alloc::{rc::Rc, string::String, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    mem,
+};
+use embedded_graphics::{
+    geometry::Size,
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Baseline, Text as TextDrawable},
+};
+use slotmap::{DefaultKey, SlotMap};
+
+mod view;
+pub use self::view::{Button, Stack, Text};
+
+/// Input from buttons or a rotary encoder.
+///
+/// Focus moves between focusable views (such as a [`Button`]) with [`Input::Previous`] and [`Input::Next`],
+/// and other inputs are sent to the focused view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Input {
+    /// Move the focus to the previous view, such as from an up button or turning an encoder counterclockwise.
+    Previous,
+    /// Move the focus to the next view, such as from a down button or turning an encoder clockwise.
+    Next,
+    /// Activate the focused view, such as from a select button or pressing an encoder.
+    Select,
+    /// Go back, such as from a back button.
+    Back,
+}
+
+/// Direction of the children of a [`Stack`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Axis {
+    #[default]
+    Column,
+    Row,
+}
+
+/// Layout of a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NodeLayout {
+    /// Stack of child nodes.
+    Stack {
+        axis: Axis,
+        spacing: u32,
+        padding: u32,
+    },
+    /// Leaf node with a fixed size.
+    Leaf { size: Size },
+}
+
+type NodeDrawFn<'a> = Rc<dyn Fn(Rectangle, bool, &mut Recording) + 'a>;
+
+type NodeInputFn<'a> = Rc<dyn Fn(Input) + 'a>;
+
+struct Node {
+    layout: NodeLayout,
+    children: Vec<DefaultKey>,
+    draw: Option<NodeDrawFn<'static>>,
+    on_input: Option<NodeInputFn<'static>>,
+    is_focusable: bool,
+    /// Bounds of this node from the last frame.
+    bounds: Rectangle,
+}
+
+impl Node {
+    fn new(layout: NodeLayout) -> Self {
+        Self {
+            layout,
+            children: Vec::new(),
+            draw: None,
+            on_input: None,
+            is_focusable: false,
+            bounds: Rectangle::zero(),
+        }
+    }
+}
+
+/// Node tree of a screen, shared by each view inside it.
+#[derive(Clone)]
+struct ScreenContext {
+    nodes: Rc<RefCell<SlotMap<DefaultKey, Node>>>,
+    root: DefaultKey,
+    focused: Rc<Cell<Option<DefaultKey>>>,
+}
+
+impl ScreenContext {
+    fn new() -> Self {
+        let mut nodes = SlotMap::new();
+        let root = nodes.insert(Node::new(NodeLayout::Stack {
+            axis: Axis::Column,
+            spacing: 0,
+            padding: 0,
+        }));
+
+        Self {
+            nodes: Rc::new(RefCell::new(nodes)),
+            root,
+            focused: Rc::default(),
+        }
+    }
+
+    /// Get the focusable nodes in drawing order.
+    fn focusable(&self) -> Vec<DefaultKey> {
+        let nodes = self.nodes.borrow();
+        let mut keys = Vec::new();
+        let mut stack = alloc::vec![self.root];
+        while let Some(key) = stack.pop() {
+            let node = &nodes[key];
+            if node.is_focusable {
+                keys.push(key);
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        keys
+    }
+
+    /// Move the focus to the next (or previous if `is_reverse`) focusable node, wrapping around.
+    fn traverse_focus(&self, is_reverse: bool) {
+        let focusable = self.focusable();
+        if focusable.is_empty() {
+            return;
+        }
+
+        let idx = self
+            .focused
+            .get()
+            .and_then(|focused| focusable.iter().position(|key| *key == focused));
+        let next = match (idx, is_reverse) {
+            (None, false) => 0,
+            (None, true) => focusable.len() - 1,
+            (Some(idx), false) => (idx + 1) % focusable.len(),
+            (Some(idx), true) => (idx + focusable.len() - 1) % focusable.len(),
+        };
+        self.focused.set(Some(focusable[next]));
+    }
+}
+
+/// Measure the size of `key` and its children.
+fn measure(nodes: &SlotMap<DefaultKey, Node>, key: DefaultKey) -> Size {
+    let node = &nodes[key];
+    match node.layout {
+        NodeLayout::Leaf { size } => size,
+        NodeLayout::Stack {
+            axis,
+            spacing,
+            padding,
+        } => {
+            let mut size = Size::zero();
+            for (idx, child) in node.children.iter().enumerate() {
+                let child_size = measure(nodes, *child);
+                let gap = if idx > 0 { spacing } else { 0 };
+                size = match axis {
+                    Axis::Column => Size::new(
+                        size.width.max(child_size.width),
+                        size.height + gap + child_size.height,
+                    ),
+                    Axis::Row => Size::new(
+                        size.width + gap + child_size.width,
+                        size.height.max(child_size.height),
+                    ),
+                };
+            }
+            size + Size::new_equal(padding * 2)
+        }
+    }
+}
+
+/// Place `key` and its children at `origin`, storing the bounds of each node.
+fn arrange(nodes: &mut SlotMap<DefaultKey, Node>, key: DefaultKey, origin: Point) {
+    let size = measure(nodes, key);
+    nodes[key].bounds = Rectangle::new(origin, size);
+
+    let NodeLayout::Stack {
+        axis,
+        spacing,
+        padding,
+    } = nodes[key].layout
+    else {
+        return;
+    };
+
+    let mut pos = origin + Point::new_equal(padding as i32);
+    for child in nodes[key].children.clone() {
+        arrange(nodes, child, pos);
+        let child_size = nodes[child].bounds.size;
+        pos += match axis {
+            Axis::Column => Point::new(0, (child_size.height + spacing) as i32),
+            Axis::Row => Point::new((child_size.width + spacing) as i32, 0),
+        };
+    }
+}
+
+/// Drawing command recorded by a view.
+enum Command {
+    Fill(Rectangle, Rgb888),
+    Stroke(Rectangle, Rgb888),
+    Text(Point, String, Rgb888),
+}
+
+/// Drawing recorded by each view in a frame, replayed into a [`DrawTarget`] of any color type.
+#[derive(Default)]
+struct Recording {
+    commands: Vec<Command>,
+}
+
+impl Recording {
+    fn fill(&mut self, rect: Rectangle, color: Rgb888) {
+        self.commands.push(Command::Fill(rect, color));
+    }
+
+    fn stroke(&mut self, rect: Rectangle, color: Rgb888) {
+        self.commands.push(Command::Stroke(rect, color));
+    }
+
+    fn text(&mut self, pos: Point, text: &str, color: Rgb888) {
+        self.commands
+            .push(Command::Text(pos, String::from(text), color));
+    }
+
+    fn replay<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+        D::Color: From<Rgb888>,
+    {
+        for command in &self.commands {
+            match command {
+                Command::Fill(rect, color) => rect
+                    .into_styled(PrimitiveStyle::with_fill((*color).into()))
+                    .draw(target)?,
+                Command::Stroke(rect, color) => rect
+                    .into_styled(PrimitiveStyle::with_stroke((*color).into(), 1))
+                    .draw(target)?,
+                Command::Text(pos, text, color) => {
+                    text_drawable(text, *pos, (*color).into()).draw(target)?;
+                }
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Create a drawable of `text` in the default font, with its top left corner at `pos`.
+fn text_drawable<C: PixelColor>(
+    text: &str,
+    pos: Point,
+    color: C,
+) -> TextDrawable<'_, MonoTextStyle<'static, C>> {
+    TextDrawable::with_baseline(
+        text,
+        pos,
+        MonoTextStyle::new(&FONT_6X10, color),
+        Baseline::Top,
+    )
+}
+
+/// Measure the size of `text` in the default font.
+fn text_size(text: &str) -> Size {
+    if text.is_empty() {
+        return Size::zero();
+    }
+    text_drawable(text, Point::zero(), Rgb888::WHITE)
+        .bounding_box()
+        .size
+}
+
+/// Parent of the nodes created by composables.
+#[derive(Clone, Copy)]
+struct ParentContext {
+    key: DefaultKey,
+}
+
+/// Use a new node in the current screen.
+fn use_node(cx: ScopeState, layout: NodeLayout) -> DefaultKey {
+    let screen_cx = use_context::<ScreenContext>(cx).unwrap();
+    let parent_cx = use_context::<ParentContext>(cx).unwrap();
+
+    let key = *use_ref(cx, || {
+        let mut nodes = screen_cx.nodes.borrow_mut();
+        let key = nodes.insert(Node::new(layout));
+        nodes[parent_cx.key].children.push(key);
+        key
+    });
+    screen_cx.nodes.borrow_mut()[key].layout = layout;
+
+    let parent_key = parent_cx.key;
+    use_drop(cx, move || {
+        let mut nodes = screen_cx.nodes.borrow_mut();
+        nodes.remove(key);
+        if let Some(parent) = nodes.get_mut(parent_key) {
+            parent.children.retain(|child| *child != key);
+        }
+        if screen_cx.focused.get() == Some(key) {
+            screen_cx.focused.set(None);
+        }
+    });
+
+    key
+}
+
+/// Set the drawing and input handlers of the node `key`.
+///
+/// Handlers may borrow from the composable that created `key`, as they're removed when it's dropped.
+fn set_node<'a>(
+    cx: ScopeState<'a>,
+    key: DefaultKey,
+    draw: Option<NodeDrawFn<'a>>,
+    on_input: Option<NodeInputFn<'a>>,
+    is_focusable: bool,
+) {
+    let screen_cx = use_context::<ScreenContext>(cx).unwrap();
+
+    let mut nodes = screen_cx.nodes.borrow_mut();
+    let node = &mut nodes[key];
+
+    // Safety: Each handler is removed from `nodes` when the node is dropped in `use_node`.
+    unsafe {
+        node.draw = draw.map(|f| mem::transmute::<NodeDrawFn, NodeDrawFn<'static>>(f));
+        node.on_input = on_input.map(|f| mem::transmute::<NodeInputFn, NodeInputFn<'static>>(f));
+    }
+    node.is_focusable = is_focusable;
+}
+
+#[derive(Data)]
+struct ScreenRoot<C> {
+    screen_cx: ScreenContext,
+    content: C,
+}
+
+impl<C: Compose> Compose for ScreenRoot<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let screen_cx = use_provider(&cx, || cx.me().screen_cx.clone());
+        use_provider(&cx, || ParentContext {
+            key: screen_cx.root,
+        });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Updater for a [`Screen`] that's drawn manually.
+///
+/// Queued updates are applied with the next call to [`Screen::draw`].
+struct PollUpdater;
+
+impl Updater for PollUpdater {
+    fn update(&self, _update: Update) {}
+}
+
+/// Composed content drawn to an `embedded-graphics` display.
+///
+/// Views are drawn in 24-bit color and converted to the color type of the display,
+/// such as `Rgb565` or `BinaryColor` for monochrome displays (where light colors are drawn as `On`).
+///
+/// Without the `std` feature, the program must call the unsafe
+/// [`assume_single_threaded`](crate::composer::assume_single_threaded) before drawing.
+///
+/// ```no_run
+/// use actuate::{embedded::{Button, Input, Screen, Stack, Text}, prelude::*};
+/// use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+///
+/// #[derive(Data)]
+/// struct Counter;
+///
+/// impl Compose for Counter {
+///     fn compose(cx: Scope<Self>) -> impl Compose {
+///         let count = use_mut(&cx, || 0);
+///
+///         Stack::column((
+///             Text::new(format!("Count: {}", *count)),
+///             Button::new("Add").on_press(move || Mut::update(count, |x| *x += 1)),
+///         ))
+///     }
+/// }
+///
+/// let mut display = MockDisplay::<BinaryColor>::new();
+/// let mut screen = Screen::new(Counter);
+/// screen.draw(&mut display).unwrap();
+///
+/// // Press the focused button.
+/// screen.input(Input::Select);
+/// screen.draw(&mut display).unwrap();
+/// ```
+pub struct Screen {
+    composer: Composer,
+    screen_cx: ScreenContext,
+    background: Rgb888,
+}
+
+impl Screen {
+    /// Create a new screen from its content.
+    ///
+    /// The content is composed on the first call to [`Screen::draw`].
+    pub fn new(content: impl Compose + 'static) -> Self {
+        Self::with_updater(content, PollUpdater)
+    }
+
+    /// Create a new screen from its content and updater.
+    ///
+    /// The updater is called when updates are queued (such as from a task),
+    /// and should wake the main loop of the program to draw this screen again.
+    pub fn with_updater(content: impl Compose + 'static, updater: impl Updater + 'static) -> Self {
+        let screen_cx = ScreenContext::new();
+
+        Self {
+            composer: Composer::with_updater(
+                ScreenRoot {
+                    screen_cx: screen_cx.clone(),
+                    content,
+                },
+                updater,
+            ),
+            screen_cx,
+            background: Rgb888::BLACK,
+        }
+    }
+
+    /// Set the background color of this screen (defaults to black).
+    pub fn with_background(mut self, color: impl Into<Rgb888>) -> Self {
+        self.background = color.into();
+        self
+    }
+
+    /// Send an input to this screen.
+    ///
+    /// [`Input::Previous`] and [`Input::Next`] move the focus,
+    /// and other inputs are sent to the focused view.
+    pub fn input(&mut self, input: Input) {
+        let _rt = self.composer.runtime().enter();
+
+        match input {
+            Input::Previous => self.screen_cx.traverse_focus(true),
+            Input::Next => self.screen_cx.traverse_focus(false),
+            Input::Select | Input::Back => {
+                let Some(focused) = self.screen_cx.focused.get() else {
+                    return;
+                };
+                let on_input = self
+                    .screen_cx
+                    .nodes
+                    .borrow()
+                    .get(focused)
+                    .and_then(|node| node.on_input.clone());
+                if let Some(on_input) = on_input {
+                    on_input(input);
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the view at `key` is focused.
+    fn is_focused(&self, key: DefaultKey) -> bool {
+        self.screen_cx.focused.get() == Some(key)
+    }
+
+    /// Apply any queued updates and recompose the content, then draw it to `target`.
+    ///
+    /// The target is cleared with the background color before drawing.
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget,
+        D::Color: From<Rgb888>,
+    {
+        self.composer.compose();
+
+        // Focus the first focusable view, so inputs can be handled right away.
+        if self.screen_cx.focused.get().is_none() {
+            self.screen_cx.traverse_focus(false);
+        }
+
+        let origin = target.bounding_box().top_left;
+        let mut recording = Recording::default();
+        {
+            let mut nodes = self.screen_cx.nodes.borrow_mut();
+            arrange(&mut nodes, self.screen_cx.root, origin);
+
+            let mut stack = alloc::vec![self.screen_cx.root];
+            while let Some(key) = stack.pop() {
+                let node = &nodes[key];
+                if let Some(draw) = &node.draw {
+                    draw(node.bounds, self.is_focused(key), &mut recording);
+                }
+                stack.extend(node.children.iter().rev());
+            }
+        }
+
+        target.clear(self.background.into())?;
+        recording.replay(target)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{Button, Input, Screen, Stack, Text};
+    use crate::prelude::*;
+    use alloc::{rc::Rc, vec::Vec};
+    use core::cell::RefCell;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        mono_font::{ascii::FONT_6X10, MonoTextStyle},
+        pixelcolor::{BinaryColor, Rgb565},
+        prelude::*,
+        text::{Baseline, Text as TextDrawable},
+    };
+
+    #[test]
+    fn it_draws_text_to_monochrome_displays() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        display.set_allow_overdraw(true);
+        Screen::new(Text::new("Hi")).draw(&mut display).unwrap();
+
+        let mut expected = MockDisplay::<BinaryColor>::new();
+        expected.set_allow_overdraw(true);
+        expected.clear(BinaryColor::Off).unwrap();
+        TextDrawable::with_baseline(
+            "Hi",
+            Point::zero(),
+            MonoTextStyle::new(&FONT_6X10, BinaryColor::On),
+            Baseline::Top,
+        )
+        .draw(&mut expected)
+        .unwrap();
+
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn it_moves_focus_and_presses_buttons() {
+        #[derive(Data)]
+        struct App {
+            presses: Rc<RefCell<Vec<&'static str>>>,
+        }
+
+        impl Compose for App {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let a = cx.me().presses.clone();
+                let b = cx.me().presses.clone();
+
+                Stack::column((
+                    Button::new("A").on_press(move || a.borrow_mut().push("A")),
+                    Button::new("B").on_press(move || b.borrow_mut().push("B")),
+                ))
+                .with_spacing(2)
+            }
+        }
+
+        let presses = Rc::new(RefCell::new(Vec::new()));
+        let mut display = MockDisplay::<Rgb565>::new();
+        display.set_allow_overdraw(true);
+        let mut screen = Screen::new(App {
+            presses: presses.clone(),
+        });
+        screen.draw(&mut display).unwrap();
+
+        // The first button is focused in the first frame.
+        screen.input(Input::Select);
+        screen.input(Input::Next);
+        screen.input(Input::Select);
+
+        // Focus wraps around to the first button.
+        screen.input(Input::Next);
+        screen.input(Input::Select);
+
+        assert_eq!(*presses.borrow(), ["A", "B", "A"]);
+    }
+}
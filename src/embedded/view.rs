@@ -0,0 +1,198 @@
+use super::{
+    set_node, text_size, use_node, Axis, Input, NodeDrawFn, NodeInputFn, NodeLayout, ParentContext,
+    Recording,
+};
+use crate::prelude::*;
+use alloc::{
+    boxed::Box,
+    rc::Rc,
+    string::{String, ToString},
+};
+use core::fmt;
+use embedded_graphics::{geometry::Size, pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
+
+/// Stack composable.
+///
+/// This lays out its content in a column or row, sized to fit its children.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with an `embedded::Screen` or returned from other composables"]
+pub struct Stack<C> {
+    axis: Axis,
+    spacing: u32,
+    padding: u32,
+    content: C,
+}
+
+impl<C> Stack<C> {
+    /// Create a new column of the given `content`.
+    pub fn column(content: C) -> Self {
+        Self {
+            axis: Axis::Column,
+            spacing: 0,
+            padding: 0,
+            content,
+        }
+    }
+
+    /// Create a new row of the given `content`.
+    pub fn row(content: C) -> Self {
+        Self {
+            axis: Axis::Row,
+            ..Self::column(content)
+        }
+    }
+
+    /// Set the spacing between children, in pixels (defaults to `0`).
+    pub fn with_spacing(mut self, spacing: u32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the padding around the children, in pixels (defaults to `0`).
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+}
+
+impl<C: Compose> Compose for Stack<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let me = cx.me();
+        let key = use_node(
+            &cx,
+            NodeLayout::Stack {
+                axis: me.axis,
+                spacing: me.spacing,
+                padding: me.padding,
+            },
+        );
+
+        use_provider(&cx, || ParentContext { key });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Text composable.
+///
+/// Text is drawn in a 6x10 pixel monospace font, and broken into lines only at explicit line breaks.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with an `embedded::Screen` or returned from other composables"]
+pub struct Text<T> {
+    content: T,
+    color: Rgb888,
+}
+
+impl<T> Text<T> {
+    /// Create a new text from the given `content`.
+    pub fn new(content: T) -> Self {
+        Self {
+            content,
+            color: Rgb888::WHITE,
+        }
+    }
+
+    /// Set the color of this text (defaults to white).
+    pub fn with_color(mut self, color: impl Into<Rgb888>) -> Self {
+        self.color = color.into();
+        self
+    }
+}
+
+impl<T> Compose for Text<T>
+where
+    T: Data + fmt::Display,
+{
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let content = cx.me().content.to_string();
+        let content = use_memo(&cx, content.clone(), || content);
+        let key = use_node(
+            &cx,
+            NodeLayout::Leaf {
+                size: text_size(&content),
+            },
+        );
+
+        let draw: NodeDrawFn = Rc::new(move |bounds: Rectangle, _, recording: &mut Recording| {
+            recording.text(bounds.top_left, &content, cx.me().color);
+        });
+        set_node(&cx, key, Some(draw), None, false);
+    }
+}
+
+/// Button composable.
+///
+/// Buttons can be focused with [`Input::Previous`] and [`Input::Next`], and pressed with [`Input::Select`].
+/// The focused button is drawn filled in with its color.
+#[must_use = "Composables do nothing unless composed with an `embedded::Screen` or returned from other composables"]
+pub struct Button<'a> {
+    label: String,
+    color: Rgb888,
+    on_press: Box<dyn Fn() + 'a>,
+}
+
+impl<'a> Button<'a> {
+    /// Create a new button from its label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            color: Rgb888::WHITE,
+            on_press: Box::new(|| {}),
+        }
+    }
+
+    /// Set the color of this button's border and label (defaults to white).
+    pub fn with_color(mut self, color: impl Into<Rgb888>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the function called when this button is pressed.
+    pub fn on_press(mut self, on_press: impl Fn() + 'a) -> Self {
+        self.on_press = Box::new(on_press);
+        self
+    }
+}
+
+unsafe impl Data for Button<'_> {}
+
+/// Space between the border and label of a [`Button`], in pixels.
+const BUTTON_PADDING: u32 = 2;
+
+impl Compose for Button<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        // The label is surrounded by padding and a 1 pixel border.
+        let inset = BUTTON_PADDING + 1;
+        let key = use_node(
+            &cx,
+            NodeLayout::Leaf {
+                size: text_size(&cx.me().label) + Size::new_equal(inset * 2),
+            },
+        );
+
+        let draw: NodeDrawFn = Rc::new(
+            move |bounds: Rectangle, is_focused, recording: &mut Recording| {
+                let me = cx.me();
+                let label_pos = bounds.top_left + Point::new_equal(inset as i32);
+                if is_focused {
+                    recording.fill(bounds, me.color);
+                    recording.text(label_pos, &me.label, invert(me.color));
+                } else {
+                    recording.stroke(bounds, me.color);
+                    recording.text(label_pos, &me.label, me.color);
+                }
+            },
+        );
+        let on_input: NodeInputFn = Rc::new(move |input| {
+            if input == Input::Select {
+                (cx.me().on_press)();
+            }
+        });
+        set_node(&cx, key, Some(draw), Some(on_input), true);
+    }
+}
+
+/// Invert `color`, to draw content on top of it.
+fn invert(color: Rgb888) -> Rgb888 {
+    Rgb888::new(255 - color.r(), 255 - color.g(), 255 - color.b())
+}
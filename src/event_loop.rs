@@ -1,61 +1,164 @@
 use crate::{
+    compose::AnyCompose,
     composer::{Composer, Update, Updater},
     prelude::*,
+    ScopeData,
 };
-use std::{cell::RefCell, collections::HashMap, mem, rc::Rc, sync::mpsc, thread};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    marker::PhantomData,
+    mem,
+    rc::{Rc, Weak},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{sync::mpsc, thread};
+use web_time::Instant;
+#[cfg(target_os = "android")]
+use winit::platform::android::activity::AndroidApp;
 use winit::{
     application::ApplicationHandler,
-    event::{Event, WindowEvent},
-    event_loop::{ActiveEventLoop, EventLoop},
-    window::{Window as RawWindow, WindowAttributes, WindowId},
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size},
+    event::{ElementState, Event, MouseButton, Touch, TouchPhase, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    window::{Fullscreen, Theme, Window as RawWindow, WindowAttributes, WindowId},
 };
 
 /// Run this content on the system event loop with a provided task executor.
+///
+/// On the web, this returns immediately and the event loop keeps running in the browser.
 pub fn run(content: impl Compose + 'static) {
-    let event_loop = EventLoop::with_user_event().build().unwrap();
+    let event_loop = EventLoop::<UserEvent<()>>::with_user_event()
+        .build()
+        .unwrap();
+    run_event_loop(event_loop, EventLoopContext::default(), content)
+}
+
+/// Run this content on an event loop created by the app, with its own user events of type `T`.
+///
+/// Events sent with an [`EventLoopProxy`](winit::event_loop::EventLoopProxy) of this event loop
+/// (wrapped in [`UserEvent::new`]) are handled by composables with [`use_event_loop_events`].
+pub fn run_with_event_loop<T: Send + 'static>(
+    event_loop: EventLoop<UserEvent<T>>,
+    content: impl Compose + 'static,
+) {
+    run_event_loop(event_loop, EventLoopContext::default(), content)
+}
+
+/// Run this content on the event loop of an Android app.
+///
+/// This is called from the `android_main` function of a native or game activity,
+/// with the `android-native-activity` or `android-game-activity` feature.
+#[cfg(target_os = "android")]
+#[cfg_attr(docsrs, doc(cfg(target_os = "android")))]
+pub fn run_android(app: AndroidApp, content: impl Compose + 'static) {
+    use winit::platform::android::EventLoopBuilderExtAndroid;
+
+    let event_loop = EventLoop::<UserEvent<()>>::with_user_event()
+        .with_android_app(app.clone())
+        .build()
+        .unwrap();
+
+    let cx = EventLoopContext::default();
+    cx.inner.borrow_mut().android_app = Some(app);
+
+    run_event_loop(event_loop, cx, content)
+}
 
+fn run_event_loop<T: Send + 'static>(
+    event_loop: EventLoop<UserEvent<T>>,
+    cx: EventLoopContext,
+    content: impl Compose + 'static,
+) {
     let proxy = event_loop.create_proxy();
-    let (tx, rx) = mpsc::channel();
-    thread::spawn(move || {
-        while let Ok(update) = rx.recv() {
-            let mut updates = vec![update];
-            while let Ok(next_update) = rx.try_recv() {
-                updates.push(next_update);
-            }
 
-            if proxy.send_event(updates).is_err() {
-                panic!("Failed to send update to event loop.");
+    #[cfg(not(target_arch = "wasm32"))]
+    let updater = {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(update) = rx.recv() {
+                let mut updates = vec![update];
+                while let Ok(next_update) = rx.try_recv() {
+                    updates.push(next_update);
+                }
+
+                if proxy.send_event(UserEvent::updates(updates)).is_err() {
+                    panic!("Failed to send update to event loop.");
+                }
             }
+        });
+        EventLoopUpdater::<T> {
+            tx,
+            _marker: PhantomData,
         }
-    });
+    };
 
-    let cx = EventLoopContext::default();
+    // Threads can't be spawned on the web, so updates are sent to the event loop directly.
+    #[cfg(target_arch = "wasm32")]
+    let updater = EventLoopUpdater::<T> { proxy };
 
-    let mut handler = Handler {
+    let handler = Handler {
         composer: Composer::with_updater(
             HandlerRoot {
                 content,
                 event_loop_cx: cx.clone(),
             },
-            EventLoopUpdater { tx },
+            updater,
         ),
         cx,
+        _marker: PhantomData,
     };
 
-    event_loop.run_app(&mut handler).unwrap();
+    #[cfg(not(target_arch = "wasm32"))]
+    event_loop.run_app(&mut { handler }).unwrap();
+
+    #[cfg(target_arch = "wasm32")]
+    winit::platform::web::EventLoopExtWebSys::spawn_app(event_loop, handler);
+}
+
+/// User event of an event loop run by this crate.
+///
+/// This wraps the app's own events of type `T`, sent with [`UserEvent::new`].
+pub struct UserEvent<T>(UserEventKind<T>);
+
+enum UserEventKind<T> {
+    Updates(Vec<Update>),
+    App(T),
 }
 
-struct UnsafeUpdate(Update);
+impl<T> UserEvent<T> {
+    /// Create a new user event from an app event.
+    pub fn new(event: T) -> Self {
+        Self(UserEventKind::App(event))
+    }
 
-unsafe impl Send for UnsafeUpdate {}
+    fn updates(updates: Vec<Update>) -> Self {
+        Self(UserEventKind::Updates(updates))
+    }
+}
 
-struct EventLoopUpdater {
-    tx: mpsc::Sender<UnsafeUpdate>,
+struct EventLoopUpdater<T: 'static> {
+    #[cfg(not(target_arch = "wasm32"))]
+    tx: mpsc::Sender<Update>,
+    #[cfg(not(target_arch = "wasm32"))]
+    _marker: PhantomData<fn() -> T>,
+    #[cfg(target_arch = "wasm32")]
+    proxy: winit::event_loop::EventLoopProxy<UserEvent<T>>,
 }
 
-impl Updater for EventLoopUpdater {
+impl<T: Send + 'static> Updater for EventLoopUpdater<T> {
     fn update(&self, update: Update) {
-        if self.tx.send(UnsafeUpdate(update)).is_err() {
+        #[cfg(not(target_arch = "wasm32"))]
+        let is_closed = self.tx.send(update).is_err();
+
+        #[cfg(target_arch = "wasm32")]
+        let is_closed = self
+            .proxy
+            .send_event(UserEvent::updates(vec![update]))
+            .is_err();
+
+        if is_closed {
             panic!("Failed to send update to event loop.");
         }
     }
@@ -70,18 +173,37 @@ unsafe impl<C: Data> Data for HandlerRoot<C> {}
 
 impl<C: Compose> Compose for HandlerRoot<C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
-        use_provider(&cx, || cx.me().event_loop_cx.clone());
+        let event_loop_cx = &cx.me().event_loop_cx;
+        use_provider(&cx, || event_loop_cx.clone());
+
+        // Monitors are refreshed by the handler when displays may have changed.
+        let monitors = use_signal(&cx, || {
+            available_monitors(event_loop_cx.inner.borrow().event_loop.unwrap())
+        });
+        use_ref(&cx, || {
+            event_loop_cx.inner.borrow_mut().monitors = Some(monitors.clone());
+        });
+        use_memo_provider(&cx, monitors.clone(), || Monitors(monitors.get()));
+
+        let system_info = use_signal(&cx, || {
+            SystemInfo::new(event_loop_cx.inner.borrow().event_loop.unwrap(), None)
+        });
+        use_ref(&cx, || {
+            event_loop_cx.inner.borrow_mut().system_info = Some(system_info.clone());
+        });
+        use_memo_provider(&cx, system_info.clone(), || system_info.get());
 
         Ref::map(cx.me(), |me| &me.content)
     }
 }
 
-struct Handler {
+struct Handler<T> {
     composer: Composer,
     cx: EventLoopContext,
+    _marker: PhantomData<fn(T)>,
 }
 
-impl Handler {
+impl<T> Handler<T> {
     fn compose(&mut self, event_loop: &ActiveEventLoop) {
         // Safety: This reference to `event_loop` must not escape the context.
         let event_loop: &'static ActiveEventLoop = unsafe { mem::transmute(event_loop) };
@@ -91,26 +213,102 @@ impl Handler {
 
         self.cx.inner.borrow_mut().event_loop = None;
     }
+
+    /// Update the available monitors, if they changed since they were last checked.
+    fn refresh_monitors(&self, event_loop: &ActiveEventLoop) {
+        let Some(signal) = self.cx.inner.borrow().monitors.clone() else {
+            return;
+        };
+
+        let monitors = available_monitors(event_loop);
+        if *signal.lock() != monitors {
+            signal.set(monitors);
+        }
+    }
+
+    /// Update the system settings, if they changed since they were last checked.
+    ///
+    /// `theme` is the latest theme reported by a window, used where the event loop can't read it.
+    fn refresh_system_info(&self, event_loop: &ActiveEventLoop, theme: Option<Theme>) {
+        let Some(signal) = self.cx.inner.borrow().system_info.clone() else {
+            return;
+        };
+
+        let last_theme = theme.or(signal.lock().theme);
+        let system_info = SystemInfo::new(event_loop, last_theme);
+        if *signal.lock() != system_info {
+            signal.set(system_info);
+        }
+    }
 }
 
-impl ApplicationHandler<Vec<UnsafeUpdate>> for Handler {
+impl<T: 'static> ApplicationHandler<UserEvent<T>> for Handler<T> {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        // Handle events in this composer's runtime, as event handlers may queue updates.
+        let _rt = self.composer.runtime().enter();
+
         #[cfg(feature = "tracing")]
         tracing::trace!("Resumed");
 
         self.compose(event_loop);
+        self.refresh_monitors(event_loop);
+        self.refresh_system_info(event_loop, None);
 
         for f in self.cx.inner.borrow_mut().handler_fns.values_mut() {
             f(&Event::Resumed)
         }
     }
 
-    fn user_event(&mut self, event_loop: &ActiveEventLoop, events: Vec<UnsafeUpdate>) {
+    fn suspended(&mut self, _event_loop: &ActiveEventLoop) {
+        let _rt = self.composer.runtime().enter();
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!("Suspended");
+
+        // Windows must drop their render surfaces, as their native windows may be destroyed (such as on Android).
+        let handler_fns: Vec<_> = self
+            .cx
+            .inner
+            .borrow()
+            .handler_fns
+            .values()
+            .cloned()
+            .collect();
+        for handler_fn in handler_fns {
+            handler_fn(&Event::Suspended);
+        }
+    }
+
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: UserEvent<T>) {
+        let _rt = self.composer.runtime().enter();
+
+        let events = match event.0 {
+            UserEventKind::Updates(events) => events,
+            UserEventKind::App(event) => {
+                #[cfg(feature = "tracing")]
+                tracing::trace!("App event");
+
+                // Listeners may be added or removed while handling this event.
+                let user_event_fns: Vec<_> = self
+                    .cx
+                    .inner
+                    .borrow()
+                    .user_event_fns
+                    .values()
+                    .cloned()
+                    .collect();
+                for user_event_fn in user_event_fns {
+                    user_event_fn(&event);
+                }
+                return;
+            }
+        };
+
         #[cfg(feature = "tracing")]
         tracing::trace!("Update");
 
         for event in events {
-            unsafe { event.0.apply() };
+            event.apply();
         }
 
         self.compose(event_loop);
@@ -126,14 +324,80 @@ impl ApplicationHandler<Vec<UnsafeUpdate>> for Handler {
         window_id: WindowId,
         event: WindowEvent,
     ) {
+        let _rt = self.composer.runtime().enter();
+
         self.compose(event_loop);
 
-        self.cx
+        // winit has no event for display changes, so monitors are checked when a window's
+        // position, size, or scale factor changes (such as after a monitor is unplugged).
+        if matches!(
+            event,
+            WindowEvent::Moved(_)
+                | WindowEvent::Resized(_)
+                | WindowEvent::ScaleFactorChanged { .. }
+        ) {
+            self.refresh_monitors(event_loop);
+        }
+
+        // System settings are also checked when a window regains focus,
+        // as the user may have changed them in another app.
+        match event {
+            WindowEvent::ThemeChanged(theme) => self.refresh_system_info(event_loop, Some(theme)),
+            WindowEvent::Focused(true) => self.refresh_system_info(event_loop, None),
+            _ => {}
+        }
+
+        // Events may still arrive for a window that was just removed.
+        let handler_fn = self.cx.inner.borrow().handler_fns.get(&window_id).cloned();
+        if let Some(handler_fn) = handler_fn {
+            handler_fn(&Event::WindowEvent {
+                window_id,
+                event: event.clone(),
+            });
+        }
+
+        // Listeners may be added or removed while handling this event.
+        let window_event_fns: Vec<_> = self
+            .cx
+            .inner
+            .borrow()
+            .window_event_fns
+            .get(&window_id)
+            .map(|fns| fns.values().cloned().collect())
+            .unwrap_or_default();
+        for window_event_fn in window_event_fns {
+            window_event_fn(&event);
+        }
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let _rt = self.composer.runtime().enter();
+
+        let handler_fns: Vec<_> = self
+            .cx
             .inner
-            .borrow_mut()
+            .borrow()
             .handler_fns
-            .get_mut(&window_id)
-            .unwrap()(&Event::WindowEvent { window_id, event });
+            .values()
+            .cloned()
+            .collect();
+        for handler_fn in handler_fns {
+            handler_fn(&Event::AboutToWait);
+        }
+
+        // Exit once the last window is closed, unless the event loop is kept alive.
+        let inner = self.cx.inner.borrow();
+        if inner.has_opened_window && inner.handler_fns.is_empty() && inner.keep_alive == 0 {
+            event_loop.exit();
+        }
+        drop(inner);
+
+        // Sleep until the earliest time requested by a window, or until the next event.
+        let control_flow = match self.cx.inner.borrow_mut().wake_at.take() {
+            Some(wake_at) => ControlFlow::WaitUntil(wake_at),
+            None => ControlFlow::Wait,
+        };
+        event_loop.set_control_flow(control_flow);
     }
 }
 
@@ -141,15 +405,52 @@ impl ApplicationHandler<Vec<UnsafeUpdate>> for Handler {
 struct Inner {
     handler_fns: HashMap<WindowId, ListenerFn<'static>>,
     event_loop: Option<&'static ActiveEventLoop>,
+    wake_at: Option<Instant>,
+    /// `true` once a window has been opened.
+    has_opened_window: bool,
+    /// Number of composables keeping the event loop alive without any windows.
+    keep_alive: usize,
+    /// Listeners of app events, added with [`use_event_loop_events`].
+    user_event_fns: HashMap<usize, UserEventFn<'static>>,
+    /// Listeners of events for each window, added with [`use_window_event`].
+    window_event_fns: HashMap<WindowId, HashMap<usize, WindowEventFn<'static>>>,
+    next_listener_id: usize,
+    /// Available monitors, provided to composables with [`use_monitors`].
+    monitors: Option<Signal<Vec<Monitor>>>,
+    /// System settings, provided to composables with [`use_system_info`].
+    system_info: Option<Signal<SystemInfo>>,
+    #[cfg(target_os = "android")]
+    android_app: Option<AndroidApp>,
 }
 
 #[derive(Clone, Default)]
-struct EventLoopContext {
+pub(crate) struct EventLoopContext {
     inner: Rc<RefCell<Inner>>,
 }
 
+impl EventLoopContext {
+    /// Wake the event loop at `wake_at`, if it would otherwise sleep past it.
+    ///
+    /// This must be called while handling [`Event::AboutToWait`].
+    #[cfg(feature = "ui")]
+    pub(crate) fn wake_at(&self, wake_at: Instant) {
+        let mut inner = self.inner.borrow_mut();
+        inner.wake_at = Some(inner.wake_at.map_or(wake_at, |last| last.min(wake_at)));
+    }
+
+    /// Get the Android app this event loop is running in, if started with [`run_android`].
+    #[cfg(target_os = "android")]
+    pub(crate) fn android_app(&self) -> Option<AndroidApp> {
+        self.inner.borrow().android_app.clone()
+    }
+}
+
 type ListenerFn<'a> = Rc<dyn Fn(&Event<()>) + 'a>;
 
+type UserEventFn<'a> = Rc<dyn Fn(&dyn Any) + 'a>;
+
+type WindowEventFn<'a> = Rc<dyn Fn(&WindowEvent) + 'a>;
+
 type EventFn<'a> = Box<dyn Fn(&RawWindow, &Event<()>) + 'a>;
 
 /// Base window composable.
@@ -178,37 +479,550 @@ impl<'a, C> Window<'a, C> {
 
 impl<C: Compose> Compose for Window<'_, C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        // The content is declared first, so it's dropped before its window.
+        let child_state = use_ref(&cx, || RefCell::new(Some(ScopeData::default())));
+
         let event_loop_cx = use_context::<EventLoopContext>(&cx).unwrap();
         let mut inner = event_loop_cx.inner.borrow_mut();
 
         let window = use_ref(&cx, || {
-            inner
+            let window = inner
                 .event_loop
                 .as_ref()
                 .unwrap()
                 .create_window(cx.me().window_attributes.clone())
-                .unwrap()
+                .unwrap();
+            inner.has_opened_window = true;
+            RefCell::new(Some(Rc::new(window)))
         });
 
-        use_memo(&cx, cx.me().window_attributes.title.clone(), || {
-            window.set_title(&cx.me().window_attributes.title);
-        });
+        let is_closed = use_signal(&cx, || false);
 
-        // TODO react to more attributes
+        let primary_touch = use_ref(&cx, || Cell::new(None));
+
+        let handle = use_provider(&cx, || {
+            let window = window.borrow();
+            let window = window.as_ref().unwrap();
+            WindowHandle {
+                id: window.id(),
+                window: Rc::downgrade(window),
+                is_closed: is_closed.clone(),
+            }
+        });
 
         let drop_inner = event_loop_cx.inner.clone();
-        let id = window.id();
+        let id = handle.id;
         use_drop(&cx, move || {
             drop_inner.borrow_mut().handler_fns.remove(&id);
         });
 
+        if is_closed.get() {
+            // Drop the content before its window.
+            *child_state.borrow_mut() = None;
+
+            if window.borrow_mut().take().is_some() {
+                inner.handler_fns.remove(&id);
+            }
+            return;
+        }
+
+        let Some(raw_window) = window.borrow().clone() else {
+            return;
+        };
+
+        use_memo(&cx, cx.me().window_attributes.title.clone(), || {
+            raw_window.set_title(&cx.me().window_attributes.title);
+        });
+
+        // TODO react to more attributes
+
         let on_event = &*cx.me().on_event;
-        let on_event: ListenerFn = Rc::new(move |event| on_event(window, event));
+        let on_event: ListenerFn = Rc::new(move |event| {
+            if let Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } = event
+            {
+                is_closed.set(true);
+            }
+
+            on_event(&raw_window, event);
+
+            // Touches of the primary finger are also sent as mouse input,
+            // so views handle taps and drags like clicks.
+            if let Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Touch(touch),
+            } = event
+            {
+                for event in touch_to_mouse(touch, primary_touch) {
+                    on_event(
+                        &raw_window,
+                        &Event::WindowEvent {
+                            window_id: *window_id,
+                            event,
+                        },
+                    );
+                }
+            }
+        });
         let on_event: ListenerFn = unsafe { mem::transmute(on_event) };
 
         inner.handler_fns.insert(id, on_event);
+        drop(inner);
 
-        // Safety: The pointer to `me.content` is guranteed to remain constant.
-        Ref::map(cx.me(), |me| &me.content)
+        let child_state = child_state.borrow();
+        let Some(state) = &*child_state else {
+            return;
+        };
+
+        state.inherit_contexts(&cx);
+
+        state.is_parent_changed.set(cx.is_parent_changed.get());
+
+        unsafe { cx.me().content.any_compose(state) }
+    }
+}
+
+/// Get the physical size of the area drawn to in `window`.
+///
+/// On iOS, the inner size of a window is its safe area, while content is drawn to the whole window.
+#[cfg(feature = "ui")]
+pub(crate) fn surface_size(window: &RawWindow) -> PhysicalSize<u32> {
+    #[cfg(target_os = "ios")]
+    return window.outer_size();
+
+    #[cfg(not(target_os = "ios"))]
+    window.inner_size()
+}
+
+/// Convert a touch to the mouse events it emulates, if it's from the primary finger.
+///
+/// The primary finger is the first finger touching the window, until it's lifted.
+pub(crate) fn touch_to_mouse(touch: &Touch, primary_touch: &Cell<Option<u64>>) -> Vec<WindowEvent> {
+    if touch.phase == TouchPhase::Started {
+        if primary_touch.get().is_some() {
+            return Vec::new();
+        }
+        primary_touch.set(Some(touch.id));
+    } else if primary_touch.get() != Some(touch.id) {
+        return Vec::new();
+    }
+
+    let moved = WindowEvent::CursorMoved {
+        device_id: touch.device_id,
+        position: touch.location,
+    };
+    let input = |state| WindowEvent::MouseInput {
+        device_id: touch.device_id,
+        state,
+        button: MouseButton::Left,
+    };
+
+    match touch.phase {
+        TouchPhase::Started => vec![moved, input(ElementState::Pressed)],
+        TouchPhase::Moved => vec![moved],
+        TouchPhase::Ended | TouchPhase::Cancelled => {
+            primary_touch.set(None);
+            vec![moved, input(ElementState::Released)]
+        }
+    }
+}
+
+/// Handle to the window containing a composable.
+///
+/// This can be created with [`use_window`].
+/// Once the window is closed, each method of this handle does nothing.
+#[derive(Clone)]
+pub struct WindowHandle {
+    id: WindowId,
+    window: Weak<RawWindow>,
+    is_closed: Signal<bool>,
+}
+
+impl WindowHandle {
+    fn with_window(&self, f: impl FnOnce(&RawWindow)) {
+        if let Some(window) = self.window.upgrade() {
+            f(&window)
+        }
+    }
+
+    /// Get the raw window, if it's still open.
+    #[cfg(feature = "menu")]
+    pub(crate) fn raw_window(&self) -> Option<Rc<RawWindow>> {
+        self.window.upgrade()
+    }
+
+    /// Get the ID of this window.
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Set the title of this window.
+    pub fn set_title(&self, title: &str) {
+        self.with_window(|window| window.set_title(title))
+    }
+
+    /// Request a new inner size for this window.
+    ///
+    /// The window may be resized asynchronously by the platform.
+    pub fn request_inner_size(&self, size: impl Into<Size>) {
+        let size = size.into();
+        self.with_window(|window| {
+            let _ = window.request_inner_size(size);
+        })
+    }
+
+    /// Set the minimum inner size of this window, or `None` to remove the constraint.
+    pub fn set_min_inner_size(&self, size: Option<impl Into<Size>>) {
+        let size = size.map(Into::into);
+        self.with_window(|window| window.set_min_inner_size(size))
+    }
+
+    /// Set the maximum inner size of this window, or `None` to remove the constraint.
+    pub fn set_max_inner_size(&self, size: Option<impl Into<Size>>) {
+        let size = size.map(Into::into);
+        self.with_window(|window| window.set_max_inner_size(size))
+    }
+
+    /// Set this window to borderless fullscreen on its current monitor, or back to windowed.
+    pub fn set_fullscreen(&self, is_fullscreen: bool) {
+        self.with_window(|window| {
+            window.set_fullscreen(is_fullscreen.then_some(Fullscreen::Borderless(None)))
+        })
+    }
+
+    /// Returns `true` if this window is fullscreen.
+    pub fn is_fullscreen(&self) -> bool {
+        self.window
+            .upgrade()
+            .is_some_and(|window| window.fullscreen().is_some())
+    }
+
+    /// Maximize or restore this window.
+    pub fn set_maximized(&self, is_maximized: bool) {
+        self.with_window(|window| window.set_maximized(is_maximized))
+    }
+
+    /// Returns `true` if this window is maximized.
+    pub fn is_maximized(&self) -> bool {
+        self.window
+            .upgrade()
+            .is_some_and(|window| window.is_maximized())
+    }
+
+    /// Minimize or restore this window.
+    pub fn set_minimized(&self, is_minimized: bool) {
+        self.with_window(|window| window.set_minimized(is_minimized))
+    }
+
+    /// Close this window, dropping its content.
+    ///
+    /// The event loop exits after its last window is closed,
+    /// unless it's kept alive with [`use_keep_alive`].
+    pub fn close(&self) {
+        self.is_closed.set(true);
+    }
+
+    /// Returns `true` if this window was closed.
+    pub fn is_closed(&self) -> bool {
+        self.window.strong_count() == 0
+    }
+}
+
+/// Use a handle to the window containing this composable.
+///
+/// Changes made through the returned [`WindowHandle`] are applied to the window directly,
+/// and closing the window drops its content with the next composition.
+pub fn use_window(cx: ScopeState<'_>) -> WindowHandle {
+    let handle =
+        use_context::<WindowHandle>(cx).expect("`use_window` must be used inside of a `Window`");
+    (*handle).clone()
+}
+
+/// Keep the event loop running while this composable exists, even without any open windows.
+///
+/// By default, the event loop exits after its last window is closed or removed.
+/// This can be used by background apps (for example, with a system tray icon)
+/// to re-open windows later on.
+pub fn use_keep_alive(cx: ScopeState<'_>) {
+    let event_loop_cx = use_context::<EventLoopContext>(cx).unwrap().clone();
+
+    use_ref(cx, || event_loop_cx.inner.borrow_mut().keep_alive += 1);
+    use_drop(cx, move || event_loop_cx.inner.borrow_mut().keep_alive -= 1);
+}
+
+/// Use app events of type `T` from the event loop, sent by the app with [`UserEvent::new`].
+///
+/// `on_event` is called with each event sent to the event loop passed to [`run_with_event_loop`],
+/// and can update state (such as with [`Signal::set`](crate::Signal::set)) to recompose.
+/// Events of other types are ignored.
+pub fn use_event_loop_events<'a, T: 'static>(cx: ScopeState<'a>, on_event: impl Fn(&T) + 'a) {
+    let event_loop_cx = use_context::<EventLoopContext>(cx).unwrap();
+
+    let id = *use_ref(cx, || {
+        let mut inner = event_loop_cx.inner.borrow_mut();
+        inner.next_listener_id += 1;
+        inner.next_listener_id
+    });
+
+    let drop_inner = event_loop_cx.inner.clone();
+    use_drop(cx, move || {
+        drop_inner.borrow_mut().user_event_fns.remove(&id);
+    });
+
+    let user_event_fn: UserEventFn = Rc::new(move |event: &dyn Any| {
+        if let Some(event) = event.downcast_ref::<T>() {
+            on_event(event)
+        }
+    });
+
+    // Safety: This listener is replaced with each composition, and removed when this scope is dropped.
+    let user_event_fn: UserEventFn<'static> = unsafe { mem::transmute(user_event_fn) };
+    event_loop_cx
+        .inner
+        .borrow_mut()
+        .user_event_fns
+        .insert(id, user_event_fn);
+}
+
+/// Use the latest event of the window containing this composable, mapped by `filter`.
+///
+/// `filter` is called with each event sent to this window, and returning `Some` stores that value
+/// and recomposes this composable. This returns `None` until the first matching event.
+///
+/// ```no_run
+/// use actuate::prelude::*;
+/// use winit::event::WindowEvent;
+///
+/// #[derive(Data)]
+/// struct FocusTitle;
+///
+/// impl Compose for FocusTitle {
+///     fn compose(cx: Scope<Self>) -> impl Compose {
+///         let is_focused = use_window_event(&cx, |event| match event {
+///             WindowEvent::Focused(is_focused) => Some(*is_focused),
+///             _ => None,
+///         });
+///
+///         let window = use_window(&cx);
+///         use_memo(&cx, is_focused, || match *is_focused {
+///             Some(false) => window.set_title("Paused"),
+///             _ => window.set_title("Running"),
+///         });
+///     }
+/// }
+/// ```
+pub fn use_window_event<'a, T: 'static>(
+    cx: ScopeState<'a>,
+    filter: impl Fn(&WindowEvent) -> Option<T> + 'a,
+) -> Ref<'a, Option<T>> {
+    let window_id = use_window(cx).id;
+    let event_loop_cx = use_context::<EventLoopContext>(cx).unwrap();
+
+    let value = use_mut(cx, || None);
+
+    let id = *use_ref(cx, || {
+        let mut inner = event_loop_cx.inner.borrow_mut();
+        inner.next_listener_id += 1;
+        inner.next_listener_id
+    });
+
+    let drop_inner = event_loop_cx.inner.clone();
+    use_drop(cx, move || {
+        let mut inner = drop_inner.borrow_mut();
+        if let Some(fns) = inner.window_event_fns.get_mut(&window_id) {
+            fns.remove(&id);
+            if fns.is_empty() {
+                inner.window_event_fns.remove(&window_id);
+            }
+        }
+    });
+
+    let window_event_fn: WindowEventFn = Rc::new(move |event| {
+        if let Some(next) = filter(event) {
+            Mut::set(value, Some(next));
+        }
+    });
+
+    // Safety: This listener is replaced with each composition, and removed when this scope is dropped.
+    let window_event_fn: WindowEventFn<'static> = unsafe { mem::transmute(window_event_fn) };
+    event_loop_cx
+        .inner
+        .borrow_mut()
+        .window_event_fns
+        .entry(window_id)
+        .or_default()
+        .insert(id, window_event_fn);
+
+    Mut::as_ref(value)
+}
+
+/// Information about a monitor (or display) connected to the system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monitor {
+    /// Name of this monitor, if available.
+    pub name: Option<String>,
+
+    /// Position of the top-left corner of this monitor on the desktop, in physical pixels.
+    pub position: PhysicalPosition<i32>,
+
+    /// Size of this monitor, in physical pixels.
+    pub size: PhysicalSize<u32>,
+
+    /// Scale factor of this monitor, used to convert logical pixels to physical pixels.
+    pub scale_factor: f64,
+
+    /// Refresh rate of this monitor in millihertz, if available.
+    pub refresh_rate_millihertz: Option<u32>,
+
+    /// `true` if this is the primary monitor of the system.
+    ///
+    /// Some platforms (such as Wayland) have no primary monitor.
+    pub is_primary: bool,
+}
+
+impl Monitor {
+    /// Get the size of this monitor in logical pixels.
+    pub fn logical_size(&self) -> LogicalSize<f64> {
+        self.size.to_logical(self.scale_factor)
+    }
+
+    /// Get the refresh rate of this monitor in hertz, if available.
+    pub fn refresh_rate(&self) -> Option<f64> {
+        self.refresh_rate_millihertz
+            .map(|millihertz| millihertz as f64 / 1000.)
+    }
+}
+
+/// Monitors provided by the event loop to [`use_monitors`].
+struct Monitors(Vec<Monitor>);
+
+fn available_monitors(event_loop: &ActiveEventLoop) -> Vec<Monitor> {
+    let primary = event_loop.primary_monitor();
+
+    event_loop
+        .available_monitors()
+        .map(|handle| Monitor {
+            name: handle.name(),
+            position: handle.position(),
+            size: handle.size(),
+            scale_factor: handle.scale_factor(),
+            refresh_rate_millihertz: handle.refresh_rate_millihertz(),
+            is_primary: primary.as_ref() == Some(&handle),
+        })
+        .collect()
+}
+
+/// Use the monitors currently connected to the system.
+///
+/// This composable is recomposed when monitors are added, removed, or changed
+/// (for example, when a monitor's resolution or scale factor changes).
+/// On platforms that don't expose monitors (such as the web), this is empty.
+pub fn use_monitors(cx: ScopeState<'_>) -> &[Monitor] {
+    let monitors =
+        use_context::<Monitors>(cx).expect("`use_monitors` must be used inside of `actuate::run`");
+    &monitors.0
+}
+
+/// System settings of the user, such as their preferred theme and language.
+///
+/// This is provided by the event loop and read with [`use_system_info`],
+/// or one of [`use_system_theme`], [`use_reduced_motion`], and [`use_locale`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SystemInfo {
+    /// Preferred theme of the system, if known.
+    pub theme: Option<Theme>,
+
+    /// `true` if the user prefers reduced motion, such as fewer or shorter animations.
+    ///
+    /// This is currently only detected on the web.
+    pub prefers_reduced_motion: bool,
+
+    /// Preferred language of the user as a BCP 47 language tag (such as `en-US`), if known.
+    ///
+    /// This is read from the browser on the web, and from the `LC_ALL`, `LC_MESSAGES`,
+    /// and `LANG` environment variables on other platforms.
+    pub locale: Option<String>,
+}
+
+impl SystemInfo {
+    fn new(event_loop: &ActiveEventLoop, last_theme: Option<Theme>) -> Self {
+        Self {
+            theme: event_loop.system_theme().or(last_theme),
+            prefers_reduced_motion: prefers_reduced_motion(),
+            locale: locale(),
+        }
     }
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+fn prefers_reduced_motion() -> bool {
+    false
+}
+
+#[cfg(target_arch = "wasm32")]
+fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|query| query.matches())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            // Locales such as `en_US.UTF-8@euro` are converted to `en-US`.
+            let tag = value.split(['.', '@']).next().unwrap_or_default();
+            if tag.is_empty() || tag == "C" || tag == "POSIX" {
+                None
+            } else {
+                Some(tag.replace('_', "-"))
+            }
+        })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn locale() -> Option<String> {
+    web_sys::window().and_then(|window| window.navigator().language())
+}
+
+/// Use the system settings of the user.
+///
+/// This composable is recomposed when these settings change.
+/// Settings without change events on the current platform (such as the locale)
+/// are checked again when a window regains focus.
+pub fn use_system_info(cx: ScopeState<'_>) -> &SystemInfo {
+    use_context::<SystemInfo>(cx).expect("`use_system_info` must be used inside of `actuate::run`")
+}
+
+/// Use the preferred theme of the system, if known.
+///
+/// For more see [`use_system_info`].
+pub fn use_system_theme(cx: ScopeState<'_>) -> Option<Theme> {
+    use_system_info(cx).theme
+}
+
+/// Returns `true` if the user prefers reduced motion.
+///
+/// For more see [`use_system_info`].
+pub fn use_reduced_motion(cx: ScopeState<'_>) -> bool {
+    use_system_info(cx).prefers_reduced_motion
+}
+
+/// Use the preferred language of the user as a BCP 47 language tag (such as `en-US`), if known.
+///
+/// For more see [`use_system_info`].
+pub fn use_locale(cx: ScopeState<'_>) -> Option<&str> {
+    use_system_info(cx).locale.as_deref()
+}
@@ -14,6 +14,14 @@ impl Executor for tokio::runtime::Runtime {
     }
 }
 
+#[cfg(feature = "rt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rt")))]
+impl Executor for tokio::runtime::Handle {
+    fn spawn(&self, future: Pin<Box<dyn Future<Output = ()> + Send>>) {
+        self.spawn(future);
+    }
+}
+
 macro_rules! impl_executor {
     ($($t:tt),*) => {
         $(
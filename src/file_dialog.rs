@@ -0,0 +1,352 @@
+use crate::{composer::Runtime, use_drop, use_mut, use_ref, Mut, ScopeState};
+use slotmap::DefaultKey;
+use std::{
+    cell::Cell,
+    future::Future,
+    mem,
+    path::{Path, PathBuf},
+    pin::Pin,
+};
+use thiserror::Error;
+
+/// Filter for the files shown in a [`FileDialog`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FileFilter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+/// Native file dialog.
+///
+/// This can be shown with a [`FileDialogHandle`] from [`use_file_dialog`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileDialog {
+    title: Option<String>,
+    directory: Option<PathBuf>,
+    file_name: Option<String>,
+    filters: Vec<FileFilter>,
+}
+
+impl FileDialog {
+    /// Create a new file dialog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of this dialog.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the directory this dialog starts in.
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Set the default file name of this dialog.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Add a filter named `name` for files with one of `extensions` (without the leading `.`).
+    pub fn with_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter {
+            name: name.into(),
+            extensions: extensions.iter().map(|ext| (*ext).to_owned()).collect(),
+        });
+        self
+    }
+}
+
+/// Kind of a [`FileDialog`] to show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileDialogKind {
+    /// Pick a single file to open.
+    Open,
+    /// Pick one or more files to open.
+    OpenMultiple,
+    /// Pick a folder.
+    PickFolder,
+    /// Pick a path to save a file to.
+    Save,
+}
+
+/// Error showing a [`FileDialog`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum FileDialogError {
+    /// Native file dialogs are not supported on this platform.
+    #[error("File dialogs are not supported on this platform")]
+    Unsupported,
+}
+
+type PathsFuture = Pin<Box<dyn Future<Output = Result<Option<Vec<PathBuf>>, FileDialogError>>>>;
+
+/// State of a [`FileDialogHandle`].
+#[derive(Default)]
+struct FileDialogState {
+    is_open: bool,
+    paths: Option<Vec<PathBuf>>,
+    error: Option<FileDialogError>,
+}
+
+/// Task waiting for the native dialog of a [`FileDialogHandle`].
+struct DialogTask {
+    /// Runtime of the composer owning this task.
+    rt: Runtime,
+    /// Key of the last task spawned on the runtime.
+    key: Cell<Option<DefaultKey>>,
+    is_pending: Cell<bool>,
+}
+
+/// Handle to show native file dialogs.
+///
+/// This can be created with [`use_file_dialog`].
+pub struct FileDialogHandle<'a> {
+    state: Mut<'a, FileDialogState>,
+    task: &'a DialogTask,
+}
+
+impl Clone for FileDialogHandle<'_> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for FileDialogHandle<'_> {}
+
+impl<'a> FileDialogHandle<'a> {
+    /// Show `dialog` to pick a single file to open.
+    pub fn open(self, dialog: FileDialog) {
+        self.show(dialog, FileDialogKind::Open)
+    }
+
+    /// Show `dialog` to pick one or more files to open.
+    pub fn open_multiple(self, dialog: FileDialog) {
+        self.show(dialog, FileDialogKind::OpenMultiple)
+    }
+
+    /// Show `dialog` to pick a folder.
+    pub fn pick_folder(self, dialog: FileDialog) {
+        self.show(dialog, FileDialogKind::PickFolder)
+    }
+
+    /// Show `dialog` to pick a path to save a file to.
+    pub fn save(self, dialog: FileDialog) {
+        self.show(dialog, FileDialogKind::Save)
+    }
+
+    /// Returns `true` if a dialog is currently open.
+    pub fn is_open(&self) -> bool {
+        self.state.is_open
+    }
+
+    /// Get the paths selected in the last completed dialog,
+    /// or `None` if no dialog has completed or it was cancelled.
+    pub fn paths(&self) -> Option<&[PathBuf]> {
+        self.state.paths.as_deref()
+    }
+
+    /// Get the error of the last dialog, or `None` if it was shown.
+    pub fn error(&self) -> Option<&FileDialogError> {
+        self.state.error.as_ref()
+    }
+
+    /// Get the first path selected in the last completed dialog,
+    /// or `None` if no dialog has completed or it was cancelled.
+    pub fn path(&self) -> Option<&Path> {
+        self.paths()
+            .and_then(|paths| paths.first())
+            .map(PathBuf::as_path)
+    }
+
+    fn show(self, dialog: FileDialog, kind: FileDialogKind) {
+        // Only one dialog can be open at a time.
+        if self.task.is_pending.get() {
+            return;
+        }
+
+        let rt = &self.task.rt;
+        if let Some(key) = self.task.key.take() {
+            rt.tasks.borrow_mut().remove(key);
+        }
+
+        let state = self.state;
+        let dialog_task = self.task;
+        dialog_task.is_pending.set(true);
+        Mut::update(state, |state| state.is_open = true);
+
+        let paths = native::show(&dialog, kind);
+        let task: Pin<Box<dyn Future<Output = ()> + 'a>> = Box::pin(async move {
+            let result = paths.await;
+
+            dialog_task.is_pending.set(false);
+            Mut::update(state, move |state| {
+                state.is_open = false;
+                (state.paths, state.error) = match result {
+                    Ok(paths) => (paths, None),
+                    Err(error) => (None, Some(error)),
+                };
+            });
+        });
+
+        // Safety: `task` is removed from the runtime when the scope is dropped.
+        let task: Pin<Box<dyn Future<Output = ()>>> = unsafe { mem::transmute(task) };
+
+        let key = rt.tasks.borrow_mut().insert(task);
+        rt.wake_task(key);
+        self.task.key.set(Some(key));
+    }
+}
+
+/// Use a handle to show native file dialogs.
+///
+/// Dialogs are shown without blocking composition,
+/// and the selected paths are set as state once the dialog is closed.
+///
+/// File dialogs are currently supported on Windows and macOS.
+/// On other platforms, they complete immediately with [`FileDialogError::Unsupported`].
+///
+/// ```no_run
+/// use actuate::{file_dialog::{use_file_dialog, FileDialog}, prelude::*};
+///
+/// #[derive(Data)]
+/// struct App;
+///
+/// impl Compose for App {
+///     fn compose(cx: Scope<Self>) -> impl Compose {
+///         let dialog = use_file_dialog(&cx);
+///
+///         Window::new((
+///             Text::new("Open").on_click(move || {
+///                 dialog.open(FileDialog::new().with_filter("Text", &["txt"]))
+///             }),
+///             dialog
+///                 .path()
+///                 .map(|path| Text::new(path.display().to_string())),
+///         ))
+///     }
+/// }
+/// ```
+pub fn use_file_dialog(cx: ScopeState<'_>) -> FileDialogHandle<'_> {
+    let state = use_mut(cx, FileDialogState::default);
+    let task = use_ref(cx, || DialogTask {
+        rt: cx.runtime().clone(),
+        key: Cell::default(),
+        is_pending: Cell::default(),
+    });
+
+    use_drop(cx, move || {
+        if let Some(key) = task.key.take() {
+            task.rt.tasks.borrow_mut().remove(key);
+        }
+    });
+
+    FileDialogHandle { state, task }
+}
+
+/// Native file dialogs, built with `rfd`.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod native {
+    use super::{FileDialog, FileDialogKind, PathsFuture};
+    use rfd::{AsyncFileDialog, FileHandle};
+    use std::path::PathBuf;
+
+    pub(super) fn show(dialog: &FileDialog, kind: FileDialogKind) -> PathsFuture {
+        let mut builder = AsyncFileDialog::new();
+        if let Some(title) = &dialog.title {
+            builder = builder.set_title(title);
+        }
+        if let Some(directory) = &dialog.directory {
+            builder = builder.set_directory(directory);
+        }
+        if let Some(file_name) = &dialog.file_name {
+            builder = builder.set_file_name(file_name);
+        }
+        for filter in &dialog.filters {
+            builder = builder.add_filter(&filter.name, filter.extensions.as_slice());
+        }
+
+        fn paths(handles: Vec<FileHandle>) -> Vec<PathBuf> {
+            handles
+                .iter()
+                .map(|handle| handle.path().to_path_buf())
+                .collect()
+        }
+
+        match kind {
+            FileDialogKind::Open => {
+                let future = builder.pick_file();
+                Box::pin(async move { Ok(future.await.map(|handle| paths(vec![handle]))) })
+            }
+            FileDialogKind::OpenMultiple => {
+                let future = builder.pick_files();
+                Box::pin(async move { Ok(future.await.map(paths)) })
+            }
+            FileDialogKind::PickFolder => {
+                let future = builder.pick_folder();
+                Box::pin(async move { Ok(future.await.map(|handle| paths(vec![handle]))) })
+            }
+            FileDialogKind::Save => {
+                let future = builder.save_file();
+                Box::pin(async move { Ok(future.await.map(|handle| paths(vec![handle]))) })
+            }
+        }
+    }
+}
+
+/// File dialogs are not supported on this platform.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod native {
+    use super::{FileDialog, FileDialogError, FileDialogKind, PathsFuture};
+
+    pub(super) fn show(dialog: &FileDialog, kind: FileDialogKind) -> PathsFuture {
+        let _ = (dialog, kind);
+        Box::pin(async { Err(FileDialogError::Unsupported) })
+    }
+}
+
+#[cfg(all(test, not(any(target_os = "windows", target_os = "macos"))))]
+mod tests {
+    use super::{use_file_dialog, FileDialog, FileDialogError};
+    use crate::{composer::Composer, prelude::*};
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    #[test]
+    fn it_fails_dialogs_on_unsupported_platforms() {
+        #[derive(Data)]
+        struct A {
+            is_open: Rc<Cell<bool>>,
+            error: Rc<RefCell<Option<FileDialogError>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let dialog = use_file_dialog(&cx);
+                use_ref(&cx, || dialog.open(FileDialog::new()));
+
+                cx.me().is_open.set(dialog.is_open());
+                *cx.me().error.borrow_mut() = dialog.error().cloned();
+            }
+        }
+
+        let is_open = Rc::new(Cell::new(false));
+        let error = Rc::new(RefCell::new(None));
+        let mut composer = Composer::new(A {
+            is_open: is_open.clone(),
+            error: error.clone(),
+        });
+
+        for _ in 0..3 {
+            composer.compose();
+        }
+
+        assert!(!is_open.get());
+        assert_eq!(*error.borrow(), Some(FileDialogError::Unsupported));
+    }
+}
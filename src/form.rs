@@ -0,0 +1,468 @@
+use crate::{use_ref, use_signal, ScopeState, Signal};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{any::Any, fmt};
+
+#[cfg(feature = "executor")]
+use core::{cell::Cell, future::Future, pin::Pin};
+
+/// Synchronous validator of a field's value.
+type ValidatorFn<T> = dyn Fn(&T) -> Result<(), String> + Send + Sync;
+
+/// Asynchronous validator of a field's value.
+#[cfg(feature = "executor")]
+type AsyncValidatorFn<T> =
+    dyn Fn(T) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync;
+
+/// Configuration of a form field, with its initial value and validators.
+///
+/// This can be used with [`use_field`].
+pub struct FieldConfig<T> {
+    initial: T,
+    validators: Vec<Box<ValidatorFn<T>>>,
+    #[cfg(feature = "executor")]
+    async_validators: Vec<Box<AsyncValidatorFn<T>>>,
+}
+
+impl<T> FieldConfig<T> {
+    /// Create a new field configuration from its initial value.
+    pub fn new(initial: T) -> Self {
+        Self {
+            initial,
+            validators: Vec::new(),
+            #[cfg(feature = "executor")]
+            async_validators: Vec::new(),
+        }
+    }
+
+    /// Add a validator that returns an error message for invalid values.
+    ///
+    /// Validators run in the order they're added, stopping at the first error.
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Add an asynchronous validator that returns an error message for invalid values.
+    ///
+    /// Asynchronous validators run on the current [`Executor`](crate::executor::Executor)
+    /// after each change that passes the synchronous validators.
+    /// Results for values that changed since validation started are discarded.
+    #[cfg(feature = "executor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "executor")))]
+    pub fn with_async_validator<F>(
+        mut self,
+        validator: impl Fn(T) -> F + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.async_validators
+            .push(Box::new(move |value| Box::pin(validator(value))));
+        self
+    }
+
+    /// Run the synchronous validators on `value`, returning the first error.
+    fn validate(&self, value: &T) -> Option<String> {
+        self.validators
+            .iter()
+            .find_map(|validator| validator(value).err())
+    }
+
+    /// Returns `true` if this field has asynchronous validators.
+    fn is_async(&self) -> bool {
+        #[cfg(feature = "executor")]
+        return !self.async_validators.is_empty();
+
+        #[cfg(not(feature = "executor"))]
+        false
+    }
+}
+
+/// Type-erased [`FieldConfig`].
+trait AnyFieldConfig: Send + Sync {
+    fn initial(&self) -> Box<dyn Any + Send>;
+
+    fn validate(&self, value: &dyn Any) -> Option<String>;
+}
+
+impl<T: Clone + Send + Sync + 'static> AnyFieldConfig for FieldConfig<T> {
+    fn initial(&self) -> Box<dyn Any + Send> {
+        Box::new(self.initial.clone())
+    }
+
+    fn validate(&self, value: &dyn Any) -> Option<String> {
+        FieldConfig::validate(self, value.downcast_ref().unwrap())
+    }
+}
+
+/// State of a field in a [`Form`].
+struct FieldState {
+    value: Box<dyn Any + Send>,
+    config: Arc<dyn AnyFieldConfig>,
+    error: Option<String>,
+    is_dirty: bool,
+    is_touched: bool,
+    is_validating: bool,
+    /// Generation of the value, incremented on each change to discard stale asynchronous validation.
+    generation: u64,
+}
+
+#[derive(Default)]
+struct FormState {
+    fields: Vec<FieldState>,
+    submit_count: usize,
+}
+
+/// Form state.
+///
+/// A form tracks the value, dirty and touched flags, and validation errors of each of its [`Field`]s,
+/// which can be checked together before submitting the form.
+///
+/// Like [`Signal`], this handle is `'static` and can be cloned into event callbacks and tasks.
+/// Changes are queued, triggering an update to the composable that created the form.
+///
+/// This can be created with [`use_form`].
+pub struct Form {
+    state: Signal<FormState>,
+}
+
+impl Form {
+    /// Returns `true` if every field of this form is valid.
+    ///
+    /// Fields being validated asynchronously are not yet valid.
+    pub fn is_valid(&self) -> bool {
+        self.state
+            .lock()
+            .fields
+            .iter()
+            .all(|field| field.error.is_none() && !field.is_validating)
+    }
+
+    /// Returns `true` if any field of this form is being validated asynchronously.
+    pub fn is_validating(&self) -> bool {
+        self.state
+            .lock()
+            .fields
+            .iter()
+            .any(|field| field.is_validating)
+    }
+
+    /// Returns `true` if the value of any field of this form differs from its initial value.
+    pub fn is_dirty(&self) -> bool {
+        self.state.lock().fields.iter().any(|field| field.is_dirty)
+    }
+
+    /// Returns `true` if any field of this form was touched.
+    pub fn is_touched(&self) -> bool {
+        self.state
+            .lock()
+            .fields
+            .iter()
+            .any(|field| field.is_touched)
+    }
+
+    /// Get the number of times this form was submitted, including failed submissions.
+    pub fn submit_count(&self) -> usize {
+        self.state.lock().submit_count
+    }
+
+    /// Submit this form, calling `on_submit` if every field is valid.
+    ///
+    /// Each field is marked as touched, so its error can be shown.
+    /// Returns `true` if `on_submit` was called.
+    pub fn submit(&self, on_submit: impl FnOnce()) -> bool {
+        let is_valid = self.is_valid();
+
+        self.state.update(|state| {
+            state.submit_count += 1;
+
+            for field in &mut state.fields {
+                field.is_touched = true;
+            }
+        });
+
+        if is_valid {
+            on_submit();
+        }
+        is_valid
+    }
+
+    /// Reset every field of this form to its initial value, clearing its flags and errors.
+    pub fn reset(&self) {
+        self.state.update(|state| {
+            state.submit_count = 0;
+
+            for field in &mut state.fields {
+                field.value = field.config.initial();
+                field.error = field.config.validate(&*field.value);
+                field.is_dirty = false;
+                field.is_touched = false;
+                field.is_validating = false;
+                field.generation += 1;
+            }
+        });
+    }
+}
+
+impl Clone for Form {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for Form {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self.state.lock().fields.len();
+        f.debug_struct("Form")
+            .field("fields", &len)
+            .field("is_valid", &self.is_valid())
+            .finish()
+    }
+}
+
+/// Use a new form.
+///
+/// Fields can be added to the returned [`Form`] with [`use_field`].
+pub fn use_form(cx: ScopeState) -> Form {
+    let state = use_signal(cx, FormState::default);
+    Form { state }
+}
+
+/// Handle to a field of a [`Form`].
+///
+/// Setting the value of a field marks it as dirty if it differs from the initial value, and validates it.
+/// Errors are tracked from the initial value, and can be shown once the field is touched.
+///
+/// This can be created with [`use_field`].
+pub struct Field<T> {
+    form: Form,
+    index: usize,
+    config: Arc<FieldConfig<T>>,
+}
+
+impl<T> Field<T> {
+    /// Get a clone of the current value of this field.
+    pub fn get(&self) -> T
+    where
+        T: Clone + 'static,
+    {
+        self.with(|field| field.value.downcast_ref::<T>().unwrap().clone())
+    }
+
+    /// Get the current validation error of this field.
+    pub fn error(&self) -> Option<String> {
+        self.with(|field| field.error.clone())
+    }
+
+    /// Returns `true` if the value of this field differs from its initial value.
+    pub fn is_dirty(&self) -> bool {
+        self.with(|field| field.is_dirty)
+    }
+
+    /// Returns `true` if this field was touched, or its form was submitted.
+    pub fn is_touched(&self) -> bool {
+        self.with(|field| field.is_touched)
+    }
+
+    /// Returns `true` if this field is being validated asynchronously.
+    pub fn is_validating(&self) -> bool {
+        self.with(|field| field.is_validating)
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&FieldState) -> R) -> R {
+        f(&self.form.state.lock().fields[self.index])
+    }
+}
+
+impl<T> Field<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Queue an update to set the value of this field, validating the new value.
+    pub fn set(&self, value: T) {
+        let index = self.index;
+        let config = self.config.clone();
+
+        self.form.state.update(move |state| {
+            let field = &mut state.fields[index];
+            field.is_dirty = value != config.initial;
+            field.error = config.validate(&value);
+            field.is_validating = field.error.is_none() && config.is_async();
+            field.generation += 1;
+            field.value = Box::new(value);
+        });
+    }
+
+    /// Queue an update to mark this field as touched.
+    pub fn touch(&self) {
+        let index = self.index;
+        self.form.state.update(move |state| {
+            state.fields[index].is_touched = true;
+        });
+    }
+}
+
+impl<T> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        Self {
+            form: self.form.clone(),
+            index: self.index,
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for Field<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|field| {
+            f.debug_struct("Field")
+                .field("value", field.value.downcast_ref::<T>().unwrap())
+                .field("error", &field.error)
+                .field("is_dirty", &field.is_dirty)
+                .field("is_touched", &field.is_touched)
+                .finish()
+        })
+    }
+}
+
+/// Use a new field of `form`, with the configuration returned by `make_config`.
+///
+/// Fields stay registered with their form for as long as the form lives.
+///
+/// # Panics
+/// Fields with asynchronous validators require an [`ExecutorContext`](crate::executor::ExecutorContext),
+/// and will panic when validated without one.
+pub fn use_field<T>(
+    cx: ScopeState,
+    form: &Form,
+    make_config: impl FnOnce() -> FieldConfig<T>,
+) -> Field<T>
+where
+    T: Clone + PartialEq + Send + Sync + 'static,
+{
+    let (index, config) = use_ref(cx, || {
+        let config = Arc::new(make_config());
+
+        let mut state = form.state.lock();
+        state.fields.push(FieldState {
+            value: Box::new(config.initial.clone()),
+            config: config.clone(),
+            error: config.validate(&config.initial),
+            is_dirty: false,
+            is_touched: false,
+            is_validating: false,
+            generation: 0,
+        });
+
+        (state.fields.len() - 1, config)
+    });
+
+    let field = Field {
+        form: form.clone(),
+        index: *index,
+        config: config.clone(),
+    };
+
+    #[cfg(feature = "executor")]
+    {
+        let last_generation = use_ref(cx, || Cell::new(0));
+        let (generation, is_validating) =
+            field.with(|state| (state.generation, state.is_validating));
+
+        if last_generation.replace(generation) != generation && is_validating {
+            let executor_cx = crate::use_context::<crate::executor::ExecutorContext>(cx).unwrap();
+            let field = field.clone();
+            let value = field.get();
+
+            executor_cx.spawn(async move {
+                let mut error = None;
+                for validator in &field.config.async_validators {
+                    if let Err(message) = validator(value.clone()).await {
+                        error = Some(message);
+                        break;
+                    }
+                }
+
+                let index = field.index;
+                field.form.state.update(move |state| {
+                    let field = &mut state.fields[index];
+                    if field.generation == generation {
+                        field.error = error;
+                        field.is_validating = false;
+                    }
+                });
+            });
+        }
+    }
+
+    field
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn it_validates_form_fields() {
+        /// Value, error, and dirty flag of a field, and whether its form is valid.
+        type State = (String, Option<String>, bool, bool);
+
+        #[derive(Data)]
+        struct A {
+            states: Rc<RefCell<Vec<State>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let form = use_form(&cx);
+                let name = use_field(&cx, &form, || {
+                    FieldConfig::new(String::new()).with_validator(|name: &String| {
+                        if name.is_empty() {
+                            Err(String::from("Required"))
+                        } else {
+                            Ok(())
+                        }
+                    })
+                });
+
+                cx.me().states.borrow_mut().push((
+                    name.get(),
+                    name.error(),
+                    name.is_dirty(),
+                    form.is_valid(),
+                ));
+
+                match form.submit_count() {
+                    0 => assert!(!form.submit(|| panic!("Submitted an invalid form."))),
+                    1 => name.set(String::from("A")),
+                    _ => {}
+                }
+            }
+        }
+
+        let states = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            states: states.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        composer.compose();
+
+        let required = Some(String::from("Required"));
+        assert_eq!(
+            *states.borrow(),
+            [
+                (String::new(), required.clone(), false, false),
+                (String::new(), required, false, false),
+                (String::from("A"), None, true, true),
+            ]
+        );
+    }
+}
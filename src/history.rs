@@ -0,0 +1,234 @@
+use crate::{use_mut, Data, Memoize, Mut, ScopeState};
+use std::{
+    collections::VecDeque,
+    fmt, mem,
+    ops::Deref,
+    time::{Duration, Instant},
+};
+
+struct HistoryState<T> {
+    value: T,
+    /// Snapshots to restore with [`History::undo`], from the oldest to the newest.
+    undo: VecDeque<T>,
+    /// Snapshots to restore with [`History::redo`], from the oldest to the newest.
+    redo: Vec<T>,
+    /// Time of the last committed change, or `None` if the next change can't be coalesced.
+    last_commit: Option<Instant>,
+}
+
+/// Value of type `T` with an undo and redo history.
+///
+/// Each committed change snapshots the previous value, which can be restored with [`History::undo`].
+/// Committing a change after undoing discards the redo history.
+///
+/// Like [`Mut`], this handle is `Copy` and each change is queued, triggering an update to the composable owning this value.
+///
+/// This can be created with [`use_history`].
+pub struct History<'a, T> {
+    state: Mut<'a, HistoryState<T>>,
+    coalesce: Option<Duration>,
+    limit: Option<usize>,
+}
+
+impl<T> History<'_, T> {
+    /// Coalesce changes committed within `window` of the last change into a single undo step (default: `None`).
+    ///
+    /// This is useful for grouping rapid edits, like typing, into one step.
+    pub fn with_coalescing(mut self, window: impl Into<Option<Duration>>) -> Self {
+        self.coalesce = window.into();
+        self
+    }
+
+    /// Set the maximum number of undo steps to keep (default: `None`).
+    ///
+    /// Once reached, the oldest snapshot is discarded with each committed change.
+    pub fn with_limit(mut self, limit: impl Into<Option<usize>>) -> Self {
+        self.limit = limit.into();
+        self
+    }
+
+    /// Returns `true` if there's a change to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.state.undo.is_empty()
+    }
+
+    /// Returns `true` if there's an undone change to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.state.redo.is_empty()
+    }
+}
+
+impl<T: Clone + 'static> History<'_, T> {
+    /// Queue a change to this value, snapshotting the current value for [`History::undo`].
+    pub fn commit(self, f: impl FnOnce(&mut T) + 'static) {
+        let coalesce = self.coalesce;
+        let limit = self.limit;
+
+        Mut::update(self.state, move |state| {
+            let now = Instant::now();
+            let is_coalesced = match (coalesce, state.last_commit) {
+                (Some(window), Some(last)) => now.duration_since(last) <= window,
+                _ => false,
+            };
+
+            if !is_coalesced {
+                state.undo.push_back(state.value.clone());
+
+                if let Some(limit) = limit {
+                    while state.undo.len() > limit {
+                        state.undo.pop_front();
+                    }
+                }
+            }
+
+            f(&mut state.value);
+            state.redo.clear();
+            state.last_commit = Some(now);
+        });
+    }
+
+    /// Queue a change to set this value, snapshotting the current value for [`History::undo`].
+    pub fn set(self, value: T) {
+        self.commit(move |dst| *dst = value)
+    }
+
+    /// Queue an update to restore the value before the last committed change.
+    ///
+    /// Does nothing if there's no change to undo.
+    pub fn undo(self) {
+        Mut::update(self.state, |state| {
+            if let Some(value) = state.undo.pop_back() {
+                let last = mem::replace(&mut state.value, value);
+                state.redo.push(last);
+                state.last_commit = None;
+            }
+        });
+    }
+
+    /// Queue an update to restore the value before the last undo.
+    ///
+    /// Does nothing if there's no change to redo.
+    pub fn redo(self) {
+        Mut::update(self.state, |state| {
+            if let Some(value) = state.redo.pop() {
+                let last = mem::replace(&mut state.value, value);
+                state.undo.push_back(last);
+                state.last_commit = None;
+            }
+        });
+    }
+
+    /// Queue an update to clear the undo and redo history, keeping the current value.
+    pub fn clear(self) {
+        Mut::update(self.state, |state| {
+            state.undo.clear();
+            state.redo.clear();
+            state.last_commit = None;
+        });
+    }
+}
+
+impl<T> Clone for History<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for History<'_, T> {}
+
+impl<T> Deref for History<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for History<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("History")
+            .field("value", &self.state.value)
+            .field("undo", &self.state.undo.len())
+            .field("redo", &self.state.redo.len())
+            .finish()
+    }
+}
+
+impl<T> Memoize for History<'_, T> {
+    type Value = u64;
+
+    fn memoized(self) -> Self::Value {
+        self.state.memoized()
+    }
+}
+
+unsafe impl<T: Data> Data for History<'_, T> {}
+
+/// Use a value of type `T` with an undo and redo history.
+///
+/// `make_value` will only be called once to initialize this value.
+pub fn use_history<T: 'static>(
+    cx: ScopeState<'_>,
+    make_value: impl FnOnce() -> T,
+) -> History<'_, T> {
+    let state = use_mut(cx, || HistoryState {
+        value: make_value(),
+        undo: VecDeque::new(),
+        redo: Vec::new(),
+        last_commit: None,
+    });
+
+    History {
+        state,
+        coalesce: None,
+        limit: None,
+    }
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn it_undoes_and_redoes_history() {
+        #[derive(Data)]
+        struct A {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let history = use_history(&cx, || 0);
+                cx.me().values.borrow_mut().push(*history);
+
+                match cx.me().values.borrow().len() {
+                    1 => {
+                        history.set(1);
+                        history.set(2);
+                    }
+                    2 => history.undo(),
+                    3 => {
+                        assert!(history.can_redo());
+                        history.redo();
+                    }
+                    4 => {
+                        history.undo();
+                        history.set(3);
+                    }
+                    _ => assert!(!history.can_redo()),
+                }
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+
+        for _ in 0..5 {
+            composer.compose();
+        }
+        assert_eq!(*values.borrow(), [0, 2, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::{use_context, use_drop, use_ref, use_signal, ScopeState, Signal};
+use serde::de::DeserializeOwned;
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::Duration,
+};
+use thiserror::Error;
+use web_time::Instant;
+
+/// Error from fetching a [`Resource`].
+#[derive(Debug, Error)]
+pub enum FetchError {
+    /// Error sending the request, reading the response, or decoding its body.
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    /// No [`ExecutorContext`](crate::executor::ExecutorContext) was provided to send the request on.
+    #[error("no `ExecutorContext` was provided to send the request on")]
+    MissingExecutor,
+}
+
+/// HTTP `GET` request for a [`Resource`].
+///
+/// Resources are cached by their request's URL and query parameters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Request {
+    url: String,
+    params: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Create a new request to `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            params: Vec::new(),
+        }
+    }
+
+    /// Add a query parameter to this request.
+    pub fn with_param(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+
+    /// Get the URL of this request.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the query parameters of this request.
+    pub fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+}
+
+impl From<String> for Request {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for Request {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Resource of type `T` fetched over HTTP.
+///
+/// This can be created with [`use_fetch`].
+#[derive(Debug)]
+pub enum Resource<T> {
+    /// The resource is loading, with no cached value to show.
+    Loading,
+    /// The resource was fetched (or loaded from the cache).
+    Ok(Arc<T>),
+    /// The last request for this resource failed.
+    Err(Arc<FetchError>),
+}
+
+impl<T> Resource<T> {
+    /// Returns `true` if this resource is loading.
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Resource::Loading)
+    }
+
+    /// Get the value of this resource, or `None` if it's loading or failed.
+    pub fn ok(&self) -> Option<&T> {
+        match self {
+            Resource::Ok(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the error of this resource, or `None` if it's loading or succeeded.
+    pub fn err(&self) -> Option<&FetchError> {
+        match self {
+            Resource::Err(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Resource::Loading => Resource::Loading,
+            Resource::Ok(value) => Resource::Ok(value.clone()),
+            Resource::Err(error) => Resource::Err(error.clone()),
+        }
+    }
+}
+
+struct CacheEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    fetched_at: Instant,
+}
+
+/// HTTP context for fetching resources, with a shared cache.
+///
+/// Cached resources are shown right away by [`use_fetch`],
+/// and revalidated in the background once they're older than the maximum age.
+///
+/// This can be provided to [`use_fetch`] with [`use_provider`](crate::use_provider),
+/// otherwise a global context created with [`HttpContext::default`] is used.
+#[derive(Clone)]
+pub struct HttpContext {
+    client: reqwest::Client,
+    cache: Arc<Mutex<HashMap<Request, CacheEntry>>>,
+    max_age: Duration,
+}
+
+impl HttpContext {
+    /// Create a new [`HttpContext`] with the provided [`reqwest::Client`] and an empty cache.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            cache: Arc::default(),
+            max_age: Duration::ZERO,
+        }
+    }
+
+    /// Set the maximum age of cached resources before they're revalidated (default: zero).
+    ///
+    /// With the default age, cached resources are shown right away and always revalidated.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Get the [`reqwest::Client`] of this context.
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Remove the cached resource for `request`.
+    pub fn invalidate(&self, request: &Request) {
+        self.cache.lock().unwrap().remove(request);
+    }
+
+    /// Remove all cached resources.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Get the cached resource for `request` and its age, if it's cached with type `T`.
+    fn get<T: Send + Sync + 'static>(&self, request: &Request) -> Option<(Arc<T>, Duration)> {
+        let cache = self.cache.lock().unwrap();
+        let entry = cache.get(request)?;
+        let value = entry.value.clone().downcast().ok()?;
+        Some((value, entry.fetched_at.elapsed()))
+    }
+
+    fn insert<T: Send + Sync + 'static>(&self, request: Request, value: Arc<T>) {
+        self.cache.lock().unwrap().insert(
+            request,
+            CacheEntry {
+                value,
+                fetched_at: Instant::now(),
+            },
+        );
+    }
+
+    async fn fetch<T: DeserializeOwned>(&self, request: &Request) -> Result<T, FetchError> {
+        let response = self
+            .client
+            .get(&request.url)
+            .query(&request.params)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json().await?)
+    }
+}
+
+impl Default for HttpContext {
+    fn default() -> Self {
+        Self::new(reqwest::Client::new())
+    }
+}
+
+impl fmt::Debug for HttpContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpContext")
+            .field("client", &self.client)
+            .field("cached", &self.cache.lock().unwrap().len())
+            .field("max_age", &self.max_age)
+            .finish()
+    }
+}
+
+/// Handle to revalidate a [`Resource`].
+///
+/// This can be created with [`use_fetch`].
+#[derive(Clone, Debug)]
+pub struct Revalidator {
+    revalidations: Signal<u64>,
+}
+
+impl Revalidator {
+    /// Queue a request to fetch the resource again, keeping its current value until the response arrives.
+    pub fn revalidate(&self) {
+        self.revalidations.update(|count| *count += 1);
+    }
+}
+
+/// Use a JSON resource of type `T` fetched with an HTTP `GET` `request`.
+///
+/// The resource is fetched whenever `request` changes, or when revalidated with the returned [`Revalidator`].
+/// A cached resource for `request` is shown right away, and revalidated in the background
+/// if it's older than the maximum age of the current [`HttpContext`].
+/// Each response is set as a state update, triggering an update to this composable.
+/// Responses to superseded requests are discarded.
+///
+/// Requests are sent on the current [`ExecutorContext`](crate::executor::ExecutorContext),
+/// which must run on the Tokio runtime.
+/// Without one, the resource fails with [`FetchError::MissingExecutor`].
+/// On the web, requests are sent with the browser's `fetch` API on the current thread instead.
+pub fn use_fetch<T>(cx: ScopeState<'_>, request: impl Into<Request>) -> (Resource<T>, Revalidator)
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    let request = request.into();
+
+    let http = use_ref(cx, || {
+        use_context::<HttpContext>(cx)
+            .cloned()
+            .unwrap_or_else(|_| global().clone())
+    });
+    let resource = use_signal(cx, || Resource::Loading);
+    let revalidations = use_signal(cx, || 0);
+    let generation = use_ref(cx, || Arc::new(AtomicU64::new(0)));
+    let last = use_ref(cx, || RefCell::new(None::<(Request, u64)>));
+
+    let revalidation = revalidations.get();
+    let is_request_changed = last.borrow().as_ref().map(|(last, _)| last) != Some(&request);
+    let is_revalidated = last.borrow().as_ref().map(|(_, last)| *last) != Some(revalidation);
+
+    if is_request_changed || is_revalidated {
+        *last.borrow_mut() = Some((request.clone(), revalidation));
+
+        let cached = http.get::<T>(&request);
+        let is_fresh = cached.as_ref().is_some_and(|(_, age)| *age < http.max_age);
+
+        // Show a changed request's cached value (or loading state) right away,
+        // since this signal is only read by this composable.
+        if is_request_changed {
+            *resource.lock() = cached.map_or(Resource::Loading, |(value, _)| Resource::Ok(value));
+        }
+
+        if (is_revalidated && !is_request_changed) || !is_fresh {
+            let job_generation = generation.fetch_add(1, Ordering::SeqCst) + 1;
+            let generation = generation.clone();
+            let http = http.clone();
+            let resource = resource.clone();
+
+            let task_resource = resource.clone();
+            let task = async move {
+                let next = match http.fetch::<T>(&request).await {
+                    Ok(value) => {
+                        let value = Arc::new(value);
+                        http.insert(request, value.clone());
+                        Resource::Ok(value)
+                    }
+                    Err(error) => Resource::Err(Arc::new(error)),
+                };
+
+                let is_current = move || generation.load(Ordering::SeqCst) == job_generation;
+                if is_current() {
+                    task_resource.update(move |resource| {
+                        if is_current() {
+                            *resource = next;
+                        }
+                    });
+                }
+            };
+
+            #[cfg(not(target_arch = "wasm32"))]
+            match use_context::<crate::executor::ExecutorContext>(cx) {
+                Ok(executor_cx) => executor_cx.spawn(task),
+                Err(_) => *resource.lock() = Resource::Err(Arc::new(FetchError::MissingExecutor)),
+            }
+
+            #[cfg(target_arch = "wasm32")]
+            wasm_bindgen_futures::spawn_local(task);
+        }
+    }
+
+    // Discard pending responses once this composable is dropped.
+    use_drop(cx, || {
+        generation.fetch_add(1, Ordering::SeqCst);
+    });
+
+    (resource.get(), Revalidator { revalidations })
+}
+
+/// Get the global [`HttpContext`], used if no context is provided.
+fn global() -> &'static HttpContext {
+    static HTTP: OnceLock<HttpContext> = OnceLock::new();
+
+    HTTP.get_or_init(HttpContext::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{use_fetch, FetchError};
+    use crate::{
+        composer::{testing::ChannelUpdater, Composer},
+        prelude::*,
+    };
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads the system's TLS configuration")]
+    fn it_fails_fetches_without_executors() {
+        #[derive(Data)]
+        struct A {
+            is_missing: Rc<Cell<bool>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let (resource, _) = use_fetch::<()>(&cx, "http://localhost");
+                cx.me()
+                    .is_missing
+                    .set(matches!(resource.err(), Some(FetchError::MissingExecutor)));
+            }
+        }
+
+        let is_missing = Rc::new(Cell::new(false));
+        let (updater, _updates) = ChannelUpdater::new();
+        let mut composer = Composer::with_updater(
+            A {
+                is_missing: is_missing.clone(),
+            },
+            updater,
+        );
+
+        composer.compose();
+        assert!(is_missing.get());
+    }
+}
@@ -0,0 +1,369 @@
+use crate::prelude::*;
+use fluent_bundle::{FluentError, FluentResource};
+use std::{cell::RefCell, fmt, fs, io, path::Path, rc::Rc};
+use thiserror::Error;
+use unic_langid::CharacterDirection;
+
+pub use fluent_bundle::{FluentArgs, FluentValue};
+pub use unic_langid::LanguageIdentifier;
+
+type FluentBundle = fluent_bundle::FluentBundle<FluentResource>;
+
+/// Error from adding a resource to a [`Localization`].
+#[derive(Debug, Error)]
+pub enum LocalizationError {
+    /// Error reading a resource file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Errors parsing a resource, or adding it to a bundle (such as a message that was already defined).
+    #[error("Failed to add Fluent resource: {}", display_errors(.0))]
+    Fluent(Vec<FluentError>),
+}
+
+fn display_errors(errors: &[impl fmt::Display]) -> String {
+    errors
+        .iter()
+        .map(|error| error.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Localized messages from Fluent (FTL) resources.
+///
+/// Messages are formatted in the active locale, falling back to another region of its language,
+/// and then to the fallback locale.
+///
+/// This can be provided to a tree of composables with [`Localized`], and read with [`use_localization`] or [`t!`](crate::t).
+///
+/// Cloning a localization is cheap, and clones share their resources.
+#[derive(Clone)]
+pub struct Localization {
+    bundles: Rc<RefCell<Vec<FluentBundle>>>,
+    locale: LanguageIdentifier,
+    fallback: LanguageIdentifier,
+}
+
+impl Localization {
+    /// Create a new localization with no resources, using the `fallback` locale as the active locale.
+    pub fn new(fallback: LanguageIdentifier) -> Self {
+        Self {
+            bundles: Rc::default(),
+            locale: fallback.clone(),
+            fallback,
+        }
+    }
+
+    /// Add the Fluent resource `source` to the bundle of `locale`.
+    ///
+    /// Resources should be added before this localization is provided,
+    /// as composables are only recomposed when the active locale changes.
+    pub fn add_resource(
+        &mut self,
+        locale: LanguageIdentifier,
+        source: impl Into<String>,
+    ) -> Result<(), LocalizationError> {
+        let resource = FluentResource::try_new(source.into()).map_err(|(_, errors)| {
+            LocalizationError::Fluent(errors.into_iter().map(FluentError::ParserError).collect())
+        })?;
+
+        let mut bundles = self.bundles.borrow_mut();
+        let bundle = match bundles
+            .iter()
+            .position(|bundle| bundle.locales[0] == locale)
+        {
+            Some(idx) => &mut bundles[idx],
+            None => {
+                bundles.push(FluentBundle::new(vec![locale]));
+                bundles.last_mut().unwrap()
+            }
+        };
+        bundle
+            .add_resource(resource)
+            .map_err(LocalizationError::Fluent)
+    }
+
+    /// Read the Fluent resource at `path` and add it to the bundle of `locale`.
+    ///
+    /// For more see [`Localization::add_resource`].
+    pub fn add_resource_file(
+        &mut self,
+        locale: LanguageIdentifier,
+        path: impl AsRef<Path>,
+    ) -> Result<(), LocalizationError> {
+        let source = fs::read_to_string(path)?;
+        self.add_resource(locale, source)
+    }
+
+    /// Set the active locale of this localization.
+    pub fn with_locale(mut self, locale: LanguageIdentifier) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Get the active locale.
+    pub fn locale(&self) -> &LanguageIdentifier {
+        &self.locale
+    }
+
+    /// Get the fallback locale.
+    pub fn fallback(&self) -> &LanguageIdentifier {
+        &self.fallback
+    }
+
+    /// Returns `true` if the active locale is written right to left (such as Arabic or Hebrew).
+    pub fn is_rtl(&self) -> bool {
+        self.locale.character_direction() == CharacterDirection::RTL
+    }
+
+    /// Format the message `id` with `args`, or an attribute of it with `id` as `message.attribute`.
+    ///
+    /// Placeables are wrapped in Unicode isolation marks, so arguments in another direction
+    /// don't reorder the surrounding text.
+    /// If no bundle has this message, `id` is returned instead.
+    pub fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let (message_id, attribute) = match id.split_once('.') {
+            Some((message_id, attribute)) => (message_id, Some(attribute)),
+            None => (id, None),
+        };
+
+        let bundles = self.bundles.borrow();
+        let candidates = [&self.locale, &self.fallback]
+            .into_iter()
+            .flat_map(|locale| {
+                let exact = bundles
+                    .iter()
+                    .filter(move |bundle| bundle.locales[0] == *locale);
+                let language = bundles.iter().filter(move |bundle| {
+                    bundle.locales[0] != *locale && bundle.locales[0].language == locale.language
+                });
+                exact.chain(language)
+            });
+
+        for bundle in candidates {
+            let Some(message) = bundle.get_message(message_id) else {
+                continue;
+            };
+            let pattern = match attribute {
+                Some(attribute) => message.get_attribute(attribute).map(|attr| attr.value()),
+                None => message.value(),
+            };
+            let Some(pattern) = pattern else {
+                continue;
+            };
+
+            let mut errors = Vec::new();
+            let value = bundle.format_pattern(pattern, args, &mut errors);
+
+            #[cfg(feature = "tracing")]
+            for error in &errors {
+                tracing::warn!("Failed to format message `{id}`: {error}");
+            }
+
+            return value.into_owned();
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Missing message `{id}` for locale `{}`", self.locale);
+
+        id.to_owned()
+    }
+}
+
+impl PartialEq for Localization {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.bundles, &other.bundles)
+            && self.locale == other.locale
+            && self.fallback == other.fallback
+    }
+}
+
+impl fmt::Debug for Localization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let locales = self
+            .bundles
+            .borrow()
+            .iter()
+            .map(|bundle| bundle.locales[0].to_string())
+            .collect::<Vec<_>>();
+
+        f.debug_struct("Localization")
+            .field("locales", &locales)
+            .field("locale", &self.locale)
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+/// Use the current [`Localization`].
+///
+/// Composables using it are recomposed when the active locale changes.
+///
+/// # Panics
+/// Panics if no localization was provided with [`Localized`].
+pub fn use_localization(cx: ScopeState<'_>) -> &Localization {
+    use_context::<Localization>(cx).unwrap()
+}
+
+/// Format a localized message from the current [`Localization`], with optional named arguments.
+///
+/// ```ignore
+/// let title = t!(&cx, "title");
+/// let greeting = t!(&cx, "greeting", name = "Matt", count = 3);
+/// ```
+///
+/// For more see [`Localization::format`].
+#[macro_export]
+macro_rules! t {
+    ($cx:expr, $id:expr) => {
+        $crate::i18n::use_localization($cx).format($id, None)
+    };
+    ($cx:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::use_localization($cx).format($id, Some(&args))
+    }};
+}
+
+/// Localized composable.
+///
+/// This provides a [`Localization`] to its content.
+/// With the `ui` feature, this also provides the layout [`Direction`](crate::ui::Direction) of its active locale,
+/// so text is aligned and flex rows are laid out right to left for locales such as Arabic or Hebrew.
+/// Changing the active locale will recompose the content with the new localization.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Localized<C> {
+    localization: Localization,
+    content: C,
+}
+
+impl<C> Localized<C> {
+    /// Create a new localized composable from its localization and the given `content`.
+    pub fn new(localization: Localization, content: C) -> Self {
+        Self {
+            localization,
+            content,
+        }
+    }
+}
+
+impl<C: Compose> Compose for Localized<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        use_memo_provider(&cx, cx.me().localization.clone(), || {
+            cx.me().localization.clone()
+        });
+
+        #[cfg(feature = "ui")]
+        {
+            let direction = if cx.me().localization.is_rtl() {
+                crate::ui::Direction::Rtl
+            } else {
+                crate::ui::Direction::Ltr
+            };
+            use_memo_provider(&cx, direction, || direction);
+        }
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use super::{FluentArgs, Localization, Localized};
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc};
+
+    fn localization() -> Localization {
+        let mut localization = Localization::new("en-US".parse().unwrap());
+        localization
+            .add_resource(
+                "en-US".parse().unwrap(),
+                "hello = Hello\nbye = Goodbye\n    .title = Leave\ngreeting = Hi, { $name }!",
+            )
+            .unwrap();
+        localization
+            .add_resource("ar".parse().unwrap(), "hello = مرحبا")
+            .unwrap();
+        localization
+    }
+
+    #[test]
+    fn it_formats_messages_with_fallbacks() {
+        let localization = localization().with_locale("ar-EG".parse().unwrap());
+        assert!(localization.is_rtl());
+
+        assert_eq!(localization.format("hello", None), "مرحبا");
+        assert_eq!(localization.format("bye", None), "Goodbye");
+        assert_eq!(localization.format("bye.title", None), "Leave");
+        assert_eq!(localization.format("missing", None), "missing");
+
+        let mut args = FluentArgs::new();
+        args.set("name", "Matt");
+        assert_eq!(
+            localization.format("greeting", Some(&args)),
+            "Hi, \u{2068}Matt\u{2069}!"
+        );
+
+        assert!(localization
+            .clone()
+            .add_resource("en-US".parse().unwrap(), "hello = Hey")
+            .is_err());
+    }
+
+    #[test]
+    fn it_recomposes_on_locale_changes() {
+        #[derive(Data)]
+        struct B {
+            values: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let value = crate::t!(&cx, "hello");
+                cx.me().values.borrow_mut().push(value);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            localization: Rc<RefCell<Localization>>,
+            values: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                // The content is only recomposed when the localization changes.
+                Localized::new(
+                    cx.me().localization.borrow().clone(),
+                    Memo::new(
+                        (),
+                        B {
+                            values: cx.me().values.clone(),
+                        },
+                    ),
+                )
+            }
+        }
+
+        let localization = Rc::new(RefCell::new(localization()));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            localization: localization.clone(),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        assert_eq!(*values.borrow(), ["Hello"]);
+
+        let ar = localization
+            .borrow()
+            .clone()
+            .with_locale("ar".parse().unwrap());
+        *localization.borrow_mut() = ar;
+        composer.compose();
+        assert_eq!(*values.borrow(), ["Hello", "مرحبا"]);
+    }
+}
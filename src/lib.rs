@@ -76,6 +76,23 @@
 //! actuate::run(App { name: String::from("Matt") })
 //! ```
 //!
+//! ## Function composables
+//! Composables can also be written as functions with `#[composable]`,
+//! generating a struct with a field for each argument after the scope.
+//! ```no_run
+//! use actuate::prelude::*;
+//!
+//! #[composable]
+//! fn greeting(cx: ScopeState, name: String) -> impl Compose {
+//!     let count = use_mut(cx, || 0);
+//!
+//!     Text::new(format!("Hello, {name}! ({})", *count))
+//!         .on_click(move || Mut::update(count, |x| *x += 1))
+//! }
+//!
+//! actuate::run(Greeting { name: String::from("Matt") })
+//! ```
+//!
 //! ## Hooks
 //! Functions that begin with `use_` are called `hooks` in Actuate.
 //! Hooks are used to manage state and side effects in composables.
@@ -91,20 +108,77 @@
 //! ```
 //!
 //! ## Features
+//! - `android-game-activity`: Runs on Android with a `GameActivity` (see [Mobile support](#mobile-support)).
+//! - `android-native-activity`: Runs on Android with a `NativeActivity` (see [Mobile support](#mobile-support)).
+//! - `clipboard`: Enables the `clipboard` module for copying and pasting with the platform clipboard.
+//! - `devtools`: Enables the `devtools` module for inspecting composable trees,
+//!   and the window inspector overlay (toggled with `Ctrl+Shift+I`) with the `ui` feature.
+//! - `embedded`: Enables the `embedded` module for drawing user interfaces to `embedded-graphics` displays,
+//!   such as on microcontrollers (see [`no_std` support](#no_std-support)).
 //! - `event-loop`: Enables the `event_loop` module for access to the system event loop.
+//! - `hot-reload`: Enables `Composer::replace` and `Composer::reload` for hot-reloading composables.
+//! - `file-dialog`: Enables the `file_dialog` module for native open and save dialogs (Windows and macOS).
+//! - `http`: Enables the `http` module for fetching and caching HTTP resources
+//!   (on native targets, requests are sent on an `ExecutorContext`, such as the Tokio runtime from `rt`).
+//! - `i18n`: Enables the `i18n` module for localizing messages with Fluent,
+//!   and right-to-left layouts for locales such as Arabic with the `ui` feature.
+//! - `image`: Enables the `Image` view for loading PNG and JPEG images.
+//! - `menu`: Enables the `menu` module for native menu bars and context menus (Windows and macOS).
 //! - `rt`: Enables the `rt` module for running async tasks on the Tokio runtime.
-//! - `tracing`: Enables the `tracing` module for logging.
+//! - `std`: Enables support for the standard library (enabled by default).
+//!   Without it, the compose and hooks engine only depends on `alloc` (see [`no_std` support](#no_std-support)).
+//!   Every other feature except `devtools` and `embedded` requires this feature.
+//! - `snapshot`: Enables the `snapshot` module for saving and restoring hook state with `Composer::snapshot`.
+//! - `software`: Renders windows on the CPU with `tiny-skia` (presented with `softbuffer`) if no GPU can render them,
+//!   such as in VMs and CI machines, and enables `SoftwareRenderer` for rendering without a GPU.
+//! - `storage`: Enables the `storage` module for persisting state to disk (or `localStorage` on the web).
+//! - `testing`: Enables the `ui::testing` and `tui::testing` modules for headless tests
+//!   of user interfaces with the `ui` and `tui` features.
+//! - `tray`: Enables the `tray` module for system tray icons (Windows and macOS).
+//! - `tracing`: Enables logging with the `tracing` crate, with spans for each composition and window frame.
+//! - `tui`: Enables the `tui` module for running terminal user interfaces with `ratatui`.
 //! - `ui`: Enables the `ui` module for building user interfaces.
-//! - `full`: Enables all features above.
+//! - `full`: Enables all features above, except the Android features.
+//!
+//! ## `no_std` support
+//! With `default-features = false`, this crate can be used on `no_std` targets with an allocator,
+//! composing with [`Composer::with_updater`](composer::Composer::with_updater) and a custom [`Updater`](composer::Updater).
+//! Channels, history, and workers are only available with the `std` feature.
+//!
+//! Without `std`, locks spin instead of blocking and the current runtime is stored in a plain static,
+//! so composers must only be used from a single thread.
+//! The program promises this by calling the unsafe
+//! `composer::assume_single_threaded` before composing (otherwise composing panics).
+//!
+//! With the `embedded` feature, an `embedded::Screen` composes views and draws them to any `embedded-graphics`
+//! display, with focus and presses from buttons or a rotary encoder.
+//!
+//! ## Web support
+//! With the `ui` feature, `run` also works on `wasm32-unknown-unknown`:
+//! each `Window` is drawn to an HTML canvas appended to the page,
+//! with WebGPU (or WebGL2 as a fallback) running the same vello renderer as on desktop.
+//! On the web, `run` returns immediately and the event loop keeps running in the browser.
+//!
+//! ## Mobile support
+//! On iOS, `run` is called from `main` like on desktop.
+//! On Android, `run_android` is called from the `android_main` function of your app
+//! (with the `android-native-activity` or `android-game-activity` feature).
+//!
+//! Render surfaces are dropped when the app is suspended and created again when it's resumed,
+//! and touches of the first finger are handled like clicks and drags of the mouse.
+//! Use `ui::use_safe_area` to keep content clear of status bars and notches.
 
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+use alloc::{boxed::Box, rc::Rc, vec::Vec};
 use composer::Runtime;
-use std::{
+use core::{
     any::{Any, TypeId},
-    cell::{Cell, RefCell, UnsafeCell},
-    collections::HashMap,
+    cell::{Cell, OnceCell, RefCell, UnsafeCell},
     fmt,
     future::Future,
     hash::{Hash, Hasher},
@@ -113,8 +187,8 @@ use std::{
     ops::Deref,
     pin::Pin,
     ptr::NonNull,
-    rc::Rc,
 };
+use hashbrown::{HashMap, HashSet};
 use thiserror::Error;
 
 macro_rules! cfg_ui {
@@ -130,29 +204,65 @@ macro_rules! cfg_ui {
 /// Prelude of commonly used items.
 pub mod prelude {
     pub use crate::{
-        compose::{self, Compose, DynCompose, Memo},
-        data::{Data, DataField, FieldWrap, FnField, StateField, StaticField},
-        use_context, use_drop, use_local_task, use_memo, use_mut, use_provider, use_ref, Cow, Map,
-        Mut, Ref, RefMap, Scope, ScopeState,
+        callback, clone,
+        compose::{self, Compose, ContextProvider, DynCompose, Memo},
+        data::{
+            composable, memo, memo_changed, Data, DataField, FieldWrap, FnField, MemoState,
+            StateField, StaticField,
+        },
+        use_callback, use_context, use_context_opt, use_context_or, use_drop, use_field, use_form,
+        use_local_task, use_memo, use_memo_provider, use_mut, use_provider, use_reducer, use_ref,
+        use_signal, without_context, Cow, Dispatcher, Field, FieldConfig, Form, Map, Memoize, Mut,
+        Ref, RefMap, Scope, ScopeState, Signal,
     };
 
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub use crate::{use_channel, use_history, use_stream, use_worker, History};
+
     #[cfg(feature = "executor")]
     #[cfg_attr(docsrs, doc(cfg(feature = "executor")))]
     pub use crate::use_task;
 
+    #[cfg(feature = "rt")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rt")))]
+    pub use crate::{use_debounced, use_interval, use_throttled, use_timeout};
+
     cfg_ui!(
         pub use crate::ui::{
-            view::{Canvas, Flex, Text, View, Window},
-            Draw,
+            animation::{use_animated, use_frame, AnimationSpec, Easing},
+            view::{Canvas, Flex, Grid, Scroll, Text, Transition, View, Window},
+            use_direction, use_theme, Direction, Draw, Renderer, Theme, Themed,
         };
 
-        pub use parley::GenericFamily;
+        pub use parley::{FontWeight, GenericFamily};
 
-        pub use taffy::prelude::*;
+        pub use taffy::{prelude::*, Overflow};
 
-        pub use vello::peniko::Color;
+        pub use vello::{
+            kurbo::Vec2,
+            peniko::{Brush, Color, Gradient},
+        };
     );
 
+    #[cfg(feature = "i18n")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+    pub use crate::{
+        i18n::{use_localization, Localization, Localized},
+        t,
+    };
+
+    #[cfg(feature = "image")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+    pub use crate::ui::view::Image;
+
+    #[cfg(feature = "event-loop")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "event-loop")))]
+    pub use crate::event_loop::{
+        use_event_loop_events, use_keep_alive, use_locale, use_monitors, use_reduced_motion,
+        use_system_info, use_system_theme, use_window, use_window_event,
+    };
+
     #[cfg(feature = "event-loop")]
     #[cfg_attr(docsrs, doc(cfg(feature = "event-loop")))]
     pub use winit::window::WindowAttributes;
@@ -169,6 +279,52 @@ pub mod composer;
 pub mod data;
 pub use crate::data::Data;
 
+pub use actuate_macros::{callback, clone};
+
+#[cfg(feature = "std")]
+mod channel;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use self::channel::{use_channel, use_stream, Channel};
+
+mod signal;
+pub use self::signal::{use_signal, Signal};
+
+mod form;
+pub use self::form::{use_field, use_form, Field, FieldConfig, Form};
+
+#[cfg(feature = "std")]
+mod history;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use self::history::{use_history, History};
+
+mod sync;
+
+#[cfg(feature = "std")]
+mod worker;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use self::worker::use_worker;
+
+#[cfg(feature = "clipboard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+/// Platform clipboard access.
+pub mod clipboard;
+
+#[cfg(feature = "devtools")]
+#[cfg_attr(docsrs, doc(cfg(feature = "devtools")))]
+/// Introspection of composable trees.
+pub mod devtools;
+
+#[cfg(feature = "embedded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded")))]
+/// User interfaces for `embedded-graphics` displays.
+pub mod embedded;
+
 #[cfg(feature = "event-loop")]
 #[cfg_attr(docsrs, doc(cfg(feature = "event-loop")))]
 /// System event loop for windowing.
@@ -179,6 +335,53 @@ pub mod event_loop;
 /// Task execution context.
 pub mod executor;
 
+#[cfg(feature = "file-dialog")]
+#[cfg_attr(docsrs, doc(cfg(feature = "file-dialog")))]
+/// Native file dialogs.
+pub mod file_dialog;
+
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+/// HTTP resources.
+pub mod http;
+
+#[cfg(feature = "i18n")]
+#[cfg_attr(docsrs, doc(cfg(feature = "i18n")))]
+/// Localization with Fluent.
+pub mod i18n;
+
+#[cfg(feature = "menu")]
+#[cfg_attr(docsrs, doc(cfg(feature = "menu")))]
+/// Native menus.
+pub mod menu;
+
+#[cfg(feature = "snapshot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+/// Snapshots of restorable state.
+pub mod snapshot;
+
+#[cfg(feature = "storage")]
+#[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+/// Persistent state storage.
+pub mod storage;
+
+#[cfg(feature = "tray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tray")))]
+/// System tray icons.
+pub mod tray;
+
+#[cfg(feature = "tui")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tui")))]
+/// Terminal user interfaces.
+pub mod tui;
+
+#[cfg(feature = "rt")]
+mod timer;
+
+#[cfg(feature = "rt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rt")))]
+pub use self::timer::{use_debounced, use_interval, use_throttled, use_timeout};
+
 #[cfg(feature = "ui")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ui")))]
 /// Run this content on the system event loop.
@@ -186,6 +389,57 @@ pub fn run(content: impl Compose + 'static) {
     event_loop::run(ui::RenderRoot { content });
 }
 
+#[cfg(all(feature = "ui", target_os = "android"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "ui", target_os = "android"))))]
+/// Run this content on the event loop of an Android app.
+///
+/// This is called from the `android_main` function of a native or game activity.
+pub fn run_android(
+    app: winit::platform::android::activity::AndroidApp,
+    content: impl Compose + 'static,
+) {
+    event_loop::run_android(app, ui::RenderRoot { content });
+}
+
+#[cfg(feature = "ui")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ui")))]
+/// Run this content on an event loop created by the app, with its own user events of type `T`.
+///
+/// Events sent with an [`EventLoopProxy`](winit::event_loop::EventLoopProxy) of this event loop
+/// (wrapped in [`UserEvent::new`](event_loop::UserEvent::new)) are handled by composables
+/// with [`use_event_loop_events`](event_loop::use_event_loop_events).
+///
+/// ```no_run
+/// use actuate::{event_loop::UserEvent, prelude::*};
+/// use winit::event_loop::EventLoop;
+///
+/// #[derive(Data)]
+/// struct App;
+///
+/// impl Compose for App {
+///     fn compose(cx: Scope<Self>) -> impl Compose {
+///         let count = use_mut(&cx, || 0);
+///         use_event_loop_events(&cx, move |&n: &i32| Mut::update(count, move |count| *count += n));
+///
+///         Window::new(Text::new(format!("Count: {}", *count)))
+///     }
+/// }
+///
+/// let event_loop = EventLoop::<UserEvent<i32>>::with_user_event().build().unwrap();
+///
+/// // Wake the UI from a background service.
+/// let proxy = event_loop.create_proxy();
+/// std::thread::spawn(move || proxy.send_event(UserEvent::new(1)));
+///
+/// actuate::run_with_event_loop(event_loop, App);
+/// ```
+pub fn run_with_event_loop<T: Send + 'static>(
+    event_loop: winit::event_loop::EventLoop<event_loop::UserEvent<T>>,
+    content: impl Compose + 'static,
+) {
+    crate::event_loop::run_with_event_loop(event_loop, ui::RenderRoot { content });
+}
+
 cfg_ui!(
     /// User interface components.
     pub mod ui;
@@ -334,11 +588,8 @@ impl<C: Compose> Compose for RefMap<'_, C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
         cx.is_container.set(true);
 
-        let state = use_ref(&cx, || {
-            let mut state = ScopeData::default();
-            state.contexts = cx.contexts.clone();
-            state
-        });
+        let state = use_ref(&cx, ScopeData::default);
+        state.inherit_contexts(&cx);
 
         state.is_parent_changed.set(cx.is_parent_changed.get());
 
@@ -349,6 +600,11 @@ impl<C: Compose> Compose for RefMap<'_, C> {
 /// Mapped immutable reference to a value of type `T`.
 ///
 /// This can be created with [`Ref::map`].
+///
+/// # Invariants
+/// `ptr` points to the value of the original [`Ref`], and `map_fn` is the function passed to [`Ref::map`],
+/// which `deref_fn` casts back to its original types to map that value again on each dereference.
+/// Both are type-erased so this reference only depends on the mapped type `T`.
 pub struct Map<'a, T> {
     ptr: *const (),
     map_fn: *const (),
@@ -370,11 +626,8 @@ impl<C: Compose> Compose for Map<'_, C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
         cx.is_container.set(true);
 
-        let state = use_ref(&cx, || {
-            let mut state = ScopeData::default();
-            state.contexts = cx.contexts.clone();
-            state
-        });
+        let state = use_ref(&cx, ScopeData::default);
+        state.inherit_contexts(&cx);
 
         state.is_parent_changed.set(cx.is_parent_changed.get());
 
@@ -432,16 +685,77 @@ impl<T> Hash for Ref<'_, T> {
     }
 }
 
+/// Pointer to the value and generation of a [`use_mut`] hook.
+///
+/// Unlike a [`Mut`], this pointer can be moved into queued updates that outlive the current composition.
+///
+/// # Invariants
+/// Both pointers are derived from the hook's [`UnsafeCell`] and [`Cell`] through a shared reference,
+/// so they stay valid (and aren't invalidated by later compositions) until the scope owning the hook is dropped.
+/// They are only dereferenced on the composer's thread.
+struct MutPtr<T> {
+    value: NonNull<T>,
+    generation: NonNull<Cell<u64>>,
+}
+
+impl<T> MutPtr<T> {
+    fn new(state: &MutState<T>) -> Self {
+        Self {
+            // Safety: `UnsafeCell::get` never returns a null pointer.
+            value: unsafe { NonNull::new_unchecked(state.value.get()) },
+            generation: NonNull::from(&state.generation),
+        }
+    }
+
+    /// Get a reference to the value.
+    ///
+    /// # Safety
+    /// The scope owning this hook must exist for `'a`, and the value must not be mutated during `'a`.
+    unsafe fn get<'a>(self) -> &'a T {
+        self.value.as_ref()
+    }
+
+    /// Get the current generation of the value.
+    ///
+    /// # Safety
+    /// The scope owning this hook must exist.
+    unsafe fn generation(self) -> u64 {
+        self.generation.as_ref().get()
+    }
+
+    /// Apply `f` to the value, incrementing its generation if `is_changed` is `true`.
+    ///
+    /// # Safety
+    /// The scope owning this hook must exist, and no other reference to the value may be in use.
+    /// This is the case for updates applied by the composer, which are skipped once their scope is dropped.
+    unsafe fn update(self, is_changed: bool, f: impl FnOnce(&mut T)) {
+        f(&mut *self.value.as_ptr());
+
+        if is_changed {
+            let generation = self.generation.as_ref();
+            generation.set(generation.get() + 1);
+        }
+    }
+}
+
+impl<T> Clone for MutPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for MutPtr<T> {}
+
 /// Mutable reference to a value of type `T`.
+///
+/// This reference can only be used on the composer's thread.
+/// To update a value from a task on another thread, use a [`Signal`] instead.
 pub struct Mut<'a, T> {
-    /// Pointer to the boxed value.
-    ptr: NonNull<T>,
-
-    /// Pointer to the scope's `is_changed` flag.
-    scope_is_changed: *const Cell<bool>,
+    /// Pointer to the hook storing this value.
+    ptr: MutPtr<T>,
 
-    /// Pointer to this value's generation.
-    generation: *const Cell<u64>,
+    /// Pointer to the scope owning this value.
+    scope: *const ScopeData<'static>,
 
     /// Marker for the lifetime of this immutable reference.
     phantom: PhantomData<&'a ()>,
@@ -450,20 +764,13 @@ pub struct Mut<'a, T> {
 impl<'a, T: 'static> Mut<'a, T> {
     /// Queue an update to this value, triggering an update to the component owning this value.
     pub fn update(me: Self, f: impl FnOnce(&mut T) + 'static) {
-        let mut ptr = me.ptr;
-        let is_changed = me.scope_is_changed;
-        let generation = me.generation;
-
-        Runtime::current().update(move || {
-            let value = unsafe { ptr.as_mut() };
-            f(value);
-
-            unsafe {
-                (*is_changed).set(true);
+        let ptr = me.ptr;
 
-                let g = &*generation;
-                g.set(g.get() + 1)
-            }
+        // Safety: The scope owning this value exists while this reference does.
+        let scope = unsafe { &*me.scope };
+        scope.runtime().update_scope(scope, true, move || {
+            // Safety: This update is only applied if the scope owning this value still exists.
+            unsafe { ptr.update(true, f) }
         });
     }
 
@@ -474,20 +781,22 @@ impl<'a, T: 'static> Mut<'a, T> {
 
     /// Queue an update to this value wtihout triggering an update.
     pub fn with(me: Self, f: impl FnOnce(&mut T) + 'static) {
-        let mut cell = Some(f);
-        let mut ptr = me.ptr;
+        let ptr = me.ptr;
 
-        Runtime::current().update(move || {
-            let value = unsafe { ptr.as_mut() };
-            cell.take().unwrap()(value);
+        // Safety: The scope owning this value exists while this reference does.
+        let scope = unsafe { &*me.scope };
+        scope.runtime().update_scope(scope, false, move || {
+            // Safety: This update is only applied if the scope owning this value still exists.
+            unsafe { ptr.update(false, f) }
         });
     }
 
     /// Convert this mutable reference to an immutable reference.
     pub fn as_ref(me: Self) -> Ref<'a, T> {
         Ref {
-            value: unsafe { me.ptr.as_ref() },
-            generation: me.generation,
+            // Safety: The value is only mutated by updates, which are applied outside of a composition.
+            value: unsafe { me.ptr.get() },
+            generation: me.ptr.generation.as_ptr(),
         }
     }
 }
@@ -496,7 +805,8 @@ impl<T> Deref for Mut<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
-        unsafe { self.ptr.as_ref() }
+        // Safety: The value is only mutated by updates, which are applied outside of a composition.
+        unsafe { self.ptr.get() }
     }
 }
 
@@ -515,7 +825,7 @@ macro_rules! impl_pointer {
                 fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                     f.debug_struct(stringify!($t))
                         .field("value", &**self)
-                        .field("generation", &unsafe { &*self.generation }.get())
+                        .field("generation", &Memoize::memoized(*self))
                         .finish()
                 }
             }
@@ -526,10 +836,6 @@ macro_rules! impl_pointer {
                 }
             }
 
-            unsafe impl<T: Send + Sync> Send for $t<'_, T> {}
-
-            unsafe impl<T: Sync + Sync> Sync for $t<'_, T> {}
-
             impl<'a, T: 'a> IntoIterator for $t<'a, T>
             where
                 &'a T: IntoIterator,
@@ -552,10 +858,90 @@ macro_rules! impl_pointer {
 }
 impl_pointer!(Ref, Map, Mut);
 
-/// Map of [`TypeId`] to context values.
+/// Context value, shared between its provider and each scope that uses it.
+struct ContextValue {
+    /// Current value.
+    value: RefCell<Rc<dyn Any>>,
+
+    /// Pointers to each scope that used this value.
+    consumers: RefCell<HashSet<*const ()>>,
+}
+
+impl ContextValue {
+    fn new(value: Rc<dyn Any>) -> Self {
+        Self {
+            value: RefCell::new(value),
+            consumers: RefCell::default(),
+        }
+    }
+
+    /// Replace this value, marking each scope that used it as changed.
+    fn replace(&self, value: Rc<dyn Any>) {
+        *self.value.borrow_mut() = value;
+
+        for consumer in &*self.consumers.borrow() {
+            // Safety: Consumers are removed from this set when their scope is dropped.
+            let scope = unsafe { &*(*consumer as *const ScopeData) };
+            scope.set_changed();
+        }
+    }
+}
+
+/// Context value used by a scope.
+struct UsedContext {
+    context: Rc<ContextValue>,
+
+    /// The value last read by the scope, kept alive until it reads this context again.
+    _value: Rc<dyn Any>,
+}
+
+/// Context values provided by a scope, by [`TypeId`].
+///
+/// A `None` value removes the context provided by a parent scope.
+type ContextMap = HashMap<TypeId, Option<Rc<ContextValue>>>;
+
+/// Layer of context values provided by one scope.
+struct ContextLayer {
+    values: ContextMap,
+    parent: Contexts,
+}
+
+/// Layered map of [`TypeId`] to context values.
+///
+/// Lookups return the value from the nearest layer,
+/// so a scope can shadow (or remove) a context for its own subtree.
 #[derive(Clone, Default)]
 struct Contexts {
-    values: HashMap<TypeId, Rc<dyn Any>>,
+    layer: Option<Rc<ContextLayer>>,
+}
+
+impl Contexts {
+    /// Get the nearest context value with the type `id`.
+    fn get(&self, id: TypeId) -> Option<Rc<ContextValue>> {
+        let mut layer = self.layer.as_ref();
+        while let Some(next) = layer {
+            if let Some(value) = next.values.get(&id) {
+                return value.clone();
+            }
+            layer = next.parent.layer.as_ref();
+        }
+
+        None
+    }
+
+    /// Create a child of these contexts, with `values` layered on top.
+    fn with_layer(&self, values: &ContextMap) -> Self {
+        if values.is_empty() {
+            return self.clone();
+        }
+
+        Self {
+            layer: Some(Rc::new(ContextLayer {
+                values: values.clone(),
+                parent: self.clone(),
+            })),
+        }
+    }
 }
 
 /// Scope state of a composable function.
@@ -576,17 +962,37 @@ pub struct ScopeData<'a> {
     /// `true` if an ancestor to this scope is changed.
     is_parent_changed: Cell<bool>,
 
+    /// `true` if a descendant of this scope is changed.
+    is_child_changed: Cell<bool>,
+
+    /// Parent of this scope, set each time this scope is composed.
+    parent: Cell<Option<NonNull<ScopeData<'static>>>>,
+
+    /// Name of the composable in this scope.
+    #[cfg(feature = "devtools")]
+    name: Cell<Option<composer::NameFn>>,
+
+    /// Child scopes, in the order they were first composed.
+    #[cfg(feature = "devtools")]
+    children: RefCell<Vec<NonNull<ScopeData<'static>>>>,
+
     /// `true` if this scope contains an empty composable.
     is_empty: Cell<bool>,
 
     /// `true` if this scope contains a container composable.
     is_container: Cell<bool>,
 
+    /// Last memoized fields of the composable in this scope, if it's declared with [`memo`](crate::data::memo).
+    memoized: RefCell<Option<Box<dyn Any>>>,
+
     /// Context values stored in this scope.
     contexts: RefCell<Contexts>,
 
-    /// Context values for child composables.
-    child_contexts: RefCell<Contexts>,
+    /// Context values provided (or removed) for child composables.
+    child_contexts: RefCell<ContextMap>,
+
+    /// Context values used by this scope.
+    used_contexts: RefCell<HashMap<TypeId, UsedContext>>,
 
     /// Drop functions to run just before this scope is dropped.
     drops: RefCell<Vec<usize>>,
@@ -594,30 +1000,117 @@ pub struct ScopeData<'a> {
     /// Current generation of this scope.
     generation: Cell<u64>,
 
+    /// ID of this scope for updates, set once it's registered to receive updates.
+    id: Cell<Option<u64>>,
+
+    /// Runtime of the composer owning this scope, set when it's first composed.
+    pub(crate) rt: OnceCell<Runtime>,
+
     /// Marker for the invariant lifetime of this scope.
     _marker: PhantomData<&'a fn(ScopeData<'a>) -> ScopeData<'a>>,
 }
 
 impl ScopeData<'_> {
+    /// Set the contexts of this scope to the contexts of `parent`,
+    /// including the contexts it provides to its children.
+    pub(crate) fn inherit_contexts(&self, parent: &ScopeData) {
+        let contexts = parent
+            .contexts
+            .borrow()
+            .with_layer(&parent.child_contexts.borrow());
+        *self.contexts.borrow_mut() = contexts;
+    }
+
+    /// Get the runtime of the composer owning this scope.
+    ///
+    /// Updates to this scope should be queued with this runtime,
+    /// as another composer's runtime may be current while handling events.
+    pub(crate) fn runtime(&self) -> &Runtime {
+        self.rt.get_or_init(Runtime::current)
+    }
+
     /// Set this scope as changed.
+    ///
+    /// This also marks each ancestor of this scope, so the next composition visits this scope.
     pub fn set_changed(&self) {
         self.is_changed.set(true);
+
+        let mut parent = self.parent.get();
+        while let Some(scope) = parent {
+            // Safety: Parent scopes outlive their children.
+            let scope = unsafe { scope.as_ref() };
+            scope.is_child_changed.set(true);
+            parent = scope.parent.get();
+        }
     }
 
     /// Returns `true` if an ancestor to this scope is changed.
     pub fn is_parent_changed(&self) -> bool {
         self.is_parent_changed.get()
     }
-}
 
-impl Drop for ScopeData<'_> {
-    fn drop(&mut self) {
+    /// Stop tracking used contexts, and run the drop functions of this scope.
+    fn run_drops(&self) {
+        let ptr = self as *const Self as *const ();
+        for used in self.used_contexts.borrow().values() {
+            used.context.consumers.borrow_mut().remove(&ptr);
+        }
+
         for idx in &*self.drops.borrow() {
             let hooks = unsafe { &mut *self.hooks.get() };
             let any = hooks.get_mut(*idx).unwrap();
             (**any).downcast_mut::<Box<dyn FnMut()>>().unwrap()();
         }
     }
+
+    /// Reset this scope by dropping its hooks, so it is composed again from its initial state.
+    ///
+    /// # Safety
+    /// The caller must ensure this scope is not currently being composed.
+    #[cfg(feature = "hot-reload")]
+    pub(crate) unsafe fn reset(&self) {
+        // Skip pending updates to the hooks dropped below.
+        composer::unregister_scope(self);
+
+        self.run_drops();
+        self.drops.borrow_mut().clear();
+        self.used_contexts.borrow_mut().clear();
+        self.child_contexts.borrow_mut().clear();
+
+        // Drop each hook in place, as child scopes point to this scope.
+        let hooks = mem::take(&mut *self.hooks.get());
+        drop(hooks);
+
+        self.hook_idx.set(0);
+        self.is_empty.set(false);
+        self.is_container.set(false);
+        self.set_changed();
+    }
+}
+
+impl Drop for ScopeData<'_> {
+    fn drop(&mut self) {
+        composer::unregister_scope(self);
+        self.run_drops();
+
+        #[cfg(feature = "devtools")]
+        for child in &*self.children.borrow() {
+            // Safety: Children remove themselves from this list when dropped.
+            unsafe { child.as_ref() }.parent.set(None);
+        }
+
+        #[cfg(feature = "devtools")]
+        if let Some(parent) = self.parent.get() {
+            let ptr = self as *const Self as *const ();
+
+            // Safety: Parent scopes outlive their children.
+            let parent = unsafe { parent.as_ref() };
+            parent
+                .children
+                .borrow_mut()
+                .retain(|child| child.as_ptr() as *const () != ptr);
+        }
+    }
 }
 
 /// Composable scope.
@@ -660,7 +1153,7 @@ impl<'a, C> Deref for Scope<'a, C> {
 /// Use an immutable reference to a value of type `T`.
 ///
 /// `make_value` will only be called once to initialize this value.
-pub fn use_ref<T: 'static>(cx: ScopeState, make_value: impl FnOnce() -> T) -> &T {
+pub fn use_ref<T: 'static>(cx: ScopeState<'_>, make_value: impl FnOnce() -> T) -> &T {
     let hooks = unsafe { &mut *cx.hooks.get() };
 
     let idx = cx.hook_idx.get();
@@ -672,18 +1165,22 @@ pub fn use_ref<T: 'static>(cx: ScopeState, make_value: impl FnOnce() -> T) -> &T
     } else {
         hooks.get(idx).unwrap()
     };
+
+    #[cfg(feature = "hot-reload")]
+    composer::check_hook::<T>(&**any);
+
     (**any).downcast_ref().unwrap()
 }
 
 struct MutState<T> {
-    value: T,
+    value: UnsafeCell<T>,
     generation: Cell<u64>,
 }
 
 /// Use a mutable reference to a value of type `T`.
 ///
 /// `make_value` will only be called once to initialize this value.
-pub fn use_mut<T: 'static>(cx: ScopeState, make_value: impl FnOnce() -> T) -> Mut<'_, T> {
+pub fn use_mut<T: 'static>(cx: ScopeState<'_>, make_value: impl FnOnce() -> T) -> Mut<'_, T> {
     let hooks = unsafe { &mut *cx.hooks.get() };
 
     let idx = cx.hook_idx.get();
@@ -691,26 +1188,114 @@ pub fn use_mut<T: 'static>(cx: ScopeState, make_value: impl FnOnce() -> T) -> Mu
 
     let any = if idx >= hooks.len() {
         let state = MutState {
-            value: make_value(),
+            value: UnsafeCell::new(make_value()),
             generation: Cell::new(0),
         };
         hooks.push(Box::new(state));
-        hooks.last_mut().unwrap()
+        hooks.last().unwrap()
     } else {
-        hooks.get_mut(idx).unwrap()
+        hooks.get(idx).unwrap()
     };
-    let state: &mut MutState<T> = any.downcast_mut().unwrap();
+
+    #[cfg(feature = "hot-reload")]
+    composer::check_hook::<MutState<T>>(&**any);
+
+    // The state is only borrowed immutably, so pointers to it from earlier compositions stay valid.
+    let state: &MutState<T> = any.downcast_ref().unwrap();
 
     Mut {
-        ptr: unsafe { NonNull::new_unchecked(&mut state.value as *mut _) },
-        scope_is_changed: &cx.is_changed,
-        generation: &state.generation,
+        ptr: MutPtr::new(state),
+        scope: cx as *const ScopeData as _,
         phantom: PhantomData::<&()>,
     }
 }
 
+type ReducerFn<S, A> = Rc<dyn Fn(&mut S, A)>;
+
+/// Dispatcher of actions to a reducer, created with [`use_reducer`].
+pub struct Dispatcher<A> {
+    f: Rc<dyn Fn(A)>,
+}
+
+impl<A> Dispatcher<A> {
+    /// Queue an action to be applied by the reducer, triggering an update to the component owning its state.
+    ///
+    /// Actions dispatched after the owning component is dropped are ignored.
+    pub fn dispatch(&self, action: A) {
+        (self.f)(action)
+    }
+}
+
+impl<A> Clone for Dispatcher<A> {
+    fn clone(&self) -> Self {
+        Self { f: self.f.clone() }
+    }
+}
+
+impl<A> fmt::Debug for Dispatcher<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dispatcher").finish_non_exhaustive()
+    }
+}
+
+/// Use a state of type `S`, updated by applying actions of type `A` with a `reducer`.
+///
+/// Dispatching an action queues a call to `reducer`, triggering an update to this component.
+/// The reducer will be updated to `reducer` whenever this component is re-composed.
+pub fn use_reducer<S, A>(
+    cx: ScopeState<'_>,
+    make_state: impl FnOnce() -> S,
+    reducer: impl Fn(&mut S, A) + 'static,
+) -> (Ref<'_, S>, Dispatcher<A>)
+where
+    S: 'static,
+    A: 'static,
+{
+    let state = use_mut(cx, make_state);
+
+    let reducer: ReducerFn<S, A> = Rc::new(reducer);
+    let reducer_cell = use_ref(cx, || Rc::new(RefCell::new(reducer.clone())));
+    *reducer_cell.borrow_mut() = reducer;
+
+    let is_alive = use_ref(cx, || Rc::new(Cell::new(true)));
+    use_drop(cx, || is_alive.set(false));
+
+    let dispatcher = use_ref(cx, || {
+        let ptr = state.ptr;
+        let scope = state.scope;
+        let reducer_cell = reducer_cell.clone();
+        let is_alive = is_alive.clone();
+        let rt = cx.runtime().clone();
+
+        Dispatcher {
+            f: Rc::new(move |action| {
+                let reducer_cell = reducer_cell.clone();
+                let is_alive = is_alive.clone();
+
+                rt.update(move || {
+                    if !is_alive.get() {
+                        return;
+                    }
+
+                    let reducer = reducer_cell.borrow().clone();
+
+                    // Safety: `is_alive` is `false` once the scope owning this state is dropped.
+                    unsafe {
+                        ptr.update(true, |state| reducer(state, action));
+                        (*scope).set_changed();
+                    }
+                });
+            }),
+        }
+    });
+
+    (Mut::as_ref(state), dispatcher.clone())
+}
+
 /// Use a callback function.
 /// The returned function will be updated to `f` whenever this component is re-composed.
+///
+/// To clone state handles into `f` first, see [`callback!`].
 pub fn use_callback<'a, T, R>(
     cx: ScopeState<'a>,
     f: impl FnMut(T) -> R + 'a,
@@ -737,23 +1322,42 @@ where
 #[derive(Error)]
 /// Error for a missing context.
 pub struct ContextError<T> {
+    path: Vec<alloc::borrow::Cow<'static, str>>,
     _marker: PhantomData<T>,
 }
 
+impl<T> ContextError<T> {
+    /// Get the names of the composables being composed when the context was used, starting from the root.
+    ///
+    /// Only composables containing the composable that used the context (like [`Memo`](crate::compose::Memo))
+    /// and the composable itself are included.
+    pub fn path(&self) -> &[alloc::borrow::Cow<'static, str>] {
+        &self.path
+    }
+}
+
 impl<T> fmt::Debug for ContextError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_tuple("ContextError")
-            .field(&std::any::type_name::<T>())
+        f.debug_struct("ContextError")
+            .field("type", &core::any::type_name::<T>())
+            .field("path", &self.path)
             .finish()
     }
 }
 
 impl<T> fmt::Display for ContextError<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(&format!(
+        write!(
+            f,
             "Context value not found for type: {}",
-            std::any::type_name::<T>()
-        ))
+            core::any::type_name::<T>()
+        )?;
+
+        if !self.path.is_empty() {
+            write!(f, " (in `{}`)", self.path.join(" > "))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -761,33 +1365,125 @@ impl<T> fmt::Display for ContextError<T> {
 ///
 /// This context must have already been provided by a parent composable with [`use_provider`],
 /// otherwise this function will return a [`ContextError`].
+///
+/// If the provided value is later replaced (see [`use_memo_provider`]), this composable is recomposed with the new value.
+///
+/// For optional contexts, see [`use_context_opt`] and [`use_context_or`].
 pub fn use_context<'a, T: 'static>(cx: ScopeState<'a>) -> Result<&'a T, ContextError<T>> {
-    let Some(any) = cx.contexts.borrow().values.get(&TypeId::of::<T>()).cloned() else {
+    let Some(context) = cx.contexts.borrow().get(TypeId::of::<T>()) else {
         return Err(ContextError {
+            path: composer::frame_names(),
             _marker: PhantomData,
         });
     };
 
+    let ptr = cx as *const ScopeData as *const ();
+    context.consumers.borrow_mut().insert(ptr);
+
+    let any = context.value.borrow().clone();
+    let last = cx.used_contexts.borrow_mut().insert(
+        TypeId::of::<T>(),
+        UsedContext {
+            context: context.clone(),
+            _value: any.clone(),
+        },
+    );
+
+    // Stop tracking a context that was provided by a different composable.
+    if let Some(last) = last {
+        if !Rc::ptr_eq(&last.context, &context) {
+            last.context.consumers.borrow_mut().remove(&ptr);
+        }
+    }
+
     let value: &T = (*any).downcast_ref().unwrap();
+
+    // Safety: `any` is kept alive in `used_contexts` until this scope uses this context again.
     let value: &'a T = unsafe { mem::transmute(value) };
 
     Ok(value)
 }
 
+/// Use a context value of type `T`, or `None` if it wasn't provided by a parent composable.
+///
+/// For more see [`use_context`].
+pub fn use_context_opt<T: 'static>(cx: ScopeState<'_>) -> Option<&T> {
+    use_context(cx).ok()
+}
+
+/// Use a context value of type `T`, or a default value if it wasn't provided by a parent composable.
+///
+/// `make_default` will only be called once, the first time the context is missing.
+/// For more see [`use_context`].
+pub fn use_context_or<'a, T: 'static>(
+    cx: ScopeState<'a>,
+    make_default: impl FnOnce() -> T,
+) -> &'a T {
+    let default = use_ref(cx, OnceCell::new);
+
+    use_context(cx).unwrap_or_else(|_| default.get_or_init(make_default))
+}
+
 /// Provide a context value of type `T`.
 ///
 /// This value will be available to [`use_context`] to all children of this composable.
+/// A value of the same type provided by a parent composable is shadowed for these children.
 pub fn use_provider<T: 'static>(cx: ScopeState<'_>, make_value: impl FnOnce() -> T) -> &Rc<T> {
     use_ref(cx, || {
         let value = Rc::new(make_value());
-        cx.child_contexts
-            .borrow_mut()
-            .values
-            .insert(TypeId::of::<T>(), value.clone());
+        cx.child_contexts.borrow_mut().insert(
+            TypeId::of::<T>(),
+            Some(Rc::new(ContextValue::new(value.clone()))),
+        );
         value
     })
 }
 
+/// Provide a context value of type `T`, replacing it whenever `dependency` changes.
+///
+/// This value will be available to [`use_context`] to all children of this composable.
+/// Replacing this value will recompose each child that used it.
+pub fn use_memo_provider<D, T>(
+    cx: ScopeState<'_>,
+    dependency: D,
+    make_value: impl FnOnce() -> T,
+) -> Rc<T>
+where
+    D: Memoize,
+    T: 'static,
+{
+    let context_cell: &RefCell<Option<Rc<ContextValue>>> = use_ref(cx, RefCell::default);
+
+    let value = use_memo(cx, dependency, || {
+        let value = Rc::new(make_value());
+
+        let mut context_cell = context_cell.borrow_mut();
+        if let Some(context) = &*context_cell {
+            context.replace(value.clone());
+        } else {
+            let context = Rc::new(ContextValue::new(value.clone()));
+            cx.child_contexts
+                .borrow_mut()
+                .insert(TypeId::of::<T>(), Some(context.clone()));
+            *context_cell = Some(context);
+        }
+
+        value
+    });
+
+    (*value).clone()
+}
+
+/// Remove the context value of type `T` for the children of this composable.
+///
+/// A value provided by a parent composable will not be available to [`use_context`] in these children,
+/// unless it's provided again by a composable between them.
+pub fn without_context<T: 'static>(cx: ScopeState<'_>) {
+    cx.child_contexts
+        .borrow_mut()
+        .insert(TypeId::of::<T>(), None);
+}
+
 /// Memoize a value, caching it until the dependency changes.
 ///
 /// This is used in [`Memo`](crate::compose::Memo) and [`use_memo`] to cache composables.
@@ -846,7 +1542,8 @@ impl<T> Memoize for Mut<'_, T> {
     type Value = u64;
 
     fn memoized(self) -> Self::Value {
-        unsafe { &*self.generation }.get()
+        // Safety: The scope owning this value exists while this reference does.
+        unsafe { self.ptr.generation() }
     }
 }
 
@@ -877,7 +1574,8 @@ where
 
 /// Use a memoized value of type `T` with a dependency of type `D`.
 ///
-/// `make_value` will update the returned value whenver `dependency` is changed.
+/// `make_value` will update the returned value whenver `dependency` is changed,
+/// and the updated value is returned from the same composition.
 pub fn use_memo<D, T>(cx: ScopeState, dependency: D, make_value: impl FnOnce() -> T) -> Ref<T>
 where
     D: Memoize,
@@ -894,9 +1592,14 @@ where
         if let Some(dependency) = dependency_cell.take() {
             if dependency != *hash_mut {
                 let value = make_value();
-                Mut::with(value_mut, move |update| *update = value);
 
-                Mut::with(hash_mut, move |dst| *dst = dependency);
+                // Replace the value in this pass, so it's returned right away,
+                // and update its generation, so content memoized on the returned `Ref` is re-composed.
+                // Safety: References to these values from earlier compositions of this scope are no longer in use.
+                unsafe {
+                    value_mut.ptr.update(true, move |dst| *dst = value);
+                    hash_mut.ptr.update(false, move |dst| *dst = dependency);
+                }
             }
         }
     }
@@ -951,24 +1654,34 @@ where
         let task: Pin<Box<dyn Future<Output = ()>>> = Box::pin(make_task());
         let task: Pin<Box<dyn Future<Output = ()>>> = unsafe { mem::transmute(task) };
 
-        let rt = Runtime::current();
+        let rt = cx.runtime();
         let key = rt.tasks.borrow_mut().insert(task);
-        rt.task_tx.send(key).unwrap();
+        rt.wake_task(key);
         key
     });
 
+    // The scope is already being dropped when this runs, so only the runtime is borrowed here.
+    let rt = use_ref(cx, || cx.runtime().clone());
     use_drop(cx, move || {
-        Runtime::current().tasks.borrow_mut().remove(key);
+        rt.tasks.borrow_mut().remove(key);
     })
 }
 
 #[cfg(feature = "executor")]
 type BoxedFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 
+#[cfg(feature = "executor")]
+type ReadFuture = Pin<Box<dyn Future<Output = tokio::sync::OwnedRwLockReadGuard<()>> + Send>>;
+
 #[cfg(feature = "executor")]
 struct TaskFuture {
     task: std::sync::Arc<std::sync::Mutex<Option<BoxedFuture>>>,
-    rt: Runtime,
+
+    /// Update lock of the composer owning this task.
+    lock: std::sync::Arc<sync::RwLock<()>>,
+
+    /// Pending read guard of the update lock, acquired before each poll of the task.
+    read: Option<ReadFuture>,
 }
 
 #[cfg(feature = "executor")]
@@ -985,22 +1698,25 @@ impl Future for TaskFuture {
         // This is to ensure the scope for this task is not dropped while polling.
         let mut guard = me.task.lock().unwrap();
 
-        if let Some(task) = &mut *guard {
-            me.rt.enter();
+        let Some(task) = &mut *guard else {
+            // The scope is dropped, we must complete this task early.
+            return std::task::Poll::Ready(());
+        };
 
-            let _guard = Box::pin(me.rt.lock.read()).as_mut().poll(cx);
+        // Wait for the composer to finish composing,
+        // so the values this task borrows don't change while it's polled.
+        let read = me
+            .read
+            .get_or_insert_with(|| Box::pin(me.lock.clone().read_owned()));
+        let std::task::Poll::Ready(_read_guard) = read.as_mut().poll(cx) else {
+            return std::task::Poll::Pending;
+        };
+        me.read = None;
 
-            task.as_mut().poll(cx)
-        } else {
-            // The scope is dropped, we must complete this task early.
-            std::task::Poll::Ready(())
-        }
+        task.as_mut().poll(cx)
     }
 }
 
-#[cfg(feature = "executor")]
-unsafe impl Send for TaskFuture {}
-
 #[cfg(feature = "executor")]
 #[cfg_attr(docsrs, doc(cfg(feature = "executor")))]
 /// Use a multi-threaded task that runs on a separate thread.
@@ -1019,7 +1735,8 @@ where
 
         runtime_cx.executor.spawn(Box::pin(TaskFuture {
             task: task_lock.clone(),
-            rt: Runtime::current(),
+            lock: cx.runtime().lock.clone(),
+            read: None,
         }));
 
         task_lock
@@ -1030,3 +1747,482 @@ where
         *task_lock.lock().unwrap() = None;
     });
 }
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{composer::Composer, prelude::*};
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    #[test]
+    fn it_recomposes_content_memoized_on_memoized_values() {
+        #[derive(Data)]
+        struct B<'a> {
+            memoized: Ref<'a, i32>,
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for B<'_> {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.me().values.borrow_mut().push(*cx.me().memoized);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let x = cx.me().x.get();
+                let value = use_memo(&cx, x, || x);
+
+                Memo::new(
+                    value,
+                    B {
+                        memoized: value,
+                        values: cx.me().values.clone(),
+                    },
+                )
+            }
+        }
+
+        let x = Rc::new(Cell::new(0));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        assert_eq!(*values.borrow(), [0]);
+
+        x.set(1);
+        composer.compose();
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 1]);
+    }
+
+    #[test]
+    fn it_returns_recomputed_memo_values_in_the_same_pass() {
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let x = cx.me().x.get();
+                let value = use_memo(&cx, x, || x * 10);
+                cx.me().values.borrow_mut().push(*value);
+            }
+        }
+
+        let x = Rc::new(Cell::new(0));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        x.set(1);
+        composer.compose();
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 10, 10]);
+    }
+
+    #[test]
+    fn it_updates_context_consumers() {
+        #[derive(Data)]
+        struct B {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let value = use_context::<i32>(&cx).unwrap();
+                cx.me().values.borrow_mut().push(*value);
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let x = cx.me().x.get();
+                use_memo_provider(&cx, x, || x);
+
+                B {
+                    values: cx.me().values.clone(),
+                }
+            }
+        }
+
+        let x = Rc::new(Cell::new(0));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [0]);
+
+        x.set(1);
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 1]);
+    }
+
+    #[test]
+    fn it_provides_contexts_with_defaults() {
+        /// Context values and error read by each composition.
+        type Values = Rc<RefCell<Vec<(Option<i32>, i32, String)>>>;
+
+        #[derive(Data)]
+        struct B {
+            values: Values,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let value = use_context_opt::<i32>(&cx).copied();
+                let or_value = *use_context_or(&cx, || -1);
+                let error = use_context::<u8>(&cx).unwrap_err().to_string();
+                cx.me().values.borrow_mut().push((value, or_value, error));
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<Option<i32>>>,
+            values: Values,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let content = B {
+                    values: cx.me().values.clone(),
+                };
+                match cx.me().x.get() {
+                    Some(x) => DynCompose::new(ContextProvider::new(x, content)),
+                    None => DynCompose::new(content),
+                }
+            }
+        }
+
+        let x = Rc::new(Cell::new(None));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        x.set(Some(1));
+        composer.compose();
+
+        let values = values.borrow();
+        assert_eq!(
+            values
+                .iter()
+                .map(|(value, or_value, _)| (*value, *or_value))
+                .collect::<Vec<_>>(),
+            [(None, -1), (Some(1), 1)]
+        );
+
+        // Errors include the composables being composed, ending with the composable that used the context.
+        for (_, _, error) in &*values {
+            assert!(error.starts_with("Context value not found for type: u8 (in `"));
+            assert!(error.ends_with(" > B`)"));
+        }
+    }
+
+    #[test]
+    fn it_shadows_and_removes_contexts() {
+        /// Name of each leaf and the context value it read.
+        type Values = Rc<RefCell<Vec<(&'static str, Option<i32>)>>>;
+
+        #[derive(Data)]
+        struct Leaf {
+            name: &'static str,
+            values: Values,
+        }
+
+        impl Compose for Leaf {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let value = use_context_opt::<i32>(&cx).copied();
+                cx.me().values.borrow_mut().push((cx.me().name, value));
+            }
+        }
+
+        #[derive(Data)]
+        struct Shadow {
+            values: Values,
+        }
+
+        impl Compose for Shadow {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_provider(&cx, || 2);
+
+                Leaf {
+                    name: "shadowed",
+                    values: cx.me().values.clone(),
+                }
+            }
+        }
+
+        #[derive(Data)]
+        struct Remove {
+            values: Values,
+        }
+
+        impl Compose for Remove {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                without_context::<i32>(&cx);
+
+                Leaf {
+                    name: "removed",
+                    values: cx.me().values.clone(),
+                }
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            values: Values,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_provider(&cx, || 1);
+
+                let values = &cx.me().values;
+                (
+                    Shadow {
+                        values: values.clone(),
+                    },
+                    Remove {
+                        values: values.clone(),
+                    },
+                    Leaf {
+                        name: "provided",
+                        values: values.clone(),
+                    },
+                )
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+        composer.compose();
+
+        assert_eq!(
+            *values.borrow(),
+            [
+                ("shadowed", Some(2)),
+                ("removed", None),
+                ("provided", Some(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn it_dispatches_actions() {
+        #[derive(Data)]
+        struct A {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let (count, dispatcher) =
+                    use_reducer(&cx, || 0, |count, delta: i32| *count += delta);
+                cx.me().values.borrow_mut().push(*count);
+
+                if *count == 0 {
+                    dispatcher.dispatch(2);
+                    dispatcher.dispatch(3);
+                }
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [0]);
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 5]);
+    }
+
+    #[test]
+    fn it_keeps_mut_handles_valid_across_compositions() {
+        #[derive(Data)]
+        struct A {
+            dispatcher: Rc<RefCell<Option<Dispatcher<i32>>>>,
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                // The dispatcher keeps a pointer to this state from the first composition.
+                let (state, dispatcher) = use_reducer(&cx, || 0, |state, n| *state += n);
+                *cx.me().dispatcher.borrow_mut() = Some(dispatcher);
+
+                let x = use_mut(&cx, || 0);
+                Mut::with(x, |x| *x += 1);
+
+                cx.me().values.borrow_mut().push(*state);
+            }
+        }
+
+        let dispatcher = Rc::new(RefCell::new(None));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            dispatcher: dispatcher.clone(),
+            values: values.clone(),
+        });
+
+        composer.compose();
+        for n in 1..=3 {
+            dispatcher.borrow().as_ref().unwrap().dispatch(n);
+            composer.compose();
+        }
+
+        assert_eq!(*values.borrow(), [0, 1, 3, 6]);
+    }
+
+    #[test]
+    fn it_maps_refs_across_compositions() {
+        #[derive(Data)]
+        struct B<'a> {
+            name: Map<'a, String>,
+            names: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Compose for B<'_> {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.me().names.borrow_mut().push((*cx.me().name).clone());
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            x: Rc<Cell<i32>>,
+            names: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                let x = cx.me().x.get();
+                let value = use_memo(&cx, x, || (x, x.to_string()));
+
+                B {
+                    name: Ref::map(value, |(_, name)| name),
+                    names: cx.me().names.clone(),
+                }
+            }
+        }
+
+        let x = Rc::new(Cell::new(0));
+        let names = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            x: x.clone(),
+            names: names.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        x.set(1);
+        composer.compose();
+
+        // The mapped reference points to the recomputed value.
+        assert_eq!(*names.borrow(), ["0", "0", "1"]);
+    }
+
+    #[test]
+    fn it_composes_mapped_content() {
+        #[derive(Data)]
+        struct C {
+            count: Rc<Cell<i32>>,
+        }
+
+        impl Compose for C {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.me().count.set(cx.me().count.get() + 1);
+            }
+        }
+
+        #[derive(Data)]
+        struct B {
+            content: Option<C>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                Ref::map(cx.me(), |me| &me.content)
+            }
+        }
+
+        #[derive(Data)]
+        struct A {
+            is_shown: Rc<Cell<bool>>,
+            count: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                cx.set_changed();
+
+                B {
+                    content: cx.me().is_shown.get().then(|| C {
+                        count: cx.me().count.clone(),
+                    }),
+                }
+            }
+        }
+
+        let is_shown = Rc::new(Cell::new(true));
+        let count = Rc::new(Cell::new(0));
+        let mut composer = Composer::new(A {
+            is_shown: is_shown.clone(),
+            count: count.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(count.get(), 1);
+
+        // The mapped content is dereferenced again after it changes from `Some` to `None`.
+        is_shown.set(false);
+        composer.compose();
+        is_shown.set(true);
+        composer.compose();
+        assert_eq!(count.get(), 2);
+    }
+}
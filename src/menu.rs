@@ -0,0 +1,409 @@
+use crate::{event_loop::use_window, prelude::*};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    mem,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(all(feature = "tray", any(target_os = "windows", target_os = "macos")))]
+pub(crate) use self::native::{build as build_native, NativeMenu};
+
+type ActivateFn<'a> = Rc<dyn Fn() + 'a>;
+
+thread_local! {
+    /// Activation functions of each registered menu, in the order of their items.
+    static MENU_FNS: RefCell<HashMap<u64, Vec<ActivateFn<'static>>>> = RefCell::default();
+}
+
+/// Allocate a new unique owner ID for a registered menu.
+pub(crate) fn next_owner() -> u64 {
+    static NEXT_OWNER: AtomicU64 = AtomicU64::new(0);
+
+    NEXT_OWNER.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Register the activation functions of `menu` for `owner`, replacing any previous menu.
+///
+/// # Safety
+/// The caller must ensure [`unregister`] is called for `owner` before `menu` is dropped.
+pub(crate) unsafe fn register(owner: u64, menu: &Menu) {
+    let mut fns = Vec::new();
+    menu.visit_items(&mut |item| {
+        let f: ActivateFn = item.on_activate.clone();
+        let f: ActivateFn<'static> = unsafe { mem::transmute(f) };
+        fns.push(f);
+    });
+
+    MENU_FNS.with(|menu_fns| menu_fns.borrow_mut().insert(owner, fns));
+}
+
+/// Remove the activation functions of `owner`.
+pub(crate) fn unregister(owner: u64) {
+    MENU_FNS.with(|menu_fns| menu_fns.borrow_mut().remove(&owner));
+}
+
+/// Activate the item at `index` of the menu registered for `owner`.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn activate(owner: u64, index: usize) {
+    let f = MENU_FNS.with(|menu_fns| {
+        menu_fns
+            .borrow()
+            .get(&owner)
+            .and_then(|fns| fns.get(index).cloned())
+    });
+
+    if let Some(f) = f {
+        f()
+    }
+}
+
+/// Show `menu` as a context menu at the cursor position of `window`.
+///
+/// # Safety
+/// The caller must ensure [`unregister`] is called for `owner` before the data borrowed by `menu` is dropped.
+#[cfg(feature = "ui")]
+pub(crate) unsafe fn show_context_menu(
+    owner: u64,
+    window: &crate::event_loop::WindowHandle,
+    menu: Menu,
+) {
+    unsafe { register(owner, &menu) };
+
+    if let Some(window) = window.raw_window() {
+        native::show_context_menu(owner, &window, &menu);
+    }
+}
+
+/// Menu item.
+pub struct MenuItem<'a> {
+    label: String,
+    is_enabled: bool,
+    on_activate: ActivateFn<'a>,
+}
+
+impl<'a> MenuItem<'a> {
+    /// Create a new menu item from its label and a function to call when it's activated.
+    pub fn new(label: impl Into<String>, on_activate: impl Fn() + 'a) -> Self {
+        Self {
+            label: label.into(),
+            is_enabled: true,
+            on_activate: Rc::new(on_activate),
+        }
+    }
+
+    /// Set whether this item can be activated (default: `true`).
+    pub fn with_enabled(mut self, is_enabled: bool) -> Self {
+        self.is_enabled = is_enabled;
+        self
+    }
+}
+
+enum MenuEntry<'a> {
+    Item(MenuItem<'a>),
+    Submenu { label: String, menu: Menu<'a> },
+    Separator,
+}
+
+/// Shape of a menu entry, used to check if a native menu needs to be rebuilt.
+#[derive(Clone, PartialEq)]
+pub(crate) enum MenuShape {
+    Item {
+        label: String,
+        is_enabled: bool,
+    },
+    Submenu {
+        label: String,
+        entries: Vec<MenuShape>,
+    },
+    Separator,
+}
+
+/// Declarative native menu.
+///
+/// Menus can be shown as the menu bar of a window with [`MenuBar`],
+/// or as a context menu with [`View::on_context_menu`](crate::ui::view::View::on_context_menu).
+#[derive(Default)]
+pub struct Menu<'a> {
+    entries: Vec<MenuEntry<'a>>,
+}
+
+impl<'a> Menu<'a> {
+    /// Create a new empty menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item to this menu.
+    pub fn with_item(mut self, item: MenuItem<'a>) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    /// Add a submenu to this menu.
+    pub fn with_submenu(mut self, label: impl Into<String>, menu: Menu<'a>) -> Self {
+        self.entries.push(MenuEntry::Submenu {
+            label: label.into(),
+            menu,
+        });
+        self
+    }
+
+    /// Add a separator to this menu.
+    pub fn with_separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Visit each item of this menu and its submenus, in order.
+    fn visit_items(&self, f: &mut impl FnMut(&MenuItem)) {
+        for entry in &self.entries {
+            match entry {
+                MenuEntry::Item(item) => f(item),
+                MenuEntry::Submenu { menu, .. } => menu.visit_items(f),
+                MenuEntry::Separator => {}
+            }
+        }
+    }
+
+    pub(crate) fn shape(&self) -> Vec<MenuShape> {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                MenuEntry::Item(item) => MenuShape::Item {
+                    label: item.label.clone(),
+                    is_enabled: item.is_enabled,
+                },
+                MenuEntry::Submenu { label, menu } => MenuShape::Submenu {
+                    label: label.clone(),
+                    entries: menu.shape(),
+                },
+                MenuEntry::Separator => MenuShape::Separator,
+            })
+            .collect()
+    }
+}
+
+unsafe impl Data for Menu<'_> {}
+
+/// Menu bar composable.
+///
+/// This sets the native menu bar of the [`Window`](crate::event_loop::Window) containing it,
+/// calling the function of an item when it's activated.
+/// The menu bar is removed when this composable is dropped.
+///
+/// Menu bars are currently supported on Windows and macOS.
+/// On macOS, the menu bar is shared by the whole application.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct MenuBar<'a> {
+    menu: Menu<'a>,
+}
+
+impl<'a> MenuBar<'a> {
+    /// Create a new menu bar from its menu.
+    pub fn new(menu: Menu<'a>) -> Self {
+        Self { menu }
+    }
+}
+
+impl Compose for MenuBar<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let window = use_window(&cx);
+        let owner = *use_ref(&cx, next_owner);
+        let native_menu = use_ref(&cx, || {
+            RefCell::new(None::<(Vec<MenuShape>, native::NativeMenu)>)
+        });
+
+        // Safety: The menu is unregistered on drop.
+        unsafe { register(owner, &cx.me().menu) };
+
+        let shape = cx.me().menu.shape();
+        let is_changed = native_menu
+            .borrow()
+            .as_ref()
+            .is_none_or(|(last, _)| *last != shape);
+
+        if is_changed {
+            if let Some(raw_window) = window.raw_window() {
+                let last = native_menu.borrow_mut().take();
+                if let Some((_, last)) = last {
+                    native::remove_menu_bar(&raw_window, &last);
+                }
+
+                let menu = native::set_menu_bar(owner, &raw_window, &cx.me().menu);
+                *native_menu.borrow_mut() = Some((shape, menu));
+            }
+        }
+
+        use_drop(&cx, move || {
+            unregister(owner);
+
+            if let (Some(raw_window), Some((_, menu))) =
+                (window.raw_window(), native_menu.borrow_mut().take())
+            {
+                native::remove_menu_bar(&raw_window, &menu);
+            }
+        });
+    }
+}
+
+/// Native menus, built with `muda`.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod native {
+    use super::{activate, Menu, MenuEntry};
+    use crate::composer::Runtime;
+    #[cfg(feature = "ui")]
+    use muda::ContextMenu;
+    use muda::{IsMenuItem, MenuEvent, MenuId, PredefinedMenuItem, Submenu};
+    use std::sync::Once;
+    use winit::{
+        raw_window_handle::{HasWindowHandle, RawWindowHandle},
+        window::Window as RawWindow,
+    };
+
+    pub(crate) type NativeMenu = muda::Menu;
+
+    fn menu_id(owner: u64, index: usize) -> MenuId {
+        MenuId::new(format!("actuate:{owner}:{index}"))
+    }
+
+    fn parse_menu_id(id: &MenuId) -> Option<(u64, usize)> {
+        let (owner, index) = id.as_ref().strip_prefix("actuate:")?.split_once(':')?;
+        Some((owner.parse().ok()?, index.parse().ok()?))
+    }
+
+    /// Route menu events to the current runtime, activating their items with the next update.
+    fn init() {
+        static INIT: Once = Once::new();
+
+        INIT.call_once(|| {
+            let sender = Runtime::current().update_sender();
+            MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+                if let Some((owner, index)) = parse_menu_id(event.id()) {
+                    sender.send(move || activate(owner, index));
+                }
+            }));
+        });
+    }
+
+    fn append_entries(
+        owner: u64,
+        menu: &Menu,
+        index: &mut usize,
+        append: &mut dyn FnMut(&dyn IsMenuItem),
+    ) {
+        for entry in &menu.entries {
+            match entry {
+                MenuEntry::Item(item) => {
+                    append(&muda::MenuItem::with_id(
+                        menu_id(owner, *index),
+                        &item.label,
+                        item.is_enabled,
+                        None,
+                    ));
+                    *index += 1;
+                }
+                MenuEntry::Submenu { label, menu } => {
+                    let submenu = Submenu::new(label, true);
+                    append_entries(owner, menu, index, &mut |item| {
+                        submenu.append(item).unwrap()
+                    });
+                    append(&submenu);
+                }
+                MenuEntry::Separator => append(&PredefinedMenuItem::separator()),
+            }
+        }
+    }
+
+    /// Build a native menu for `menu`, registered for `owner`.
+    pub(crate) fn build(owner: u64, menu: &Menu) -> NativeMenu {
+        init();
+
+        let native_menu = NativeMenu::new();
+        append_entries(owner, menu, &mut 0, &mut |item| {
+            native_menu.append(item).unwrap()
+        });
+        native_menu
+    }
+
+    #[cfg_attr(not(any(target_os = "windows", feature = "ui")), allow(dead_code))]
+    fn raw_handle(window: &RawWindow) -> Option<RawWindowHandle> {
+        window.window_handle().ok().map(|handle| handle.as_raw())
+    }
+
+    pub(super) fn set_menu_bar(owner: u64, window: &RawWindow, menu: &Menu) -> NativeMenu {
+        let native_menu = build(owner, menu);
+
+        #[cfg(target_os = "windows")]
+        if let Some(RawWindowHandle::Win32(handle)) = raw_handle(window) {
+            // Safety: `hwnd` is valid while `window` exists.
+            let _ = unsafe { native_menu.init_for_hwnd(handle.hwnd.get()) };
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let _ = window;
+            native_menu.init_for_nsapp();
+        }
+
+        native_menu
+    }
+
+    pub(super) fn remove_menu_bar(window: &RawWindow, native_menu: &NativeMenu) {
+        #[cfg(target_os = "windows")]
+        if let Some(RawWindowHandle::Win32(handle)) = raw_handle(window) {
+            // Safety: `hwnd` is valid while `window` exists.
+            let _ = unsafe { native_menu.remove_for_hwnd(handle.hwnd.get()) };
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let _ = window;
+            native_menu.remove_for_nsapp();
+        }
+    }
+
+    #[cfg(feature = "ui")]
+    pub(super) fn show_context_menu(owner: u64, window: &RawWindow, menu: &Menu) {
+        let native_menu = build(owner, menu);
+
+        match raw_handle(window) {
+            #[cfg(target_os = "windows")]
+            // Safety: `hwnd` is valid while `window` exists.
+            Some(RawWindowHandle::Win32(handle)) => unsafe {
+                native_menu.show_context_menu_for_hwnd(handle.hwnd.get(), None)
+            },
+            #[cfg(target_os = "macos")]
+            // Safety: `ns_view` is valid while `window` exists.
+            Some(RawWindowHandle::AppKit(handle)) => unsafe {
+                native_menu.show_context_menu_for_nsview(handle.ns_view.as_ptr(), None)
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Native menus are not supported on this platform.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod native {
+    use super::Menu;
+    use winit::window::Window as RawWindow;
+
+    pub(super) struct NativeMenu;
+
+    pub(super) fn set_menu_bar(owner: u64, window: &RawWindow, menu: &Menu) -> NativeMenu {
+        let _ = (owner, window, menu);
+        NativeMenu
+    }
+
+    pub(super) fn remove_menu_bar(window: &RawWindow, native_menu: &NativeMenu) {
+        let _ = (window, native_menu);
+    }
+
+    #[cfg(feature = "ui")]
+    pub(super) fn show_context_menu(owner: u64, window: &RawWindow, menu: &Menu) {
+        let _ = (owner, window, menu);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{
+    composer::{self, Runtime, UpdateSender},
+    sync::{self, Mutex, MutexGuard},
+    use_ref, Memoize, ScopeState,
+};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct SignalState<T> {
+    value: Mutex<T>,
+    /// Number of updates to this signal (kept pointer-sized for targets without 64-bit atomics).
+    generation: AtomicUsize,
+    /// ID of the owning scope.
+    scope: u64,
+    sender: UpdateSender,
+}
+
+/// Handle to a value of type `T` that can be updated from anywhere.
+///
+/// Unlike [`Mut`](crate::Mut), this handle is `'static` and can be cloned into event callbacks and tasks.
+/// If `T` is `Send`, this handle can also be sent to other threads.
+///
+/// This can be created with [`use_signal`].
+pub struct Signal<T> {
+    state: Arc<SignalState<T>>,
+}
+
+impl<T> Signal<T> {
+    /// Get a clone of the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.lock().clone()
+    }
+
+    /// Lock the current value for reading.
+    ///
+    /// Updates to this signal are only applied on the composer's thread, at the start of
+    /// [`Composer::compose`](crate::composer::Composer::compose), so the value won't change while
+    /// this guard is held between compositions (or inside one).
+    ///
+    /// This guard must not be held across a call to `Composer::compose` on the same thread,
+    /// as applying an update to this signal would then wait for the guard forever.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        sync::lock(&self.state.value)
+    }
+
+    /// Get the current generation of this signal, incremented on each update.
+    pub fn generation(&self) -> u64 {
+        self.state.generation.load(Ordering::SeqCst) as u64
+    }
+}
+
+impl<T: Send + 'static> Signal<T> {
+    /// Queue an update to this value, triggering an update to the component owning this value.
+    ///
+    /// The update is applied on the composer's thread before its next composition,
+    /// so this can be called while holding a guard from [`Signal::lock`].
+    /// Updates queued after the owning component is dropped are ignored.
+    pub fn update(&self, f: impl FnOnce(&mut T) + Send + 'static) {
+        let state = self.state.clone();
+
+        self.state
+            .sender
+            .send_scope(self.state.scope, true, move || {
+                f(&mut sync::lock(&state.value));
+                state.generation.fetch_add(1, Ordering::SeqCst);
+            });
+    }
+
+    /// Queue an update to set this value, triggering an update to the component owning this value.
+    pub fn set(&self, value: T) {
+        self.update(move |dst| *dst = value)
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Signal").field(&*self.lock()).finish()
+    }
+}
+
+impl<T> Memoize for Signal<T> {
+    type Value = u64;
+
+    fn memoized(self) -> Self::Value {
+        self.generation()
+    }
+}
+
+/// Use a signal with an initial value.
+///
+/// The returned [`Signal`] can be cloned into callbacks and tasks to update this value,
+/// triggering an update to this component.
+pub fn use_signal<T: 'static>(cx: ScopeState<'_>, make_value: impl FnOnce() -> T) -> Signal<T> {
+    let signal = use_ref(cx, || Signal {
+        state: Arc::new(SignalState {
+            value: Mutex::new(make_value()),
+            generation: AtomicUsize::new(0),
+            scope: composer::scope_id(cx),
+            sender: Runtime::current().update_sender(),
+        }),
+    });
+
+    signal.clone()
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc, sync::mpsc, thread};
+
+    #[test]
+    fn it_updates_signals_while_locked() {
+        #[derive(Data)]
+        struct A {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, || 0);
+
+                let value = signal.lock();
+                cx.me().values.borrow_mut().push(*value);
+
+                // Setting the signal only queues an update, so this doesn't wait for `value`.
+                if *value == 0 {
+                    signal.set(1);
+                }
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 1]);
+    }
+
+    #[test]
+    fn it_keeps_values_stable_until_composed() {
+        #[derive(Data)]
+        struct A {
+            tx: mpsc::Sender<Signal<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, || 0);
+                use_ref(&cx, || cx.me().tx.send(signal.clone()).unwrap());
+            }
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut composer = Composer::new(A { tx });
+        composer.compose();
+
+        let signal = rx.recv().unwrap();
+        let thread_signal = signal.clone();
+        thread::spawn(move || thread_signal.set(1)).join().unwrap();
+
+        // Updates from other threads are applied by the composer.
+        assert_eq!(signal.get(), 0);
+
+        composer.compose();
+        assert_eq!(signal.get(), 1);
+    }
+
+    #[test]
+    fn it_updates_signals() {
+        #[derive(Data)]
+        struct A {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let signal = use_signal(&cx, || 0);
+                cx.me().values.borrow_mut().push(signal.get());
+
+                if signal.get() == 0 {
+                    signal.set(1);
+                }
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [0]);
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 1]);
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 1]);
+    }
+}
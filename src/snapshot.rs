@@ -0,0 +1,184 @@
+use crate::{use_drop, use_mut, use_ref, Memoize, Mut, ScopeState};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+/// Snapshot of restorable state, keyed by the key of each [`use_restorable`] hook.
+///
+/// This can be created with [`Composer::snapshot`](crate::composer::Composer::snapshot),
+/// serialized to save state across restarts, and restored with [`Composer::restore`](crate::composer::Composer::restore).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    values: HashMap<String, Value>,
+}
+
+impl Snapshot {
+    /// Get the serialized value stored with `key`.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    /// Get the number of values in this snapshot.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if this snapshot contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl Serialize for Snapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.values.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Snapshot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::deserialize(deserializer).map(|values| Self { values })
+    }
+}
+
+/// Restorable state of a composer.
+#[derive(Default)]
+pub(crate) struct SnapshotState {
+    /// Serialized value of each composed [`use_restorable`] hook.
+    values: HashMap<String, Value>,
+
+    /// Values to restore into [`use_restorable`] hooks the next time they're composed.
+    restored: HashMap<String, Value>,
+}
+
+impl SnapshotState {
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            values: self.values.clone(),
+        }
+    }
+
+    pub(crate) fn restore(&mut self, snapshot: Snapshot) {
+        self.restored.extend(snapshot.values);
+    }
+
+    /// Take the value to restore with `key`, if it can be deserialized as `T`.
+    fn take_restored<T: DeserializeOwned>(&mut self, key: &str) -> Option<T> {
+        let value = self.restored.remove(key)?;
+
+        serde_json::from_value(value)
+            .inspect_err(|_error| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Failed to restore {}: {}", key, _error);
+            })
+            .ok()
+    }
+}
+
+/// Use a value of type `T` that can be saved in a [`Snapshot`] with `key`.
+///
+/// On the first composition, the value is restored from the composer's last restored snapshot,
+/// or created with `make_value` if it contains no value for `key`.
+/// A value restored after the first composition is set as a state update the next time this composable is composed.
+///
+/// Each change to the returned value is serialized for the next snapshot.
+/// `key` should be unique within a composer, and is only read on the first composition.
+///
+/// This is [`use_mut`] with a serializable value.
+/// Values are opted into snapshots with this hook instead of with every `use_mut`,
+/// since `use_mut` accepts values of any type and only some of them implement [`Serialize`].
+/// The `key` keeps a value restorable when the composables around it change, such as after hot reloading.
+pub fn use_restorable<'a, T>(
+    cx: ScopeState<'a>,
+    key: &str,
+    make_value: impl FnOnce() -> T,
+) -> Mut<'a, T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let state: &Rc<RefCell<SnapshotState>> = use_ref(cx, || cx.runtime().snapshot.clone());
+    let key = use_ref(cx, || key.to_owned());
+
+    let value = use_mut(cx, || {
+        let restored = state.borrow_mut().take_restored(key);
+        restored.unwrap_or_else(make_value)
+    });
+
+    let restored = state.borrow_mut().take_restored(key);
+    if let Some(restored) = restored {
+        Mut::set(value, restored);
+    }
+
+    let generation = value.memoized();
+    let last_generation = use_ref(cx, || Cell::new(None));
+    if last_generation.replace(Some(generation)) != Some(generation) {
+        match serde_json::to_value(&*value) {
+            Ok(serialized) => {
+                state.borrow_mut().values.insert(key.clone(), serialized);
+            }
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Failed to serialize {}: {}", key, _error);
+            }
+        }
+    }
+
+    use_drop(cx, move || {
+        state.borrow_mut().values.remove(key);
+    });
+
+    value
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use super::{use_restorable, Snapshot};
+    use crate::{composer::Composer, prelude::*};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn it_restores_snapshots() {
+        #[derive(Data)]
+        struct A {
+            values: Rc<RefCell<Vec<i32>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let count = use_restorable(&cx, "count", || 0);
+                cx.me().values.borrow_mut().push(*count);
+
+                if *count < 2 {
+                    Mut::update(count, |count| *count += 1);
+                }
+            }
+        }
+
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+
+        composer.compose();
+        composer.compose();
+        composer.compose();
+        assert_eq!(*values.borrow(), [0, 1, 2]);
+
+        let json = serde_json::to_string(&composer.snapshot()).unwrap();
+        let snapshot: Snapshot = serde_json::from_str(&json).unwrap();
+        drop(composer);
+
+        values.borrow_mut().clear();
+        let mut composer = Composer::new(A {
+            values: values.clone(),
+        });
+        composer.restore(snapshot);
+
+        composer.compose();
+        assert_eq!(*values.borrow(), [2]);
+    }
+}
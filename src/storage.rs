@@ -0,0 +1,342 @@
+use crate::{use_context, use_mut, use_ref, Memoize, Mut, ScopeState};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cell::Cell,
+    env,
+    fmt::Write,
+    io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    collections::HashMap,
+    fs,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Time to wait after the last change to a persistent value before saving it.
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+/// Error from a [`Storage`].
+#[derive(Debug, Error)]
+pub enum StorageError {
+    /// Error reading or writing a stored value.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Error serializing or deserializing a stored value.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Storage for persistent values, as JSON files in a directory.
+///
+/// By default, values are stored in a directory named after the current executable,
+/// in the platform's config directory (such as `~/.config` on Linux).
+/// On the web, values are stored in the browser's `localStorage` instead, keyed by their path.
+///
+/// This can be provided to [`use_persistent`] with [`use_provider`](crate::use_provider).
+#[derive(Clone, Debug)]
+pub struct Storage {
+    dir: PathBuf,
+}
+
+impl Storage {
+    /// Create a new storage in the directory `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Get the directory of this storage.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the path of the value stored with `key`.
+    ///
+    /// Characters other than ASCII letters, digits, `-`, `_`, and `.` are percent-encoded,
+    /// so a key can't refer to a path outside of this storage's directory.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let mut name = String::with_capacity(key.len() + 5);
+        for byte in key.bytes() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+                name.push(byte as char);
+            } else {
+                write!(name, "%{byte:02X}").unwrap();
+            }
+        }
+        name.push_str(".json");
+
+        self.dir.join(name)
+    }
+
+    /// Load the value stored with `key`, or `None` if no value is stored.
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, StorageError> {
+        match read(&self.path(key))? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Save `value` with `key`, replacing any stored value.
+    pub fn save<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<(), StorageError> {
+        let bytes = serde_json::to_vec_pretty(value)?;
+        write(&self.path(key), &bytes)?;
+        Ok(())
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        let name = env::current_exe()
+            .ok()
+            .and_then(|path| path.file_stem().map(|name| name.to_owned()))
+            .unwrap_or_else(|| "actuate".into());
+
+        Self::new(config_dir().unwrap_or_default().join(name))
+    }
+}
+
+/// Get the platform's config directory.
+fn config_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| Path::new(&home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+    }
+}
+
+/// Read the bytes at `path`, or `None` if the file doesn't exist.
+#[cfg(not(target_arch = "wasm32"))]
+fn read(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Write `bytes` to `path`, replacing its content at once so an interrupted write can't corrupt it.
+#[cfg(not(target_arch = "wasm32"))]
+fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(tmp, path)
+}
+
+/// Read the item stored with the key `path` in `localStorage`, or `None` if no item is stored.
+#[cfg(target_arch = "wasm32")]
+fn read(path: &Path) -> io::Result<Option<Vec<u8>>> {
+    local_storage()?
+        .get_item(&path.to_string_lossy())
+        .map(|item| item.map(String::into_bytes))
+        .map_err(|_| io::Error::other("failed to read from localStorage"))
+}
+
+/// Store `bytes` as the item with the key `path` in `localStorage`.
+#[cfg(target_arch = "wasm32")]
+fn write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let item = std::str::from_utf8(bytes).map_err(io::Error::other)?;
+    local_storage()?
+        .set_item(&path.to_string_lossy(), item)
+        .map_err(|_| io::Error::other("failed to write to localStorage"))
+}
+
+/// Get the `localStorage` of the current window.
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> io::Result<web_sys::Storage> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "localStorage is unavailable"))
+}
+
+/// Write `bytes` to `path`, logging any error.
+fn write_or_warn(path: &Path, bytes: &[u8]) {
+    if let Err(_error) = write(path, bytes) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Failed to save {}: {}", path.display(), _error);
+    }
+}
+
+/// Message to the background thread saving persistent values.
+#[cfg(not(target_arch = "wasm32"))]
+enum Message {
+    /// Save a new value to a path, replacing any pending value.
+    Save(PathBuf, Vec<u8>),
+    /// Save the pending value of a path immediately, and notify the sender once it's saved.
+    Flush(PathBuf, mpsc::Sender<()>),
+}
+
+/// Send `message` to the background thread saving persistent values, spawning it on first use.
+///
+/// A single thread is shared by every persistent value.
+#[cfg(not(target_arch = "wasm32"))]
+fn send(message: Message) {
+    static TX: OnceLock<mpsc::Sender<Message>> = OnceLock::new();
+
+    TX.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || run_writer(rx));
+        tx
+    })
+    .send(message)
+    .ok();
+}
+
+/// Save the latest value sent for each path, once no other value is sent for it for [`SAVE_DELAY`].
+#[cfg(not(target_arch = "wasm32"))]
+fn run_writer(rx: mpsc::Receiver<Message>) {
+    let mut pending: HashMap<PathBuf, (Vec<u8>, Instant)> = HashMap::new();
+
+    loop {
+        let message = match pending.values().map(|(_, deadline)| *deadline).min() {
+            Some(deadline) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(message) => Some(message),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            None => match rx.recv() {
+                Ok(message) => Some(message),
+                Err(_) => break,
+            },
+        };
+
+        match message {
+            Some(Message::Save(path, bytes)) => {
+                pending.insert(path, (bytes, Instant::now() + SAVE_DELAY));
+            }
+            Some(Message::Flush(path, done)) => {
+                if let Some((bytes, _)) = pending.remove(&path) {
+                    write_or_warn(&path, &bytes);
+                }
+                done.send(()).ok();
+            }
+            None => {
+                let now = Instant::now();
+                pending.retain(|path, (bytes, deadline)| {
+                    if *deadline > now {
+                        return true;
+                    }
+                    write_or_warn(path, bytes);
+                    false
+                });
+            }
+        }
+    }
+
+    for (path, (bytes, _)) in pending {
+        write_or_warn(&path, &bytes);
+    }
+}
+
+/// Writer of a persistent value to its path.
+///
+/// Values are saved in the background by a shared thread, and the pending value is saved when this is dropped.
+/// On the web, values are saved immediately.
+struct Writer {
+    path: PathBuf,
+}
+
+impl Writer {
+    fn send(&self, bytes: Vec<u8>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        send(Message::Save(self.path.clone(), bytes));
+
+        #[cfg(target_arch = "wasm32")]
+        write_or_warn(&self.path, &bytes);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for Writer {
+    fn drop(&mut self) {
+        // Save a pending value immediately, and wait for it.
+        let (tx, rx) = mpsc::channel();
+        send(Message::Flush(self.path.clone(), tx));
+        rx.recv().ok();
+    }
+}
+
+/// Use a persistent value of type `T`, stored with `key`.
+///
+/// On the first composition, the value is loaded from the current [`Storage`],
+/// or from `make_value` if no value is stored (or the stored value can't be loaded).
+/// Each change to the returned value is saved in the background,
+/// once no other change is made for a short delay or this composable is dropped
+/// (on the web, each change is saved immediately).
+///
+/// Values are stored in the [`Storage`] provided to this composable, or in [`Storage::default`].
+pub fn use_persistent<'a, T>(
+    cx: ScopeState<'a>,
+    key: &str,
+    make_value: impl FnOnce() -> T,
+) -> Mut<'a, T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let storage = use_ref(cx, || {
+        use_context::<Storage>(cx).cloned().unwrap_or_default()
+    });
+
+    let value = use_mut(cx, || match storage.load(key) {
+        Ok(value) => value.unwrap_or_else(make_value),
+        Err(_error) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Failed to load {}: {}", storage.path(key).display(), _error);
+
+            make_value()
+        }
+    });
+
+    let writer = use_ref(cx, || Writer {
+        path: storage.path(key),
+    });
+
+    let generation = value.memoized();
+    let last_generation = use_ref(cx, || Cell::new(generation));
+    if last_generation.replace(generation) != generation {
+        match serde_json::to_vec_pretty(&*value) {
+            Ok(bytes) => writer.send(bytes),
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Failed to serialize {}: {}", key, _error);
+            }
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Storage;
+    use std::path::Path;
+
+    #[test]
+    fn it_escapes_keys() {
+        let storage = Storage::new("dir");
+
+        assert_eq!(storage.path("settings"), Path::new("dir/settings.json"));
+        assert_eq!(
+            storage.path("../a/b\\c"),
+            Path::new("dir/..%2Fa%2Fb%5Cc.json")
+        );
+    }
+}
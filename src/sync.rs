@@ -0,0 +1,109 @@
+//! Locks and thread-local storage for the composer.
+//!
+//! Without the `std` feature, locks spin instead of blocking,
+//! and thread-local statics are stored in plain statics shared by the whole program.
+
+#[cfg(feature = "std")]
+pub(crate) use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "std")]
+pub(crate) use tokio::sync::{RwLock, RwLockWriteGuard};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use spin::{Mutex, MutexGuard, RwLock, RwLockWriteGuard};
+
+/// Lock `mutex`, blocking until it's available.
+///
+/// # Panics
+/// Panics if `mutex` was poisoned by a panic on another thread.
+pub(crate) fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    #[cfg(feature = "std")]
+    return mutex.lock().unwrap();
+
+    #[cfg(not(feature = "std"))]
+    mutex.lock()
+}
+
+/// Lock `lock` for writing, blocking until it's available.
+pub(crate) fn write<T: ?Sized>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    #[cfg(feature = "std")]
+    return lock.blocking_write();
+
+    #[cfg(not(feature = "std"))]
+    lock.write()
+}
+
+/// Declare statics that are local to the current thread.
+///
+/// Without the `std` feature, these are stored in a [`Local`], shared by the whole program.
+macro_rules! local {
+    ($($(#[$attr:meta])* static $name:ident: $t:ty = const { $init:expr };)*) => {
+        #[cfg(feature = "std")]
+        std::thread_local! {
+            $(
+                $(#[$attr])*
+                static $name: $t = const { $init };
+            )*
+        }
+
+        $(
+            #[cfg(not(feature = "std"))]
+            $(#[$attr])*
+            static $name: $crate::sync::Local<$t> = $crate::sync::Local::new($init);
+        )*
+    };
+}
+
+pub(crate) use local;
+
+/// Set once the program promises to only use composers from a single thread.
+#[cfg(not(feature = "std"))]
+static IS_SINGLE_THREADED: core::sync::atomic::AtomicBool =
+    core::sync::atomic::AtomicBool::new(false);
+
+/// Assume this program only uses composers from a single thread.
+///
+/// Without the `std` feature, the current runtime and scope are stored in plain statics,
+/// so this must be called before the first composition.
+/// Composing before calling this panics.
+///
+/// # Safety
+/// Composers, and the values they own, must only be used from a single thread
+/// for the rest of the program, and never from an interrupt handler.
+#[cfg(not(feature = "std"))]
+pub unsafe fn assume_single_threaded() {
+    IS_SINGLE_THREADED.store(true, core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Static storage for a value that's local to the only thread of a `no_std` program.
+///
+/// The value is only reachable once [`assume_single_threaded`] is called.
+#[cfg(not(feature = "std"))]
+pub(crate) struct Local<T> {
+    value: T,
+}
+
+// Safety: The value is only accessed after `assume_single_threaded`,
+// whose caller guarantees that it's only accessed from a single thread.
+#[cfg(not(feature = "std"))]
+unsafe impl<T> Sync for Local<T> {}
+
+#[cfg(not(feature = "std"))]
+impl<T> Local<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Run `f` with a reference to this value, like `LocalKey::with` in `std`.
+    ///
+    /// # Panics
+    /// Panics if [`assume_single_threaded`] wasn't called.
+    pub(crate) fn with<R>(&'static self, f: impl FnOnce(&T) -> R) -> R {
+        assert!(
+            IS_SINGLE_THREADED.load(core::sync::atomic::Ordering::Relaxed),
+            "`actuate::composer::assume_single_threaded` must be called before composing without `std`"
+        );
+
+        f(&self.value)
+    }
+}
@@ -0,0 +1,361 @@
+use crate::{
+    composer::Runtime, executor::ExecutorContext, use_callback, use_context, use_drop, use_mut,
+    use_ref, Mut, Ref, ScopeState,
+};
+use slotmap::DefaultKey;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+use tokio::sync::mpsc;
+
+/// Use an interval that calls `f` every `duration`, starting `duration` from now.
+///
+/// `f` is called on the composer's thread, and will be updated whenever this component is re-composed.
+/// The interval is restarted when `duration` changes, and cancelled when this composable is dropped.
+///
+/// The interval is timed on the current [`ExecutorContext`], which must run on the Tokio runtime,
+/// or on a shared Tokio runtime if no context is provided.
+pub fn use_interval<'a>(cx: ScopeState<'a>, duration: Duration, f: impl FnMut() + 'a) {
+    use_timer(cx, Some(duration), 0, false, f)
+}
+
+/// Use a timeout that calls `f` once after `duration`.
+///
+/// `f` is called on the composer's thread, and will be updated whenever this component is re-composed.
+/// The timeout is restarted when `duration` changes, and cancelled when this composable is dropped.
+///
+/// The timeout is timed on the current [`ExecutorContext`], which must run on the Tokio runtime,
+/// or on a shared Tokio runtime if no context is provided.
+pub fn use_timeout<'a>(cx: ScopeState<'a>, duration: Duration, f: impl FnMut() + 'a) {
+    use_timer(cx, Some(duration), 0, true, f)
+}
+
+/// State of a timer used with [`use_timer`].
+#[derive(Default)]
+struct TimerState {
+    /// Duration and generation the timer was last started with.
+    started: Cell<Option<(Option<Duration>, u64)>>,
+
+    /// Local task calling the timer's function.
+    task: Cell<Option<DefaultKey>>,
+}
+
+impl TimerState {
+    fn stop(&self, rt: &Runtime) {
+        if let Some(key) = self.task.take() {
+            rt.tasks.borrow_mut().remove(key);
+        }
+    }
+}
+
+/// Use a timer that calls `f` after `duration`, and then every `duration` unless `is_once` is `true`.
+///
+/// The timer is restarted when `duration` or `generation` changes, and stopped while `duration` is `None`.
+fn use_timer<'a>(
+    cx: ScopeState<'a>,
+    duration: Option<Duration>,
+    generation: u64,
+    is_once: bool,
+    mut f: impl FnMut() + 'a,
+) {
+    let callback = use_callback(cx, move |()| f());
+    let timer = use_ref(cx, TimerState::default);
+    // The scope is already being dropped when the timer is stopped, so only the runtime is borrowed there.
+    let rt = use_ref(cx, || cx.runtime().clone());
+
+    if timer.started.replace(Some((duration, generation))) != Some((duration, generation)) {
+        timer.stop(rt);
+
+        if let Some(duration) = duration {
+            // Time each tick on the executor, and call `f` from a local task on the composer's thread.
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            spawn(cx, async move {
+                loop {
+                    tokio::time::sleep(duration).await;
+
+                    // Stop once the local task is dropped.
+                    if tx.send(()).is_err() || is_once {
+                        break;
+                    }
+                }
+            });
+
+            let callback = callback.clone();
+            let task: Pin<Box<dyn Future<Output = ()> + 'a>> = Box::pin(async move {
+                while rx.recv().await.is_some() {
+                    callback(());
+                }
+            });
+
+            // Safety: The task is removed before this scope is dropped.
+            let task: Pin<Box<dyn Future<Output = ()>>> = unsafe { mem::transmute(task) };
+
+            let key = rt.tasks.borrow_mut().insert(task);
+            rt.wake_task(key);
+            timer.task.set(Some(key));
+        }
+    }
+
+    use_drop(cx, move || timer.stop(rt));
+}
+
+/// Spawn `future` on the [`ExecutorContext`] provided to `cx`,
+/// or on a shared Tokio runtime if no context is provided.
+fn spawn(cx: ScopeState, future: impl Future<Output = ()> + Send + 'static) {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+    match use_context::<ExecutorContext>(cx) {
+        Ok(executor_cx) => executor_cx.spawn(future),
+        Err(_) => {
+            let rt = RUNTIME.get_or_init(|| {
+                tokio::runtime::Runtime::new()
+                    .expect("failed to start the Tokio runtime for timers")
+            });
+            rt.spawn(future);
+        }
+    }
+}
+
+/// Use a debounced copy of `value`, updated to the latest `value` once it stops changing for `delay`.
+///
+/// Each change to `value` restarts the delay, so rapid changes (like typing) only trigger one update.
+/// The debounced value starts as the first `value`.
+pub fn use_debounced<T>(cx: ScopeState<'_>, value: T, delay: Duration) -> Ref<'_, T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let debounced = use_mut(cx, || value.clone());
+    let latest = use_ref(cx, || RefCell::new(value.clone()));
+    let generation = use_ref(cx, || Cell::new(0));
+
+    let is_changed = *latest.borrow() != value;
+    if is_changed {
+        *latest.borrow_mut() = value;
+        generation.set(generation.get() + 1);
+    }
+
+    let is_pending = *latest.borrow() != *debounced;
+    use_timer(
+        cx,
+        is_pending.then_some(delay),
+        generation.get(),
+        true,
+        move || Mut::set(debounced, latest.borrow().clone()),
+    );
+
+    Mut::as_ref(debounced)
+}
+
+/// Use a throttled copy of `value`, updated to the latest `value` at most once every `interval`.
+///
+/// A change after `interval` has passed since the last update is applied right away,
+/// and changes during `interval` are applied together once it ends.
+/// The throttled value starts as the first `value`.
+pub fn use_throttled<T>(cx: ScopeState<'_>, value: T, interval: Duration) -> Ref<'_, T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    let throttled = use_mut(cx, || value.clone());
+    let latest = use_ref(cx, || RefCell::new(value.clone()));
+    let last_update = use_ref(cx, || Cell::new(None::<Instant>));
+
+    // Delay and generation of the pending update at the end of the current interval.
+    let pending = use_ref(cx, || Cell::new(None::<Duration>));
+    let generation = use_ref(cx, || Cell::new(0));
+
+    if *latest.borrow() != value {
+        *latest.borrow_mut() = value.clone();
+
+        let remaining = last_update
+            .get()
+            .and_then(|last| interval.checked_sub(last.elapsed()));
+        match remaining {
+            Some(remaining) => {
+                if pending.get().is_none() {
+                    pending.set(Some(remaining));
+                    generation.set(generation.get() + 1);
+                }
+            }
+            None => {
+                last_update.set(Some(Instant::now()));
+                Mut::set(throttled, value);
+            }
+        }
+    }
+
+    use_timer(cx, pending.get(), generation.get(), true, move || {
+        pending.set(None);
+        last_update.set(Some(Instant::now()));
+        Mut::set(throttled, latest.borrow().clone());
+    });
+
+    Mut::as_ref(throttled)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        composer::{testing::ChannelUpdater, Composer},
+        executor::ExecutorContext,
+        prelude::*,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+        time::Duration,
+    };
+    use tokio::runtime::{Builder, Runtime};
+
+    /// Create a runtime with a paused clock, advanced only while blocking on it.
+    fn paused_runtime() -> Runtime {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap()
+    }
+
+    /// Run `rt` until its clock advances by `duration`.
+    fn sleep(rt: &Runtime, duration: Duration) {
+        rt.block_on(async { tokio::time::sleep(duration).await });
+    }
+
+    #[test]
+    fn it_calls_timers() {
+        #[derive(Data)]
+        struct A {
+            executor: tokio::runtime::Handle,
+            ticks: Rc<Cell<i32>>,
+            timeouts: Rc<Cell<i32>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_provider(&cx, || ExecutorContext::new(cx.me().executor.clone()));
+
+                B {
+                    ticks: cx.me().ticks.clone(),
+                    timeouts: cx.me().timeouts.clone(),
+                }
+            }
+        }
+
+        #[derive(Data)]
+        struct B {
+            ticks: Rc<Cell<i32>>,
+            timeouts: Rc<Cell<i32>>,
+        }
+
+        impl Compose for B {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_interval(&cx, Duration::from_millis(10), move || {
+                    cx.me().ticks.set(cx.me().ticks.get() + 1)
+                });
+                use_timeout(&cx, Duration::from_millis(10), move || {
+                    cx.me().timeouts.set(cx.me().timeouts.get() + 1)
+                });
+            }
+        }
+
+        let rt = paused_runtime();
+        let ticks = Rc::new(Cell::new(0));
+        let timeouts = Rc::new(Cell::new(0));
+        let (updater, _updates) = ChannelUpdater::new();
+        let mut composer = Composer::with_updater(
+            A {
+                executor: rt.handle().clone(),
+                ticks: ticks.clone(),
+                timeouts: timeouts.clone(),
+            },
+            updater,
+        );
+
+        composer.compose();
+        assert_eq!((ticks.get(), timeouts.get()), (0, 0));
+
+        sleep(&rt, Duration::from_millis(25));
+        composer.compose();
+        assert_eq!((ticks.get(), timeouts.get()), (2, 1));
+
+        sleep(&rt, Duration::from_millis(20));
+        composer.compose();
+        assert_eq!((ticks.get(), timeouts.get()), (4, 1));
+    }
+
+    #[test]
+    fn it_debounces_values() {
+        #[derive(Data)]
+        struct A {
+            executor: tokio::runtime::Handle,
+            values: Rc<RefCell<Vec<(i32, i32)>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_provider(&cx, || ExecutorContext::new(cx.me().executor.clone()));
+
+                let input = use_mut(&cx, || 0);
+                B {
+                    input: *input,
+                    values: cx.me().values.clone(),
+                    on_composed: Box::new(move || {
+                        if *input < 2 {
+                            Mut::update(input, |input| *input += 1)
+                        }
+                    }),
+                }
+            }
+        }
+
+        #[derive(Data)]
+        struct B<'a> {
+            input: i32,
+            values: Rc<RefCell<Vec<(i32, i32)>>>,
+            on_composed: Box<dyn Fn() + 'a>,
+        }
+
+        impl Compose for B<'_> {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let debounced = use_debounced(&cx, cx.me().input, Duration::from_millis(20));
+                cx.me()
+                    .values
+                    .borrow_mut()
+                    .push((cx.me().input, *debounced));
+
+                (cx.me().on_composed)();
+            }
+        }
+
+        let rt = paused_runtime();
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let (updater, _updates) = ChannelUpdater::new();
+        let mut composer = Composer::with_updater(
+            A {
+                executor: rt.handle().clone(),
+                values: values.clone(),
+            },
+            updater,
+        );
+
+        for _ in 0..3 {
+            composer.compose();
+        }
+        assert_eq!(*values.borrow(), [(0, 0), (1, 0), (2, 0)]);
+
+        // The value is still changing, so the delay restarted with each change.
+        sleep(&rt, Duration::from_millis(10));
+        composer.compose();
+        assert_eq!(values.borrow().last(), Some(&(2, 0)));
+
+        sleep(&rt, Duration::from_millis(20));
+        composer.compose();
+        composer.compose();
+        assert_eq!(values.borrow().last(), Some(&(2, 2)));
+        assert!(values.borrow().iter().all(|(_, debounced)| *debounced != 1));
+    }
+}
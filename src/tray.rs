@@ -0,0 +1,266 @@
+use crate::{
+    event_loop::use_keep_alive,
+    menu::{self, Menu, MenuShape},
+    prelude::*,
+};
+use std::{cell::RefCell, collections::HashMap, mem, rc::Rc};
+use thiserror::Error;
+
+type ClickFn<'a> = Rc<dyn Fn() + 'a>;
+
+thread_local! {
+    /// Click functions of each tray icon.
+    static CLICK_FNS: RefCell<HashMap<u64, ClickFn<'static>>> = RefCell::default();
+}
+
+/// Call the click function of the tray icon registered for `owner`.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn click(owner: u64) {
+    let f = CLICK_FNS.with(|click_fns| click_fns.borrow().get(&owner).cloned());
+    if let Some(f) = f {
+        f()
+    }
+}
+
+/// Error creating an [`Icon`].
+#[derive(Debug, Error)]
+pub enum IconError {
+    /// The length of the RGBA data doesn't match the size of the icon.
+    #[error("Expected {expected} bytes of RGBA data for the icon size, found {len}")]
+    InvalidLength {
+        /// Expected length of the RGBA data.
+        expected: usize,
+        /// Actual length of the RGBA data.
+        len: usize,
+    },
+}
+
+/// Tray icon image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Icon {
+    rgba: Rc<[u8]>,
+    width: u32,
+    height: u32,
+}
+
+impl Icon {
+    /// Create a new icon from its RGBA pixel data and size.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, IconError> {
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(IconError::InvalidLength {
+                expected,
+                len: rgba.len(),
+            });
+        }
+
+        Ok(Self {
+            rgba: rgba.into(),
+            width,
+            height,
+        })
+    }
+}
+
+/// Shape of a tray icon, used to check if its native icon needs to be updated.
+#[derive(PartialEq)]
+struct TrayShape {
+    icon: Icon,
+    tooltip: Option<String>,
+    menu: Option<Vec<MenuShape>>,
+}
+
+/// System tray icon composable.
+///
+/// This shows an icon in the system tray while it exists,
+/// with an optional tooltip and context [`Menu`].
+///
+/// The event loop is kept alive while a tray icon exists, even after every window is closed,
+/// so windows can be re-opened from its click handler or menu.
+///
+/// Tray icons are currently supported on Windows and macOS.
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Tray<'a> {
+    icon: Icon,
+    tooltip: Option<String>,
+    menu: Option<Menu<'a>>,
+    on_click: Option<ClickFn<'a>>,
+}
+
+impl<'a> Tray<'a> {
+    /// Create a new tray icon from its image.
+    pub fn new(icon: Icon) -> Self {
+        Self {
+            icon,
+            tooltip: None,
+            menu: None,
+            on_click: None,
+        }
+    }
+
+    /// Set the tooltip of this tray icon.
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set the menu of this tray icon.
+    pub fn with_menu(mut self, menu: Menu<'a>) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
+    /// Set the function to call when this tray icon is clicked.
+    pub fn on_click(mut self, on_click: impl Fn() + 'a) -> Self {
+        self.on_click = Some(Rc::new(on_click));
+        self
+    }
+
+    fn shape(&self) -> TrayShape {
+        TrayShape {
+            icon: self.icon.clone(),
+            tooltip: self.tooltip.clone(),
+            menu: self.menu.as_ref().map(Menu::shape),
+        }
+    }
+}
+
+unsafe impl Data for Tray<'_> {}
+
+impl Compose for Tray<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        use_keep_alive(&cx);
+
+        let owner = *use_ref(&cx, menu::next_owner);
+        let native_tray = use_ref(&cx, || RefCell::new(None::<native::NativeTray>));
+        let last_shape = use_ref(&cx, || RefCell::new(None));
+
+        use_drop(&cx, move || {
+            menu::unregister(owner);
+            CLICK_FNS.with(|click_fns| click_fns.borrow_mut().remove(&owner));
+
+            native::remove(&mut native_tray.borrow_mut());
+        });
+
+        let tray = cx.me();
+
+        // Safety: The menu and click function are unregistered on drop.
+        match &tray.menu {
+            Some(menu) => unsafe { menu::register(owner, menu) },
+            None => menu::unregister(owner),
+        }
+        match &tray.on_click {
+            Some(on_click) => {
+                let f: ClickFn = on_click.clone();
+                let f: ClickFn<'static> = unsafe { mem::transmute(f) };
+                CLICK_FNS.with(|click_fns| click_fns.borrow_mut().insert(owner, f));
+            }
+            None => {
+                CLICK_FNS.with(|click_fns| click_fns.borrow_mut().remove(&owner));
+            }
+        }
+
+        let shape = tray.shape();
+        if last_shape.borrow().as_ref() != Some(&shape) {
+            native::update(&mut native_tray.borrow_mut(), owner, &tray);
+            *last_shape.borrow_mut() = Some(shape);
+        }
+    }
+}
+
+/// Native tray icons, built with `tray-icon`.
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod native {
+    use super::{click, Tray};
+    use crate::{composer::Runtime, menu};
+    use std::sync::Once;
+    use tray_icon::{
+        menu::ContextMenu, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+        TrayIconId,
+    };
+
+    pub(super) type NativeTray = TrayIcon;
+
+    fn tray_id(owner: u64) -> TrayIconId {
+        TrayIconId::new(format!("actuate:{owner}"))
+    }
+
+    fn parse_tray_id(id: &TrayIconId) -> Option<u64> {
+        id.as_ref().strip_prefix("actuate:")?.parse().ok()
+    }
+
+    /// Route tray icon clicks to the current runtime, calling their click functions with the next update.
+    fn init() {
+        static INIT: Once = Once::new();
+
+        INIT.call_once(|| {
+            let sender = Runtime::current().update_sender();
+            TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+                if let TrayIconEvent::Click {
+                    id,
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    if let Some(owner) = parse_tray_id(&id) {
+                        sender.send(move || click(owner));
+                    }
+                }
+            }));
+        });
+    }
+
+    pub(super) fn update(native_tray: &mut Option<NativeTray>, owner: u64, tray: &Tray) {
+        init();
+
+        let icon =
+            tray_icon::Icon::from_rgba(tray.icon.rgba.to_vec(), tray.icon.width, tray.icon.height)
+                .ok();
+        let menu = tray
+            .menu
+            .as_ref()
+            .map(|menu| Box::new(menu::build_native(owner, menu)) as Box<dyn ContextMenu>);
+
+        match native_tray {
+            Some(native_tray) => {
+                let _ = native_tray.set_icon(icon);
+                let _ = native_tray.set_tooltip(tray.tooltip.as_deref());
+                native_tray.set_menu(menu);
+            }
+            None => {
+                let mut builder = TrayIconBuilder::new().with_id(tray_id(owner));
+                if let Some(icon) = icon {
+                    builder = builder.with_icon(icon);
+                }
+                if let Some(tooltip) = &tray.tooltip {
+                    builder = builder.with_tooltip(tooltip);
+                }
+                if let Some(menu) = menu {
+                    builder = builder.with_menu(menu);
+                }
+                *native_tray = builder.build().ok();
+            }
+        }
+    }
+
+    pub(super) fn remove(native_tray: &mut Option<NativeTray>) {
+        *native_tray = None;
+    }
+}
+
+/// Tray icons are not supported on this platform.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod native {
+    use super::Tray;
+
+    pub(super) enum NativeTray {}
+
+    pub(super) fn update(native_tray: &mut Option<NativeTray>, owner: u64, tray: &Tray) {
+        let _ = (native_tray, owner, tray);
+    }
+
+    pub(super) fn remove(native_tray: &mut Option<NativeTray>) {
+        let _ = native_tray;
+    }
+}
@@ -0,0 +1,682 @@
+use crate::{
+    composer::{Composer, Update, Updater},
+    executor::ExecutorContext,
+    prelude::*,
+    ui::{Event, KeyboardInput},
+};
+use ratatui::{
+    backend::Backend,
+    buffer::Buffer,
+    crossterm::{
+        event::{
+            self as terminal_event, DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent,
+            KeyEventKind, KeyModifiers, MouseButton as TerminalMouseButton, MouseEvent,
+            MouseEventKind,
+        },
+        execute,
+    },
+    layout::{Position, Rect},
+    Terminal,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    io, mem,
+    rc::Rc,
+    thread,
+};
+use taffy::{AvailableSpace, NodeId, Size, Style, TaffyTree};
+use tokio::sync::mpsc;
+use vello::kurbo::Vec2;
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, ModifiersState, NamedKey, NativeKey, NativeKeyCode, PhysicalKey},
+};
+
+/// Headless testing utilities.
+#[cfg(any(test, feature = "testing"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+mod view;
+pub use self::view::{Block, Canvas, Flex, List, Text};
+
+type NodeDrawFn<'a> = Rc<dyn Fn(Rect, &mut Buffer) + 'a>;
+
+type NodeMeasureFn<'a> = Rc<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32> + 'a>;
+
+type NodeListenerFn<'a> = Rc<dyn Fn(Event) + 'a>;
+
+/// Drawing and input handlers of a layout node.
+#[derive(Clone, Default)]
+struct Node {
+    draw: Option<NodeDrawFn<'static>>,
+    measure: Option<NodeMeasureFn<'static>>,
+    listener: Option<NodeListenerFn<'static>>,
+    is_focusable: bool,
+}
+
+/// Layout tree of a terminal, shared by each view inside it.
+///
+/// Layout units are character cells.
+#[derive(Clone)]
+struct TuiContext {
+    taffy: Rc<RefCell<TaffyTree>>,
+    nodes: Rc<RefCell<HashMap<NodeId, Node>>>,
+    root: NodeId,
+    /// Area of each node in drawing order, from the last frame.
+    areas: Rc<RefCell<Vec<(NodeId, Rect)>>>,
+    focused: Rc<Cell<Option<NodeId>>>,
+}
+
+impl TuiContext {
+    fn new() -> Self {
+        let mut taffy = TaffyTree::new();
+        let root = taffy.new_leaf(Style::default()).unwrap();
+
+        Self {
+            taffy: Rc::new(RefCell::new(taffy)),
+            nodes: Rc::default(),
+            root,
+            areas: Rc::default(),
+            focused: Rc::default(),
+        }
+    }
+
+    /// Compute the layout of the tree in an area of `width` by `height` cells,
+    /// and store the area of each node.
+    fn compute_layout(&self, width: u16, height: u16) {
+        let mut taffy = self.taffy.borrow_mut();
+        taffy
+            .set_style(
+                self.root,
+                Style {
+                    size: Size::from_lengths(width as f32, height as f32),
+                    flex_direction: taffy::FlexDirection::Column,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let nodes = self.nodes.borrow();
+        taffy
+            .compute_layout_with_measure(
+                self.root,
+                Size {
+                    width: AvailableSpace::Definite(width as f32),
+                    height: AvailableSpace::Definite(height as f32),
+                },
+                |known_dimensions, available_space, key, _, _| match nodes
+                    .get(&key)
+                    .and_then(|node| node.measure.as_ref())
+                {
+                    Some(measure) => measure(known_dimensions, available_space),
+                    None => Size::ZERO,
+                },
+            )
+            .unwrap();
+
+        let mut areas = self.areas.borrow_mut();
+        areas.clear();
+        collect_areas(&taffy, self.root, (0., 0.), &mut areas);
+    }
+
+    /// Get the area of `key` from the last frame.
+    fn area(&self, key: NodeId) -> Option<Rect> {
+        self.areas
+            .borrow()
+            .iter()
+            .find(|(area_key, _)| *area_key == key)
+            .map(|(_, area)| *area)
+    }
+
+    /// Get the topmost node with a listener at `pos`.
+    fn hit_test(&self, pos: Position) -> Option<NodeId> {
+        let nodes = self.nodes.borrow();
+        self.areas
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(key, area)| {
+                area.contains(pos) && nodes.get(key).is_some_and(|node| node.listener.is_some())
+            })
+            .map(|(key, _)| *key)
+    }
+
+    /// Send `event` to the listener of `key`, with positions relative to its area.
+    fn send(&self, key: NodeId, event: Event) {
+        let Some(listener) = self
+            .nodes
+            .borrow()
+            .get(&key)
+            .and_then(|node| node.listener.clone())
+        else {
+            return;
+        };
+
+        let origin = self
+            .area(key)
+            .map(|area| Vec2::new(area.x as f64, area.y as f64))
+            .unwrap_or_default();
+        let event = match event {
+            Event::MouseInput { button, state, pos } => Event::MouseInput {
+                button,
+                state,
+                pos: pos - origin,
+            },
+            Event::MouseMove { pos } => Event::MouseMove { pos: pos - origin },
+            Event::Tap { pos } => Event::Tap { pos: pos - origin },
+            event => event,
+        };
+
+        listener(event);
+    }
+
+    /// Move the focus to `key`, sending blur and focus events.
+    fn focus(&self, key: Option<NodeId>) {
+        let last = self.focused.replace(key);
+        if last == key {
+            return;
+        }
+
+        if let Some(last) = last {
+            self.send(last, Event::Blur);
+        }
+        if let Some(key) = key {
+            self.send(key, Event::Focus);
+        }
+    }
+
+    /// Move the focus to the next (or previous if `is_reverse`) focusable node in drawing order.
+    fn traverse_focus(&self, is_reverse: bool) {
+        let focusable: Vec<_> = {
+            let nodes = self.nodes.borrow();
+            self.areas
+                .borrow()
+                .iter()
+                .filter(|(key, _)| nodes.get(key).is_some_and(|node| node.is_focusable))
+                .map(|(key, _)| *key)
+                .collect()
+        };
+        if focusable.is_empty() {
+            return;
+        }
+
+        let idx = self
+            .focused
+            .get()
+            .and_then(|focused| focusable.iter().position(|key| *key == focused));
+        let next = match (idx, is_reverse) {
+            (None, false) => 0,
+            (None, true) => focusable.len() - 1,
+            (Some(idx), false) => (idx + 1) % focusable.len(),
+            (Some(idx), true) => (idx + focusable.len() - 1) % focusable.len(),
+        };
+        self.focus(Some(focusable[next]));
+    }
+}
+
+fn collect_areas(
+    taffy: &TaffyTree,
+    key: NodeId,
+    parent_origin: (f32, f32),
+    areas: &mut Vec<(NodeId, Rect)>,
+) {
+    let layout = taffy.layout(key).unwrap();
+    let x = parent_origin.0 + layout.location.x;
+    let y = parent_origin.1 + layout.location.y;
+    areas.push((
+        key,
+        Rect::new(
+            x.max(0.) as u16,
+            y.max(0.) as u16,
+            layout.size.width as u16,
+            layout.size.height as u16,
+        ),
+    ));
+
+    for child in taffy.children(key).unwrap() {
+        collect_areas(taffy, child, (x, y), areas);
+    }
+}
+
+/// Parent of the layout nodes created by composables.
+#[derive(Clone, Copy)]
+struct ParentContext {
+    key: NodeId,
+}
+
+/// Use a new layout node in the current terminal.
+fn use_node(cx: ScopeState, style: Style) -> NodeId {
+    let tui_cx = use_context::<TuiContext>(cx).unwrap();
+    let parent_cx = use_context::<ParentContext>(cx).unwrap();
+
+    let key = *use_ref(cx, || {
+        let mut taffy = tui_cx.taffy.borrow_mut();
+        let key = taffy.new_leaf(style.clone()).unwrap();
+        taffy.add_child(parent_cx.key, key).unwrap();
+        key
+    });
+
+    let last_style = use_ref(cx, || RefCell::new(style.clone()));
+    if style != *last_style.borrow() {
+        *last_style.borrow_mut() = style.clone();
+        tui_cx.taffy.borrow_mut().set_style(key, style).unwrap();
+    }
+
+    use_drop(cx, move || {
+        tui_cx.taffy.borrow_mut().remove(key).unwrap();
+        tui_cx.nodes.borrow_mut().remove(&key);
+        if tui_cx.focused.get() == Some(key) {
+            tui_cx.focused.set(None);
+        }
+    });
+
+    key
+}
+
+/// Set the drawing and input handlers of the layout node `key`.
+///
+/// Handlers may borrow from the composable that created `key`, as they're removed when it's dropped.
+fn set_node<'a>(
+    cx: ScopeState<'a>,
+    key: NodeId,
+    draw: Option<NodeDrawFn<'a>>,
+    measure: Option<NodeMeasureFn<'a>>,
+    listener: Option<NodeListenerFn<'a>>,
+    is_focusable: bool,
+) {
+    let tui_cx = use_context::<TuiContext>(cx).unwrap();
+
+    // Safety: Each handler is removed from `nodes` when the node is dropped in `use_node`.
+    let node = unsafe {
+        Node {
+            draw: draw.map(|f| mem::transmute::<NodeDrawFn, NodeDrawFn<'static>>(f)),
+            measure: measure.map(|f| mem::transmute::<NodeMeasureFn, NodeMeasureFn<'static>>(f)),
+            listener: listener
+                .map(|f| mem::transmute::<NodeListenerFn, NodeListenerFn<'static>>(f)),
+            is_focusable,
+        }
+    };
+    tui_cx.nodes.borrow_mut().insert(key, node);
+
+    // The measured size of this node may have changed.
+    tui_cx.taffy.borrow_mut().mark_dirty(key).unwrap();
+}
+
+#[derive(Data)]
+struct TuiRoot<C> {
+    tui_cx: TuiContext,
+    content: C,
+}
+
+impl<C: Compose> Compose for TuiRoot<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let tui_cx = use_provider(&cx, || cx.me().tui_cx.clone());
+        use_provider(&cx, || ParentContext { key: tui_cx.root });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Root of the content run by [`run`], which provides the Tokio runtime as its executor.
+#[derive(Data)]
+struct RunRoot<C> {
+    executor: tokio::runtime::Handle,
+    content: C,
+}
+
+impl<C: Compose> Compose for RunRoot<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        use_provider(&cx, || ExecutorContext::new(cx.me().executor.clone()));
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Composed content drawn to a terminal.
+struct Tui<B: Backend> {
+    composer: Composer,
+    tui_cx: TuiContext,
+    terminal: Terminal<B>,
+    /// Node the left mouse button was pressed on.
+    pressed: Option<NodeId>,
+    hovered: Option<NodeId>,
+}
+
+impl<B: Backend> Tui<B> {
+    fn new(
+        terminal: Terminal<B>,
+        content: impl Compose + 'static,
+        updater: impl Updater + 'static,
+    ) -> Self {
+        let tui_cx = TuiContext::new();
+
+        Self {
+            composer: Composer::with_updater(
+                TuiRoot {
+                    tui_cx: tui_cx.clone(),
+                    content,
+                },
+                updater,
+            ),
+            tui_cx,
+            terminal,
+            pressed: None,
+            hovered: None,
+        }
+    }
+
+    /// Recompose any changed content, compute its layout, and draw it to the terminal.
+    fn frame(&mut self) -> io::Result<()> {
+        self.composer.compose();
+
+        let size = self.terminal.size()?;
+        self.tui_cx.compute_layout(size.width, size.height);
+
+        // Focus the first focusable view, so keys can be handled without a mouse.
+        if self.tui_cx.focused.get().is_none() {
+            let _rt = self.composer.runtime().enter();
+            self.tui_cx.traverse_focus(false);
+        }
+
+        // Compose again to apply any changes from focus events.
+        self.composer.compose();
+
+        let draws: Vec<_> = {
+            let nodes = self.tui_cx.nodes.borrow();
+            self.tui_cx
+                .areas
+                .borrow()
+                .iter()
+                .filter_map(|(key, area)| {
+                    let draw = nodes.get(key)?.draw.clone()?;
+                    Some((draw, *area))
+                })
+                .collect()
+        };
+
+        self.terminal.draw(|frame| {
+            let frame_area = frame.area();
+            let buf = frame.buffer_mut();
+            for (draw, area) in &draws {
+                let area = area.intersection(frame_area);
+                if !area.is_empty() {
+                    draw(area, buf);
+                }
+            }
+        })?;
+
+        Ok(())
+    }
+
+    /// Translate a terminal event and send it to the focused view, or the view under the cursor.
+    fn handle_event(&mut self, event: terminal_event::Event) {
+        let _rt = self.composer.runtime().enter();
+
+        match event {
+            terminal_event::Event::Key(key_event) => self.handle_key(key_event),
+            terminal_event::Event::Mouse(mouse_event) => self.handle_mouse(mouse_event),
+            _ => {}
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        let input = keyboard_input(key_event);
+
+        if input.state == ElementState::Pressed && input.key == Key::Named(NamedKey::Tab) {
+            self.tui_cx.traverse_focus(input.modifiers.shift_key());
+        } else if let Some(focused) = self.tui_cx.focused.get() {
+            self.tui_cx.send(focused, Event::KeyboardInput(input));
+        }
+    }
+
+    fn handle_mouse(&mut self, mouse_event: MouseEvent) {
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        let pos = Vec2::new(mouse_event.column as f64, mouse_event.row as f64);
+        let target = self.tui_cx.hit_test(position);
+
+        if target != self.hovered {
+            if let Some(last) = mem::replace(&mut self.hovered, target) {
+                self.tui_cx.send(last, Event::MouseOut);
+            }
+            if let Some(key) = target {
+                self.tui_cx.send(key, Event::MouseIn);
+            }
+        }
+
+        let Some(key) = target else {
+            if let MouseEventKind::Up(_) = mouse_event.kind {
+                self.pressed = None;
+            }
+            return;
+        };
+
+        match mouse_event.kind {
+            MouseEventKind::Down(button) => {
+                let is_focusable = self
+                    .tui_cx
+                    .nodes
+                    .borrow()
+                    .get(&key)
+                    .is_some_and(|node| node.is_focusable);
+                if is_focusable {
+                    self.tui_cx.focus(Some(key));
+                }
+
+                let button = mouse_button(button);
+                if button == MouseButton::Left {
+                    self.pressed = Some(key);
+                }
+                self.tui_cx.send(
+                    key,
+                    Event::MouseInput {
+                        button,
+                        state: ElementState::Pressed,
+                        pos,
+                    },
+                );
+            }
+            MouseEventKind::Up(button) => {
+                let button = mouse_button(button);
+                self.tui_cx.send(
+                    key,
+                    Event::MouseInput {
+                        button,
+                        state: ElementState::Released,
+                        pos,
+                    },
+                );
+
+                if button == MouseButton::Left && self.pressed.take() == Some(key) {
+                    self.tui_cx.send(key, Event::Tap { pos });
+                }
+            }
+            MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                self.tui_cx.send(key, Event::MouseMove { pos });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Translate a terminal key event to keyboard input.
+fn keyboard_input(key_event: KeyEvent) -> KeyboardInput {
+    let key = match key_event.code {
+        KeyCode::Char(c) => Key::Character(c.to_string().into()),
+        KeyCode::Backspace => Key::Named(NamedKey::Backspace),
+        KeyCode::Enter => Key::Named(NamedKey::Enter),
+        KeyCode::Left => Key::Named(NamedKey::ArrowLeft),
+        KeyCode::Right => Key::Named(NamedKey::ArrowRight),
+        KeyCode::Up => Key::Named(NamedKey::ArrowUp),
+        KeyCode::Down => Key::Named(NamedKey::ArrowDown),
+        KeyCode::Home => Key::Named(NamedKey::Home),
+        KeyCode::End => Key::Named(NamedKey::End),
+        KeyCode::PageUp => Key::Named(NamedKey::PageUp),
+        KeyCode::PageDown => Key::Named(NamedKey::PageDown),
+        KeyCode::Tab | KeyCode::BackTab => Key::Named(NamedKey::Tab),
+        KeyCode::Delete => Key::Named(NamedKey::Delete),
+        KeyCode::Insert => Key::Named(NamedKey::Insert),
+        KeyCode::Esc => Key::Named(NamedKey::Escape),
+        KeyCode::F(n) => match n {
+            1 => Key::Named(NamedKey::F1),
+            2 => Key::Named(NamedKey::F2),
+            3 => Key::Named(NamedKey::F3),
+            4 => Key::Named(NamedKey::F4),
+            5 => Key::Named(NamedKey::F5),
+            6 => Key::Named(NamedKey::F6),
+            7 => Key::Named(NamedKey::F7),
+            8 => Key::Named(NamedKey::F8),
+            9 => Key::Named(NamedKey::F9),
+            10 => Key::Named(NamedKey::F10),
+            11 => Key::Named(NamedKey::F11),
+            12 => Key::Named(NamedKey::F12),
+            _ => Key::Unidentified(NativeKey::Unidentified),
+        },
+        _ => Key::Unidentified(NativeKey::Unidentified),
+    };
+
+    let (state, repeat) = match key_event.kind {
+        KeyEventKind::Press => (ElementState::Pressed, false),
+        KeyEventKind::Repeat => (ElementState::Pressed, true),
+        KeyEventKind::Release => (ElementState::Released, false),
+    };
+
+    let mut modifiers = ModifiersState::empty();
+    modifiers.set(
+        ModifiersState::SHIFT,
+        key_event.modifiers.contains(KeyModifiers::SHIFT) || key_event.code == KeyCode::BackTab,
+    );
+    modifiers.set(
+        ModifiersState::CONTROL,
+        key_event.modifiers.contains(KeyModifiers::CONTROL),
+    );
+    modifiers.set(
+        ModifiersState::ALT,
+        key_event.modifiers.contains(KeyModifiers::ALT),
+    );
+    modifiers.set(
+        ModifiersState::SUPER,
+        key_event.modifiers.contains(KeyModifiers::SUPER),
+    );
+
+    KeyboardInput {
+        key,
+        physical_key: PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
+        state,
+        repeat,
+        modifiers,
+    }
+}
+
+fn mouse_button(button: TerminalMouseButton) -> MouseButton {
+    match button {
+        TerminalMouseButton::Left => MouseButton::Left,
+        TerminalMouseButton::Right => MouseButton::Right,
+        TerminalMouseButton::Middle => MouseButton::Middle,
+    }
+}
+
+/// Message sent to the terminal event loop.
+enum Message {
+    Update(Update),
+    Event(terminal_event::Event),
+}
+
+struct TuiUpdater {
+    tx: mpsc::UnboundedSender<Message>,
+}
+
+impl Updater for TuiUpdater {
+    fn update(&self, update: Update) {
+        if self.tx.send(Message::Update(update)).is_err() {
+            panic!("Failed to send update to terminal event loop.");
+        }
+    }
+}
+
+/// Run this content in the terminal.
+///
+/// The terminal is switched to raw mode and the alternate screen until `Ctrl+C` is pressed.
+/// Terminal events are read on a background thread and handled with composer updates
+/// on a Tokio event loop, which is also provided to the content as its [`ExecutorContext`].
+///
+/// ```no_run
+/// use actuate::{prelude::*, tui};
+///
+/// #[derive(Data)]
+/// struct App;
+///
+/// impl Compose for App {
+///     fn compose(cx: Scope<Self>) -> impl Compose {
+///         let selected = use_mut(&cx, || Some(0));
+///
+///         tui::Block::new(
+///             tui::List::new(["Apples", "Oranges"])
+///                 .with_selected(*selected)
+///                 .on_select(move |idx| Mut::set(selected, Some(idx))),
+///         )
+///         .with_title("Fruits")
+///     }
+/// }
+///
+/// tui::run(App).unwrap();
+/// ```
+pub fn run(content: impl Compose + 'static) -> io::Result<()> {
+    let rt = tokio::runtime::Runtime::new()?;
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let terminal = ratatui::try_init()?;
+    execute!(io::stdout(), EnableMouseCapture)?;
+
+    let content = RunRoot {
+        executor: rt.handle().clone(),
+        content,
+    };
+    let mut tui = Tui::new(terminal, content, TuiUpdater { tx: tx.clone() });
+
+    // Reading terminal events blocks, so they're read on their own thread.
+    thread::spawn(move || {
+        while let Ok(event) = terminal_event::read() {
+            if tx.send(Message::Event(event)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let result = rt.block_on(async {
+        tui.frame()?;
+
+        while let Some(message) = rx.recv().await {
+            let mut messages = vec![message];
+            while let Ok(next_message) = rx.try_recv() {
+                messages.push(next_message);
+            }
+
+            for message in messages {
+                match message {
+                    Message::Update(update) => {
+                        let _rt = tui.composer.runtime().enter();
+                        update.apply();
+                    }
+                    Message::Event(terminal_event::Event::Key(KeyEvent {
+                        code: KeyCode::Char('c'),
+                        modifiers,
+                        kind: KeyEventKind::Press,
+                        ..
+                    })) if modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+                    Message::Event(event) => tui.handle_event(event),
+                }
+            }
+
+            tui.frame()?;
+        }
+
+        Ok(())
+    });
+
+    execute!(io::stdout(), DisableMouseCapture)?;
+    ratatui::try_restore()?;
+
+    result
+}
@@ -0,0 +1,154 @@
+use super::Tui;
+use crate::{prelude::*, ui::testing::ImmediateUpdater};
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
+    Terminal,
+};
+
+/// Headless terminal for testing terminal user interfaces.
+///
+/// This composes and draws content like [`run`](super::run), to an in-memory buffer.
+/// Terminal events can be sent to the content, and the drawn cells can be queried after each frame.
+///
+/// ```no_run
+/// use actuate::tui::{self, testing::TestTerminal};
+///
+/// let mut terminal = TestTerminal::new(20, 3, tui::Block::new(tui::Text::new("Hello World!")));
+/// terminal.frame();
+///
+/// assert_eq!(terminal.lines()[1], "│Hello World!      │");
+/// ```
+pub struct TestTerminal {
+    tui: Tui<TestBackend>,
+}
+
+impl TestTerminal {
+    /// Create a new test terminal of `width` by `height` cells from its content.
+    ///
+    /// The content is composed on the first call to [`TestTerminal::frame`].
+    pub fn new(width: u16, height: u16, content: impl Compose + 'static) -> Self {
+        let terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+
+        Self {
+            tui: Tui::new(terminal, content, ImmediateUpdater),
+        }
+    }
+
+    /// Recompose any changed content, compute its layout, and draw it.
+    pub fn frame(&mut self) {
+        self.tui.frame().unwrap();
+    }
+
+    /// Resize this terminal to `width` by `height` cells.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.tui.terminal.backend_mut().resize(width, height);
+    }
+
+    /// Get the buffer drawn in the last frame.
+    pub fn buffer(&self) -> &Buffer {
+        self.tui.terminal.backend().buffer()
+    }
+
+    /// Get the text of each line drawn in the last frame.
+    pub fn lines(&self) -> Vec<String> {
+        let buffer = self.buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Send a terminal event to the content.
+    pub fn send(&mut self, event: Event) {
+        self.tui.handle_event(event);
+    }
+
+    /// Press `code` with no modifier keys.
+    pub fn key_press(&mut self, code: KeyCode) {
+        self.send(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+
+    /// Click the left mouse button on the cell at `column` and `row`.
+    pub fn click(&mut self, column: u16, row: u16) {
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Up(MouseButton::Left),
+        ] {
+            self.send(Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestTerminal;
+    use crate::{prelude::*, tui};
+    use ratatui::crossterm::event::KeyCode;
+
+    #[derive(Data)]
+    struct Fruits;
+
+    impl Compose for Fruits {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let selected = use_mut(&cx, || None);
+
+            tui::Flex::column((
+                tui::Block::new(
+                    tui::List::new(["Apples", "Oranges", "Pears"])
+                        .with_selected(*selected)
+                        .on_select(move |idx| Mut::set(selected, Some(idx))),
+                )
+                .with_title("Fruits"),
+                tui::Text::new(format!("Selected: {:?}", *selected)),
+            ))
+        }
+    }
+
+    #[test]
+    fn it_draws_borders_text_and_lists() {
+        let mut terminal = TestTerminal::new(16, 6, Fruits);
+        terminal.frame();
+
+        assert_eq!(
+            terminal.lines(),
+            [
+                "┌Fruits────────┐",
+                "│  Apples      │",
+                "│  Oranges     │",
+                "│  Pears       │",
+                "└──────────────┘",
+                "Selected: None  ",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_selects_list_items_with_keys_and_clicks() {
+        let mut terminal = TestTerminal::new(16, 6, Fruits);
+        terminal.frame();
+
+        // The list is focused in the first frame, as it's the only focusable view.
+        terminal.key_press(KeyCode::Down);
+        terminal.frame();
+        terminal.key_press(KeyCode::Down);
+        terminal.frame();
+        assert_eq!(terminal.lines()[2], "│> Oranges     │");
+        assert_eq!(terminal.lines()[5], "Selected: Some(1");
+
+        terminal.click(3, 3);
+        terminal.frame();
+        assert_eq!(terminal.lines()[3], "│> Pears       │");
+    }
+}
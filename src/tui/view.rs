@@ -0,0 +1,354 @@
+use super::{set_node, use_node, NodeDrawFn, NodeListenerFn, NodeMeasureFn, ParentContext};
+use crate::{prelude::*, ui::Event};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style as TextStyle},
+    text::Line,
+    widgets::{self, HighlightSpacing, ListState, Paragraph, StatefulWidget, Widget},
+};
+use std::{fmt, rc::Rc};
+use taffy::{AvailableSpace, FlexDirection, LengthPercentage, Size, Style};
+use winit::{
+    event::ElementState,
+    keyboard::{Key, NamedKey},
+};
+
+type DrawFn<'a> = Box<dyn Fn(Rect, &mut Buffer) + 'a>;
+
+type DynMeasureFn<'a> = dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32> + 'a;
+
+/// Terminal canvas composable.
+///
+/// The draw function of a canvas is called with its area in the terminal and the terminal's buffer
+/// each frame, and can draw any ratatui widget.
+#[must_use = "Composables do nothing unless composed with `actuate::tui::run` or returned from other composables"]
+pub struct Canvas<'a> {
+    style: Style,
+    f: DrawFn<'a>,
+    measure: Option<Box<DynMeasureFn<'a>>>,
+    on_event: Option<Box<dyn Fn(Event) + 'a>>,
+    is_focusable: bool,
+}
+
+impl<'a> Canvas<'a> {
+    /// Create a new canvas from its style and draw function.
+    pub fn new(style: Style, draw_fn: impl Fn(Rect, &mut Buffer) + 'a) -> Self {
+        Self {
+            style,
+            f: Box::new(draw_fn),
+            measure: None,
+            on_event: None,
+            is_focusable: false,
+        }
+    }
+
+    /// Set the measure function of this canvas, in character cells.
+    pub fn with_measure(
+        mut self,
+        measure: impl Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32> + 'a,
+    ) -> Self {
+        self.measure = Some(Box::new(measure));
+        self
+    }
+
+    /// Set the event handler of this canvas.
+    ///
+    /// Mouse events are sent with positions in cells relative to the canvas' origin,
+    /// and keyboard events are sent while this canvas is focused.
+    pub fn on_event(mut self, on_event: impl Fn(Event) + 'a) -> Self {
+        self.on_event = Some(Box::new(on_event));
+        self
+    }
+
+    /// Set whether this canvas can be focused with the tab key or a click (defaults to `false`).
+    pub fn with_focusable(mut self, is_focusable: bool) -> Self {
+        self.is_focusable = is_focusable;
+        self
+    }
+}
+
+unsafe impl Data for Canvas<'_> {}
+
+impl Compose for Canvas<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let key = use_node(&cx, cx.me().style.clone());
+
+        let draw: NodeDrawFn = Rc::new(move |area, buf| (cx.me().f)(area, buf));
+        let measure = cx.me().measure.as_ref().map(|_| {
+            let f: NodeMeasureFn = Rc::new(move |known_dimensions, available_space| {
+                (cx.me().measure.as_ref().unwrap())(known_dimensions, available_space)
+            });
+            f
+        });
+        let listener = cx.me().on_event.as_ref().map(|_| {
+            let f: NodeListenerFn =
+                Rc::new(move |event| (cx.me().on_event.as_ref().unwrap())(event));
+            f
+        });
+
+        set_node(
+            &cx,
+            key,
+            Some(draw),
+            measure,
+            listener,
+            cx.me().is_focusable,
+        );
+    }
+}
+
+/// Terminal flex composable.
+///
+/// Flex layouts are measured in character cells.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::tui::run` or returned from other composables"]
+pub struct Flex<C> {
+    style: Style,
+    content: C,
+}
+
+impl<C> Flex<C> {
+    /// Create a new flex from its style and the given `content`.
+    pub fn new(style: Style, content: C) -> Self {
+        Self { style, content }
+    }
+
+    /// Create a new flex column from the given `content`.
+    pub fn column(content: C) -> Self {
+        Self::new(
+            Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            },
+            content,
+        )
+    }
+
+    /// Create a new flex row from the given `content`.
+    pub fn row(content: C) -> Self {
+        Self::new(
+            Style {
+                flex_direction: FlexDirection::Row,
+                ..Default::default()
+            },
+            content,
+        )
+    }
+}
+
+impl<C: Compose> Compose for Flex<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let key = use_node(&cx, cx.me().style.clone());
+        set_node(&cx, key, None, None, None, false);
+
+        use_provider(&cx, || ParentContext { key });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Terminal block composable.
+///
+/// This draws a border around its content, with an optional title.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::tui::run` or returned from other composables"]
+pub struct Block<C> {
+    title: Option<String>,
+    content: C,
+}
+
+impl<C> Block<C> {
+    /// Create a new block from the given `content`.
+    pub fn new(content: C) -> Self {
+        Self {
+            title: None,
+            content,
+        }
+    }
+
+    /// Set the title of this block, drawn on its top border.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl<C: Compose> Compose for Block<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let key = use_node(
+            &cx,
+            Style {
+                flex_direction: FlexDirection::Column,
+                border: taffy::Rect {
+                    left: LengthPercentage::Length(1.),
+                    right: LengthPercentage::Length(1.),
+                    top: LengthPercentage::Length(1.),
+                    bottom: LengthPercentage::Length(1.),
+                },
+                ..Default::default()
+            },
+        );
+
+        let draw: NodeDrawFn = Rc::new(move |area, buf| {
+            let me = cx.me();
+            let mut block = widgets::Block::bordered();
+            if let Some(title) = &me.title {
+                block = block.title(title.as_str());
+            }
+            block.render(area, buf);
+        });
+        set_node(&cx, key, Some(draw), None, None, false);
+
+        use_provider(&cx, || ParentContext { key });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Terminal text composable.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::tui::run` or returned from other composables"]
+pub struct Text<T> {
+    content: T,
+    style: TextStyle,
+}
+
+impl<T> Text<T> {
+    /// Create a new text from the given `content`.
+    pub fn new(content: T) -> Self {
+        Self {
+            content,
+            style: TextStyle::default(),
+        }
+    }
+
+    /// Set the style of this text, such as its colors and modifiers.
+    pub fn with_style(mut self, style: impl Into<TextStyle>) -> Self {
+        self.style = style.into();
+        self
+    }
+}
+
+impl<T> Compose for Text<T>
+where
+    T: Data + fmt::Display,
+{
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let content = cx.me().content.to_string();
+        let content = use_memo(&cx, content.clone(), || content);
+
+        // Text is sized to its widest line, and lines past the width of its area are cut off.
+        let width = content.lines().map(|line| Line::raw(line).width()).max();
+        let height = content.lines().count();
+
+        Canvas::new(Style::default(), move |area, buf| {
+            Paragraph::new(content.as_str())
+                .style(cx.me().style)
+                .render(area, buf)
+        })
+        .with_measure(move |known_dimensions, _| Size {
+            width: known_dimensions
+                .width
+                .unwrap_or(width.unwrap_or_default() as f32),
+            height: known_dimensions.height.unwrap_or(height as f32),
+        })
+    }
+}
+
+/// Terminal list composable.
+///
+/// The selected item is highlighted, and selected by clicking an item
+/// or pressing the up and down arrow keys while this list is focused.
+#[must_use = "Composables do nothing unless composed with `actuate::tui::run` or returned from other composables"]
+pub struct List<'a> {
+    items: Vec<String>,
+    selected: Option<usize>,
+    on_select: Box<dyn Fn(usize) + 'a>,
+}
+
+impl<'a> List<'a> {
+    /// Create a new list from its items.
+    pub fn new(items: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            items: items.into_iter().map(Into::into).collect(),
+            selected: None,
+            on_select: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the index of the selected item (defaults to `None`).
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the function called with the index of an item when it's selected.
+    pub fn on_select(mut self, on_select: impl Fn(usize) + 'a) -> Self {
+        self.on_select = Box::new(on_select);
+        self
+    }
+}
+
+unsafe impl Data for List<'_> {}
+
+/// Symbol drawn before the selected item of a [`List`].
+const HIGHLIGHT_SYMBOL: &str = "> ";
+
+impl Compose for List<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let width = cx
+            .me()
+            .items
+            .iter()
+            .map(|item| Line::raw(item.as_str()).width() + HIGHLIGHT_SYMBOL.len())
+            .max()
+            .unwrap_or_default();
+        let height = cx.me().items.len();
+
+        Canvas::new(Style::default(), move |area, buf| {
+            let me = cx.me();
+            let list = widgets::List::new(me.items.iter().map(String::as_str))
+                .highlight_symbol(HIGHLIGHT_SYMBOL)
+                .highlight_spacing(HighlightSpacing::Always)
+                .highlight_style(Modifier::REVERSED);
+            let mut state = ListState::default().with_selected(me.selected);
+            StatefulWidget::render(list, area, buf, &mut state);
+        })
+        .with_measure(move |known_dimensions, _| Size {
+            width: known_dimensions.width.unwrap_or(width as f32),
+            height: known_dimensions.height.unwrap_or(height as f32),
+        })
+        .with_focusable(true)
+        .on_event(move |event| {
+            let me = cx.me();
+            let len = me.items.len();
+            if len == 0 {
+                return;
+            }
+
+            let selected = match event {
+                Event::KeyboardInput(input) if input.state == ElementState::Pressed => {
+                    match input.key {
+                        Key::Named(NamedKey::ArrowUp) => {
+                            me.selected.map_or(len - 1, |idx| idx.saturating_sub(1))
+                        }
+                        Key::Named(NamedKey::ArrowDown) => {
+                            me.selected.map_or(0, |idx| (idx + 1).min(len - 1))
+                        }
+                        Key::Named(NamedKey::Home) => 0,
+                        Key::Named(NamedKey::End) => len - 1,
+                        _ => return,
+                    }
+                }
+                // Lists are drawn from their first item, so rows map to item indices.
+                Event::Tap { pos } if (pos.y as usize) < len => pos.y as usize,
+                _ => return,
+            };
+
+            if me.selected != Some(selected) {
+                (me.on_select)(selected);
+            }
+        })
+    }
+}
@@ -0,0 +1,258 @@
+use super::{Event, KeyboardInput, WindowContext};
+use crate::composer::{Priority, UpdateSender};
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node,
+    NodeId as AccessNodeId, Rect, Toggled, Tree, TreeUpdate,
+};
+use std::{
+    ops::RangeInclusive,
+    sync::mpsc,
+};
+use taffy::NodeId;
+use vello::kurbo::Vec2;
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, ModifiersState, NamedKey, NativeKeyCode, PhysicalKey},
+};
+
+pub use accesskit::{Action, Role};
+
+/// Accessibility properties of a view.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accessibility {
+    /// Role of this view.
+    pub role: Role,
+    /// Label for this view, read by screen readers.
+    pub label: Option<String>,
+    /// Actions supported by this view.
+    pub actions: Vec<Action>,
+    /// Checked state of this view, if it can be toggled (such as a checkbox or switch).
+    pub toggled: Option<bool>,
+    /// Numeric value of this view and its range, if it shows a value (such as a slider or progress bar).
+    pub numeric_value: Option<(f64, RangeInclusive<f64>)>,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            role: Role::GenericContainer,
+            label: None,
+            actions: Vec::new(),
+            toggled: None,
+            numeric_value: None,
+        }
+    }
+}
+
+impl Accessibility {
+    /// Create new accessibility properties from a role.
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            ..Default::default()
+        }
+    }
+
+    /// Set the label of these accessibility properties.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the checked state of these accessibility properties.
+    pub fn toggled(mut self, is_toggled: bool) -> Self {
+        self.toggled = Some(is_toggled);
+        self
+    }
+
+    /// Set the numeric value and range of these accessibility properties.
+    pub fn numeric_value(mut self, value: f64, range: RangeInclusive<f64>) -> Self {
+        self.numeric_value = Some((value, range));
+        self
+    }
+
+    /// Merge `other` into these properties, keeping any role or label already set.
+    pub(crate) fn merge(&mut self, other: &Accessibility) {
+        if self.role == Role::GenericContainer {
+            self.role = other.role;
+        }
+
+        if self.label.is_none() {
+            self.label.clone_from(&other.label);
+        }
+
+        if self.toggled.is_none() {
+            self.toggled = other.toggled;
+        }
+
+        if self.numeric_value.is_none() {
+            self.numeric_value.clone_from(&other.numeric_value);
+        }
+
+        for action in &other.actions {
+            if !self.actions.contains(action) {
+                self.actions.push(*action);
+            }
+        }
+    }
+}
+
+/// Activation handler that builds the initial tree on the next update.
+pub(crate) struct InitialTreeHandler;
+
+impl ActivationHandler for InitialTreeHandler {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        None
+    }
+}
+
+/// Action handler that forwards requests to the event loop.
+pub(crate) struct ActionForwarder {
+    pub(crate) tx: mpsc::Sender<ActionRequest>,
+    pub(crate) sender: UpdateSender,
+}
+
+impl ActionHandler for ActionForwarder {
+    fn do_action(&mut self, request: ActionRequest) {
+        if self.tx.send(request).is_ok() {
+            // Wake the event loop to handle this request.
+            self.sender.send_with_priority(Priority::Input, || {});
+        }
+    }
+}
+
+/// Deactivation handler that ignores deactivation.
+pub(crate) struct NoopDeactivationHandler;
+
+impl DeactivationHandler for NoopDeactivationHandler {
+    fn deactivate_accessibility(&mut self) {}
+}
+
+fn access_id(key: NodeId) -> AccessNodeId {
+    AccessNodeId(u64::from(key))
+}
+
+impl WindowContext {
+    /// Build a full accessibility tree update from the layout tree starting at `root`.
+    pub(crate) fn accessibility_update(&self, root: NodeId) -> TreeUpdate {
+        let mut nodes = Vec::new();
+
+        let mut keys = vec![root];
+        while let Some(key) = keys.pop() {
+            let mut children = self.taffy.borrow().children(key).unwrap();
+            children.retain(|child| !self.is_hidden(*child));
+            if key == root {
+                // Layers are shown above the window's content.
+                children.extend(self.layers.roots());
+            }
+
+            let mut node = if key == root {
+                Node::new(Role::Window)
+            } else if let Some(accessibility) = self.accessibility.borrow().get(&key) {
+                let mut node = Node::new(accessibility.role);
+                if let Some(label) = &accessibility.label {
+                    node.set_label(label.as_str());
+                }
+                for action in &accessibility.actions {
+                    node.add_action(*action);
+                }
+                if let Some(is_toggled) = accessibility.toggled {
+                    node.set_toggled(if is_toggled {
+                        Toggled::True
+                    } else {
+                        Toggled::False
+                    });
+                }
+                if let Some((value, range)) = &accessibility.numeric_value {
+                    node.set_numeric_value(*value);
+                    node.set_min_numeric_value(*range.start());
+                    node.set_max_numeric_value(*range.end());
+                }
+                node
+            } else {
+                Node::new(Role::GenericContainer)
+            };
+
+            if self.focus.is_focusable(key) {
+                node.add_action(Action::Focus);
+            }
+
+            // Bounds are in physical pixels.
+            let (pos, _) = self.absolute_position(key);
+            let layout = *self.taffy.borrow().layout(key).unwrap();
+            let scale_factor = self.scale_factor.get();
+            node.set_bounds(Rect::new(
+                pos.x * scale_factor,
+                pos.y * scale_factor,
+                (pos.x + layout.size.width as f64) * scale_factor,
+                (pos.y + layout.size.height as f64) * scale_factor,
+            ));
+
+            node.set_children(
+                children
+                    .iter()
+                    .map(|child_key| access_id(*child_key))
+                    .collect::<Vec<_>>(),
+            );
+
+            nodes.push((access_id(key), node));
+            keys.extend(children);
+        }
+
+        TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(access_id(root))),
+            focus: access_id(self.focus.focused().unwrap_or(root)),
+        }
+    }
+
+    /// Handle an action requested by an assistive technology.
+    pub(crate) fn handle_action(&self, request: ActionRequest) {
+        let key = NodeId::from(request.target.0);
+
+        match request.action {
+            Action::Focus if self.focus.is_focusable(key) => {
+                self.focus.set(Some(key), &self.listeners.borrow());
+            }
+            Action::Click => {
+                let (pos, _) = self.absolute_position(key);
+                let layout = *self.taffy.borrow().layout(key).unwrap();
+                let pos = pos + Vec2::new(layout.size.width as _, layout.size.height as _) / 2.;
+
+                if let Some(listeners) = self.listeners.borrow().get(&key) {
+                    for state in [ElementState::Pressed, ElementState::Released] {
+                        for f in listeners {
+                            f(Event::MouseInput {
+                                button: MouseButton::Left,
+                                state,
+                                pos,
+                            })
+                        }
+                    }
+                }
+            }
+            // Adjustable views handle increments as arrow key presses.
+            Action::Increment | Action::Decrement => {
+                let named_key = if request.action == Action::Increment {
+                    NamedKey::ArrowUp
+                } else {
+                    NamedKey::ArrowDown
+                };
+                let input = KeyboardInput {
+                    key: Key::Named(named_key),
+                    physical_key: PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
+                    state: ElementState::Pressed,
+                    repeat: false,
+                    modifiers: ModifiersState::empty(),
+                };
+
+                if let Some(listeners) = self.listeners.borrow().get(&key) {
+                    for f in listeners {
+                        f(Event::KeyboardInput(input.clone()))
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use super::WindowContext;
+use crate::prelude::*;
+use std::{cell::RefCell, mem, rc::Rc, time::Duration};
+use vello::kurbo::Vec2;
+use web_time::Instant;
+
+/// Animatable value.
+pub trait Animate: Clone + PartialEq + 'static {
+    /// Interpolate between this value and `to` by `t`.
+    ///
+    /// `t` may be outside of `0..=1` for animations that overshoot their target (such as springs).
+    fn interpolate(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Animate for f32 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t as f32
+    }
+}
+
+impl Animate for f64 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animate for Vec2 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self.lerp(*to, t)
+    }
+}
+
+impl Animate for Color {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        let channel = |from: u8, to: u8| {
+            (from as f64 + (to as f64 - from as f64) * t)
+                .round()
+                .clamp(0., 255.) as u8
+        };
+
+        Color::rgba8(
+            channel(self.r, to.r),
+            channel(self.g, to.g),
+            channel(self.b, to.b),
+            channel(self.a, to.a),
+        )
+    }
+}
+
+/// Easing curve for a tween animation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Start slow and speed up.
+    EaseIn,
+    /// Start fast and slow down.
+    EaseOut,
+    /// Start and end slow.
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Apply this easing curve to the linear progress `t`.
+    pub fn ease(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1. - (1. - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+        }
+    }
+}
+
+/// Specification of an animation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationSpec {
+    /// Animation over a fixed duration with an easing curve.
+    Tween {
+        /// Duration of this animation.
+        duration: Duration,
+        /// Easing curve.
+        easing: Easing,
+    },
+    /// Physically-based spring animation.
+    Spring {
+        /// Stiffness of the spring.
+        stiffness: f64,
+        /// Damping of the spring.
+        damping: f64,
+        /// Mass attached to the spring.
+        mass: f64,
+    },
+}
+
+impl AnimationSpec {
+    /// Create a new tween animation with the default easing curve.
+    pub fn tween(duration: Duration) -> Self {
+        AnimationSpec::Tween {
+            duration,
+            easing: Easing::default(),
+        }
+    }
+
+    /// Create a new spring animation from its stiffness and damping.
+    pub fn spring(stiffness: f64, damping: f64) -> Self {
+        AnimationSpec::Spring {
+            stiffness,
+            damping,
+            mass: 1.,
+        }
+    }
+}
+
+impl Default for AnimationSpec {
+    fn default() -> Self {
+        Self::tween(Duration::from_millis(250))
+    }
+}
+
+/// Maximum time step for spring simulations.
+const SPRING_STEP: f64 = 1. / 240.;
+
+/// Distance and velocity from the target below which a spring is considered at rest.
+const SPRING_REST: f64 = 1e-3;
+
+struct AnimationState<T> {
+    from: T,
+    to: T,
+    start: Option<Instant>,
+    last: Option<Instant>,
+    progress: f64,
+    velocity: f64,
+    is_active: bool,
+}
+
+impl<T> AnimationState<T> {
+    /// Advance this animation to `now`, returning `true` if it's still active.
+    fn advance(&mut self, spec: AnimationSpec, now: Instant) -> bool {
+        let start = *self.start.get_or_insert(now);
+        let last = self.last.replace(now).unwrap_or(now);
+
+        match spec {
+            AnimationSpec::Tween { duration, easing } => {
+                let elapsed = now.duration_since(start).as_secs_f64();
+                let t = if duration.is_zero() {
+                    1.
+                } else {
+                    (elapsed / duration.as_secs_f64()).min(1.)
+                };
+
+                self.progress = easing.ease(t);
+                t < 1.
+            }
+            AnimationSpec::Spring {
+                stiffness,
+                damping,
+                mass,
+            } => {
+                let mut dt = now.duration_since(last).as_secs_f64();
+                while dt > 0. {
+                    let step = dt.min(SPRING_STEP);
+                    let force = -stiffness * (self.progress - 1.) - damping * self.velocity;
+                    self.velocity += force / mass * step;
+                    self.progress += self.velocity * step;
+                    dt -= step;
+                }
+
+                if (self.progress - 1.).abs() < SPRING_REST && self.velocity.abs() < SPRING_REST {
+                    self.progress = 1.;
+                    self.velocity = 0.;
+                    false
+                } else {
+                    true
+                }
+            }
+        }
+    }
+}
+
+pub(crate) type AnimationFn = Rc<dyn Fn(Instant) -> bool>;
+
+pub(crate) type FrameFn = Rc<dyn Fn(Duration)>;
+
+impl WindowContext {
+    /// Advance the frame clock to `now`, calling each frame callback and advancing each active animation.
+    pub(crate) fn tick(&self, now: Instant) {
+        let dt = self
+            .last_frame
+            .replace(Some(now))
+            .map(|last| now.saturating_duration_since(last))
+            .unwrap_or_default();
+
+        let frame_fns: Vec<_> = self.frame_fns.borrow().values().cloned().collect();
+        for f in frame_fns {
+            f(dt);
+        }
+
+        self.tick_animations(now);
+
+        // Suspend the clock, so the next frame after idling doesn't include the idle time.
+        if !self.needs_frame() {
+            self.last_frame.set(None);
+        }
+    }
+
+    /// Advance each active animation to `now`.
+    pub(crate) fn tick_animations(&self, now: Instant) {
+        let animations: Vec<_> = self.animations.borrow().values().cloned().collect();
+        for animation in animations {
+            animation(now);
+        }
+    }
+
+    /// Returns `true` if any animation is active.
+    pub(crate) fn is_animating(&self) -> bool {
+        self.active_animations.get() > 0
+    }
+
+    /// Returns `true` if another frame is needed for an active animation or frame callback.
+    pub(crate) fn needs_frame(&self) -> bool {
+        self.is_animating() || !self.frame_fns.borrow().is_empty()
+    }
+
+    /// Get the time to draw the next frame at for the target frame rate `frame_rate`,
+    /// or `None` to draw it as soon as possible.
+    pub(crate) fn next_frame(&self, frame_rate: Option<f64>) -> Option<Instant> {
+        let frame_rate = frame_rate.filter(|frame_rate| *frame_rate > 0.)?;
+        let last = self.last_frame.get()?;
+        Some(last + Duration::from_secs_f64(1. / frame_rate))
+    }
+}
+
+/// Use a frame callback, called with the time elapsed since the last frame each time the window draws a frame.
+///
+/// The window keeps drawing frames while this callback is in use.
+/// Windows without any frame callbacks or active animations stop drawing frames until their content changes.
+///
+/// This must be used inside of a [`Window`](crate::ui::view::Window).
+pub fn use_frame<'a>(cx: ScopeState<'a>, f: impl Fn(Duration) + 'a) {
+    let window_cx = use_context::<WindowContext>(cx).unwrap();
+
+    let f: Rc<dyn Fn(Duration) + 'a> = Rc::new(f);
+
+    // Safety: `f` is removed from `frame_fns` on drop.
+    let f: FrameFn = unsafe { mem::transmute(f) };
+
+    let key = *use_ref(cx, || {
+        // Request a frame to start the clock.
+        window_cx.is_changed.set(true);
+
+        window_cx.frame_fns.borrow_mut().insert(f.clone())
+    });
+    window_cx.frame_fns.borrow_mut()[key] = f;
+
+    use_drop(cx, move || {
+        window_cx.frame_fns.borrow_mut().remove(key);
+    });
+}
+
+/// Use an animated value that moves towards `target` with the given `spec`.
+///
+/// Whenever `target` changes, the value is animated from its current state to the new target.
+/// The window will request redraws while this animation is active.
+///
+/// This must be used inside of a [`Window`](crate::ui::view::Window).
+pub fn use_animated<T: Animate>(cx: ScopeState<'_>, target: T, spec: AnimationSpec) -> Ref<'_, T> {
+    let window_cx = use_context::<WindowContext>(cx).unwrap();
+
+    let value = use_mut(cx, || target.clone());
+
+    let state = use_ref(cx, || {
+        RefCell::new(AnimationState {
+            from: target.clone(),
+            to: target.clone(),
+            start: None,
+            last: None,
+            progress: 1.,
+            velocity: 0.,
+            is_active: false,
+        })
+    });
+
+    let spec_cell = use_ref(cx, || RefCell::new(spec));
+    *spec_cell.borrow_mut() = spec;
+
+    let key = *use_ref(cx, || {
+        let f: Rc<dyn Fn(Instant) -> bool> = Rc::new(move |now| {
+            let mut state = state.borrow_mut();
+            if !state.is_active {
+                return false;
+            }
+
+            let is_active = state.advance(*spec_cell.borrow(), now);
+            let next = state.from.interpolate(&state.to, state.progress);
+            Mut::set(value, next);
+
+            if !is_active {
+                state.is_active = false;
+                window_cx
+                    .active_animations
+                    .set(window_cx.active_animations.get() - 1);
+            }
+
+            is_active
+        });
+
+        // Safety: `f` is removed from `animations` on drop.
+        let f: AnimationFn = unsafe { mem::transmute(f) };
+
+        window_cx.animations.borrow_mut().insert(f)
+    });
+
+    use_drop(cx, move || {
+        window_cx.animations.borrow_mut().remove(key);
+
+        if state.borrow().is_active {
+            window_cx
+                .active_animations
+                .set(window_cx.active_animations.get() - 1);
+        }
+    });
+
+    {
+        let mut state = state.borrow_mut();
+        if target != state.to {
+            state.from = (*value).clone();
+            state.to = target;
+            state.start = None;
+            state.last = None;
+            state.progress = 0.;
+            state.velocity = 0.;
+
+            if !state.is_active {
+                state.is_active = true;
+                window_cx
+                    .active_animations
+                    .set(window_cx.active_animations.get() + 1);
+            }
+        }
+    }
+
+    Mut::as_ref(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animate, AnimationSpec, AnimationState, Easing};
+    use std::time::Duration;
+    use web_time::Instant;
+
+    const EASINGS: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ];
+
+    fn state() -> AnimationState<f64> {
+        AnimationState {
+            from: 0.,
+            to: 1.,
+            start: None,
+            last: None,
+            progress: 0.,
+            velocity: 0.,
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn it_eases_between_endpoints() {
+        for easing in EASINGS {
+            assert_eq!(easing.ease(0.), 0., "{easing:?}");
+            assert_eq!(easing.ease(1.), 1., "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn it_eases_monotonically() {
+        for easing in EASINGS {
+            let mut last = easing.ease(0.);
+            for i in 1..=100 {
+                let t = easing.ease(i as f64 / 100.);
+                assert!(t >= last, "{easing:?} decreased at {i}%");
+                last = t;
+            }
+        }
+    }
+
+    #[test]
+    fn it_finishes_tweens_after_their_duration() {
+        let spec = AnimationSpec::Tween {
+            duration: Duration::from_millis(100),
+            easing: Easing::Linear,
+        };
+        let mut state = state();
+        let start = Instant::now();
+
+        assert!(state.advance(spec, start));
+        assert_eq!(state.progress, 0.);
+
+        assert!(state.advance(spec, start + Duration::from_millis(50)));
+        assert!((state.progress - 0.5).abs() < 1e-9);
+
+        assert!(!state.advance(spec, start + Duration::from_millis(150)));
+        assert_eq!(state.progress, 1.);
+    }
+
+    #[test]
+    fn it_settles_springs_at_the_target() {
+        let spec = AnimationSpec::spring(170., 26.);
+        let mut state = state();
+        let start = Instant::now();
+
+        let mut frames = 0;
+        while state.advance(spec, start + Duration::from_millis(16) * frames) {
+            frames += 1;
+            assert!(frames < 600, "spring did not settle");
+        }
+
+        assert_eq!(state.progress, 1.);
+        assert_eq!(state.velocity, 0.);
+        assert_eq!(state.from.interpolate(&state.to, state.progress), 1.);
+    }
+
+    #[test]
+    fn it_overshoots_underdamped_springs() {
+        let spec = AnimationSpec::spring(300., 5.);
+        let mut state = state();
+        let start = Instant::now();
+
+        let mut max = 0f64;
+        for frame in 0..60 {
+            state.advance(spec, start + Duration::from_millis(16) * frame);
+            max = max.max(state.progress);
+        }
+
+        assert!(max > 1.);
+    }
+}
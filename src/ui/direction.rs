@@ -0,0 +1,72 @@
+use crate::prelude::*;
+use parley::Alignment;
+use taffy::FlexDirection;
+
+/// Layout direction of text and rows.
+///
+/// This can be provided to a tree of composables with [`use_provider`],
+/// or from the locale of an `i18n::Localized` composable, and read with [`use_direction`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Data)]
+pub enum Direction {
+    /// Left to right.
+    #[default]
+    Ltr,
+    /// Right to left.
+    ///
+    /// Text is aligned to the right edge of its view, and the children of flex rows are laid out from the right.
+    Rtl,
+}
+
+impl Direction {
+    /// Alignment of lines of text in this direction.
+    pub(crate) fn text_alignment(self) -> Alignment {
+        match self {
+            Direction::Ltr => Alignment::Start,
+            Direction::Rtl => Alignment::End,
+        }
+    }
+
+    /// Mirror a flex direction for this direction.
+    ///
+    /// Taffy has no support for layout direction, so rows are reversed instead.
+    pub(crate) fn flex_direction(self, flex_direction: FlexDirection) -> FlexDirection {
+        match (self, flex_direction) {
+            (Direction::Rtl, FlexDirection::Row) => FlexDirection::RowReverse,
+            (Direction::Rtl, FlexDirection::RowReverse) => FlexDirection::Row,
+            (_, flex_direction) => flex_direction,
+        }
+    }
+}
+
+/// Use the current layout [`Direction`] (defaults to [`Direction::Ltr`]).
+pub fn use_direction(cx: ScopeState<'_>) -> Direction {
+    use_context_opt::<Direction>(cx).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Direction;
+    use crate::{prelude::*, ui::testing::TestWindow};
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_mirrors_rows_in_rtl_layouts() {
+        #[derive(Data)]
+        struct App;
+
+        impl Compose for App {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                use_provider(&cx, || Direction::Rtl);
+
+                Flex::row((Text::new("First"), Text::new("Second")))
+            }
+        }
+
+        let mut window = TestWindow::new(App);
+        window.frame();
+
+        let first = window.find_by_label("First").unwrap();
+        let second = window.find_by_label("Second").unwrap();
+        assert!(window.position(first).x > window.position(second).x);
+    }
+}
@@ -0,0 +1,163 @@
+use super::{Event, WindowContext};
+use std::{any::Any, cell::RefCell, collections::HashMap, fmt, rc::Rc};
+use taffy::NodeId;
+
+/// Payload of a drag-and-drop operation.
+///
+/// This is set on a view with [`View::draggable`](crate::ui::view::View::draggable),
+/// and sent to the accepting drop target in [`Event::Drop`].
+#[derive(Clone)]
+pub struct DragPayload {
+    value: Rc<dyn Any>,
+}
+
+impl DragPayload {
+    /// Create a new drag payload from its value.
+    pub fn new(value: impl Any) -> Self {
+        Self {
+            value: Rc::new(value),
+        }
+    }
+
+    /// Returns `true` if this payload contains a value of type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.value.is::<T>()
+    }
+
+    /// Get a reference to the value of this payload, if it's of type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragPayload").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for DragPayload {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+pub(crate) type AcceptsFn = Rc<dyn Fn(&DragPayload) -> bool>;
+
+/// Current drag-and-drop operation.
+struct Drag {
+    payload: DragPayload,
+    /// Drop target currently under the cursor.
+    target: Option<NodeId>,
+}
+
+/// Drag-and-drop manager for a window.
+#[derive(Default)]
+pub(crate) struct DragManager {
+    /// Payload of each draggable node.
+    pub(crate) payloads: RefCell<HashMap<NodeId, Rc<RefCell<DragPayload>>>>,
+
+    /// Functions to check if a drop target accepts a payload.
+    pub(crate) drop_targets: RefCell<HashMap<NodeId, Vec<AcceptsFn>>>,
+
+    drag: RefCell<Option<Drag>>,
+}
+
+impl DragManager {
+    /// Remove `key` from this manager.
+    pub(crate) fn remove(&self, key: NodeId) {
+        self.payloads.borrow_mut().remove(&key);
+        self.drop_targets.borrow_mut().remove(&key);
+
+        if let Some(drag) = &mut *self.drag.borrow_mut() {
+            if drag.target == Some(key) {
+                drag.target = None;
+            }
+        }
+    }
+}
+
+impl WindowContext {
+    /// Start a drag-and-drop operation if `key`, or its nearest draggable ancestor, has a payload.
+    pub(crate) fn drag_start(&self, key: NodeId) {
+        let payload = {
+            let taffy = self.taffy.borrow();
+            let payloads = self.drag.payloads.borrow();
+
+            let mut next = Some(key);
+            let mut payload = None;
+            while let Some(key) = next {
+                payload = payloads.get(&key).map(|payload| payload.borrow().clone());
+                if payload.is_some() {
+                    break;
+                }
+                next = taffy.parent(key);
+            }
+            payload
+        };
+
+        *self.drag.drag.borrow_mut() = payload.map(|payload| Drag {
+            payload,
+            target: None,
+        });
+    }
+
+    /// Move the current drag over `hit`, sending drag enter and leave events to the accepting drop targets.
+    pub(crate) fn drag_over(&self, hit: Option<NodeId>) {
+        let Some((payload, last_target)) = self
+            .drag
+            .drag
+            .borrow()
+            .as_ref()
+            .map(|drag| (drag.payload.clone(), drag.target))
+        else {
+            return;
+        };
+
+        let target = self.drop_target(hit, &payload);
+        if target == last_target {
+            return;
+        }
+
+        if let Some(drag) = &mut *self.drag.drag.borrow_mut() {
+            drag.target = target;
+        }
+
+        if let Some(last_target) = last_target {
+            self.send(last_target, Event::DragLeave);
+        }
+        if let Some(target) = target {
+            self.send(target, Event::DragEnter);
+        }
+    }
+
+    /// End the current drag, dropping its payload on the drop target under the cursor.
+    pub(crate) fn drag_end(&self) {
+        let Some(drag) = self.drag.drag.take() else {
+            return;
+        };
+
+        if let Some(target) = drag.target {
+            self.send(target, Event::DragLeave);
+            self.send(target, Event::Drop(drag.payload));
+        }
+    }
+
+    /// Find the nearest drop target to `hit` (or `hit` itself) accepting `payload`.
+    fn drop_target(&self, hit: Option<NodeId>, payload: &DragPayload) -> Option<NodeId> {
+        let taffy = self.taffy.borrow();
+        let drop_targets = self.drag.drop_targets.borrow();
+
+        let mut next = hit;
+        while let Some(key) = next {
+            let accepts = drop_targets
+                .get(&key)
+                .is_some_and(|fns| fns.iter().any(|f| f(payload)));
+            if accepts {
+                return Some(key);
+            }
+            next = taffy.parent(key);
+        }
+        None
+    }
+}
@@ -1,39 +1,120 @@
 use crate::prelude::*;
-use parley::Rect;
-use peniko::Fill;
+use peniko::Brush;
 use taffy::Layout;
-use vello::{kurbo::Affine, Scene};
+use vello::kurbo::{Affine, Rect, Stroke, Vec2};
 
 /// Drawable modifiers.
 pub trait Draw {
-    /// Pre-process the scene, this is run before a composable in rendered.
-    fn pre_process(&self, layout: &Layout, scene: &mut Scene) {
+    /// Pre-process the renderer, this is run before a composable in rendered.
+    fn pre_process(&self, layout: &Layout, renderer: &mut dyn Renderer) {
         let _ = layout;
-        let _ = scene;
+        let _ = renderer;
     }
 
-    /// Post-process the scene, this is run after a composable in rendered.
-    fn post_process(&self, layout: &Layout, scene: &mut Scene) {
+    /// Post-process the renderer, this is run after a composable in rendered.
+    fn post_process(&self, layout: &Layout, renderer: &mut dyn Renderer) {
         let _ = layout;
-        let _ = scene;
+        let _ = renderer;
     }
 }
 
-/// Background color modifier.
+/// Background modifier.
+///
+/// The background is filled with a [`Brush`], such as a solid color, a gradient, or an image pattern,
+/// positioned relative to the view's origin.
 #[derive(Data)]
-pub struct BackgroundColor {
-    /// Background color.
-    pub color: Color,
+pub struct Background {
+    /// Background brush.
+    pub brush: Brush,
 }
 
-impl Draw for BackgroundColor {
-    fn pre_process(&self, layout: &Layout, scene: &mut Scene) {
-        scene.fill(
-            Fill::NonZero,
+impl Draw for Background {
+    fn pre_process(&self, layout: &Layout, renderer: &mut dyn Renderer) {
+        renderer.fill(
             Affine::default(),
-            self.color,
-            None,
-            &Rect::new(0., 0., layout.size.width as _, layout.size.height as _),
+            (&self.brush).into(),
+            Rect::new(0., 0., layout.size.width as _, layout.size.height as _).into(),
         );
     }
 }
+
+/// Border drawn around a view.
+#[derive(Clone, Copy, Debug, PartialEq, Data)]
+pub struct Border {
+    /// Border width.
+    pub width: f64,
+    /// Border color.
+    pub color: Color,
+}
+
+/// Shadow drawn below a view.
+#[derive(Clone, Copy, Debug, PartialEq, Data)]
+pub struct Shadow {
+    /// Offset of the shadow from the view.
+    pub offset: Vec2,
+    /// Blur radius (standard deviation) of the shadow.
+    pub blur: f64,
+    /// Shadow color.
+    pub color: Color,
+}
+
+/// Decorations drawn around the layout rect of a view.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Decoration {
+    pub(crate) corner_radius: f64,
+    pub(crate) border: Option<Border>,
+    pub(crate) shadows: Vec<Shadow>,
+}
+
+impl Decoration {
+    /// Returns `true` if this decoration draws nothing.
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn rect(layout: &Layout) -> Rect {
+        Rect::new(0., 0., layout.size.width as _, layout.size.height as _)
+    }
+
+    /// Draw the shadows of this decoration, and clip the rest of the view to its rounded corners.
+    pub(crate) fn pre_process(&self, layout: &Layout, renderer: &mut dyn Renderer) {
+        let rect = Self::rect(layout);
+
+        for shadow in &self.shadows {
+            renderer.draw_blurred_rect(
+                Affine::translate(shadow.offset),
+                rect,
+                shadow.color,
+                self.corner_radius,
+                shadow.blur,
+            );
+        }
+
+        if self.corner_radius > 0. {
+            renderer.push_clip(
+                Affine::IDENTITY,
+                rect.to_rounded_rect(self.corner_radius).into(),
+            );
+        }
+    }
+
+    /// Draw the border of this decoration above the rest of the view.
+    pub(crate) fn post_process(&self, layout: &Layout, renderer: &mut dyn Renderer) {
+        if self.corner_radius > 0. {
+            renderer.pop_clip();
+        }
+
+        if let Some(border) = self.border {
+            // Strokes are centered on their path, so inset it to keep the border inside the view.
+            let inset = border.width / 2.;
+            let rect = Self::rect(layout).inset(-inset);
+            renderer.stroke(
+                &Stroke::new(border.width),
+                Affine::IDENTITY,
+                border.color.into(),
+                rect.to_rounded_rect((self.corner_radius - inset).max(0.))
+                    .into(),
+            );
+        }
+    }
+}
@@ -0,0 +1,487 @@
+use super::{
+    create_renderer, fallback_render_context,
+    view::{text::FontContext, window::hit_test},
+    Event, KeyboardInput, LayoutContext, RenderRoot, WindowContext,
+};
+use crate::{
+    composer::{Composer, Update, Updater},
+    event_loop,
+    prelude::*,
+};
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{mpsc, Arc, Mutex},
+};
+use vello::{
+    kurbo::{Affine, Rect, Vec2},
+    peniko::Fill,
+    util::{RenderContext, RenderSurface},
+    wgpu::{self, PresentMode},
+    AaConfig, AaSupport, RenderParams, Scene,
+};
+use web_time::Instant;
+use winit::{
+    event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent},
+    keyboard::{Key, NamedKey},
+};
+
+/// Distance to scroll for each line of a mouse wheel.
+const LINE_HEIGHT: f64 = 20.;
+
+type UpdateFn = Arc<Mutex<Option<Box<dyn Fn() + Send + Sync>>>>;
+
+/// Updater that queues updates until the next frame of an [`Embed`].
+struct EmbedUpdater {
+    tx: mpsc::Sender<Update>,
+    on_update: UpdateFn,
+}
+
+impl Updater for EmbedUpdater {
+    fn update(&self, update: Update) {
+        if self.tx.send(update).is_ok() {
+            if let Some(f) = &*self.on_update.lock().unwrap() {
+                f();
+            }
+        }
+    }
+}
+
+/// Pointers to the state of composed [`Embed`] content.
+#[derive(Clone, Copy)]
+struct Handle {
+    window_cx: *const WindowContext,
+    root_key: NodeId,
+}
+
+#[derive(Data)]
+struct EmbedRoot<C> {
+    content: C,
+    handle: Rc<Cell<Option<Handle>>>,
+}
+
+impl<C: Compose> Compose for EmbedRoot<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let font_cx = use_context::<FontContext>(&cx).unwrap();
+
+        let mut root_key_cell = None;
+        let window_cx = use_provider(&cx, || {
+            let (window_cx, root_key) = WindowContext::new(font_cx.clone());
+            root_key_cell = Some(root_key);
+            window_cx
+        });
+
+        let theme = use_theme(&cx);
+        window_cx.base_color.set(theme.colors.background);
+
+        let layout_cx = use_provider(&cx, || LayoutContext::new(root_key_cell.unwrap()));
+
+        cx.me().handle.set(Some(Handle {
+            window_cx: &**window_cx,
+            root_key: layout_cx.parent_id,
+        }));
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Attach content to a window owned by a host application (such as a game engine editor or an existing winit app),
+/// drawing to a surface of the given physical size.
+///
+/// The host owns the event loop, forwarding events with [`Embed::handle_event`]
+/// and drawing frames with [`Embed::frame`].
+///
+/// ```no_run
+/// use actuate::{prelude::*, ui::embed};
+/// # fn f(window: std::sync::Arc<winit::window::Window>) {
+///
+/// let size = window.inner_size();
+/// let mut embed = embed::attach(window.clone(), size.width, size.height, Text::new("Hello World!"))
+///     .unwrap()
+///     .on_update(move || window.request_redraw());
+///
+/// // In the host's event loop:
+/// # let event = winit::event::WindowEvent::RedrawRequested;
+/// embed.handle_event(&event);
+/// if let winit::event::WindowEvent::RedrawRequested = event {
+///     embed.frame();
+/// }
+/// # }
+/// ```
+pub fn attach(
+    window: impl wgpu::WindowHandle + Clone + 'static,
+    width: u32,
+    height: u32,
+    content: impl Compose + 'static,
+) -> Result<Embed, vello::Error> {
+    let create = |render_cx: &mut RenderContext| {
+        pollster::block_on(render_cx.create_surface(
+            window.clone(),
+            width,
+            height,
+            PresentMode::AutoVsync,
+        ))
+    };
+
+    let mut render_cx = RenderContext::new();
+    let surface = match create(&mut render_cx) {
+        Ok(surface) => surface,
+        Err(_error) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Failed to create render surface ({_error}), trying fallback backends");
+
+            render_cx = fallback_render_context();
+            create(&mut render_cx)?
+        }
+    };
+
+    let renderer = create_renderer(
+        &render_cx.devices[surface.dev_id].device,
+        Some(surface.format),
+        AaSupport::all(),
+    )?;
+
+    let handle = Rc::new(Cell::new(None));
+    let (tx, rx) = mpsc::channel();
+    let on_update = UpdateFn::default();
+
+    Ok(Embed {
+        composer: Composer::with_updater(
+            RenderRoot {
+                content: EmbedRoot {
+                    content,
+                    handle: handle.clone(),
+                },
+            },
+            EmbedUpdater {
+                tx,
+                on_update: on_update.clone(),
+            },
+        ),
+        handle,
+        updates: rx,
+        on_update,
+        render_cx,
+        surface,
+        renderer,
+        content_scene: Scene::new(),
+        scale_factor: 1.,
+        cursor_pos: Vec2::ZERO,
+        target: None,
+        scroll_drag: None,
+        primary_touch: Cell::new(None),
+    })
+}
+
+/// Content embedded in a window owned by a host application.
+///
+/// This can be created with [`attach`].
+pub struct Embed {
+    composer: Composer,
+    handle: Rc<Cell<Option<Handle>>>,
+    updates: mpsc::Receiver<Update>,
+    on_update: UpdateFn,
+    render_cx: RenderContext,
+    surface: RenderSurface<'static>,
+    renderer: vello::Renderer,
+    content_scene: Scene,
+    scale_factor: f64,
+    cursor_pos: Vec2,
+    target: Option<NodeId>,
+    scroll_drag: Option<(NodeId, Vec2)>,
+    primary_touch: Cell<Option<u64>>,
+}
+
+impl Embed {
+    /// Set a function to call when the content is updated, such as to request a redraw from the host.
+    ///
+    /// This may be called from any thread.
+    pub fn on_update(self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        *self.on_update.lock().unwrap() = Some(Box::new(f));
+        self
+    }
+
+    fn window_cx(&self) -> Option<&WindowContext> {
+        // Safety: The window context is owned by the composer, which lives as long as `self`.
+        self.handle
+            .get()
+            .map(|handle| unsafe { &*handle.window_cx })
+    }
+
+    /// Get the scale factor from logical to physical pixels (default: `1.`).
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Set the scale factor from logical to physical pixels.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        if let Some(window_cx) = self.window_cx() {
+            window_cx.scale_factor.set(scale_factor);
+            window_cx.is_changed.set(true);
+        }
+    }
+
+    /// Resize the surface to a new physical size.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        // Surfaces can't be configured with a zero size, such as while the window is minimized.
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        self.render_cx
+            .resize_surface(&mut self.surface, width, height);
+        if let Some(window_cx) = self.window_cx() {
+            window_cx.is_changed.set(true);
+        }
+    }
+
+    /// Handle an event from the host's window.
+    ///
+    /// Pointer, keyboard, input method, and file drop events are sent to the content,
+    /// and resize and scale factor events update the surface.
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) => self.resize(size.width, size.height),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.set_scale_factor(*scale_factor)
+            }
+            WindowEvent::Touch(touch) => {
+                for event in event_loop::touch_to_mouse(touch, &self.primary_touch) {
+                    self.handle_event(&event);
+                }
+            }
+            event => {
+                // Events are ignored until the content is composed with the first frame.
+                let Some(handle) = self.handle.get() else {
+                    return;
+                };
+
+                // Safety: The window context is owned by the composer, which lives as long as `self`.
+                let window_cx = unsafe { &*handle.window_cx };
+
+                let _rt = self.composer.runtime().enter();
+                self.handle_content_event(window_cx, &LayoutContext::new(handle.root_key), event);
+            }
+        }
+    }
+
+    fn handle_content_event(
+        &mut self,
+        window_cx: &WindowContext,
+        layout_cx: &LayoutContext,
+        event: &WindowEvent,
+    ) {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                let pos = Vec2::new(position.x, position.y) / self.scale_factor;
+                self.cursor_pos = pos;
+
+                if let Some((key, last_pos)) = self.scroll_drag {
+                    window_cx.scroll_by(key, last_pos - pos);
+                    self.scroll_drag = Some((key, pos));
+                }
+
+                let hit = hit_test(window_cx, pos, layout_cx);
+                if let Some(key) = hit {
+                    if let Some(last_key) = self.target.replace(key) {
+                        if last_key != key {
+                            window_cx.send(last_key, Event::MouseOut);
+                            window_cx.send(key, Event::MouseIn);
+                        }
+                    } else {
+                        window_cx.send(key, Event::MouseIn);
+                    }
+                }
+
+                // Move events are only sent to the captured view while the pointer is captured.
+                if !window_cx.pointer_move(pos, hit) {
+                    if let Some(key) = hit {
+                        window_cx.send(key, Event::MouseMove { pos });
+                    }
+                }
+            }
+            WindowEvent::MouseInput { button, state, .. } => {
+                let pos = self.cursor_pos;
+                let target = hit_test(window_cx, pos, layout_cx);
+
+                if *button == MouseButton::Left {
+                    self.scroll_drag = match state {
+                        ElementState::Pressed => {
+                            window_cx.scroll_drag_target(target).map(|key| (key, pos))
+                        }
+                        ElementState::Released => None,
+                    };
+                }
+
+                if *state == ElementState::Pressed {
+                    window_cx.dismiss_outside(target);
+
+                    if let Some(key) = target {
+                        let focus_key = Some(key).filter(|key| window_cx.focus.is_focusable(*key));
+                        window_cx
+                            .focus
+                            .set(focus_key, &window_cx.listeners.borrow());
+                    }
+                }
+
+                window_cx.pointer_input(target, *button, *state, pos, Instant::now());
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => Vec2::new(*x as _, *y as _) * LINE_HEIGHT,
+                    MouseScrollDelta::PixelDelta(pos) => {
+                        Vec2::new(pos.x, pos.y) / self.scale_factor
+                    }
+                };
+
+                window_cx.dismiss_tooltips();
+
+                if let Some(key) = hit_test(window_cx, self.cursor_pos, layout_cx) {
+                    window_cx.scroll_by(key, -delta);
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                window_cx.modifiers.set(modifiers.state());
+            }
+            WindowEvent::KeyboardInput { event, .. } => {
+                if event.state == ElementState::Pressed
+                    && event.logical_key == Key::Named(NamedKey::Tab)
+                {
+                    window_cx.focus.traverse(
+                        &window_cx.taffy.borrow(),
+                        &window_cx.focus_roots(layout_cx.parent_id),
+                        window_cx.modifiers.get().shift_key(),
+                        &window_cx.listeners.borrow(),
+                    );
+                } else if event.state == ElementState::Pressed
+                    && event.logical_key == Key::Named(NamedKey::Escape)
+                    && window_cx.dismiss_top_layer()
+                {
+                    // Escape dismisses the topmost layer instead of being sent to the focused view.
+                } else if event.state == ElementState::Pressed
+                    && window_cx.handle_shortcut(&event.logical_key)
+                {
+                    // Keys pressed for a shortcut are not sent to the focused view.
+                } else if let Some(key) = window_cx.focus.focused() {
+                    window_cx.send(
+                        key,
+                        Event::KeyboardInput(KeyboardInput {
+                            key: event.logical_key.clone(),
+                            physical_key: event.physical_key,
+                            state: event.state,
+                            repeat: event.repeat,
+                            modifiers: window_cx.modifiers.get(),
+                        }),
+                    );
+                }
+            }
+            WindowEvent::DroppedFile(path) => {
+                if let Some(key) = hit_test(window_cx, self.cursor_pos, layout_cx) {
+                    window_cx.send(key, Event::FileDrop(path.clone()));
+                }
+            }
+            WindowEvent::Ime(ime) => window_cx.send_ime(ime.clone()),
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if another frame should be drawn, such as while any animation is active.
+    pub fn needs_frame(&self) -> bool {
+        self.window_cx()
+            .is_some_and(|window_cx| window_cx.is_changed.get() || window_cx.needs_frame())
+    }
+
+    /// Apply each queued update, then lay out and draw a new frame to the surface.
+    pub fn frame(&mut self) {
+        {
+            let _rt = self.composer.runtime().enter();
+            while let Ok(update) = self.updates.try_recv() {
+                update.apply();
+            }
+        }
+        self.composer.compose();
+
+        let Some(handle) = self.handle.get() else {
+            return;
+        };
+        let root = handle.root_key;
+
+        // Safety: The window context is owned by the composer, which lives as long as `self`.
+        let window_cx = unsafe { &*handle.window_cx };
+
+        let config = &self.surface.config;
+        window_cx.scale_factor.set(self.scale_factor);
+        window_cx
+            .set_size(Vec2::new(config.width as f64, config.height as f64) / self.scale_factor);
+        window_cx.focus.apply_pending(&window_cx.listeners.borrow());
+        window_cx.update_scrolls();
+
+        if window_cx.is_layout_changed.take() {
+            window_cx.compute_layout(root);
+        }
+        window_cx.update_layers();
+
+        if window_cx.is_changed.take() {
+            window_cx.update_fragments();
+        }
+
+        window_cx.tick(Instant::now());
+
+        // Compose again to apply any changes from the layout, canvases, or animations.
+        self.composer.compose();
+
+        let texture = match self.surface.surface.get_current_texture() {
+            Ok(texture) => texture,
+            Err(_error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Failed to get surface texture: {_error}");
+
+                // The surface may be outdated after the host's window changed, so it's configured again.
+                let (width, height) = (config.width, config.height);
+                self.render_cx
+                    .resize_surface(&mut self.surface, width, height);
+                return;
+            }
+        };
+
+        // Build the scene from the recorded fragment of each canvas, in logical pixels.
+        let size = window_cx.size.get();
+        self.content_scene.reset();
+        self.content_scene.fill(
+            Fill::NonZero,
+            Affine::default(),
+            window_cx.base_color.get(),
+            None,
+            &Rect::new(0., 0., size.x, size.y),
+        );
+        window_cx.build_scene(&mut self.content_scene, root);
+        window_cx.build_layers(&mut self.content_scene);
+
+        // Scale the scene to physical pixels.
+        let mut scene = window_cx.scene.borrow_mut();
+        scene.reset();
+        scene.append(&self.content_scene, Some(Affine::scale(self.scale_factor)));
+
+        let device_handle = &self.render_cx.devices[self.surface.dev_id];
+        let result = self.renderer.render_to_surface(
+            &device_handle.device,
+            &device_handle.queue,
+            &scene,
+            &texture,
+            &RenderParams {
+                base_color: window_cx.base_color.get(),
+                width: self.surface.config.width,
+                height: self.surface.config.height,
+                antialiasing_method: AaConfig::Msaa16,
+            },
+        );
+        if let Err(_error) = result {
+            #[cfg(feature = "tracing")]
+            tracing::error!("Failed to render embedded content: {_error}");
+        }
+
+        texture.present();
+        device_handle.device.poll(wgpu::Maintain::Poll);
+    }
+}
@@ -0,0 +1,239 @@
+use super::{Event, LayoutTree, ListenerFn, WindowContext};
+use crate::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+use taffy::NodeId;
+
+type Listeners = HashMap<NodeId, Vec<ListenerFn>>;
+
+/// Focus manager for a window.
+#[derive(Default)]
+pub(crate) struct FocusManager {
+    /// Currently focused node.
+    focused: Cell<Option<NodeId>>,
+
+    /// Focus request to apply after the current event.
+    pending: Cell<Option<Option<NodeId>>>,
+
+    /// Nodes that can receive focus.
+    pub(crate) focusable: RefCell<HashSet<NodeId>>,
+}
+
+impl FocusManager {
+    /// Get the currently focused node.
+    pub(crate) fn focused(&self) -> Option<NodeId> {
+        self.focused.get()
+    }
+
+    /// Returns `true` if `key` can receive focus.
+    pub(crate) fn is_focusable(&self, key: NodeId) -> bool {
+        self.focusable.borrow().contains(&key)
+    }
+
+    /// Request focus for `key`, or clear the focus if `None`.
+    ///
+    /// This request is applied with [`FocusManager::apply_pending`].
+    pub(crate) fn request(&self, key: Option<NodeId>) {
+        self.pending.set(Some(key));
+    }
+
+    /// Apply the last focus request, if any.
+    pub(crate) fn apply_pending(&self, listeners: &Listeners) {
+        if let Some(key) = self.pending.take() {
+            self.set(key, listeners);
+        }
+    }
+
+    /// Focus `key`, sending blur and focus events to the affected listeners.
+    pub(crate) fn set(&self, key: Option<NodeId>, listeners: &Listeners) {
+        let last = self.focused.replace(key);
+        if last == key {
+            return;
+        }
+
+        if let Some(last_listeners) = last.and_then(|last| listeners.get(&last)) {
+            for f in last_listeners {
+                f(Event::Blur)
+            }
+        }
+
+        if let Some(key_listeners) = key.and_then(|key| listeners.get(&key)) {
+            for f in key_listeners {
+                f(Event::Focus)
+            }
+        }
+    }
+
+    /// Move the focus to the next (or previous if `reverse` is `true`) focusable node
+    /// in the layout order of the trees starting at `roots`.
+    pub(crate) fn traverse(
+        &self,
+        taffy: &LayoutTree,
+        roots: &[NodeId],
+        reverse: bool,
+        listeners: &Listeners,
+    ) {
+        let order = {
+            let focusable = self.focusable.borrow();
+            let mut order = Vec::new();
+            let mut keys: Vec<_> = roots.iter().rev().copied().collect();
+            while let Some(key) = keys.pop() {
+                // Hidden views can't receive focus.
+                if taffy.style(key).unwrap().display == Display::None {
+                    continue;
+                }
+
+                if focusable.contains(&key) {
+                    order.push(key);
+                }
+
+                keys.extend(taffy.children(key).unwrap().into_iter().rev());
+            }
+            order
+        };
+
+        if order.is_empty() {
+            return;
+        }
+
+        let len = order.len();
+        let idx = self
+            .focused
+            .get()
+            .and_then(|focused| order.iter().position(|key| *key == focused));
+        let next_idx = match (idx, reverse) {
+            (Some(idx), false) => (idx + 1) % len,
+            (Some(idx), true) => (idx + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+
+        self.set(Some(order[next_idx]), listeners);
+    }
+
+    /// Remove `key` from this manager, clearing its focus without sending a blur event.
+    pub(crate) fn remove(&self, key: NodeId) {
+        self.focusable.borrow_mut().remove(&key);
+
+        if self.focused.get() == Some(key) {
+            self.focused.set(None);
+        }
+
+        if self.pending.get() == Some(Some(key)) {
+            self.pending.set(None);
+        }
+    }
+}
+
+/// Handle to the focus of a view.
+///
+/// This can be created with [`use_focus`] and attached to a view with
+/// [`View::focusable`](crate::ui::view::View::focusable).
+#[derive(Clone)]
+pub struct FocusHandle {
+    pub(crate) key: Rc<Cell<Option<NodeId>>>,
+    manager: Rc<FocusManager>,
+}
+
+impl FocusHandle {
+    /// Request focus for the attached view.
+    pub fn focus(&self) {
+        if let Some(key) = self.key.get() {
+            self.manager.request(Some(key));
+        }
+    }
+
+    /// Remove focus from the attached view, if it's currently focused.
+    pub fn blur(&self) {
+        if self.is_focused() {
+            self.manager.request(None);
+        }
+    }
+
+    /// Returns `true` if the attached view is currently focused.
+    pub fn is_focused(&self) -> bool {
+        self.key.get().is_some() && self.key.get() == self.manager.focused()
+    }
+}
+
+/// Use a focus handle to programmatically focus a view.
+///
+/// The returned handle must be attached to a view with [`View::focusable`](crate::ui::view::View::focusable).
+pub fn use_focus(cx: ScopeState<'_>) -> &FocusHandle {
+    let window_cx = use_context::<WindowContext>(cx).unwrap();
+
+    use_ref(cx, || FocusHandle {
+        key: Rc::default(),
+        manager: window_cx.focus.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::RefCell, rc::Rc};
+    use winit::keyboard::{Key, NamedKey};
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_skips_pointer_only_views_when_traversing_focus() {
+        let mut window = TestWindow::new((
+            Text::new("Clickable").on_click(|| {}),
+            Text::new("Keyboard").on_key_down(|_| {}),
+        ));
+        window.frame();
+
+        window.key_press(Key::Named(NamedKey::Tab));
+        assert_eq!(window.focused(), window.find_by_label("Keyboard"));
+
+        window.key_press(Key::Named(NamedKey::Tab));
+        assert_eq!(window.focused(), window.find_by_label("Keyboard"));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_sends_focus_changes_and_keys_to_the_focused_view() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+
+        let view = |label: &'static str| {
+            let (focus, blur, key_down) = (events.clone(), events.clone(), events.clone());
+            Text::new(label)
+                .on_focus(move || focus.borrow_mut().push(format!("focus {label}")))
+                .on_blur(move || blur.borrow_mut().push(format!("blur {label}")))
+                .on_key_down(move |input| {
+                    key_down
+                        .borrow_mut()
+                        .push(format!("{:?} {label}", input.key))
+                })
+        };
+        let mut window = TestWindow::new((view("A"), view("B")));
+        window.frame();
+
+        // Keys are dropped while no view is focused.
+        window.key_press(Key::Character("x".into()));
+        window.key_press(Key::Named(NamedKey::Tab));
+        window.key_press(Key::Character("y".into()));
+        window.key_press(Key::Named(NamedKey::Tab));
+        window.key_press(Key::Character("z".into()));
+
+        // Clicking a focusable view focuses it.
+        window.click_node(window.find_by_label("A").unwrap());
+        assert_eq!(window.focused(), window.find_by_label("A"));
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                "focus A",
+                "Character(\"y\") A",
+                "blur A",
+                "focus B",
+                "Character(\"z\") B",
+                "blur B",
+                "focus A",
+            ]
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use super::{Event, WindowContext};
+use crate::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+use taffy::NodeId;
+use vello::kurbo::Rect;
+use winit::{
+    dpi::{LogicalPosition, LogicalSize},
+    event::Ime,
+    window::Window as RawWindow,
+};
+
+/// Input method (IME) manager for a window.
+#[derive(Default)]
+pub(crate) struct ImeManager {
+    /// Cursor area of each node accepting text input, relative to the node.
+    pub(crate) inputs: RefCell<HashMap<NodeId, Rc<Cell<Rect>>>>,
+
+    /// Absolute cursor area last applied to the window, or `None` if the IME is disabled.
+    applied: Cell<Option<Rect>>,
+}
+
+impl ImeManager {
+    /// Remove `key` from this manager.
+    pub(crate) fn remove(&self, key: NodeId) {
+        self.inputs.borrow_mut().remove(&key);
+    }
+}
+
+impl WindowContext {
+    /// Get the absolute cursor area of the focused node, if it accepts text input.
+    pub(crate) fn ime_cursor_area(&self) -> Option<Rect> {
+        let key = self.focus.focused()?;
+        let area = self.ime.inputs.borrow().get(&key)?.get();
+
+        let (pos, _) = self.absolute_position(key);
+        Some(area + pos)
+    }
+
+    /// Send an IME event to the focused node.
+    pub(crate) fn send_ime(&self, ime: Ime) {
+        if let Some(key) = self.focus.focused() {
+            self.send(key, Event::Ime(ime));
+        }
+    }
+
+    /// Enable the IME of `window` while the focused node accepts text input,
+    /// placing the candidate window at its cursor area.
+    pub(crate) fn update_ime(&self, window: &RawWindow) {
+        let area = self.ime_cursor_area();
+        let last = self.ime.applied.replace(area);
+        if area == last {
+            return;
+        }
+
+        if area.is_some() != last.is_some() {
+            window.set_ime_allowed(area.is_some());
+        }
+
+        if let Some(area) = area {
+            window.set_ime_cursor_area(
+                LogicalPosition::new(area.x0, area.y0),
+                LogicalSize::new(area.width(), area.height()),
+            );
+        }
+    }
+}
+
+/// Handle to the text input of a view.
+///
+/// This can be created with [`use_ime`] and attached to a view with [`View::ime`](crate::ui::view::View::ime).
+#[derive(Clone, Default)]
+pub struct ImeHandle {
+    pub(crate) key: Rc<Cell<Option<NodeId>>>,
+    pub(crate) cursor_area: Rc<Cell<Rect>>,
+}
+
+impl ImeHandle {
+    /// Set the area of the text cursor, relative to the attached view.
+    ///
+    /// The input method's candidate window is placed near this area.
+    pub fn set_cursor_area(&self, area: Rect) {
+        self.cursor_area.set(area);
+    }
+}
+
+/// Use a text input handle to receive input method (IME) events in a view.
+///
+/// The returned handle must be attached to a view with [`View::ime`](crate::ui::view::View::ime).
+/// While the attached view is focused, the window's input method is enabled,
+/// and [`Event::Ime`] events are sent to the view.
+pub fn use_ime(cx: ScopeState<'_>) -> &ImeHandle {
+    use_ref(cx, ImeHandle::default)
+}
@@ -0,0 +1,256 @@
+use super::{
+    view::text::{build_text_layout, draw_text_layout, TextContext},
+    Renderer, WindowContext,
+};
+use crate::{devtools, ScopeData};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Write,
+    ptr::NonNull,
+};
+use taffy::NodeId;
+use vello::{
+    kurbo::{Affine, Rect, Stroke, Vec2},
+    peniko::Color,
+};
+use winit::keyboard::{Key, ModifiersState};
+
+/// Padding around the label of the hovered node.
+const LABEL_PADDING: f64 = 6.;
+
+/// Inspector overlay for a window.
+///
+/// This is toggled with `Ctrl+Shift+I`, and draws the bounds of each layout node.
+#[derive(Default)]
+pub(crate) struct Inspector {
+    /// `true` if the overlay is shown.
+    pub(crate) is_enabled: Cell<bool>,
+
+    /// Currently hovered layout node.
+    pub(crate) hovered: Cell<Option<NodeId>>,
+
+    /// Scope of the composable owning each layout node.
+    scopes: RefCell<HashMap<NodeId, NonNull<ScopeData<'static>>>>,
+}
+
+impl Inspector {
+    /// Returns `true` if this keyboard input toggles the inspector.
+    pub(crate) fn is_toggle(key: &Key, modifiers: ModifiersState) -> bool {
+        modifiers.control_key()
+            && modifiers.shift_key()
+            && matches!(key, Key::Character(c) if c.eq_ignore_ascii_case("i"))
+    }
+
+    /// Toggle the inspector overlay.
+    pub(crate) fn toggle(&self) {
+        self.is_enabled.set(!self.is_enabled.get());
+        self.hovered.set(None);
+    }
+
+    /// Set the scope owning the layout node `key`.
+    pub(crate) fn insert(&self, key: NodeId, scope: &ScopeData) {
+        self.scopes
+            .borrow_mut()
+            .insert(key, NonNull::from(scope).cast());
+    }
+
+    /// Remove the layout node `key`.
+    pub(crate) fn remove(&self, key: NodeId) {
+        self.scopes.borrow_mut().remove(&key);
+
+        if self.hovered.get() == Some(key) {
+            self.hovered.set(None);
+        }
+    }
+
+    /// Get the path of composable names from the root to the owner of the layout node `key`.
+    pub(crate) fn path(&self, key: NodeId) -> Vec<Cow<'static, str>> {
+        self.scopes
+            .borrow()
+            .get(&key)
+            // Safety: Scopes remove their layout nodes before they are dropped.
+            .map(|scope| devtools::path(unsafe { scope.as_ref() }))
+            .unwrap_or_default()
+    }
+
+    /// Describe the layout node `key` with its owning composable, layout, and style,
+    /// or return `None` if `key` was removed from the layout tree.
+    pub(crate) fn describe(&self, window_cx: &WindowContext, key: NodeId) -> Option<String> {
+        let taffy = window_cx.taffy.borrow();
+        let (Ok(layout), Ok(style)) = (taffy.layout(key), taffy.style(key)) else {
+            return None;
+        };
+        let (pos, _) = window_cx.absolute_position(key);
+
+        let mut s = String::new();
+        let name = self.path(key).pop();
+        writeln!(s, "{}", name.as_deref().unwrap_or("<unknown>")).unwrap();
+        writeln!(s, "position: {:.1}, {:.1}", pos.x, pos.y).unwrap();
+        writeln!(
+            s,
+            "size: {:.1} x {:.1}",
+            layout.size.width, layout.size.height
+        )
+        .unwrap();
+        write!(
+            s,
+            "display: {:?}, direction: {:?}",
+            style.display, style.flex_direction
+        )
+        .unwrap();
+        Some(s)
+    }
+
+    /// Log the composable path, layout, and style of the layout node `key` (with the `tracing` feature).
+    pub(crate) fn log(&self, _window_cx: &WindowContext, _key: NodeId) {
+        #[cfg(feature = "tracing")]
+        {
+            let taffy = _window_cx.taffy.borrow();
+            let (Ok(layout), Ok(style)) = (taffy.layout(_key), taffy.style(_key)) else {
+                return;
+            };
+
+            tracing::debug!(
+                path = %self.path(_key).join(" > "),
+                ?layout,
+                ?style,
+                "Inspected layout node"
+            );
+        }
+    }
+
+    /// Draw the overlay for the layout tree starting at `root` with `renderer`.
+    pub(crate) fn draw(&self, window_cx: &WindowContext, renderer: &mut dyn Renderer, root: NodeId) {
+        let stroke = Stroke::new(1.);
+        let outline_color = Color::rgba8(236, 72, 153, 160);
+
+        {
+            let taffy = window_cx.taffy.borrow();
+            let scrolls = window_cx.scrolls.borrow();
+
+            let mut keys = vec![(Vec2::ZERO, root)];
+            while let Some((parent_pos, key)) = keys.pop() {
+                let Ok(layout) = taffy.layout(key) else {
+                    continue;
+                };
+                let pos = parent_pos + Vec2::new(layout.location.x as _, layout.location.y as _);
+                let rect = Rect::new(
+                    pos.x,
+                    pos.y,
+                    pos.x + layout.size.width as f64,
+                    pos.y + layout.size.height as f64,
+                );
+
+                if self.hovered.get() == Some(key) {
+                    renderer.fill(
+                        Affine::IDENTITY,
+                        (&Color::rgba8(59, 130, 246, 64)).into(),
+                        rect.into(),
+                    );
+                }
+                renderer.stroke(
+                    &stroke,
+                    Affine::IDENTITY,
+                    (&outline_color).into(),
+                    rect.into(),
+                );
+
+                let mut child_pos = pos;
+                if let Some(scroll) = scrolls.get(&key) {
+                    child_pos -= scroll.offset;
+                }
+                keys.extend(
+                    taffy
+                        .children(key)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|key| (child_pos, key)),
+                );
+            }
+        }
+
+        let Some(key) = self.hovered.get() else {
+            return;
+        };
+        let Some(description) = self.describe(window_cx, key) else {
+            return;
+        };
+
+        let text_cx = TextContext {
+            color: Color::WHITE,
+            font_size: 12.,
+            ..TextContext::default()
+        };
+        let layout = build_text_layout(&window_cx.font_cx, &text_cx, &description);
+
+        // Place the label just below the hovered node.
+        let (pos, _) = window_cx.absolute_position(key);
+        let height = window_cx
+            .taffy
+            .borrow()
+            .layout(key)
+            .map_or(0., |layout| layout.size.height as f64);
+        let origin = Vec2::new(pos.x, pos.y + height) + Vec2::new(LABEL_PADDING, LABEL_PADDING);
+
+        renderer.fill(
+            Affine::IDENTITY,
+            (&Color::rgba8(24, 24, 27, 224)).into(),
+            Rect::new(
+                origin.x - LABEL_PADDING,
+                origin.y - LABEL_PADDING,
+                origin.x + layout.full_width() as f64 + LABEL_PADDING,
+                origin.y + layout.height() as f64 + LABEL_PADDING,
+            )
+            .into(),
+        );
+        draw_text_layout(renderer, &layout, Affine::translate(origin));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::Cell, rc::Rc};
+    use vello::kurbo::Vec2;
+    use winit::keyboard::{Key, ModifiersState};
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_inspects_hovered_nodes_without_sending_clicks() {
+        let clicks = Rc::new(Cell::new(0));
+
+        let clicks_ref = clicks.clone();
+        let mut window = TestWindow::new(
+            Text::new("Inspected").on_click(move || clicks_ref.set(clicks_ref.get() + 1)),
+        );
+        window.frame();
+
+        window.set_modifiers(ModifiersState::CONTROL | ModifiersState::SHIFT);
+        window.key_press(Key::Character("I".into()));
+        window.set_modifiers(ModifiersState::empty());
+        assert!(window.window_cx().inspector.is_enabled.get());
+
+        let key = window.find_by_label("Inspected").unwrap();
+        window.click(window.position(key) + Vec2::new(1., 1.));
+        assert_eq!(window.window_cx().inspector.hovered.get(), Some(key));
+        assert_eq!(clicks.get(), 0);
+
+        let description = window
+            .window_cx()
+            .inspector
+            .describe(window.window_cx(), key)
+            .unwrap();
+        let mut lines = description.lines();
+        assert_eq!(lines.next(), Some("Canvas"));
+        assert_eq!(lines.next(), Some("position: 0.0, 0.0"));
+
+        // Toggling the inspector off sends clicks to views again.
+        window.set_modifiers(ModifiersState::CONTROL | ModifiersState::SHIFT);
+        window.key_press(Key::Character("I".into()));
+        window.set_modifiers(ModifiersState::empty());
+        window.click(window.position(key) + Vec2::new(1., 1.));
+        assert_eq!(clicks.get(), 1);
+    }
+}
@@ -0,0 +1,395 @@
+use super::{Renderer, WindowContext};
+use crate::prelude::*;
+use std::{cell::RefCell, mem, rc::Rc};
+use taffy::NodeId;
+use vello::kurbo::{Affine, Rect, Vec2};
+
+/// Placement of a popup relative to its anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Below the anchor, aligned to its left edge.
+    #[default]
+    Below,
+    /// Above the anchor, aligned to its left edge.
+    Above,
+    /// Left of the anchor, aligned to its top edge.
+    Left,
+    /// Right of the anchor, aligned to its top edge.
+    Right,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum LayerKind {
+    /// Popup positioned next to the layout node `anchor`.
+    Popup {
+        anchor: NodeId,
+        placement: Placement,
+    },
+    /// Modal dialog centered in the window, blocking input to the layers below it.
+    Modal { scrim: Color },
+    /// Tooltip positioned next to the layout node `anchor`.
+    ///
+    /// Tooltips don't receive input, and are dismissed by any press or scroll.
+    Tooltip {
+        anchor: NodeId,
+        placement: Placement,
+    },
+}
+
+type DismissFn = Rc<dyn Fn()>;
+
+/// Layer drawn above the window's content.
+struct Layer {
+    /// Root layout node of this layer, outside of the window's layout tree.
+    root: NodeId,
+    kind: LayerKind,
+    on_dismiss: DismissFn,
+    /// Absolute position of the root layout node.
+    origin: Vec2,
+}
+
+/// Stack of layers in a window, in paint order.
+#[derive(Default)]
+pub(crate) struct LayerStack {
+    layers: RefCell<Vec<Layer>>,
+}
+
+impl LayerStack {
+    /// Get the absolute position of the layer with the root layout node `root`.
+    pub(crate) fn origin(&self, root: NodeId) -> Option<Vec2> {
+        self.layers
+            .borrow()
+            .iter()
+            .find(|layer| layer.root == root)
+            .map(|layer| layer.origin)
+    }
+
+    /// Get the root layout node of each layer, in paint order.
+    pub(crate) fn roots(&self) -> Vec<NodeId> {
+        self.layers
+            .borrow()
+            .iter()
+            .map(|layer| layer.root)
+            .collect()
+    }
+
+    /// Get the root layout node, absolute position, and `true` if it's modal, for each layer from top to bottom.
+    ///
+    /// Tooltips are skipped, as they don't receive input.
+    pub(crate) fn hit_order(&self) -> Vec<(NodeId, Vec2, bool)> {
+        self.layers
+            .borrow()
+            .iter()
+            .rev()
+            .filter(|layer| !matches!(layer.kind, LayerKind::Tooltip { .. }))
+            .map(|layer| {
+                let is_modal = matches!(layer.kind, LayerKind::Modal { .. });
+                (layer.root, layer.origin, is_modal)
+            })
+            .collect()
+    }
+
+    fn remove(&self, root: NodeId) {
+        self.layers.borrow_mut().retain(|layer| layer.root != root);
+    }
+}
+
+impl WindowContext {
+    /// Compute the layout of the tree starting at `root` sized to the window, and of each layer within the window's size,
+    /// marking the fragment of each canvas whose layout size changed as dirty.
+    pub(crate) fn compute_layout(&self, root: NodeId) {
+        let size = self.size.get();
+        let available_space = Size {
+            width: AvailableSpace::Definite(size.x as _),
+            height: AvailableSpace::Definite(size.y as _),
+        };
+
+        let mut taffy = self.taffy.borrow_mut();
+
+        // The root layout node fills the window.
+        let root_size = Size {
+            width: length(size.x as f32),
+            height: length(size.y as f32),
+        };
+        if taffy.style(root).unwrap().size != root_size {
+            let mut style = taffy.style(root).unwrap().clone();
+            style.size = root_size;
+            taffy.set_style(root, style).unwrap();
+        }
+
+        for root in Some(root).into_iter().chain(self.layers.roots()) {
+            taffy
+                .compute_layout_with_measure(
+                    root,
+                    available_space,
+                    |known_dimensions, available_space, _key, measure, _style| match measure {
+                        Some(measure) => measure(known_dimensions, available_space),
+                        None => known_dimensions.unwrap_or(Size::ZERO),
+                    },
+                )
+                .unwrap();
+        }
+        drop(taffy);
+
+        self.invalidate_fragments();
+    }
+
+    /// Position each layer in the window, in paint order.
+    pub(crate) fn update_layers(&self) {
+        let window_size = self.size.get();
+
+        let len = self.layers.layers.borrow().len();
+        for idx in 0..len {
+            let (root, kind) = {
+                let layers = self.layers.layers.borrow();
+                (layers[idx].root, layers[idx].kind)
+            };
+
+            let size = {
+                let taffy = self.taffy.borrow();
+                let size = taffy.layout(root).unwrap().size;
+                Vec2::new(size.width as _, size.height as _)
+            };
+
+            let origin =
+                match kind {
+                    LayerKind::Popup { anchor, placement }
+                    | LayerKind::Tooltip { anchor, placement } => {
+                        let Ok(anchor_size) = self.taffy.borrow().layout(anchor).map(|layout| {
+                            Vec2::new(layout.size.width as _, layout.size.height as _)
+                        }) else {
+                            continue;
+                        };
+                        let (anchor_pos, _) = self.absolute_position(anchor);
+
+                        popup_origin(anchor_pos, anchor_size, size, placement, window_size)
+                    }
+                    LayerKind::Modal { .. } => {
+                        let origin = (window_size - size) / 2.;
+                        Vec2::new(origin.x.max(0.), origin.y.max(0.))
+                    }
+                };
+
+            let mut layers = self.layers.layers.borrow_mut();
+            if layers[idx].origin != origin {
+                layers[idx].origin = origin;
+                self.is_changed.set(true);
+            }
+        }
+    }
+
+    /// Draw each layer with `renderer` in paint order, drawing a scrim below each modal layer.
+    pub(crate) fn build_layers(&self, renderer: &mut dyn Renderer) {
+        let size = self.size.get();
+
+        let layers: Vec<_> = self
+            .layers
+            .layers
+            .borrow()
+            .iter()
+            .map(|layer| (layer.root, layer.kind))
+            .collect();
+        for (root, kind) in layers {
+            if let LayerKind::Modal { scrim } = kind {
+                renderer.fill(
+                    Affine::IDENTITY,
+                    (&scrim).into(),
+                    Rect::new(0., 0., size.x, size.y).into(),
+                );
+            }
+
+            self.build_scene(renderer, root);
+        }
+    }
+
+    /// Get the root layout nodes to traverse for keyboard focus, in order.
+    ///
+    /// While a modal layer is open, focus is trapped in it and the layers above it.
+    pub(crate) fn focus_roots(&self, root: NodeId) -> Vec<NodeId> {
+        let layers = self.layers.layers.borrow();
+        match layers
+            .iter()
+            .rposition(|layer| matches!(layer.kind, LayerKind::Modal { .. }))
+        {
+            Some(idx) => layers[idx..].iter().map(|layer| layer.root).collect(),
+            None => Some(root)
+                .into_iter()
+                .chain(layers.iter().map(|layer| layer.root))
+                .collect(),
+        }
+    }
+
+    /// Dismiss the topmost layer, returning `true` if any layer is open.
+    pub(crate) fn dismiss_top_layer(&self) -> bool {
+        let on_dismiss = self
+            .layers
+            .layers
+            .borrow()
+            .last()
+            .map(|layer| layer.on_dismiss.clone());
+
+        match on_dismiss {
+            Some(on_dismiss) => {
+                on_dismiss();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Dismiss each tooltip.
+    pub(crate) fn dismiss_tooltips(&self) {
+        let on_dismiss: Vec<_> = self
+            .layers
+            .layers
+            .borrow()
+            .iter()
+            .filter(|layer| matches!(layer.kind, LayerKind::Tooltip { .. }))
+            .map(|layer| layer.on_dismiss.clone())
+            .collect();
+
+        for f in on_dismiss {
+            f();
+        }
+    }
+
+    /// Dismiss each tooltip, and the topmost other layer if `target`, the node pressed by the pointer, is outside of it.
+    ///
+    /// Pressing the anchor of a popup doesn't dismiss it.
+    pub(crate) fn dismiss_outside(&self, target: Option<NodeId>) {
+        self.dismiss_tooltips();
+
+        let Some((root, kind)) = self
+            .layers
+            .layers
+            .borrow()
+            .iter()
+            .rev()
+            .find(|layer| !matches!(layer.kind, LayerKind::Tooltip { .. }))
+            .map(|layer| (layer.root, layer.kind))
+        else {
+            return;
+        };
+
+        let is_inside = target.is_some_and(|target| match kind {
+            LayerKind::Popup { anchor, .. } => {
+                self.is_ancestor(root, target) || self.is_ancestor(anchor, target)
+            }
+            LayerKind::Modal { .. } => target != root && self.is_ancestor(root, target),
+            LayerKind::Tooltip { .. } => unreachable!(),
+        });
+
+        if !is_inside {
+            self.dismiss_top_layer();
+        }
+    }
+
+    /// Returns `true` if `ancestor` is `key` or one of its ancestors.
+    fn is_ancestor(&self, ancestor: NodeId, key: NodeId) -> bool {
+        let taffy = self.taffy.borrow();
+
+        let mut next = Some(key);
+        while let Some(key) = next {
+            if key == ancestor {
+                return true;
+            }
+            next = taffy.parent(key);
+        }
+        false
+    }
+}
+
+/// Position a popup of `size` next to its anchor, flipping it to the opposite side
+/// if it overflows the window and clamping it to the window's bounds.
+fn popup_origin(
+    anchor_pos: Vec2,
+    anchor_size: Vec2,
+    size: Vec2,
+    placement: Placement,
+    window_size: Vec2,
+) -> Vec2 {
+    let below = anchor_pos.y + anchor_size.y;
+    let above = anchor_pos.y - size.y;
+    let right = anchor_pos.x + anchor_size.x;
+    let left = anchor_pos.x - size.x;
+
+    let origin = match placement {
+        Placement::Below if below + size.y > window_size.y && above >= 0. => {
+            Vec2::new(anchor_pos.x, above)
+        }
+        Placement::Below => Vec2::new(anchor_pos.x, below),
+        Placement::Above if above < 0. && below + size.y <= window_size.y => {
+            Vec2::new(anchor_pos.x, below)
+        }
+        Placement::Above => Vec2::new(anchor_pos.x, above),
+        Placement::Right if right + size.x > window_size.x && left >= 0. => {
+            Vec2::new(left, anchor_pos.y)
+        }
+        Placement::Right => Vec2::new(right, anchor_pos.y),
+        Placement::Left if left < 0. && right + size.x <= window_size.x => {
+            Vec2::new(right, anchor_pos.y)
+        }
+        Placement::Left => Vec2::new(left, anchor_pos.y),
+    };
+
+    Vec2::new(
+        origin.x.min(window_size.x - size.x).max(0.),
+        origin.y.min(window_size.y - size.y).max(0.),
+    )
+}
+
+/// Use a new layer above the window's content, returning the root layout node of the layer.
+///
+/// The layer is removed when this scope is dropped.
+pub(crate) fn use_layer<'a>(
+    cx: ScopeState<'a>,
+    kind: LayerKind,
+    on_dismiss: impl FnOnce() -> Rc<dyn Fn() + 'a>,
+) -> NodeId {
+    let window_cx = use_context::<WindowContext>(cx).unwrap();
+
+    let root = *use_ref(cx, || {
+        let root = window_cx
+            .taffy
+            .borrow_mut()
+            .new_leaf(Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            })
+            .unwrap();
+
+        // Safety: `on_dismiss` is removed from the layer stack on drop.
+        let on_dismiss: DismissFn = unsafe { mem::transmute(on_dismiss()) };
+
+        window_cx.layers.layers.borrow_mut().push(Layer {
+            root,
+            kind,
+            on_dismiss,
+            origin: Vec2::ZERO,
+        });
+        window_cx.is_layout_changed.set(true);
+
+        root
+    });
+
+    if let Some(layer) = window_cx
+        .layers
+        .layers
+        .borrow_mut()
+        .iter_mut()
+        .find(|layer| layer.root == root)
+    {
+        if layer.kind != kind {
+            layer.kind = kind;
+            window_cx.is_changed.set(true);
+        }
+    }
+
+    use_drop(cx, move || {
+        window_cx.layers.remove(root);
+        window_cx.taffy.borrow_mut().remove(root).unwrap();
+        window_cx.is_changed.set(true);
+    });
+
+    root
+}
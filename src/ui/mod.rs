@@ -1,28 +1,107 @@
 use crate::prelude::*;
+use slotmap::{DefaultKey, SlotMap};
 use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
+    num::NonZeroUsize,
+    path::PathBuf,
     rc::Rc,
 };
-use vello::{kurbo::Vec2, Scene};
+use vello::{
+    kurbo::{Affine, Rect, RoundedRect, Vec2},
+    util::{RenderContext, RenderSurface},
+    wgpu::{self, PresentMode},
+    AaSupport, RendererOptions, Scene,
+};
 use view::{
     canvas::CanvasContext,
+    scroll::ScrollState,
     text::{FontContext, TextContext},
+    transition::TransitionEffect,
+    Effect,
+};
+use web_time::Instant;
+use winit::{
+    event::{ElementState, Ime, MouseButton},
+    keyboard::{Key, ModifiersState, PhysicalKey},
 };
-use winit::event::{ElementState, MouseButton};
+
+pub(crate) mod accessibility;
+pub use self::accessibility::{Accessibility, Action, Role};
+
+/// Animated values.
+pub mod animation;
+use self::animation::{AnimationFn, FrameFn};
 
 /// Drawable modifiers.
 pub mod draw;
 pub use self::draw::Draw;
 
+mod direction;
+pub use self::direction::{use_direction, Direction};
+
+mod drag;
+pub use self::drag::DragPayload;
+pub(crate) use self::drag::{AcceptsFn, DragManager};
+
+/// Embedding content in windows owned by another application.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod embed;
+
+mod focus;
+pub(crate) use self::focus::FocusManager;
+pub use self::focus::{use_focus, FocusHandle};
+
+mod ime;
+pub(crate) use self::ime::ImeManager;
+pub use self::ime::{use_ime, ImeHandle};
+
+#[cfg(feature = "devtools")]
+mod inspector;
+#[cfg(feature = "devtools")]
+pub(crate) use self::inspector::Inspector;
+
+mod layer;
+pub use self::layer::Placement;
+pub(crate) use self::layer::{use_layer, LayerKind, LayerStack};
+
+mod pointer;
+use self::pointer::Pointer;
+
+mod shortcut;
+pub(crate) use self::shortcut::ShortcutRegistry;
+pub use self::shortcut::{use_shortcut, ParseShortcutError, Shortcut};
+
+/// Rendering backends.
+pub mod render;
+pub use self::render::Renderer;
+use self::render::Recording;
+
+/// Headless testing utilities.
+#[cfg(any(test, feature = "testing"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+mod router;
+pub use self::router::{router, use_navigator, Navigator, Router};
+
+mod theme;
+pub use self::theme::{use_theme, Spacing, Theme, ThemeColors, Themed, Typography};
+
 /// View modifiers.
 pub mod view;
 
 /// Use a new layout node.
-pub fn use_layout(cx: ScopeState, style: Style) -> (NodeId, Layout) {
+///
+/// Any style modifiers applied to the composable using this layout are applied to `style`.
+pub fn use_layout(cx: ScopeState, mut style: Style) -> (NodeId, Layout) {
     let layout_cx = use_context::<LayoutContext>(cx).unwrap();
     let renderer_cx = use_context::<WindowContext>(cx).unwrap();
 
+    for f in &layout_cx.styles {
+        (f.borrow())(&mut style);
+    }
+
     let parent_key = layout_cx.parent_id;
     let key = *use_ref(cx, || {
         let key = renderer_cx
@@ -38,11 +117,15 @@ pub fn use_layout(cx: ScopeState, style: Style) -> (NodeId, Layout) {
 
         renderer_cx.is_layout_changed.set(true);
 
+        #[cfg(feature = "devtools")]
+        renderer_cx.inspector.insert(key, cx);
+
         key
     });
 
-    let last_style = use_ref(cx, || style.clone());
-    if style != *last_style {
+    let last_style = use_ref(cx, || RefCell::new(style.clone()));
+    if style != *last_style.borrow() {
+        *last_style.borrow_mut() = style.clone();
         renderer_cx.is_layout_changed.set(true);
         renderer_cx
             .taffy
@@ -51,21 +134,156 @@ pub fn use_layout(cx: ScopeState, style: Style) -> (NodeId, Layout) {
             .unwrap();
     }
 
+    let effect = layout_cx
+        .effects
+        .iter()
+        .fold(NodeEffect::default(), |acc, effect| acc.then(effect.get()));
+    let last_effect = renderer_cx.effects.borrow().get(&key).copied();
+    if last_effect.unwrap_or_default() != effect {
+        if effect == NodeEffect::default() {
+            renderer_cx.effects.borrow_mut().remove(&key);
+        } else {
+            renderer_cx.effects.borrow_mut().insert(key, effect);
+        }
+        renderer_cx.is_changed.set(true);
+    }
+
     use_drop(cx, move || {
         renderer_cx.taffy.borrow_mut().remove(key).unwrap();
         renderer_cx.listeners.borrow_mut().remove(&key);
+        renderer_cx.effects.borrow_mut().remove(&key);
+        renderer_cx.accessibility.borrow_mut().remove(&key);
+        renderer_cx.focus.remove(key);
+        renderer_cx.ime.remove(key);
+        renderer_cx.drag.remove(key);
+        renderer_cx.pointer.remove(key);
+
+        #[cfg(feature = "devtools")]
+        renderer_cx.inspector.remove(key);
     });
 
     let layout = *renderer_cx.taffy.borrow().layout(key).unwrap();
     (key, layout)
 }
 
-struct LayoutContext {
+/// Scale factor of the current window, provided by [`Window`](view::Window).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ScaleFactor(pub(crate) f64);
+
+/// Use the scale factor of the current window, from logical to physical pixels.
+///
+/// Layout, drawing, and events use logical pixels, and the window's scene is scaled by this factor when rendered.
+/// Changing the scale factor (such as by moving the window to a display with a different DPI)
+/// recomposes each composable using it.
+pub fn use_scale_factor(cx: ScopeState) -> f64 {
+    use_context::<ScaleFactor>(cx).map_or(1., |scale_factor| scale_factor.0)
+}
+
+/// Insets of the safe area of a window, in logical pixels.
+///
+/// Content within these insets may be covered by system UI, such as status bars, notches, or rounded corners.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SafeArea {
+    /// Inset from the top edge.
+    pub top: f64,
+    /// Inset from the right edge.
+    pub right: f64,
+    /// Inset from the bottom edge.
+    pub bottom: f64,
+    /// Inset from the left edge.
+    pub left: f64,
+}
+
+/// Use the safe area insets of the current window, provided by [`Window`](view::Window).
+///
+/// These are only non-zero on iOS and Android, and each composable using them is recomposed when they change
+/// (such as when the device is rotated).
+pub fn use_safe_area(cx: ScopeState) -> SafeArea {
+    use_context::<SafeArea>(cx).map_or(SafeArea::default(), |safe_area| *safe_area)
+}
+
+type StyleFn = Rc<dyn Fn(&mut Style)>;
+
+/// Function to measure the size of a leaf layout node from its known dimensions and available space.
+pub(crate) type MeasureFn = Rc<dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32>>;
+
+/// Layout tree of a window, with the measure function of each measured leaf node.
+pub(crate) type LayoutTree = TaffyTree<MeasureFn>;
+
+#[derive(Clone)]
+pub(crate) struct LayoutContext {
     parent_id: NodeId,
+    styles: Vec<Rc<RefCell<StyleFn>>>,
+    effects: Vec<Rc<Cell<Effect>>>,
 }
 
-/// User interface event.
+impl LayoutContext {
+    fn new(parent_id: NodeId) -> Self {
+        Self {
+            parent_id,
+            styles: Vec::new(),
+            effects: Vec::new(),
+        }
+    }
+}
+
+/// Visual effect of a layout node, applied to the node and every node inside it.
 #[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct NodeEffect {
+    opacity: f32,
+    /// Transform around the center of the node.
+    transform: Affine,
+}
+
+impl Default for NodeEffect {
+    fn default() -> Self {
+        Self {
+            opacity: 1.,
+            transform: Affine::IDENTITY,
+        }
+    }
+}
+
+impl NodeEffect {
+    /// Apply `effect` to the content of this effect.
+    fn then(self, effect: Effect) -> Self {
+        let (opacity, transform) = match effect {
+            Effect::Opacity(opacity) => (opacity, Affine::IDENTITY),
+            Effect::Rotate(angle) => (1., Affine::rotate(angle)),
+            Effect::Scale(x, y) => (1., Affine::scale_non_uniform(x, y)),
+            Effect::Translate(x, y) => (1., Affine::translate((x, y))),
+        };
+
+        Self {
+            opacity: self.opacity * opacity,
+            transform: self.transform * transform,
+        }
+    }
+
+    /// Get the transform of this effect in the local coordinates of a node with `size`.
+    fn local_transform(&self, size: taffy::Size<f32>) -> Affine {
+        let center = Vec2::new(size.width as f64 / 2., size.height as f64 / 2.);
+        Affine::translate(center) * self.transform * Affine::translate(-center)
+    }
+}
+
+/// Keyboard input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardInput {
+    /// Logical key, with the current keyboard layout applied.
+    pub key: Key,
+    /// Physical key, independent of the current keyboard layout.
+    pub physical_key: PhysicalKey,
+    /// Element state.
+    pub state: ElementState,
+    /// `true` if this is a repeated key press from a held key.
+    pub repeat: bool,
+    /// Modifier keys held during this input.
+    pub modifiers: ModifiersState,
+}
+
+/// User interface event.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Event {
     /// Mouse input event.
     MouseInput {
@@ -85,18 +303,517 @@ pub enum Event {
     },
     /// Mouse out event.
     MouseOut,
+    /// Keyboard input event.
+    ///
+    /// This is only sent to the focused view.
+    KeyboardInput(KeyboardInput),
+    /// Focus event.
+    Focus,
+    /// Blur event, sent when a view loses focus.
+    Blur,
+    /// Drag enter event, sent to a drop target when a payload it accepts is dragged over it.
+    DragEnter,
+    /// Drag leave event, sent to a drop target when the payload dragged over it leaves or is dropped.
+    DragLeave,
+    /// Drop event, sent to a drop target when a payload it accepts is dropped on it.
+    Drop(DragPayload),
+    /// File drop event, sent to the view under the cursor when a file is dropped on the window.
+    FileDrop(PathBuf),
+    /// Input method (IME) event.
+    ///
+    /// This is only sent to the focused view, if it accepts text input with [`use_ime`].
+    Ime(Ime),
+    /// Drag start event, sent to the pressed view once the cursor moves past the drag threshold.
+    DragStart {
+        /// Cursor position where the left mouse button was pressed.
+        pos: Vec2,
+    },
+    /// Drag move event, sent to the dragged view each time the cursor moves.
+    DragMove {
+        /// Cursor position.
+        pos: Vec2,
+        /// Distance moved since the last drag event.
+        delta: Vec2,
+    },
+    /// Drag end event, sent to the dragged view when the left mouse button is released.
+    DragEnd {
+        /// Cursor position.
+        pos: Vec2,
+    },
+    /// Tap event, sent when the left mouse button is pressed and released without dragging.
+    Tap {
+        /// Cursor position.
+        pos: Vec2,
+    },
+    /// Double tap event, sent after the second of two quick taps on the same view.
+    DoubleTap {
+        /// Cursor position.
+        pos: Vec2,
+    },
 }
 
 type ListenerFn = Rc<dyn Fn(Event)>;
 
+/// Drawing recorded by a canvas, replayed into the window's renderer at the canvas' position.
+#[derive(Default)]
+pub(crate) struct Fragment {
+    pub(crate) recording: Recording,
+    /// Layout size this fragment was recorded with.
+    pub(crate) size: taffy::Size<f32>,
+    /// Corner radius of the canvas, used to clip its children.
+    pub(crate) corner_radius: f64,
+    /// Transitions containing the canvas, applied when appending this fragment.
+    pub(crate) transitions: Vec<Rc<Cell<TransitionEffect>>>,
+    /// `true` if this fragment must be recorded again.
+    pub(crate) is_dirty: bool,
+}
+
 pub(crate) struct WindowContext {
     scene: RefCell<Scene>,
-    taffy: RefCell<TaffyTree>,
+    taffy: RefCell<LayoutTree>,
     is_changed: Cell<bool>,
     is_layout_changed: Cell<bool>,
     canvas_update_fns: RefCell<HashMap<NodeId, Box<dyn Fn()>>>,
+    fragments: RefCell<HashMap<NodeId, Fragment>>,
     listeners: Rc<RefCell<HashMap<NodeId, Vec<ListenerFn>>>>,
     base_color: Cell<Color>,
+    focus: Rc<FocusManager>,
+    drag: DragManager,
+    ime: ImeManager,
+    shortcuts: ShortcutRegistry,
+    modifiers: Cell<ModifiersState>,
+    scrolls: RefCell<HashMap<NodeId, ScrollState>>,
+    effects: RefCell<HashMap<NodeId, NodeEffect>>,
+    accessibility: RefCell<HashMap<NodeId, Accessibility>>,
+    animations: RefCell<SlotMap<DefaultKey, AnimationFn>>,
+    active_animations: Cell<usize>,
+    frame_fns: RefCell<SlotMap<DefaultKey, FrameFn>>,
+    last_frame: Cell<Option<Instant>>,
+    pointer: Pointer,
+    layers: LayerStack,
+    /// Inner size of the window, in logical pixels.
+    size: Cell<Vec2>,
+    /// Scale factor from logical to physical pixels.
+    scale_factor: Cell<f64>,
+    /// Fonts shared with every other window.
+    font_cx: FontContext,
+    #[cfg(feature = "devtools")]
+    inspector: Inspector,
+}
+
+impl WindowContext {
+    /// Create a new window context, returning it with the key of its root layout node.
+    pub(crate) fn new(font_cx: FontContext) -> (Self, NodeId) {
+        let mut taffy = LayoutTree::new();
+        let root_key = taffy
+            .new_leaf(Style {
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let window_cx = WindowContext {
+            scene: RefCell::default(),
+            taffy: RefCell::new(taffy),
+            is_changed: Cell::new(false),
+            is_layout_changed: Cell::new(false),
+            canvas_update_fns: RefCell::default(),
+            fragments: RefCell::default(),
+            listeners: Rc::default(),
+            base_color: Cell::new(Color::WHITE),
+            focus: Rc::default(),
+            drag: DragManager::default(),
+            ime: ImeManager::default(),
+            shortcuts: ShortcutRegistry::default(),
+            modifiers: Cell::default(),
+            scrolls: RefCell::default(),
+            effects: RefCell::default(),
+            accessibility: RefCell::default(),
+            animations: RefCell::default(),
+            active_animations: Cell::new(0),
+            frame_fns: RefCell::default(),
+            last_frame: Cell::new(None),
+            pointer: Pointer::default(),
+            layers: LayerStack::default(),
+            size: Cell::new(Vec2::ZERO),
+            scale_factor: Cell::new(1.),
+            font_cx,
+            #[cfg(feature = "devtools")]
+            inspector: Inspector::default(),
+        };
+
+        (window_cx, root_key)
+    }
+
+    /// Get the absolute position of `key` with scroll offsets and visual effects applied,
+    /// and the bounds of the viewport of each scroll container containing it.
+    pub(crate) fn absolute_position(&self, key: NodeId) -> (Vec2, Vec<Rect>) {
+        let (transform, clips) = self.transform(key);
+        let clips = clips
+            .into_iter()
+            .map(|(transform, clip)| transform.transform_rect_bbox(clip))
+            .collect();
+        (transform.translation(), clips)
+    }
+
+    /// Get the transform from the local coordinates of `key` to window coordinates,
+    /// and the viewport of each scroll container containing it, with the transform of that container.
+    pub(crate) fn transform(&self, key: NodeId) -> (Affine, Vec<(Affine, Rect)>) {
+        let taffy = self.taffy.borrow();
+        let scrolls = self.scrolls.borrow();
+        let effects = self.effects.borrow();
+
+        let mut keys = vec![key];
+        while let Some(parent_key) = taffy.parent(*keys.last().unwrap()) {
+            keys.push(parent_key);
+        }
+
+        // Layers are positioned in the window by their root layout node.
+        let mut transform = Affine::translate(
+            self.layers
+                .origin(*keys.last().unwrap())
+                .unwrap_or_default(),
+        );
+        let mut clips = Vec::new();
+        for &ancestor_key in keys.iter().rev() {
+            let layout = taffy.layout(ancestor_key).unwrap();
+            transform *= Affine::translate((layout.location.x as f64, layout.location.y as f64));
+            if let Some(effect) = effects.get(&ancestor_key) {
+                transform *= effect.local_transform(layout.size);
+            }
+
+            if ancestor_key != key {
+                if let Some(scroll) = scrolls.get(&ancestor_key) {
+                    clips.push((
+                        transform,
+                        Rect::new(0., 0., layout.size.width as _, layout.size.height as _),
+                    ));
+                    transform *= Affine::translate(-scroll.offset);
+                }
+            }
+        }
+
+        (transform, clips)
+    }
+
+    /// Set the inner size of the window, positioning its layers again if changed.
+    pub(crate) fn set_size(&self, size: Vec2) {
+        if self.size.replace(size) != size {
+            self.is_layout_changed.set(true);
+        }
+    }
+
+    /// Mark the fragment of each canvas whose layout size changed as dirty.
+    pub(crate) fn invalidate_fragments(&self) {
+        let taffy = self.taffy.borrow();
+        for (key, fragment) in self.fragments.borrow_mut().iter_mut() {
+            if taffy.layout(*key).unwrap().size != fragment.size {
+                fragment.is_dirty = true;
+                self.is_changed.set(true);
+            }
+        }
+    }
+
+    /// Recompose each canvas with a dirty (or missing) fragment, so it's recorded again.
+    pub(crate) fn update_fragments(&self) {
+        let fragments = self.fragments.borrow();
+        for (key, f) in self.canvas_update_fns.borrow().iter() {
+            if fragments.get(key).is_none_or(|fragment| fragment.is_dirty) {
+                f()
+            }
+        }
+    }
+
+    /// Draw the fragment of each canvas in the tree starting at `root` with `renderer`, in paint order.
+    ///
+    /// Fragments are positioned here, so scrolling or moving a canvas doesn't record it again.
+    pub(crate) fn build_scene(&self, renderer: &mut dyn Renderer, root: NodeId) {
+        let fragments = self.fragments.borrow();
+        self.build_node(renderer, &fragments, root);
+    }
+
+    /// Draw a frame of this window with `renderer`: its base color, the tree starting at `root`,
+    /// its layers, and the inspector overlay if enabled.
+    pub(crate) fn draw(&self, renderer: &mut dyn Renderer, root: NodeId) {
+        renderer.begin_frame();
+
+        let size = self.size.get();
+        renderer.fill(
+            Affine::IDENTITY,
+            (&self.base_color.get()).into(),
+            Rect::new(0., 0., size.x, size.y).into(),
+        );
+        self.build_scene(renderer, root);
+        self.build_layers(renderer);
+
+        #[cfg(feature = "devtools")]
+        if self.inspector.is_enabled.get() {
+            self.inspector.draw(self, renderer, root);
+        }
+
+        renderer.end_frame();
+    }
+
+    /// Draw the fragments of `key` and its children with `renderer`,
+    /// compositing them together with the opacity of `key`.
+    fn build_node(
+        &self,
+        renderer: &mut dyn Renderer,
+        fragments: &HashMap<NodeId, Fragment>,
+        key: NodeId,
+    ) {
+        if self.is_hidden(key) {
+            return;
+        }
+
+        let opacity = self
+            .effects
+            .borrow()
+            .get(&key)
+            .map_or(1., |effect| effect.opacity);
+        if opacity <= 0. {
+            return;
+        }
+
+        if opacity < 1. {
+            let size = self.size.get();
+            let bounds = Rect::new(0., 0., size.x, size.y);
+            renderer.push_opacity(opacity, Affine::IDENTITY, bounds.into());
+        }
+
+        let fragment = fragments.get(&key);
+        if let Some(fragment) = fragment {
+            self.append_fragment(renderer, key, fragment);
+        }
+
+        let clip = self.overflow_clip(key, fragment.map_or(0., |f| f.corner_radius));
+        if let Some((transform, clip)) = clip {
+            renderer.push_clip(transform, clip.into());
+        }
+
+        let children = self.taffy.borrow().children(key).unwrap();
+        for child in children {
+            self.build_node(renderer, fragments, child);
+        }
+
+        if clip.is_some() {
+            renderer.pop_clip();
+        }
+
+        if opacity < 1. {
+            renderer.pop_opacity();
+        }
+    }
+
+    /// Returns `true` if `key` is hidden from the window with [`Display::None`].
+    pub(crate) fn is_hidden(&self, key: NodeId) -> bool {
+        self.taffy.borrow().style(key).unwrap().display == Display::None
+    }
+
+    /// Get the shape (and its transform) to clip the children of `key` to,
+    /// if its style hides overflowing content on either axis.
+    fn overflow_clip(&self, key: NodeId, corner_radius: f64) -> Option<(Affine, RoundedRect)> {
+        let (overflow, size) = {
+            let taffy = self.taffy.borrow();
+            (
+                taffy.style(key).unwrap().overflow,
+                taffy.layout(key).unwrap().size,
+            )
+        };
+
+        let is_clipped = |overflow| matches!(overflow, Overflow::Hidden | Overflow::Clip);
+        let (clip_x, clip_y) = (is_clipped(overflow.x), is_clipped(overflow.y));
+        if !clip_x && !clip_y {
+            return None;
+        }
+
+        // Content is only clipped on the axes hiding overflow.
+        const UNBOUNDED: f64 = 1e9;
+        let (x0, x1) = if clip_x {
+            (0., size.width as f64)
+        } else {
+            (-UNBOUNDED, UNBOUNDED)
+        };
+        let (y0, y1) = if clip_y {
+            (0., size.height as f64)
+        } else {
+            (-UNBOUNDED, UNBOUNDED)
+        };
+        let radius = if clip_x && clip_y { corner_radius } else { 0. };
+
+        let (transform, _) = self.transform(key);
+        Some((transform, RoundedRect::new(x0, y0, x1, y1, radius)))
+    }
+
+    fn append_fragment(&self, renderer: &mut dyn Renderer, key: NodeId, fragment: &Fragment) {
+        let (transform, clips) = self.transform(key);
+
+        let (opacity, offset) =
+            fragment
+                .transitions
+                .iter()
+                .fold((1., Vec2::ZERO), |(opacity, offset), effect| {
+                    let effect = effect.get();
+                    (opacity * effect.opacity, offset + effect.offset)
+                });
+        let transform = Affine::translate(offset) * transform;
+
+        for (clip_transform, clip) in &clips {
+            renderer.push_clip(*clip_transform, (*clip).into());
+        }
+
+        if opacity < 1. {
+            let bounds = Rect::new(0., 0., fragment.size.width as _, fragment.size.height as _);
+            renderer.push_opacity(opacity, transform, bounds.into());
+        }
+
+        fragment.recording.replay(renderer, transform);
+
+        if opacity < 1. {
+            renderer.pop_opacity();
+        }
+
+        for _ in &clips {
+            renderer.pop_clip();
+        }
+    }
+}
+
+/// Render context shared by each window.
+///
+/// On the web, each window has its own context, as each canvas has its own WebGL context.
+pub(crate) struct RendererContext {
+    pub(crate) render_cx: Rc<RefCell<RenderContext>>,
+}
+
+impl Default for RendererContext {
+    fn default() -> Self {
+        Self {
+            render_cx: Rc::new(RefCell::new(RenderContext::new())),
+        }
+    }
+}
+
+impl RendererContext {
+    /// Create a render surface for `window`.
+    ///
+    /// If no device is compatible, and no other window has a surface yet,
+    /// this falls back to the render context from [`fallback_render_context`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn create_surface<'w>(
+        &self,
+        window: &'w winit::window::Window,
+    ) -> Result<RenderSurface<'w>, vello::Error> {
+        let size = crate::event_loop::surface_size(window);
+        let create = |render_cx: &mut RenderContext| {
+            pollster::block_on(render_cx.create_surface(
+                window,
+                size.width,
+                size.height,
+                PresentMode::AutoVsync,
+            ))
+        };
+
+        let mut render_cx = self.render_cx.borrow_mut();
+        create(&mut render_cx).or_else(|error| {
+            // Surfaces of other windows refer to the devices of this context.
+            if !render_cx.devices.is_empty() {
+                return Err(error);
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Failed to create render surface ({error}), trying fallback backends");
+
+            *render_cx = fallback_render_context();
+            create(&mut render_cx)
+        })
+    }
+
+    /// Create a render surface for the canvas of `window`, calling `f` with the result once it's ready.
+    ///
+    /// Devices can't be requested synchronously on the web, so the surface is created in a local task
+    /// that replaces the devices of this context.
+    /// Like [`RendererContext::create_surface`], this falls back to [`fallback_render_context`]
+    /// (including WebGL2) if WebGPU isn't available.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn spawn_surface(
+        &self,
+        window: &winit::window::Window,
+        f: impl FnOnce(Result<RenderSurface<'static>, vello::Error>) + 'static,
+    ) {
+        use winit::platform::web::WindowExtWebSys;
+
+        let canvas = window.canvas().unwrap();
+        let size = crate::event_loop::surface_size(window);
+        let shared_render_cx = self.render_cx.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            // The shared context isn't borrowed while waiting for a device.
+            let mut render_cx = RenderContext::new();
+            let mut result = render_cx
+                .create_surface(
+                    wgpu::SurfaceTarget::Canvas(canvas.clone()),
+                    size.width,
+                    size.height,
+                    PresentMode::AutoVsync,
+                )
+                .await;
+
+            if let Err(_error) = &result {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    "Failed to create render surface ({_error}), trying fallback backends"
+                );
+
+                render_cx = fallback_render_context();
+                result = render_cx
+                    .create_surface(
+                        wgpu::SurfaceTarget::Canvas(canvas),
+                        size.width,
+                        size.height,
+                        PresentMode::AutoVsync,
+                    )
+                    .await;
+            }
+
+            *shared_render_cx.borrow_mut() = render_cx;
+            f(result)
+        });
+    }
+}
+
+/// Create a render context with every wgpu backend,
+/// including OpenGL (and its software rasterizers, such as in VMs and CI machines).
+pub(crate) fn fallback_render_context() -> RenderContext {
+    RenderContext {
+        instance: wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        }),
+        devices: Vec::new(),
+    }
+}
+
+/// Create a renderer for `device`,
+/// falling back to running vello's shaders on the CPU if its GPU pipelines can't be created.
+pub(crate) fn create_renderer(
+    device: &wgpu::Device,
+    surface_format: Option<wgpu::TextureFormat>,
+    antialiasing_support: AaSupport,
+) -> Result<vello::Renderer, vello::Error> {
+    let options = |use_cpu| RendererOptions {
+        surface_format,
+        use_cpu,
+        antialiasing_support,
+        num_init_threads: NonZeroUsize::new(1),
+    };
+
+    vello::Renderer::new(device, options(false)).or_else(|_error| {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Failed to create GPU renderer ({_error}), falling back to CPU shaders");
+
+        vello::Renderer::new(device, options(true))
+    })
 }
 
 #[derive(Data)]
@@ -106,6 +823,8 @@ pub(crate) struct RenderRoot<C> {
 
 impl<C: Compose> Compose for RenderRoot<C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
+        use_provider(&cx, RendererContext::default);
+
         use_provider(&cx, CanvasContext::default);
 
         use_provider(&cx, FontContext::default);
@@ -113,6 +832,9 @@ impl<C: Compose> Compose for RenderRoot<C> {
         let text_context = use_context::<TextContext>(&cx).map(|rc| (*rc).clone());
         use_provider(&cx, || text_context.unwrap_or_default());
 
+        let theme = use_context::<Theme>(&cx).map(|rc| (*rc).clone());
+        use_provider(&cx, || theme.unwrap_or_default());
+
         Ref::map(cx.me(), |me| &me.content)
     }
 }
@@ -0,0 +1,205 @@
+use super::{Event, WindowContext};
+use std::{cell::Cell, time::Duration};
+use taffy::NodeId;
+use vello::kurbo::Vec2;
+use web_time::Instant;
+use winit::event::{ElementState, MouseButton};
+
+/// Distance the cursor must move while pressed before a drag starts.
+const DRAG_THRESHOLD: f64 = 4.;
+
+/// Maximum time between two taps of a double tap.
+const DOUBLE_TAP_DURATION: Duration = Duration::from_millis(300);
+
+/// Pressed mouse button capturing the pointer.
+#[derive(Clone, Copy)]
+struct Press {
+    key: NodeId,
+    button: MouseButton,
+    start: Vec2,
+    last: Vec2,
+    is_dragging: bool,
+}
+
+/// Last tap, used to recognize double taps.
+#[derive(Clone, Copy)]
+struct Tap {
+    key: NodeId,
+    pos: Vec2,
+    time: Instant,
+}
+
+/// Pointer capture and gesture state of a window.
+///
+/// A view receiving a mouse press captures the pointer until the button is released,
+/// receiving every move and release event, even outside of its bounds.
+#[derive(Default)]
+pub(crate) struct Pointer {
+    press: Cell<Option<Press>>,
+    last_tap: Cell<Option<Tap>>,
+}
+
+impl Pointer {
+    /// Get the layout node currently capturing the pointer.
+    pub(crate) fn captured(&self) -> Option<NodeId> {
+        self.press.get().map(|press| press.key)
+    }
+
+    /// Release the pointer if it is captured by the removed layout node `key`.
+    pub(crate) fn remove(&self, key: NodeId) {
+        if self.captured() == Some(key) {
+            self.press.set(None);
+        }
+
+        if self.last_tap.get().map(|tap| tap.key) == Some(key) {
+            self.last_tap.set(None);
+        }
+    }
+
+    /// Move the pointer to `pos`, returning the gesture events for the captured node.
+    fn move_to(&self, pos: Vec2) -> Vec<Event> {
+        let Some(mut press) = self.press.get() else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        if press.button == MouseButton::Left {
+            if !press.is_dragging && (pos - press.start).hypot() >= DRAG_THRESHOLD {
+                press.is_dragging = true;
+                events.push(Event::DragStart { pos: press.start });
+            }
+
+            if press.is_dragging {
+                events.push(Event::DragMove {
+                    pos,
+                    delta: pos - press.last,
+                });
+            }
+        }
+
+        press.last = pos;
+        self.press.set(Some(press));
+
+        events
+    }
+
+    /// Release the pointer at `pos`, returning the gesture events for the released press.
+    fn release(&self, press: Press, pos: Vec2, now: Instant) -> Vec<Event> {
+        if press.button != MouseButton::Left {
+            return Vec::new();
+        }
+
+        if press.is_dragging {
+            return vec![Event::DragEnd { pos }];
+        }
+
+        let mut events = vec![Event::Tap { pos }];
+
+        let is_double_tap = self.last_tap.take().is_some_and(|tap| {
+            tap.key == press.key
+                && now.saturating_duration_since(tap.time) <= DOUBLE_TAP_DURATION
+                && (pos - tap.pos).hypot() < DRAG_THRESHOLD
+        });
+
+        if is_double_tap {
+            events.push(Event::DoubleTap { pos });
+        } else {
+            self.last_tap.set(Some(Tap {
+                key: press.key,
+                pos,
+                time: now,
+            }));
+        }
+
+        events
+    }
+}
+
+impl WindowContext {
+    /// Send `event` to each listener of `key`.
+    pub(crate) fn send(&self, key: NodeId, event: Event) {
+        let listeners = self.listeners.borrow().get(&key).cloned();
+        for f in listeners.into_iter().flatten() {
+            f(event.clone())
+        }
+    }
+
+    /// Move the pointer to `pos` over `hit`, sending move and drag events to the node capturing the pointer.
+    ///
+    /// Returns `true` if the pointer is captured.
+    pub(crate) fn pointer_move(&self, pos: Vec2, hit: Option<NodeId>) -> bool {
+        let Some(key) = self.pointer.captured() else {
+            return false;
+        };
+
+        self.send(key, Event::MouseMove { pos });
+        for event in self.pointer.move_to(pos) {
+            if let Event::DragStart { .. } = event {
+                self.drag_start(key);
+            }
+
+            self.send(key, event);
+        }
+        self.drag_over(hit);
+
+        true
+    }
+
+    /// Send a mouse input at `pos` to `target`, or to the node capturing the pointer.
+    ///
+    /// Pressing a button captures the pointer for `target` until it's released,
+    /// and releasing the left button sends tap or drag end events,
+    /// dropping any dragged payload on the drop target under the cursor.
+    pub(crate) fn pointer_input(
+        &self,
+        target: Option<NodeId>,
+        button: MouseButton,
+        state: ElementState,
+        pos: Vec2,
+        now: Instant,
+    ) {
+        let event = Event::MouseInput { button, state, pos };
+
+        match state {
+            ElementState::Pressed => {
+                let Some(key) = target else {
+                    return;
+                };
+
+                if self.pointer.press.get().is_none() {
+                    self.pointer.press.set(Some(Press {
+                        key,
+                        button,
+                        start: pos,
+                        last: pos,
+                        is_dragging: false,
+                    }));
+                }
+
+                self.send(key, event);
+            }
+            ElementState::Released => match self.pointer.press.get() {
+                Some(press) if press.button == button => {
+                    self.pointer.press.set(None);
+
+                    self.send(press.key, event);
+                    for event in self.pointer.release(press, pos, now) {
+                        let is_drag_end = matches!(event, Event::DragEnd { .. });
+                        self.send(press.key, event);
+
+                        if is_drag_end {
+                            self.drag_over(target);
+                            self.drag_end();
+                        }
+                    }
+                }
+                Some(press) => self.send(press.key, event),
+                None => {
+                    if let Some(key) = target {
+                        self.send(key, event);
+                    }
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,517 @@
+use peniko::{
+    kurbo::{Affine, BezPath, Circle, Rect, RoundedRect, Stroke},
+    Brush, BrushRef, Color, Fill, Font, Image, Mix,
+};
+use vello::{skrifa::instance::NormalizedCoord, Scene};
+
+#[cfg(feature = "software")]
+mod software;
+
+#[cfg(feature = "software")]
+#[cfg_attr(docsrs, doc(cfg(feature = "software")))]
+pub use self::software::SoftwareRenderer;
+
+/// Shape drawn by a [`Renderer`].
+#[derive(Clone, Copy, Debug)]
+pub enum ShapeRef<'a> {
+    /// Rectangle.
+    Rect(Rect),
+    /// Rectangle with rounded corners.
+    RoundedRect(RoundedRect),
+    /// Circle.
+    Circle(Circle),
+    /// Path of lines and curves.
+    Path(&'a BezPath),
+}
+
+impl From<Rect> for ShapeRef<'_> {
+    fn from(value: Rect) -> Self {
+        Self::Rect(value)
+    }
+}
+
+impl From<RoundedRect> for ShapeRef<'_> {
+    fn from(value: RoundedRect) -> Self {
+        Self::RoundedRect(value)
+    }
+}
+
+impl From<Circle> for ShapeRef<'_> {
+    fn from(value: Circle) -> Self {
+        Self::Circle(value)
+    }
+}
+
+impl<'a> From<&'a BezPath> for ShapeRef<'a> {
+    fn from(value: &'a BezPath) -> Self {
+        Self::Path(value)
+    }
+}
+
+/// Owned [`ShapeRef`], stored in a [`Recording`].
+#[derive(Clone, Debug)]
+enum Shape {
+    Rect(Rect),
+    RoundedRect(RoundedRect),
+    Circle(Circle),
+    Path(BezPath),
+}
+
+impl Shape {
+    fn as_ref(&self) -> ShapeRef<'_> {
+        match self {
+            Self::Rect(rect) => ShapeRef::Rect(*rect),
+            Self::RoundedRect(rect) => ShapeRef::RoundedRect(*rect),
+            Self::Circle(circle) => ShapeRef::Circle(*circle),
+            Self::Path(path) => ShapeRef::Path(path),
+        }
+    }
+}
+
+impl From<ShapeRef<'_>> for Shape {
+    fn from(value: ShapeRef<'_>) -> Self {
+        match value {
+            ShapeRef::Rect(rect) => Self::Rect(rect),
+            ShapeRef::RoundedRect(rect) => Self::RoundedRect(rect),
+            ShapeRef::Circle(circle) => Self::Circle(circle),
+            ShapeRef::Path(path) => Self::Path(path.clone()),
+        }
+    }
+}
+
+/// Glyph positioned relative to the transform of its [`GlyphRun`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Glyph {
+    /// Glyph identifier in its font.
+    pub id: u32,
+
+    /// X-offset of the glyph.
+    pub x: f32,
+
+    /// Y-offset of the glyph's baseline.
+    pub y: f32,
+}
+
+/// Run of glyphs with the same font and brush, drawn by a [`Renderer`].
+#[derive(Clone, Copy, Debug)]
+pub struct GlyphRun<'a> {
+    /// Font of each glyph.
+    pub font: &'a Font,
+
+    /// Font size, in pixels.
+    pub font_size: f32,
+
+    /// Normalized variation coordinates of the font, as 2.14 fixed-point numbers.
+    pub normalized_coords: &'a [i16],
+
+    /// Transform of the run.
+    pub transform: Affine,
+
+    /// Transform of each glyph (for example, to skew synthesized italics).
+    pub glyph_transform: Option<Affine>,
+
+    /// Brush to fill each glyph with.
+    pub brush: BrushRef<'a>,
+
+    /// Glyphs in this run, positioned relative to the run's transform.
+    pub glyphs: &'a [Glyph],
+}
+
+/// Backend for drawing views.
+///
+/// Each canvas records its drawing (including draw modifiers and decorations),
+/// positioned at the canvas' origin, and the window draws each recording with its renderer.
+/// Shapes and brushes are described with [`kurbo`](peniko::kurbo) and [`peniko`],
+/// so a backend only has to rasterize them.
+/// vello's `Scene` implements this trait for windows rendered on the GPU.
+pub trait Renderer {
+    /// Begin a new frame, clearing everything drawn in the last frame.
+    fn begin_frame(&mut self);
+
+    /// End the current frame.
+    fn end_frame(&mut self) {}
+
+    /// Fill `shape` with `brush`.
+    fn fill(&mut self, transform: Affine, brush: BrushRef<'_>, shape: ShapeRef<'_>);
+
+    /// Stroke the outline of `shape` with `brush`.
+    fn stroke(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        brush: BrushRef<'_>,
+        shape: ShapeRef<'_>,
+    );
+
+    /// Draw a blurred rounded rectangle, such as a shadow.
+    ///
+    /// `std_dev` is the standard deviation of the blur.
+    fn draw_blurred_rect(
+        &mut self,
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    );
+
+    /// Draw an image with its top-left corner at the origin of `transform`.
+    fn draw_image(&mut self, image: &Image, transform: Affine);
+
+    /// Draw a run of glyphs.
+    fn draw_glyphs(&mut self, run: GlyphRun<'_>);
+
+    /// Clip everything drawn until the matching [`Renderer::pop_clip`] to `shape`.
+    fn push_clip(&mut self, transform: Affine, shape: ShapeRef<'_>);
+
+    /// Remove the last clip pushed with [`Renderer::push_clip`].
+    fn pop_clip(&mut self);
+
+    /// Draw everything until the matching [`Renderer::pop_opacity`] as a group,
+    /// composited with `opacity` inside `bounds`.
+    fn push_opacity(&mut self, opacity: f32, transform: Affine, bounds: ShapeRef<'_>);
+
+    /// Composite the last group pushed with [`Renderer::push_opacity`].
+    fn pop_opacity(&mut self);
+}
+
+/// Command recorded by a [`Recording`].
+#[derive(Clone, Debug)]
+enum Command {
+    Fill {
+        transform: Affine,
+        brush: Brush,
+        shape: Shape,
+    },
+    Stroke {
+        style: Stroke,
+        transform: Affine,
+        brush: Brush,
+        shape: Shape,
+    },
+    BlurredRect {
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    },
+    Image {
+        image: Image,
+        transform: Affine,
+    },
+    Glyphs {
+        font: Font,
+        font_size: f32,
+        normalized_coords: Vec<i16>,
+        transform: Affine,
+        glyph_transform: Option<Affine>,
+        brush: Brush,
+        glyphs: Vec<Glyph>,
+    },
+    PushClip {
+        transform: Affine,
+        shape: Shape,
+    },
+    PopClip,
+    PushOpacity {
+        opacity: f32,
+        transform: Affine,
+        bounds: Shape,
+    },
+    PopOpacity,
+}
+
+/// Renderer that records commands to draw again later with another [`Renderer`].
+///
+/// Each canvas records its fragment into one of these,
+/// so a fragment can be moved without being recorded again.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Recording {
+    commands: Vec<Command>,
+}
+
+impl Recording {
+    /// Draw the recorded commands with `renderer`, transformed by `transform`.
+    pub(crate) fn replay(&self, renderer: &mut dyn Renderer, transform: Affine) {
+        for command in &self.commands {
+            match command {
+                Command::Fill {
+                    transform: t,
+                    brush,
+                    shape,
+                } => renderer.fill(transform * *t, brush.into(), shape.as_ref()),
+                Command::Stroke {
+                    style,
+                    transform: t,
+                    brush,
+                    shape,
+                } => renderer.stroke(style, transform * *t, brush.into(), shape.as_ref()),
+                Command::BlurredRect {
+                    transform: t,
+                    rect,
+                    color,
+                    radius,
+                    std_dev,
+                } => renderer.draw_blurred_rect(transform * *t, *rect, *color, *radius, *std_dev),
+                Command::Image {
+                    image,
+                    transform: t,
+                } => renderer.draw_image(image, transform * *t),
+                Command::Glyphs {
+                    font,
+                    font_size,
+                    normalized_coords,
+                    transform: t,
+                    glyph_transform,
+                    brush,
+                    glyphs,
+                } => renderer.draw_glyphs(GlyphRun {
+                    font,
+                    font_size: *font_size,
+                    normalized_coords,
+                    transform: transform * *t,
+                    glyph_transform: *glyph_transform,
+                    brush: brush.into(),
+                    glyphs,
+                }),
+                Command::PushClip {
+                    transform: t,
+                    shape,
+                } => renderer.push_clip(transform * *t, shape.as_ref()),
+                Command::PopClip => renderer.pop_clip(),
+                Command::PushOpacity {
+                    opacity,
+                    transform: t,
+                    bounds,
+                } => renderer.push_opacity(*opacity, transform * *t, bounds.as_ref()),
+                Command::PopOpacity => renderer.pop_opacity(),
+            }
+        }
+    }
+}
+
+impl Renderer for Recording {
+    fn begin_frame(&mut self) {
+        self.commands.clear();
+    }
+
+    fn fill(&mut self, transform: Affine, brush: BrushRef<'_>, shape: ShapeRef<'_>) {
+        self.commands.push(Command::Fill {
+            transform,
+            brush: brush.to_owned(),
+            shape: shape.into(),
+        });
+    }
+
+    fn stroke(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        brush: BrushRef<'_>,
+        shape: ShapeRef<'_>,
+    ) {
+        self.commands.push(Command::Stroke {
+            style: style.clone(),
+            transform,
+            brush: brush.to_owned(),
+            shape: shape.into(),
+        });
+    }
+
+    fn draw_blurred_rect(
+        &mut self,
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    ) {
+        self.commands.push(Command::BlurredRect {
+            transform,
+            rect,
+            color,
+            radius,
+            std_dev,
+        });
+    }
+
+    fn draw_image(&mut self, image: &Image, transform: Affine) {
+        self.commands.push(Command::Image {
+            image: image.clone(),
+            transform,
+        });
+    }
+
+    fn draw_glyphs(&mut self, run: GlyphRun<'_>) {
+        self.commands.push(Command::Glyphs {
+            font: run.font.clone(),
+            font_size: run.font_size,
+            normalized_coords: run.normalized_coords.to_vec(),
+            transform: run.transform,
+            glyph_transform: run.glyph_transform,
+            brush: run.brush.to_owned(),
+            glyphs: run.glyphs.to_vec(),
+        });
+    }
+
+    fn push_clip(&mut self, transform: Affine, shape: ShapeRef<'_>) {
+        self.commands.push(Command::PushClip {
+            transform,
+            shape: shape.into(),
+        });
+    }
+
+    fn pop_clip(&mut self) {
+        self.commands.push(Command::PopClip);
+    }
+
+    fn push_opacity(&mut self, opacity: f32, transform: Affine, bounds: ShapeRef<'_>) {
+        self.commands.push(Command::PushOpacity {
+            opacity,
+            transform,
+            bounds: bounds.into(),
+        });
+    }
+
+    fn pop_opacity(&mut self) {
+        self.commands.push(Command::PopOpacity);
+    }
+}
+
+/// Call `$f` with a reference to the concrete shape of `$shape`.
+macro_rules! with_shape {
+    ($shape:expr, |$s:ident| $f:expr) => {
+        match $shape {
+            ShapeRef::Rect(ref $s) => $f,
+            ShapeRef::RoundedRect(ref $s) => $f,
+            ShapeRef::Circle(ref $s) => $f,
+            ShapeRef::Path($s) => $f,
+        }
+    };
+}
+
+impl Renderer for Scene {
+    fn begin_frame(&mut self) {
+        self.reset();
+    }
+
+    fn fill(&mut self, transform: Affine, brush: BrushRef<'_>, shape: ShapeRef<'_>) {
+        with_shape!(shape, |s| Scene::fill(
+            self,
+            Fill::NonZero,
+            transform,
+            brush,
+            None,
+            s
+        ))
+    }
+
+    fn stroke(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        brush: BrushRef<'_>,
+        shape: ShapeRef<'_>,
+    ) {
+        with_shape!(shape, |s| Scene::stroke(
+            self, style, transform, brush, None, s
+        ))
+    }
+
+    fn draw_blurred_rect(
+        &mut self,
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    ) {
+        self.draw_blurred_rounded_rect(transform, rect, color, radius, std_dev);
+    }
+
+    fn draw_image(&mut self, image: &Image, transform: Affine) {
+        Scene::draw_image(self, image, transform);
+    }
+
+    fn draw_glyphs(&mut self, run: GlyphRun<'_>) {
+        let coords: Vec<_> = run
+            .normalized_coords
+            .iter()
+            .map(|coord| NormalizedCoord::from_bits(*coord))
+            .collect();
+        Scene::draw_glyphs(self, run.font)
+            .brush(run.brush)
+            .hint(true)
+            .transform(run.transform)
+            .glyph_transform(run.glyph_transform)
+            .font_size(run.font_size)
+            .normalized_coords(&coords)
+            .draw(
+                Fill::NonZero,
+                run.glyphs.iter().map(|glyph| vello::Glyph {
+                    id: glyph.id,
+                    x: glyph.x,
+                    y: glyph.y,
+                }),
+            );
+    }
+
+    fn push_clip(&mut self, transform: Affine, shape: ShapeRef<'_>) {
+        with_shape!(shape, |s| self.push_layer(Mix::Clip, 1., transform, s))
+    }
+
+    fn pop_clip(&mut self) {
+        self.pop_layer();
+    }
+
+    fn push_opacity(&mut self, opacity: f32, transform: Affine, bounds: ShapeRef<'_>) {
+        with_shape!(bounds, |s| self.push_layer(
+            Mix::Normal,
+            opacity,
+            transform,
+            s
+        ))
+    }
+
+    fn pop_opacity(&mut self) {
+        self.pop_layer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Command, Recording, Renderer};
+    use peniko::{
+        kurbo::{Affine, Rect},
+        Color,
+    };
+
+    #[test]
+    fn it_replays_recordings_with_transforms() {
+        let mut recording = Recording::default();
+        recording.push_clip(Affine::IDENTITY, Rect::new(0., 0., 10., 10.).into());
+        recording.fill(
+            Affine::translate((1., 2.)),
+            (&Color::BLACK).into(),
+            Rect::new(0., 0., 5., 5.).into(),
+        );
+        recording.pop_clip();
+
+        let mut replayed = Recording::default();
+        recording.replay(&mut replayed, Affine::translate((10., 20.)));
+
+        let transforms: Vec<_> = replayed
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::PushClip { transform, .. } | Command::Fill { transform, .. } => {
+                    Some(transform.translation())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(transforms, [(10., 20.).into(), (11., 22.).into()]);
+        assert!(matches!(replayed.commands.last(), Some(Command::PopClip)));
+    }
+}
@@ -0,0 +1,533 @@
+use super::{GlyphRun, Renderer, ShapeRef};
+#[cfg(any(test, feature = "testing"))]
+use crate::ui::testing::Snapshot;
+use peniko::{
+    kurbo::{self, Affine, PathEl, Rect, RoundedRect, Shape},
+    BrushRef, Color, Extend, Format, GradientKind, Image,
+};
+use skrifa::{
+    instance::{LocationRef, NormalizedCoord, Size},
+    outline::{DrawSettings, OutlinePen},
+    FontRef, GlyphId, MetadataProvider,
+};
+use tiny_skia::{
+    FillRule, FilterQuality, GradientStop, LineCap, LineJoin, LinearGradient, Mask, Paint, Path,
+    PathBuilder, Pattern, Pixmap, PixmapPaint, Point, RadialGradient, SpreadMode, StrokeDash,
+    Transform,
+};
+
+/// Tolerance for flattening curves of shapes, in pixels.
+const TOLERANCE: f64 = 0.1;
+
+/// Number of rounded rectangles blended to approximate a blurred rectangle.
+const BLUR_STEPS: usize = 8;
+
+/// Group drawn into its own pixmap, composited into the group below it once popped.
+struct Layer {
+    pixmap: Pixmap,
+    mask: Option<Mask>,
+    opacity: f32,
+}
+
+/// Renderer that rasterizes on the CPU with tiny-skia.
+///
+/// This draws without a GPU, such as in VMs and CI machines without a compatible wgpu adapter.
+/// Blurred rectangles are approximated, and sweep gradients are drawn with their middle color.
+pub struct SoftwareRenderer {
+    pixmap: Pixmap,
+    layers: Vec<Layer>,
+    base_color: Color,
+    transform: Affine,
+}
+
+impl SoftwareRenderer {
+    /// Create a new renderer for a frame of the given size, cleared to `base_color` at the start of each frame.
+    ///
+    /// # Panics
+    /// Panics if `width` or `height` is zero.
+    pub fn new(width: u32, height: u32, base_color: Color) -> Self {
+        Self {
+            pixmap: Pixmap::new(width, height).expect("Frame size must not be zero"),
+            layers: Vec::new(),
+            base_color,
+            transform: Affine::IDENTITY,
+        }
+    }
+
+    /// Width of each frame.
+    pub fn width(&self) -> u32 {
+        self.pixmap.width()
+    }
+
+    /// Height of each frame.
+    pub fn height(&self) -> u32 {
+        self.pixmap.height()
+    }
+
+    /// Resize the next frame, if `width` and `height` aren't zero.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if (width, height) != (self.width(), self.height()) {
+            if let Some(pixmap) = Pixmap::new(width, height) {
+                self.pixmap = pixmap;
+            }
+        }
+    }
+
+    /// Set the transform applied to everything drawn (for example, to scale to physical pixels).
+    pub fn set_transform(&mut self, transform: Affine) {
+        self.transform = transform;
+    }
+
+    /// Copy the last frame to a [`Snapshot`].
+    #[cfg(any(test, feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+    pub fn to_snapshot(&self) -> Snapshot {
+        let pixels = self
+            .pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect();
+
+        Snapshot {
+            width: self.width(),
+            height: self.height(),
+            pixels,
+        }
+    }
+
+    /// Copy the last frame to `buffer` as `0RGB` pixels, composited over black.
+    pub(crate) fn copy_to(&self, buffer: &mut [u32]) {
+        for (dst, pixel) in buffer.iter_mut().zip(self.pixmap.pixels()) {
+            *dst = (pixel.red() as u32) << 16 | (pixel.green() as u32) << 8 | pixel.blue() as u32;
+        }
+    }
+
+    /// Get the pixmap of the topmost layer.
+    fn target(&mut self) -> &mut Pixmap {
+        self.layers
+            .last_mut()
+            .map_or(&mut self.pixmap, |layer| &mut layer.pixmap)
+    }
+
+    fn to_transform(&self, transform: Affine) -> Transform {
+        let [sx, ky, kx, sy, tx, ty] = (self.transform * transform).as_coeffs();
+        Transform::from_row(sx as _, ky as _, kx as _, sy as _, tx as _, ty as _)
+    }
+
+    fn push_layer(&mut self, opacity: f32, transform: Affine, shape: ShapeRef<'_>) {
+        let transform = self.to_transform(transform);
+        let mut mask = Mask::new(self.width(), self.height()).unwrap();
+        if let Some(path) = to_path(shape) {
+            mask.fill_path(&path, FillRule::Winding, true, transform);
+        }
+
+        self.layers.push(Layer {
+            pixmap: Pixmap::new(self.width(), self.height()).unwrap(),
+            mask: Some(mask),
+            opacity,
+        });
+    }
+
+    fn pop_layer(&mut self) {
+        let Some(layer) = self.layers.pop() else {
+            return;
+        };
+
+        let paint = PixmapPaint {
+            opacity: layer.opacity,
+            ..PixmapPaint::default()
+        };
+        self.target().draw_pixmap(
+            0,
+            0,
+            layer.pixmap.as_ref(),
+            &paint,
+            Transform::identity(),
+            layer.mask.as_ref(),
+        );
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn begin_frame(&mut self) {
+        self.layers.clear();
+
+        let Color { r, g, b, a } = self.base_color;
+        self.pixmap
+            .fill(tiny_skia::Color::from_rgba8(r, g, b, a));
+    }
+
+    fn fill(&mut self, transform: Affine, brush: BrushRef<'_>, shape: ShapeRef<'_>) {
+        let Some(path) = to_path(shape) else {
+            return;
+        };
+
+        let transform = self.to_transform(transform);
+        with_paint(brush, |paint| {
+            self.target()
+                .fill_path(&path, paint, FillRule::Winding, transform, None)
+        });
+    }
+
+    fn stroke(
+        &mut self,
+        style: &kurbo::Stroke,
+        transform: Affine,
+        brush: BrushRef<'_>,
+        shape: ShapeRef<'_>,
+    ) {
+        let Some(path) = to_path(shape) else {
+            return;
+        };
+
+        let stroke = tiny_skia::Stroke {
+            width: style.width as _,
+            miter_limit: style.miter_limit as _,
+            line_cap: match style.start_cap {
+                kurbo::Cap::Butt => LineCap::Butt,
+                kurbo::Cap::Square => LineCap::Square,
+                kurbo::Cap::Round => LineCap::Round,
+            },
+            line_join: match style.join {
+                kurbo::Join::Bevel => LineJoin::Bevel,
+                kurbo::Join::Miter => LineJoin::Miter,
+                kurbo::Join::Round => LineJoin::Round,
+            },
+            dash: StrokeDash::new(
+                style.dash_pattern.iter().map(|len| *len as f32).collect(),
+                style.dash_offset as _,
+            ),
+        };
+
+        let transform = self.to_transform(transform);
+        with_paint(brush, |paint| {
+            self.target()
+                .stroke_path(&path, paint, &stroke, transform, None)
+        });
+    }
+
+    fn draw_blurred_rect(
+        &mut self,
+        transform: Affine,
+        rect: Rect,
+        color: Color,
+        radius: f64,
+        std_dev: f64,
+    ) {
+        // Blend rectangles spread evenly across the blur, fading each edge linearly.
+        let extent = std_dev * 1.5;
+        let step_color = color.multiply_alpha(1. / BLUR_STEPS as f32);
+        for idx in 0..BLUR_STEPS {
+            let inset = extent - 2. * extent * (idx as f64 + 0.5) / BLUR_STEPS as f64;
+            let step_rect = rect.inflate(inset, inset);
+            if step_rect.width() <= 0. || step_rect.height() <= 0. {
+                continue;
+            }
+
+            let shape = RoundedRect::from_rect(step_rect, (radius + inset).max(0.));
+            self.fill(transform, (&step_color).into(), shape.into());
+        }
+    }
+
+    fn draw_image(&mut self, image: &Image, transform: Affine) {
+        let Some(pixmap) = to_pixmap(image) else {
+            return;
+        };
+
+        let paint = PixmapPaint {
+            opacity: image.alpha as f32 / 255.,
+            quality: FilterQuality::Bilinear,
+            ..PixmapPaint::default()
+        };
+        let transform = self.to_transform(transform);
+        self.target()
+            .draw_pixmap(0, 0, pixmap.as_ref(), &paint, transform, None);
+    }
+
+    fn draw_glyphs(&mut self, run: GlyphRun<'_>) {
+        let Ok(font) = FontRef::from_index(run.font.data.as_ref(), run.font.index) else {
+            return;
+        };
+        let outlines = font.outline_glyphs();
+        let coords: Vec<_> = run
+            .normalized_coords
+            .iter()
+            .map(|coord| NormalizedCoord::from_bits(*coord))
+            .collect();
+        let location = LocationRef::new(&coords);
+
+        let mut builder = PathBuilder::new();
+        for glyph in run.glyphs {
+            let Some(outline) = outlines.get(GlyphId::new(glyph.id)) else {
+                continue;
+            };
+
+            // Outlines are drawn with the y-axis pointing up.
+            let transform = run.transform
+                * Affine::translate((glyph.x as f64, glyph.y as f64))
+                * run.glyph_transform.unwrap_or_default()
+                * Affine::FLIP_Y;
+
+            let mut pen = PathPen(builder);
+            let settings = DrawSettings::unhinted(Size::new(run.font_size), location);
+            let is_drawn = outline.draw(settings, &mut pen).is_ok();
+            let path = pen.0.finish();
+            builder = PathBuilder::new();
+
+            let (true, Some(path)) = (is_drawn, path) else {
+                continue;
+            };
+            let transform = self.to_transform(transform);
+            with_paint(run.brush, |paint| {
+                self.target()
+                    .fill_path(&path, paint, FillRule::Winding, transform, None)
+            });
+        }
+    }
+
+    fn push_clip(&mut self, transform: Affine, shape: ShapeRef<'_>) {
+        self.push_layer(1., transform, shape);
+    }
+
+    fn pop_clip(&mut self) {
+        self.pop_layer();
+    }
+
+    fn push_opacity(&mut self, opacity: f32, transform: Affine, bounds: ShapeRef<'_>) {
+        self.push_layer(opacity, transform, bounds);
+    }
+
+    fn pop_opacity(&mut self) {
+        self.pop_layer();
+    }
+}
+
+/// Pen that builds a tiny-skia path from a glyph outline.
+struct PathPen(PathBuilder);
+
+impl OutlinePen for PathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.0.quad_to(cx0, cy0, x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.0.cubic_to(cx0, cy0, cx1, cy1, x, y);
+    }
+
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Convert `shape` to a path, or `None` if it's empty.
+fn to_path(shape: ShapeRef<'_>) -> Option<Path> {
+    let mut builder = PathBuilder::new();
+    let mut push = |el: PathEl| match el {
+        PathEl::MoveTo(p) => builder.move_to(p.x as _, p.y as _),
+        PathEl::LineTo(p) => builder.line_to(p.x as _, p.y as _),
+        PathEl::QuadTo(p1, p2) => builder.quad_to(p1.x as _, p1.y as _, p2.x as _, p2.y as _),
+        PathEl::CurveTo(p1, p2, p3) => builder.cubic_to(
+            p1.x as _, p1.y as _, p2.x as _, p2.y as _, p3.x as _, p3.y as _,
+        ),
+        PathEl::ClosePath => builder.close(),
+    };
+
+    match shape {
+        ShapeRef::Rect(rect) => rect.path_elements(TOLERANCE).for_each(&mut push),
+        ShapeRef::RoundedRect(rect) => rect.path_elements(TOLERANCE).for_each(&mut push),
+        ShapeRef::Circle(circle) => circle.path_elements(TOLERANCE).for_each(&mut push),
+        ShapeRef::Path(path) => path.path_elements(TOLERANCE).for_each(&mut push),
+    }
+
+    builder.finish()
+}
+
+fn to_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, color.a)
+}
+
+fn to_point(point: kurbo::Point) -> Point {
+    Point::from_xy(point.x as _, point.y as _)
+}
+
+fn to_spread_mode(extend: Extend) -> SpreadMode {
+    match extend {
+        Extend::Pad => SpreadMode::Pad,
+        Extend::Repeat => SpreadMode::Repeat,
+        Extend::Reflect => SpreadMode::Reflect,
+    }
+}
+
+/// Convert `image` to a pixmap with premultiplied alpha.
+fn to_pixmap(image: &Image) -> Option<Pixmap> {
+    if !matches!(image.format, Format::Rgba8) {
+        return None;
+    }
+
+    let mut pixmap = Pixmap::new(image.width, image.height)?;
+    for (dst, src) in pixmap
+        .pixels_mut()
+        .iter_mut()
+        .zip(image.data.as_ref().chunks_exact(4))
+    {
+        *dst = tiny_skia::ColorU8::from_rgba(src[0], src[1], src[2], src[3]).premultiply();
+    }
+    Some(pixmap)
+}
+
+/// Call `f` with the paint for `brush`, unless it can't be drawn.
+fn with_paint(brush: BrushRef<'_>, f: impl FnOnce(&Paint<'_>)) {
+    // Image brushes are converted to a pixmap borrowed by their paint.
+    let pixmap;
+
+    let shader = match brush {
+        BrushRef::Solid(color) => tiny_skia::Shader::SolidColor(to_color(color)),
+        BrushRef::Gradient(gradient) => {
+            let stops: Vec<_> = gradient
+                .stops
+                .iter()
+                .map(|stop| GradientStop::new(stop.offset, to_color(stop.color)))
+                .collect();
+            let mode = to_spread_mode(gradient.extend);
+
+            let shader = match gradient.kind {
+                GradientKind::Linear { start, end } => LinearGradient::new(
+                    to_point(start),
+                    to_point(end),
+                    stops,
+                    mode,
+                    Transform::identity(),
+                ),
+                GradientKind::Radial {
+                    start_center,
+                    end_center,
+                    end_radius,
+                    ..
+                } => RadialGradient::new(
+                    to_point(start_center),
+                    to_point(end_center),
+                    end_radius,
+                    stops,
+                    mode,
+                    Transform::identity(),
+                ),
+                GradientKind::Sweep { .. } => gradient
+                    .stops
+                    .get(gradient.stops.len() / 2)
+                    .map(|stop| tiny_skia::Shader::SolidColor(to_color(stop.color))),
+            };
+            let Some(shader) = shader else {
+                return;
+            };
+            shader
+        }
+        BrushRef::Image(image) => {
+            let Some(image_pixmap) = to_pixmap(image) else {
+                return;
+            };
+            pixmap = image_pixmap;
+
+            Pattern::new(
+                pixmap.as_ref(),
+                to_spread_mode(image.extend),
+                FilterQuality::Bilinear,
+                image.alpha as f32 / 255.,
+                Transform::identity(),
+            )
+        }
+    };
+
+    f(&Paint {
+        shader,
+        anti_alias: true,
+        ..Paint::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SoftwareRenderer;
+    use crate::{
+        prelude::*,
+        ui::{testing::TestWindow, Renderer},
+    };
+    use peniko::{
+        kurbo::{Affine, Rect},
+        Color,
+    };
+
+    #[test]
+    fn it_fills_clipped_shapes() {
+        let mut renderer = SoftwareRenderer::new(20, 20, Color::WHITE);
+        renderer.set_transform(Affine::scale(2.));
+        renderer.begin_frame();
+
+        renderer.push_clip(Affine::IDENTITY, Rect::new(0., 0., 5., 10.).into());
+        renderer.fill(
+            Affine::IDENTITY,
+            (&Color::BLACK).into(),
+            Rect::new(0., 0., 10., 10.).into(),
+        );
+        renderer.pop_clip();
+
+        let snapshot = renderer.to_snapshot();
+        assert_eq!(snapshot.pixel(5, 5), [0, 0, 0, 255]);
+        assert_eq!(snapshot.pixel(15, 5), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn it_composites_opacity_groups() {
+        let mut renderer = SoftwareRenderer::new(10, 10, Color::WHITE);
+        renderer.begin_frame();
+
+        let bounds = Rect::new(0., 0., 10., 10.);
+        renderer.push_opacity(0.5, Affine::IDENTITY, bounds.into());
+        // Overlapping shapes in a group are composited together.
+        for _ in 0..2 {
+            renderer.fill(Affine::IDENTITY, (&Color::BLACK).into(), bounds.into());
+        }
+        renderer.pop_opacity();
+
+        let [r, g, b, a] = renderer.to_snapshot().pixel(5, 5);
+        assert!((126..=129).contains(&r), "{r}");
+        assert_eq!((r, r, 255), (g, b, a));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_renders_test_windows_with_or_without_a_gpu() {
+        let mut window = TestWindow::new(Canvas::new(
+            Style {
+                size: Size::from_lengths(10., 10.),
+                ..Default::default()
+            },
+            |_, renderer| {
+                renderer.fill(
+                    Affine::IDENTITY,
+                    (&Color::BLACK).into(),
+                    Rect::new(0., 0., 10., 10.).into(),
+                );
+            },
+        ));
+        // Draw the canvas after its layout is computed.
+        window.frame();
+        window.frame();
+
+        // Without a GPU device, this falls back to rendering on the CPU.
+        let snapshot = window.render(20, 20).unwrap();
+        assert_eq!(snapshot.pixel(5, 5), [0, 0, 0, 255]);
+        assert_eq!(snapshot.pixel(15, 15), [255, 255, 255, 255]);
+    }
+}
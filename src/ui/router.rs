@@ -0,0 +1,411 @@
+use crate::{
+    compose::AnyCompose,
+    prelude::*,
+    ui::{use_layout, view::canvas::use_container_fragment, LayoutContext},
+    ScopeData,
+};
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+};
+
+/// Create a new router from its initial route and a function to compose the screen of a route.
+pub fn router<'a, R, C>(initial: R, f: impl Fn(&R) -> C + 'a) -> Router<'a, R, C> {
+    Router::new(initial, f)
+}
+
+/// Back stack of a [`Router`].
+struct Stack<R> {
+    /// Unique ID and route of each entry, from the bottom to the top of the stack.
+    entries: Vec<(u64, R)>,
+    next_id: u64,
+}
+
+impl<R> Stack<R> {
+    fn push(&mut self, route: R) {
+        self.entries.push((self.next_id, route));
+        self.next_id += 1;
+    }
+}
+
+/// Navigator of a [`Router`].
+///
+/// Each change to the back stack is queued, and applied before the router is recomposed.
+///
+/// This can be used with [`use_navigator`].
+pub struct Navigator<R> {
+    stack: Signal<Stack<R>>,
+}
+
+impl<R: Send + 'static> Navigator<R> {
+    /// Push `route` to the top of the back stack, showing its screen.
+    pub fn push(&self, route: R) {
+        self.stack.update(move |stack| stack.push(route));
+    }
+
+    /// Pop the top route from the back stack, showing the screen below it.
+    ///
+    /// The initial route is never popped.
+    pub fn pop(&self) {
+        self.stack.update(|stack| {
+            if stack.entries.len() > 1 {
+                stack.entries.pop();
+            }
+        });
+    }
+
+    /// Replace the top route of the back stack with `route`.
+    ///
+    /// The screen of the replaced route is torn down, and the screen of `route` is composed from scratch.
+    pub fn replace(&self, route: R) {
+        self.stack.update(move |stack| {
+            stack.entries.pop();
+            stack.push(route);
+        });
+    }
+
+    /// Returns `true` if the back stack has a route to pop.
+    pub fn can_pop(&self) -> bool {
+        self.stack.lock().entries.len() > 1
+    }
+}
+
+impl<R: Clone> Navigator<R> {
+    /// Get the current route, at the top of the back stack.
+    pub fn current(&self) -> R {
+        let stack = self.stack.lock();
+        stack.entries.last().unwrap().1.clone()
+    }
+
+    /// Get the routes of the back stack, from the bottom to the top.
+    pub fn routes(&self) -> Vec<R> {
+        let stack = self.stack.lock();
+        stack
+            .entries
+            .iter()
+            .map(|(_, route)| route.clone())
+            .collect()
+    }
+}
+
+impl<R> Clone for Navigator<R> {
+    fn clone(&self) -> Self {
+        Self {
+            stack: self.stack.clone(),
+        }
+    }
+}
+
+/// Use the [`Navigator`] of the nearest [`Router`] with routes of type `R`.
+///
+/// This must be used inside of a [`Router`].
+pub fn use_navigator<R: 'static>(cx: ScopeState) -> Navigator<R> {
+    use_context::<Navigator<R>>(cx).unwrap().clone()
+}
+
+/// Router composable.
+///
+/// A router shows the screen of the route at the top of its back stack,
+/// which can be changed from its content with [`use_navigator`].
+/// Routes can be of any type (such as an enum with a variant for each screen), with their parameters as fields.
+///
+/// By default, the screens of routes below the top of the stack are kept alive:
+/// their content stays composed (and keeps its hook state) but is hidden from the window.
+/// With [`Router::with_keep_alive`], they can instead be torn down and composed again from scratch when they're shown.
+/// Screens popped from the stack or replaced are always torn down.
+///
+/// The back stack is kept in memory, starting with the router's initial route.
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Router<'a, R, C> {
+    initial: R,
+    f: Box<dyn Fn(&R) -> C + 'a>,
+    is_keep_alive: bool,
+}
+
+unsafe impl<R: Data, C: Data> Data for Router<'_, R, C> {}
+
+impl<'a, R, C> Router<'a, R, C> {
+    /// Create a new router from its initial route and a function to compose the screen of a route.
+    pub fn new(initial: R, f: impl Fn(&R) -> C + 'a) -> Self {
+        Self {
+            initial,
+            f: Box::new(f),
+            is_keep_alive: true,
+        }
+    }
+
+    /// Set whether the screens of routes below the top of the back stack are kept alive (default: `true`).
+    pub fn with_keep_alive(mut self, is_keep_alive: bool) -> Self {
+        self.is_keep_alive = is_keep_alive;
+        self
+    }
+}
+
+/// Composed screen of a route.
+struct Screen {
+    // The scope is declared first, so it's dropped before the content it borrows.
+    scope: ScopeData<'static>,
+    id: u64,
+    compose: Box<dyn AnyCompose>,
+}
+
+impl<R, C> Compose for Router<'_, R, C>
+where
+    R: Clone + Data + Send + 'static,
+    C: Compose,
+{
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let (key, layout) = use_layout(
+            &cx,
+            Style {
+                flex_direction: FlexDirection::Column,
+                flex_grow: 1.,
+                ..Default::default()
+            },
+        );
+        use_provider(&cx, || LayoutContext::new(key));
+        use_container_fragment(&cx, key, layout);
+
+        let stack = use_signal(&cx, || {
+            let mut stack = Stack {
+                entries: Vec::new(),
+                next_id: 0,
+            };
+            stack.push(cx.me().initial.clone());
+            stack
+        });
+        use_provider(&cx, || Navigator {
+            stack: stack.clone(),
+        });
+
+        let generation = stack.generation();
+        let last_generation = use_ref(&cx, || Cell::new(None));
+        let is_stack_changed = last_generation.replace(Some(generation)) != Some(generation);
+
+        let entries = stack.lock().entries.clone();
+        let top = use_ref(&cx, || Rc::new(Cell::new(0)));
+        top.set(entries.last().unwrap().0);
+
+        let screens = use_ref(&cx, || RefCell::new(Vec::<Box<Screen>>::new()));
+        let mut screens = screens.borrow_mut();
+
+        // Tear down the screens of removed routes, and of routes below the top if they aren't kept alive.
+        let is_keep_alive = cx.me().is_keep_alive;
+        let is_shown = |id: u64| is_keep_alive || id == top.get();
+        screens
+            .retain(|screen| is_shown(screen.id) && entries.iter().any(|(id, _)| *id == screen.id));
+
+        // Routes are only pushed to the top of the stack, so new screens are appended in order.
+        for (id, route) in &entries {
+            if !is_shown(*id) || screens.iter().any(|screen| screen.id == *id) {
+                continue;
+            }
+
+            let screen = RouteScreen {
+                router: cx.me().value,
+                route: route.clone(),
+                id: *id,
+                top: top.clone(),
+            };
+            let compose: Box<dyn AnyCompose + '_> = Box::new(screen);
+
+            // Safety: Screens are dropped with this composable's scope.
+            let compose: Box<dyn AnyCompose> = unsafe { mem::transmute(compose) };
+
+            screens.push(Box::new(Screen {
+                scope: ScopeData::default(),
+                id: *id,
+                compose,
+            }));
+        }
+
+        for screen in screens.iter() {
+            screen.scope.inherit_contexts(&cx);
+
+            screen
+                .scope
+                .is_parent_changed
+                .set(cx.is_parent_changed.get() || is_stack_changed);
+
+            unsafe { screen.compose.any_compose(&screen.scope) }
+        }
+    }
+}
+
+/// Screen of a route in a [`Router`].
+///
+/// Screens below the top of the back stack are hidden from the window.
+struct RouteScreen<'a, R, C> {
+    router: &'a Router<'a, R, C>,
+    route: R,
+    id: u64,
+    top: Rc<Cell<u64>>,
+}
+
+unsafe impl<R: Data, C: Data> Data for RouteScreen<'_, R, C> {}
+
+impl<R: Data, C: Compose> Compose for RouteScreen<'_, R, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let style = Style {
+            display: if cx.me().top.get() == cx.me().id {
+                Display::Flex
+            } else {
+                Display::None
+            },
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.,
+            ..Default::default()
+        };
+
+        Flex::new(style, (cx.me().router.f)(&cx.me().route))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{router, use_navigator, Navigator};
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Clone, Debug, PartialEq, Data)]
+    enum Route {
+        Home,
+        Detail(u32),
+    }
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    #[derive(Data)]
+    struct Screen {
+        route: Route,
+        log: Log,
+        navigator: Rc<RefCell<Option<Navigator<Route>>>>,
+    }
+
+    impl Compose for Screen {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let name = format!("{:?}", cx.me().route);
+
+            let navigator = use_navigator::<Route>(&cx);
+            use_ref(&cx, || {
+                cx.me().log.borrow_mut().push(format!("compose {name}"));
+                *cx.me().navigator.borrow_mut() = Some(navigator);
+            });
+
+            let log = cx.me().log.clone();
+            let drop_name = name.clone();
+            use_drop(&cx, move || log.borrow_mut().push(format!("drop {drop_name}")));
+
+            Text::new(name)
+        }
+    }
+
+    struct Harness {
+        window: TestWindow,
+        log: Log,
+        navigator: Rc<RefCell<Option<Navigator<Route>>>>,
+    }
+
+    impl Harness {
+        fn new(is_keep_alive: bool) -> Self {
+            let log = Log::default();
+            let navigator = Rc::new(RefCell::new(None));
+
+            let screen_log = log.clone();
+            let screen_navigator = navigator.clone();
+            let mut window = TestWindow::new(
+                router(Route::Home, move |route| Screen {
+                    route: route.clone(),
+                    log: screen_log.clone(),
+                    navigator: screen_navigator.clone(),
+                })
+                .with_keep_alive(is_keep_alive),
+            );
+            window.frame();
+
+            Self {
+                window,
+                log,
+                navigator,
+            }
+        }
+
+        fn navigate(&mut self, f: impl FnOnce(&Navigator<Route>)) {
+            f(self.navigator.borrow().as_ref().unwrap());
+            self.window.frame();
+        }
+
+        fn routes(&self) -> Vec<Route> {
+            self.navigator.borrow().as_ref().unwrap().routes()
+        }
+
+        fn is_shown(&self, label: &str) -> bool {
+            self.window
+                .find_by_label(label)
+                .is_some_and(|key| self.window.layout(key).size.width > 0.)
+        }
+
+        fn take_log(&self) -> Vec<String> {
+            self.log.take()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_navigates_the_back_stack() {
+        let mut harness = Harness::new(true);
+        assert_eq!(harness.take_log(), ["compose Home"]);
+        assert!(harness.is_shown("Home"));
+
+        harness.navigate(|navigator| navigator.push(Route::Detail(1)));
+        assert_eq!(harness.routes(), [Route::Home, Route::Detail(1)]);
+        assert!(harness.is_shown("Detail(1)"));
+        assert!(!harness.is_shown("Home"));
+
+        harness.navigate(|navigator| navigator.replace(Route::Detail(2)));
+        assert_eq!(harness.routes(), [Route::Home, Route::Detail(2)]);
+        assert!(harness.is_shown("Detail(2)"));
+
+        harness.navigate(Navigator::pop);
+        assert_eq!(harness.routes(), [Route::Home]);
+        assert!(harness.is_shown("Home"));
+
+        // The initial route is never popped.
+        harness.navigate(Navigator::pop);
+        assert_eq!(harness.routes(), [Route::Home]);
+
+        // The home screen is kept alive while it's below the top of the stack.
+        assert_eq!(
+            harness.take_log(),
+            [
+                "compose Detail(1)",
+                "drop Detail(1)",
+                "compose Detail(2)",
+                "drop Detail(2)",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_tears_down_hidden_screens_without_keep_alive() {
+        let mut harness = Harness::new(false);
+        harness.navigate(|navigator| navigator.push(Route::Detail(1)));
+        harness.navigate(Navigator::pop);
+
+        assert_eq!(
+            harness.take_log(),
+            [
+                "compose Home",
+                "drop Home",
+                "compose Detail(1)",
+                "drop Detail(1)",
+                "compose Home",
+            ]
+        );
+        assert!(harness.is_shown("Home"));
+    }
+}
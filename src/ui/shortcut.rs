@@ -0,0 +1,454 @@
+use super::{LayoutContext, WindowContext};
+use crate::prelude::*;
+use slotmap::{DefaultKey, SlotMap};
+use std::{
+    cell::{Cell, RefCell},
+    fmt, mem,
+    rc::Rc,
+    str::FromStr,
+};
+use taffy::NodeId;
+use thiserror::Error;
+use winit::keyboard::{Key, ModifiersState, NamedKey, SmolStr};
+
+/// Keyboard shortcut, such as `Ctrl+S`.
+///
+/// This can be parsed from a string of modifiers and a key separated by `+`,
+/// such as `Ctrl+Shift+Z`, `Alt+Enter`, or `CmdOrCtrl+S`.
+///
+/// Modifiers are `Ctrl` (or `Control`), `Shift`, `Alt` (or `Option`), `Super` (or `Cmd`, `Meta`),
+/// and `CmdOrCtrl`, which is `Super` on macOS and iOS, and `Ctrl` on other platforms.
+/// Keys are single characters, named keys such as `Enter`, `Escape`, `Tab`, `Space`,
+/// `Backspace`, `Delete`, `Home`, `End`, `PageUp`, `PageDown`, `Up`, `Down`, `Left`, `Right`,
+/// and `F1` to `F12`, or `Plus` for the `+` key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    /// Modifier keys that must be held.
+    pub modifiers: ModifiersState,
+    /// Logical key that must be pressed.
+    ///
+    /// Characters are matched case-insensitively.
+    pub key: Key,
+}
+
+impl Shortcut {
+    /// Create a new shortcut from its modifiers and key.
+    pub fn new(modifiers: ModifiersState, key: Key) -> Self {
+        let key = match key {
+            Key::Character(c) => Key::Character(SmolStr::new(c.to_lowercase())),
+            key => key,
+        };
+        Self { modifiers, key }
+    }
+
+    /// Returns `true` if pressing `key` while holding `modifiers` triggers this shortcut.
+    pub fn matches(&self, key: &Key, modifiers: ModifiersState) -> bool {
+        if self.modifiers != modifiers {
+            return false;
+        }
+
+        match (&self.key, key) {
+            (Key::Character(a), Key::Character(b)) => a.as_str() == b.to_lowercase(),
+            (a, b) => a == b,
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (ModifiersState::CONTROL, "Ctrl"),
+            (ModifiersState::SHIFT, "Shift"),
+            (ModifiersState::ALT, "Alt"),
+            (ModifiersState::SUPER, "Super"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match &self.key {
+            Key::Character(c) => f.write_str(&c.to_uppercase()),
+            Key::Named(named) => write!(f, "{named:?}"),
+            key => write!(f, "{key:?}"),
+        }
+    }
+}
+
+/// Error parsing a [`Shortcut`].
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ParseShortcutError {
+    /// The shortcut has no key after its modifiers.
+    #[error("Shortcut is missing a key")]
+    MissingKey,
+    /// Unknown modifier.
+    #[error("Unknown shortcut modifier `{0}`")]
+    UnknownModifier(String),
+    /// Unknown key.
+    #[error("Unknown shortcut key `{0}`")]
+    UnknownKey(String),
+}
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A trailing `++` is the `+` key, such as in `Ctrl++`.
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if s.trim() == "+" => ("", "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let mut state = ModifiersState::empty();
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            state |= parse_modifier(modifier.trim())
+                .ok_or_else(|| ParseShortcutError::UnknownModifier(modifier.to_owned()))?;
+        }
+
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(ParseShortcutError::MissingKey);
+        }
+
+        Ok(Self::new(state, parse_key(key)?))
+    }
+}
+
+fn parse_modifier(name: &str) -> Option<ModifiersState> {
+    let modifier = match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => ModifiersState::CONTROL,
+        "shift" => ModifiersState::SHIFT,
+        "alt" | "option" => ModifiersState::ALT,
+        "super" | "cmd" | "command" | "meta" => ModifiersState::SUPER,
+        "cmdorctrl" if cfg!(any(target_os = "macos", target_os = "ios")) => ModifiersState::SUPER,
+        "cmdorctrl" => ModifiersState::CONTROL,
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+fn parse_key(name: &str) -> Result<Key, ParseShortcutError> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(Key::Character(SmolStr::new(c.to_string())));
+    }
+
+    let named = match name.to_ascii_lowercase().as_str() {
+        "plus" => return Ok(Key::Character(SmolStr::new("+"))),
+        "enter" | "return" => NamedKey::Enter,
+        "escape" | "esc" => NamedKey::Escape,
+        "tab" => NamedKey::Tab,
+        "space" => NamedKey::Space,
+        "backspace" => NamedKey::Backspace,
+        "delete" | "del" => NamedKey::Delete,
+        "insert" => NamedKey::Insert,
+        "home" => NamedKey::Home,
+        "end" => NamedKey::End,
+        "pageup" => NamedKey::PageUp,
+        "pagedown" => NamedKey::PageDown,
+        "up" | "arrowup" => NamedKey::ArrowUp,
+        "down" | "arrowdown" => NamedKey::ArrowDown,
+        "left" | "arrowleft" => NamedKey::ArrowLeft,
+        "right" | "arrowright" => NamedKey::ArrowRight,
+        "f1" => NamedKey::F1,
+        "f2" => NamedKey::F2,
+        "f3" => NamedKey::F3,
+        "f4" => NamedKey::F4,
+        "f5" => NamedKey::F5,
+        "f6" => NamedKey::F6,
+        "f7" => NamedKey::F7,
+        "f8" => NamedKey::F8,
+        "f9" => NamedKey::F9,
+        "f10" => NamedKey::F10,
+        "f11" => NamedKey::F11,
+        "f12" => NamedKey::F12,
+        _ => return Err(ParseShortcutError::UnknownKey(name.to_owned())),
+    };
+    Ok(Key::Named(named))
+}
+
+type ShortcutFn = Rc<dyn Fn()>;
+
+struct Registration {
+    shortcut: Shortcut,
+    /// Layout node containing the composable that registered this shortcut.
+    key: NodeId,
+    /// Registration order, used to break ties between conflicting shortcuts.
+    order: u64,
+    f: ShortcutFn,
+}
+
+/// Shortcut registry for a window.
+#[derive(Default)]
+pub(crate) struct ShortcutRegistry {
+    registrations: RefCell<SlotMap<DefaultKey, Registration>>,
+    next_order: Cell<u64>,
+}
+
+impl ShortcutRegistry {
+    /// Register `shortcut` for the composable inside the layout node `key`.
+    fn insert(&self, shortcut: Shortcut, key: NodeId, f: ShortcutFn) -> DefaultKey {
+        let mut registrations = self.registrations.borrow_mut();
+
+        #[cfg(feature = "tracing")]
+        if registrations
+            .values()
+            .any(|registration| registration.shortcut == shortcut && registration.key == key)
+        {
+            tracing::warn!(
+                "Shortcut `{shortcut}` is already registered in this view, \
+                only the first registration will be triggered"
+            );
+        }
+
+        let order = self.next_order.get();
+        self.next_order.set(order + 1);
+
+        registrations.insert(Registration {
+            shortcut,
+            key,
+            order,
+            f,
+        })
+    }
+}
+
+impl WindowContext {
+    /// Trigger the shortcut for `key` with the current modifiers, returning `true` if one was found.
+    ///
+    /// Shortcuts registered closest to the focused view are triggered first,
+    /// followed by shortcuts outside of it in the order they were registered.
+    pub(crate) fn handle_shortcut(&self, key: &Key) -> bool {
+        let modifiers = self.modifiers.get();
+
+        // Ancestors of the focused view, starting with the focused view itself.
+        let mut focus_path = Vec::new();
+        {
+            let taffy = self.taffy.borrow();
+            let mut next = self.focus.focused();
+            while let Some(key) = next {
+                focus_path.push(key);
+                next = taffy.parent(key);
+            }
+        }
+
+        let f = self
+            .shortcuts
+            .registrations
+            .borrow()
+            .values()
+            .filter(|registration| registration.shortcut.matches(key, modifiers))
+            .min_by_key(|registration| {
+                let depth = focus_path
+                    .iter()
+                    .position(|node| *node == registration.key)
+                    .unwrap_or(usize::MAX);
+                (depth, registration.order)
+            })
+            .map(|registration| registration.f.clone());
+
+        if let Some(f) = f {
+            f();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Use a keyboard shortcut (such as `Ctrl+S`) that calls `f` when pressed in the current window.
+///
+/// If several composables use the same shortcut, the one closest to the focused view is triggered.
+/// Shortcuts are removed when this composable is dropped.
+/// Keys pressed for a shortcut are not sent to the focused view.
+///
+/// See [`Shortcut`] for the format of `shortcut`.
+/// This must be used inside of a [`Window`](crate::ui::view::Window).
+///
+/// # Panics
+/// Panics if `shortcut` is not a valid [`Shortcut`].
+pub fn use_shortcut<'a>(cx: ScopeState<'a>, shortcut: &str, f: impl Fn() + 'a) {
+    let window_cx = use_context::<WindowContext>(cx).unwrap();
+    let layout_cx = use_context::<LayoutContext>(cx).unwrap();
+
+    let shortcut = Shortcut::clone(&use_memo(cx, shortcut.to_owned(), || {
+        shortcut
+            .parse()
+            .unwrap_or_else(|error| panic!("Invalid shortcut `{shortcut}`: {error}"))
+    }));
+
+    let f: Rc<dyn Fn() + 'a> = Rc::new(f);
+
+    // Safety: `f` is removed from the registry on drop.
+    let f: ShortcutFn = unsafe { mem::transmute(f) };
+
+    let registration = use_ref(cx, || {
+        Cell::new(
+            window_cx
+                .shortcuts
+                .insert(shortcut.clone(), layout_cx.parent_id, f.clone()),
+        )
+    });
+
+    let is_changed = {
+        let registrations = window_cx.shortcuts.registrations.borrow();
+        let current = &registrations[registration.get()];
+        current.shortcut != shortcut || current.key != layout_cx.parent_id
+    };
+
+    if is_changed {
+        // Register the changed shortcut again, to check it for conflicts.
+        window_cx
+            .shortcuts
+            .registrations
+            .borrow_mut()
+            .remove(registration.get());
+        registration.set(window_cx.shortcuts.insert(shortcut, layout_cx.parent_id, f));
+    } else {
+        window_cx.shortcuts.registrations.borrow_mut()[registration.get()].f = f;
+    }
+
+    use_drop(cx, move || {
+        window_cx
+            .shortcuts
+            .registrations
+            .borrow_mut()
+            .remove(registration.get());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseShortcutError, Shortcut};
+    use crate::{
+        prelude::*,
+        ui::{testing::TestWindow, use_shortcut},
+    };
+    use std::{cell::RefCell, rc::Rc};
+    use winit::keyboard::{Key, ModifiersState, NamedKey, SmolStr};
+
+    fn parse(s: &str) -> Result<Shortcut, ParseShortcutError> {
+        s.parse()
+    }
+
+    fn character(c: &str) -> Key {
+        Key::Character(SmolStr::new(c))
+    }
+
+    #[test]
+    fn it_parses_shortcuts() {
+        assert_eq!(
+            parse("Ctrl+Shift+Z"),
+            Ok(Shortcut::new(
+                ModifiersState::CONTROL | ModifiersState::SHIFT,
+                character("z")
+            ))
+        );
+        assert_eq!(
+            parse("alt + enter"),
+            Ok(Shortcut::new(
+                ModifiersState::ALT,
+                Key::Named(NamedKey::Enter)
+            ))
+        );
+        assert_eq!(
+            parse("Cmd+F5"),
+            Ok(Shortcut::new(ModifiersState::SUPER, Key::Named(NamedKey::F5)))
+        );
+
+        // Modifiers can be in any order, and characters are case-insensitive.
+        assert_eq!(parse("Shift+Control+z"), parse("Ctrl+Shift+Z"));
+
+        // The `+` key can be written as a trailing `+`, or as `Plus`.
+        let plus = Ok(Shortcut::new(ModifiersState::CONTROL, character("+")));
+        assert_eq!(parse("Ctrl++"), plus);
+        assert_eq!(parse("Ctrl+Plus"), plus);
+        assert_eq!(parse("+"), Ok(Shortcut::new(ModifiersState::empty(), character("+"))));
+    }
+
+    #[test]
+    fn it_fails_to_parse_invalid_shortcuts() {
+        assert_eq!(parse(""), Err(ParseShortcutError::MissingKey));
+        assert_eq!(parse("Ctrl+"), Err(ParseShortcutError::MissingKey));
+        assert_eq!(
+            parse("Hyper+S"),
+            Err(ParseShortcutError::UnknownModifier("Hyper".to_owned()))
+        );
+        assert_eq!(
+            parse("Ctrl+Launch"),
+            Err(ParseShortcutError::UnknownKey("Launch".to_owned()))
+        );
+    }
+
+    #[test]
+    fn it_displays_shortcuts_in_modifier_order() {
+        assert_eq!(
+            parse("Super+Alt+Shift+Ctrl+s").unwrap().to_string(),
+            "Ctrl+Shift+Alt+Super+S"
+        );
+        assert_eq!(parse("Alt+Escape").unwrap().to_string(), "Alt+Escape");
+
+        // Displayed shortcuts can be parsed again.
+        for s in ["Ctrl+Shift+Z", "Alt+Enter", "Ctrl++"] {
+            let shortcut = parse(s).unwrap();
+            assert_eq!(parse(&shortcut.to_string()), Ok(shortcut));
+        }
+    }
+
+    #[test]
+    fn it_matches_keys_with_exact_modifiers() {
+        let shortcut = parse("Ctrl+S").unwrap();
+
+        assert!(shortcut.matches(&character("s"), ModifiersState::CONTROL));
+        assert!(shortcut.matches(&character("S"), ModifiersState::CONTROL));
+        assert!(!shortcut.matches(&character("a"), ModifiersState::CONTROL));
+        assert!(!shortcut.matches(&character("s"), ModifiersState::empty()));
+        assert!(!shortcut.matches(
+            &character("s"),
+            ModifiersState::CONTROL | ModifiersState::SHIFT
+        ));
+    }
+
+    #[derive(Data)]
+    struct Save {
+        name: &'static str,
+        saves: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl Compose for Save {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let name = cx.me().name;
+            let saves = cx.me().saves.clone();
+            use_shortcut(&cx, "Ctrl+S", move || saves.borrow_mut().push(name));
+
+            Text::new(name).on_key_down(|_| {})
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_triggers_conflicting_shortcuts_closest_to_the_focus() {
+        let saves = Rc::new(RefCell::new(Vec::new()));
+        let save = |name| {
+            Flex::column(Save {
+                name,
+                saves: saves.clone(),
+            })
+        };
+        let mut window = TestWindow::new((save("A"), save("B")));
+        window.frame();
+        window.set_modifiers(ModifiersState::CONTROL);
+
+        // Without a focused view, the first registered shortcut is triggered.
+        window.key_press(character("s"));
+
+        window.click_node(window.find_by_label("B").unwrap());
+        window.key_press(character("s"));
+
+        window.click_node(window.find_by_label("A").unwrap());
+        window.key_press(character("s"));
+
+        assert_eq!(*saves.borrow(), ["A", "B", "A"]);
+    }
+}
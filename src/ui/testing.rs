@@ -0,0 +1,705 @@
+use super::{
+    create_renderer, fallback_render_context,
+    view::{text::FontContext, window::hit_test},
+    Event, KeyboardInput, LayoutContext, RenderRoot, WindowContext,
+};
+#[cfg(feature = "software")]
+use super::render::SoftwareRenderer;
+#[cfg(feature = "devtools")]
+use super::Inspector;
+use crate::{
+    composer::{Composer, Update, Updater},
+    prelude::*,
+};
+use std::{cell::Cell, path::PathBuf, rc::Rc, time::Duration};
+use thiserror::Error;
+use vello::{
+    kurbo::Vec2, util::RenderContext, wgpu, AaConfig, AaSupport, RenderParams, Renderer, Scene,
+};
+use web_time::Instant;
+use winit::{
+    event::{ElementState, Ime, MouseButton},
+    keyboard::{Key, ModifiersState, NamedKey, PhysicalKey},
+};
+
+/// Duration of each frame in a [`TestWindow`].
+pub const FRAME_DURATION: Duration = Duration::from_nanos(16_666_667);
+
+/// Default inner size of a [`TestWindow`].
+pub const DEFAULT_SIZE: Vec2 = Vec2::new(800., 600.);
+
+pub(crate) struct ImmediateUpdater;
+
+impl Updater for ImmediateUpdater {
+    fn update(&self, update: Update) {
+        update.apply()
+    }
+}
+
+/// Pointers to the state of a composed [`TestWindow`].
+#[derive(Clone, Copy)]
+struct Handle {
+    window_cx: *const WindowContext,
+    root_key: NodeId,
+}
+
+#[derive(Data)]
+struct TestRoot<C> {
+    content: C,
+    handle: Rc<Cell<Option<Handle>>>,
+}
+
+impl<C: Compose> Compose for TestRoot<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let font_cx = use_context::<FontContext>(&cx).unwrap();
+
+        let mut root_key_cell = None;
+        let window_cx = use_provider(&cx, || {
+            let (window_cx, root_key) = WindowContext::new(font_cx.clone());
+            root_key_cell = Some(root_key);
+            window_cx
+        });
+
+        let layout_cx = use_provider(&cx, || LayoutContext::new(root_key_cell.unwrap()));
+
+        cx.me().handle.set(Some(Handle {
+            window_cx: &**window_cx,
+            root_key: layout_cx.parent_id,
+        }));
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Headless window for testing user interfaces.
+///
+/// This composes content like a [`Window`](crate::ui::view::Window),
+/// without a display or GPU.
+/// Events can be sent to the content, and its layout can be queried after each frame.
+///
+/// ```no_run
+/// use actuate::{prelude::*, ui::testing::TestWindow};
+///
+/// let mut window = TestWindow::new(Text::new("Hello World!"));
+/// window.frame();
+///
+/// let root = window.root();
+/// assert!(window.layout(root).size.width > 0.);
+/// ```
+pub struct TestWindow {
+    composer: Composer,
+    handle: Rc<Cell<Option<Handle>>>,
+    cursor_pos: Vec2,
+    /// Node scrolled by dragging, and the last cursor position of the drag.
+    scroll_drag: Option<(NodeId, Vec2)>,
+    size: Vec2,
+    now: Instant,
+    renderer: Option<HeadlessRenderer>,
+    /// `true` if no GPU device was found, so frames are rendered on the CPU.
+    #[cfg(feature = "software")]
+    is_software: bool,
+}
+
+impl TestWindow {
+    /// Create a new test window from its content.
+    ///
+    /// The content is composed on the first call to [`TestWindow::frame`].
+    pub fn new(content: impl Compose + 'static) -> Self {
+        let handle = Rc::new(Cell::new(None));
+
+        Self {
+            composer: Composer::with_updater(
+                RenderRoot {
+                    content: TestRoot {
+                        content,
+                        handle: handle.clone(),
+                    },
+                },
+                ImmediateUpdater,
+            ),
+            handle,
+            cursor_pos: Vec2::ZERO,
+            scroll_drag: None,
+            size: DEFAULT_SIZE,
+            now: Instant::now(),
+            renderer: None,
+            #[cfg(feature = "software")]
+            is_software: false,
+        }
+    }
+
+    pub(crate) fn window_cx(&self) -> &WindowContext {
+        let handle = self
+            .handle
+            .get()
+            .expect("TestWindow::frame must be called before accessing the window");
+
+        // Safety: The window context is owned by the composer, which lives as long as `self`.
+        unsafe { &*handle.window_cx }
+    }
+
+    /// Advance this window by one frame.
+    ///
+    /// This recomposes any changed content, computes the layout, and advances the frame clock by [`FRAME_DURATION`].
+    pub fn frame(&mut self) {
+        self.composer.compose();
+
+        let window_cx = self.window_cx();
+        window_cx.set_size(self.size);
+        window_cx.focus.apply_pending(&window_cx.listeners.borrow());
+        window_cx.update_scrolls();
+
+        if window_cx.is_layout_changed.take() {
+            window_cx.compute_layout(self.root());
+        }
+        window_cx.update_layers();
+
+        if window_cx.is_changed.take() {
+            window_cx.update_fragments();
+        }
+
+        self.now += FRAME_DURATION;
+        self.window_cx().tick(self.now);
+
+        // Compose again to apply any changes from the layout, canvases, or animations.
+        self.composer.compose();
+
+        // Build the scene from the recorded fragment of each canvas.
+        let window_cx = self.window_cx();
+        window_cx.draw(&mut *window_cx.scene.borrow_mut(), self.root());
+    }
+
+    /// Advance this window by frames until `duration` has passed.
+    pub fn advance(&mut self, duration: Duration) {
+        let end = self.now + duration;
+        while self.now < end {
+            self.frame();
+        }
+    }
+
+    /// Returns `true` if any animation in this window is active.
+    pub fn is_animating(&self) -> bool {
+        self.window_cx().is_animating()
+    }
+
+    /// Set the inner size of this window (default: [`DEFAULT_SIZE`]), applied with the next frame.
+    ///
+    /// Popups and modal dialogs are positioned within this size.
+    pub fn set_size(&mut self, width: f64, height: f64) {
+        self.size = Vec2::new(width, height);
+    }
+
+    /// Get the root layout node of this window.
+    pub fn root(&self) -> NodeId {
+        self.handle
+            .get()
+            .expect("TestWindow::frame must be called before accessing the window")
+            .root_key
+    }
+
+    /// Get the computed layout of `key`, relative to its parent.
+    pub fn layout(&self, key: NodeId) -> Layout {
+        *self.window_cx().taffy.borrow().layout(key).unwrap()
+    }
+
+    /// Get the absolute position of `key` in this window, with scroll offsets applied.
+    pub fn position(&self, key: NodeId) -> Vec2 {
+        self.window_cx().absolute_position(key).0
+    }
+
+    /// Get the children of the layout node `key`.
+    pub fn children(&self, key: NodeId) -> Vec<NodeId> {
+        self.window_cx().taffy.borrow().children(key).unwrap()
+    }
+
+    /// Find the deepest layout node containing `pos`.
+    pub fn hit_test(&self, pos: Vec2) -> Option<NodeId> {
+        hit_test(self.window_cx(), pos, &LayoutContext::new(self.root()))
+    }
+
+    /// Get the accessibility label of `key`, if any.
+    pub fn label(&self, key: NodeId) -> Option<String> {
+        self.window_cx()
+            .accessibility
+            .borrow()
+            .get(&key)
+            .and_then(|accessibility| accessibility.label.clone())
+    }
+
+    /// Find the first layout node (in layout order) with the accessibility `label`.
+    ///
+    /// The window's content is searched first, followed by each open popup and modal dialog.
+    pub fn find_by_label(&self, label: &str) -> Option<NodeId> {
+        let mut keys: Vec<_> = self.window_cx().layers.roots();
+        keys.reverse();
+        keys.push(self.root());
+        while let Some(key) = keys.pop() {
+            if self.label(key).as_deref() == Some(label) {
+                return Some(key);
+            }
+
+            keys.extend(self.children(key).into_iter().rev());
+        }
+        None
+    }
+
+    /// Get the currently focused layout node.
+    pub fn focused(&self) -> Option<NodeId> {
+        self.window_cx().focus.focused()
+    }
+
+    /// Send an event to each listener of `key`.
+    pub fn send(&self, key: NodeId, event: Event) {
+        let _rt = self.composer.runtime().enter();
+        self.window_cx().send(key, event);
+    }
+
+    /// Move the cursor to `pos`, sending mouse in, out, and move events.
+    ///
+    /// While the pointer is captured, move and drag events are only sent to the captured node.
+    /// While dragging content without pointer listeners, this also scrolls its container.
+    /// While the inspector is shown, this only changes the inspected node.
+    pub fn mouse_move(&mut self, pos: Vec2) {
+        let _rt = self.composer.runtime().enter();
+        let last_target = self.hit_test(self.cursor_pos);
+        self.cursor_pos = pos;
+
+        #[cfg(feature = "devtools")]
+        if self.window_cx().inspector.is_enabled.get() {
+            let window_cx = self.window_cx();
+            let hovered = self.hit_test(pos);
+            if window_cx.inspector.hovered.replace(hovered) != hovered {
+                window_cx.is_changed.set(true);
+            }
+            return;
+        }
+
+        if let Some((key, last_pos)) = self.scroll_drag {
+            self.window_cx().scroll_by(key, last_pos - pos);
+            self.scroll_drag = Some((key, pos));
+        }
+
+        let target = self.hit_test(pos);
+        if let Some(key) = target {
+            if last_target != Some(key) {
+                if let Some(last_key) = last_target {
+                    self.send(last_key, Event::MouseOut);
+                }
+                self.send(key, Event::MouseIn);
+            }
+        }
+
+        if !self.window_cx().pointer_move(pos, target) {
+            if let Some(key) = target {
+                self.send(key, Event::MouseMove { pos });
+            }
+        }
+    }
+
+    /// Send a mouse input event at the current cursor position.
+    ///
+    /// Pressing a button captures the pointer for the node under the cursor until it's released.
+    /// Clicks are not sent to views while the inspector is shown.
+    pub fn mouse_input(&mut self, button: MouseButton, state: ElementState) {
+        let _rt = self.composer.runtime().enter();
+
+        #[cfg(feature = "devtools")]
+        if self.window_cx().inspector.is_enabled.get() {
+            return;
+        }
+
+        let target = self.hit_test(self.cursor_pos);
+
+        if button == MouseButton::Left {
+            self.scroll_drag = match state {
+                ElementState::Pressed => self
+                    .window_cx()
+                    .scroll_drag_target(target)
+                    .map(|key| (key, self.cursor_pos)),
+                ElementState::Released => None,
+            };
+        }
+
+        let window_cx = self.window_cx();
+        if state == ElementState::Pressed {
+            window_cx.dismiss_outside(target);
+        }
+
+        if let (Some(key), ElementState::Pressed) = (target, state) {
+            let focus_key = Some(key).filter(|key| window_cx.focus.is_focusable(*key));
+            window_cx
+                .focus
+                .set(focus_key, &window_cx.listeners.borrow());
+        }
+
+        window_cx.pointer_input(target, button, state, self.cursor_pos, self.now);
+    }
+
+    /// Click the left mouse button at `pos`.
+    pub fn click(&mut self, pos: Vec2) {
+        self.mouse_move(pos);
+        self.mouse_input(MouseButton::Left, ElementState::Pressed);
+        self.mouse_input(MouseButton::Left, ElementState::Released);
+    }
+
+    /// Drag with the left mouse button from `from` to `to`.
+    pub fn drag(&mut self, from: Vec2, to: Vec2) {
+        self.mouse_move(from);
+        self.mouse_input(MouseButton::Left, ElementState::Pressed);
+        self.mouse_move(to);
+        self.mouse_input(MouseButton::Left, ElementState::Released);
+    }
+
+    /// Click the left mouse button at the center of `key`.
+    pub fn click_node(&mut self, key: NodeId) {
+        let size = self.layout(key).size;
+        let pos = self.position(key) + Vec2::new(size.width as f64 / 2., size.height as f64 / 2.);
+        self.click(pos);
+    }
+
+    /// Set the modifier keys held during keyboard input.
+    pub fn set_modifiers(&mut self, modifiers: ModifiersState) {
+        self.window_cx().modifiers.set(modifiers);
+    }
+
+    /// Send a keyboard input event for `key`.
+    ///
+    /// Pressing the tab key moves the focus, shortcuts (see [`use_shortcut`](crate::ui::use_shortcut))
+    /// are triggered, and other keys are sent to the focused node.
+    /// With the `devtools` feature, pressing `Ctrl+Shift+I` toggles the inspector overlay.
+    pub fn key_input(&mut self, key: Key, state: ElementState) {
+        let _rt = self.composer.runtime().enter();
+        let window_cx = self.window_cx();
+
+        #[cfg(feature = "devtools")]
+        if state == ElementState::Pressed && Inspector::is_toggle(&key, window_cx.modifiers.get()) {
+            window_cx.inspector.toggle();
+            window_cx.is_changed.set(true);
+            return;
+        }
+
+        if state == ElementState::Pressed && key == Key::Named(NamedKey::Tab) {
+            window_cx.focus.traverse(
+                &window_cx.taffy.borrow(),
+                &window_cx.focus_roots(self.root()),
+                window_cx.modifiers.get().shift_key(),
+                &window_cx.listeners.borrow(),
+            );
+        } else if state == ElementState::Pressed
+            && key == Key::Named(NamedKey::Escape)
+            && window_cx.dismiss_top_layer()
+        {
+            // Escape dismisses the topmost layer instead of being sent to the focused node.
+        } else if state == ElementState::Pressed && window_cx.handle_shortcut(&key) {
+            // Keys pressed for a shortcut are not sent to the focused node.
+        } else if let Some(focused) = window_cx.focus.focused() {
+            self.send(
+                focused,
+                Event::KeyboardInput(KeyboardInput {
+                    key,
+                    physical_key: PhysicalKey::Unidentified(
+                        winit::keyboard::NativeKeyCode::Unidentified,
+                    ),
+                    state,
+                    repeat: false,
+                    modifiers: window_cx.modifiers.get(),
+                }),
+            );
+        }
+    }
+
+    /// Drop the file at `path` on the window at the current cursor position.
+    pub fn drop_file(&mut self, path: impl Into<PathBuf>) {
+        if let Some(key) = self.hit_test(self.cursor_pos) {
+            self.send(key, Event::FileDrop(path.into()));
+        }
+    }
+
+    /// Returns `true` if the focused node accepts input method (IME) text input.
+    pub fn is_ime_allowed(&self) -> bool {
+        self.window_cx().ime_cursor_area().is_some()
+    }
+
+    /// Send an input method (IME) event to the focused node.
+    pub fn ime(&mut self, ime: Ime) {
+        let _rt = self.composer.runtime().enter();
+        self.window_cx().send_ime(ime);
+    }
+
+    /// Press and release `key`.
+    pub fn key_press(&mut self, key: Key) {
+        self.key_input(key.clone(), ElementState::Pressed);
+        self.key_input(key, ElementState::Released);
+    }
+
+    /// Render the current scene of this window to a [`Snapshot`] of the given size.
+    ///
+    /// This renders offscreen with the first available GPU device.
+    /// With the `software` feature, this renders on the CPU with a [`SoftwareRenderer`]
+    /// if no device is found.
+    pub fn render(&mut self, width: u32, height: u32) -> Result<Snapshot, RenderError> {
+        #[cfg(feature = "software")]
+        if self.is_software {
+            return Ok(self.render_software(width, height));
+        }
+
+        if self.renderer.is_none() {
+            match HeadlessRenderer::new() {
+                Ok(renderer) => self.renderer = Some(renderer),
+                #[cfg(feature = "software")]
+                Err(RenderError::NoDevice) => {
+                    self.is_software = true;
+                    return Ok(self.render_software(width, height));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        let renderer = self.renderer.as_mut().unwrap();
+
+        // Safety: The window context is owned by the composer, which lives as long as `self`.
+        let handle = self
+            .handle
+            .get()
+            .expect("TestWindow::frame must be called before rendering");
+        let window_cx = unsafe { &*handle.window_cx };
+
+        renderer.render(
+            &window_cx.scene.borrow(),
+            width,
+            height,
+            window_cx.base_color.get(),
+        )
+    }
+
+    /// Render the current frame of this window on the CPU.
+    #[cfg(feature = "software")]
+    fn render_software(&self, width: u32, height: u32) -> Snapshot {
+        let window_cx = self.window_cx();
+        let mut renderer = SoftwareRenderer::new(width, height, window_cx.base_color.get());
+        window_cx.draw(&mut renderer, self.root());
+        renderer.to_snapshot()
+    }
+
+    /// Compare the current scene of this window with the golden image at `path`.
+    ///
+    /// If `path` doesn't exist, or the `ACTUATE_UPDATE_SNAPSHOTS` environment variable is set,
+    /// the golden image is written instead.
+    ///
+    /// # Panics
+    /// Panics if the rendered scene doesn't match the golden image.
+    #[cfg(feature = "image")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+    pub fn assert_snapshot(&mut self, path: impl AsRef<std::path::Path>, width: u32, height: u32) {
+        let path = path.as_ref();
+        let snapshot = self.render(width, height).unwrap();
+
+        if !path.exists() || std::env::var_os("ACTUATE_UPDATE_SNAPSHOTS").is_some() {
+            snapshot.to_image().save(path).unwrap();
+            return;
+        }
+
+        let golden = Snapshot::from_image(image::open(path).unwrap().into_rgba8());
+        let diff = snapshot.diff(&golden, 0);
+        assert!(
+            diff == 0,
+            "Snapshot {} differs from the rendered scene by {} pixels",
+            path.display(),
+            diff
+        );
+    }
+}
+
+/// Error for a failed render with [`HeadlessRenderer`].
+#[derive(Debug, Error)]
+pub enum RenderError {
+    /// No compatible GPU device was found.
+    #[error("No compatible GPU device found")]
+    NoDevice,
+    /// Error from the renderer.
+    #[error(transparent)]
+    Vello(#[from] vello::Error),
+}
+
+/// Rendered image of a scene, stored as RGBA8 pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Width of this image.
+    pub width: u32,
+    /// Height of this image.
+    pub height: u32,
+    /// RGBA8 pixels of this image, in row-major order.
+    pub pixels: Vec<u8>,
+}
+
+impl Snapshot {
+    /// Get the RGBA color of the pixel at (`x`, `y`).
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let idx = (y * self.width + x) as usize * 4;
+        self.pixels[idx..idx + 4].try_into().unwrap()
+    }
+
+    /// Count the pixels that differ from `other` by more than `tolerance` in any channel.
+    ///
+    /// Snapshots of different sizes differ in every pixel.
+    pub fn diff(&self, other: &Snapshot, tolerance: u8) -> usize {
+        if (self.width, self.height) != (other.width, other.height) {
+            return (self.width * self.height).max(other.width * other.height) as usize;
+        }
+
+        self.pixels
+            .chunks_exact(4)
+            .zip(other.pixels.chunks_exact(4))
+            .filter(|(a, b)| a.iter().zip(*b).any(|(a, b)| a.abs_diff(*b) > tolerance))
+            .count()
+    }
+
+    /// Convert this snapshot to an image.
+    #[cfg(feature = "image")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+    pub fn to_image(&self) -> image::RgbaImage {
+        image::RgbaImage::from_raw(self.width, self.height, self.pixels.clone()).unwrap()
+    }
+
+    /// Create a snapshot from an image.
+    #[cfg(feature = "image")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+    pub fn from_image(image: image::RgbaImage) -> Self {
+        Self {
+            width: image.width(),
+            height: image.height(),
+            pixels: image.into_raw(),
+        }
+    }
+}
+
+/// Offscreen renderer for scenes.
+pub struct HeadlessRenderer {
+    render_cx: RenderContext,
+    dev_id: usize,
+    renderer: Renderer,
+}
+
+impl HeadlessRenderer {
+    /// Create a new headless renderer with the first available GPU device.
+    ///
+    /// If no device is found with the primary wgpu backends, every other backend is tried
+    /// (such as OpenGL software rasterizers on CI machines).
+    pub fn new() -> Result<Self, RenderError> {
+        let mut render_cx = RenderContext::new();
+        let dev_id = match pollster::block_on(render_cx.device(None)) {
+            Some(dev_id) => dev_id,
+            None => {
+                render_cx = fallback_render_context();
+                pollster::block_on(render_cx.device(None)).ok_or(RenderError::NoDevice)?
+            }
+        };
+
+        let renderer = create_renderer(
+            &render_cx.devices[dev_id].device,
+            None,
+            AaSupport::area_only(),
+        )?;
+
+        Ok(Self {
+            render_cx,
+            dev_id,
+            renderer,
+        })
+    }
+
+    /// Render `scene` to a [`Snapshot`] of the given size.
+    pub fn render(
+        &mut self,
+        scene: &Scene,
+        width: u32,
+        height: u32,
+        base_color: Color,
+    ) -> Result<Snapshot, RenderError> {
+        let device_handle = &self.render_cx.devices[self.dev_id];
+        let device = &device_handle.device;
+        let queue = &device_handle.queue;
+
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("actuate snapshot"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        self.renderer.render_to_texture(
+            device,
+            queue,
+            scene,
+            &view,
+            &RenderParams {
+                base_color,
+                width,
+                height,
+                antialiasing_method: AaConfig::Area,
+            },
+        )?;
+
+        // Rows copied from a texture must be aligned to `COPY_BYTES_PER_ROW_ALIGNMENT`.
+        let row_len = width * 4;
+        let padded_row_len = row_len.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("actuate snapshot buffer"),
+            size: padded_row_len as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("actuate snapshot copy"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_len),
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        device.poll(wgpu::Maintain::Wait);
+
+        let mut pixels = Vec::with_capacity((row_len * height) as usize);
+        for row in slice
+            .get_mapped_range()
+            .chunks_exact(padded_row_len as usize)
+        {
+            pixels.extend_from_slice(&row[..row_len as usize]);
+        }
+        buffer.unmap();
+
+        Ok(Snapshot {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
@@ -0,0 +1,164 @@
+use super::view::text::TextContext;
+use crate::prelude::*;
+
+/// Theme colors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeColors {
+    /// Window background color.
+    pub background: Color,
+    /// Color of surfaces drawn on top of the background, such as cards and menus.
+    pub surface: Color,
+    /// Accent color for primary actions.
+    pub primary: Color,
+    /// Color of content drawn on top of the primary color.
+    pub on_primary: Color,
+    /// Default text color.
+    pub text: Color,
+    /// Color of secondary text, such as captions and hints.
+    pub text_secondary: Color,
+    /// Color of borders and dividers.
+    pub border: Color,
+    /// Color drawn over the content below a modal dialog.
+    pub scrim: Color,
+}
+
+/// Theme font sizes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Typography {
+    /// Font size for captions and hints.
+    pub caption: f32,
+    /// Default font size.
+    pub body: f32,
+    /// Font size for titles.
+    pub title: f32,
+    /// Font size for large headings.
+    pub headline: f32,
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            caption: 14.,
+            body: 18.,
+            title: 24.,
+            headline: 32.,
+        }
+    }
+}
+
+/// Theme spacing scale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spacing {
+    /// Small spacing.
+    pub small: f32,
+    /// Medium spacing.
+    pub medium: f32,
+    /// Large spacing.
+    pub large: f32,
+}
+
+impl Default for Spacing {
+    fn default() -> Self {
+        Self {
+            small: 4.,
+            medium: 8.,
+            large: 16.,
+        }
+    }
+}
+
+/// UI theme.
+///
+/// This can be provided to a tree of composables with [`Themed`], and read with [`use_theme`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Theme colors.
+    pub colors: ThemeColors,
+    /// Theme font sizes.
+    pub typography: Typography,
+    /// Theme spacing scale.
+    pub spacing: Spacing,
+}
+
+impl Theme {
+    /// Create the default light theme.
+    pub fn light() -> Self {
+        Self {
+            colors: ThemeColors {
+                background: Color::WHITE,
+                surface: Color::rgb8(245, 245, 245),
+                primary: Color::rgb8(37, 99, 235),
+                on_primary: Color::WHITE,
+                text: Color::BLACK,
+                text_secondary: Color::rgb8(96, 96, 96),
+                border: Color::rgb8(212, 212, 212),
+                scrim: Color::rgba8(0, 0, 0, 96),
+            },
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+        }
+    }
+
+    /// Create the default dark theme.
+    pub fn dark() -> Self {
+        Self {
+            colors: ThemeColors {
+                background: Color::rgb8(24, 24, 27),
+                surface: Color::rgb8(39, 39, 42),
+                primary: Color::rgb8(96, 165, 250),
+                on_primary: Color::BLACK,
+                text: Color::WHITE,
+                text_secondary: Color::rgb8(161, 161, 170),
+                border: Color::rgb8(63, 63, 70),
+                scrim: Color::rgba8(0, 0, 0, 160),
+            },
+            typography: Typography::default(),
+            spacing: Spacing::default(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+/// Use the current [`Theme`].
+pub fn use_theme(cx: ScopeState<'_>) -> &Theme {
+    use_context::<Theme>(cx).unwrap()
+}
+
+/// Themed composable.
+///
+/// This provides a [`Theme`] to its content, and sets the default text color and size from it.
+/// Changing the theme will recompose the content with the new theme.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Themed<C> {
+    theme: Theme,
+    content: C,
+}
+
+impl<C> Themed<C> {
+    /// Create a new themed composable from its theme and the given `content`.
+    pub fn new(theme: Theme, content: C) -> Self {
+        Self { theme, content }
+    }
+}
+
+impl<C: Compose> Compose for Themed<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let text_cx = use_context::<TextContext>(&cx).unwrap();
+
+        use_memo_provider(&cx, cx.me().theme.clone(), || cx.me().theme.clone());
+
+        use_memo_provider(&cx, cx.me().theme.clone(), || TextContext {
+            color: cx.me().theme.colors.text,
+            font_size: cx.me().theme.typography.body,
+            ..text_cx.clone()
+        });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    prelude::*,
+    ui::view::widget::{use_interaction, Interactive, Surface, SurfaceStyle},
+};
+use std::{cell::Cell, rc::Rc};
+
+/// Create a new button from its label.
+pub fn button<'a>(label: impl Into<String>) -> Button<'a> {
+    Button::new(label)
+}
+
+/// Button composable.
+///
+/// A button draws its label on the current [`Theme`](crate::ui::Theme)'s primary color,
+/// with hover and pressed states.
+/// It can be pressed with the mouse, or with the space or enter key while focused.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Button<'a> {
+    label: String,
+    on_press: Box<dyn Fn() + 'a>,
+}
+
+impl<'a> Button<'a> {
+    /// Create a new button from its label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            on_press: Box::new(|| {}),
+        }
+    }
+
+    /// Set the function to call when this button is pressed.
+    pub fn on_press(mut self, on_press: impl Fn() + 'a) -> Self {
+        self.on_press = Box::new(on_press);
+        self
+    }
+}
+
+impl Compose for Button<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let surface = use_ref(&cx, || Rc::new(Cell::new(SurfaceStyle::default())));
+        surface.set(SurfaceStyle {
+            background: theme.colors.primary,
+            overlay: interaction.overlay(theme.colors.on_primary),
+            border: interaction.focus_ring(theme),
+            corner_radius: 6.,
+        });
+
+        let padding = Rect {
+            left: length(theme.spacing.large),
+            right: length(theme.spacing.large),
+            top: length(theme.spacing.medium),
+            bottom: length(theme.spacing.medium),
+        };
+
+        Flex::row(
+            Text::new(cx.me().label.clone())
+                .color(theme.colors.on_primary)
+                .padding(padding),
+        )
+        .draw(Surface {
+            style: surface.clone(),
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, move || (cx.me().on_press)()))
+    }
+}
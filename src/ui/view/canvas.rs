@@ -1,38 +1,97 @@
 use crate::prelude::*;
-use crate::ui::{use_layout, ListenerFn, WindowContext};
-use std::{cell::RefCell, mem, rc::Rc};
-use taffy::{Layout, Style};
-use vello::{
-    kurbo::{Affine, Vec2},
-    Scene,
+use crate::ui::{
+    draw::Decoration, use_layout, view::transition::TransitionEffect, AcceptsFn, Accessibility,
+    Action, DragPayload, Event, FocusHandle, Fragment, ImeHandle, ListenerFn, MeasureFn, Role,
+    WindowContext,
 };
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+};
+use taffy::{AvailableSpace, Layout, NodeId, Size, Style};
+use vello::kurbo::{Affine, Vec2};
 
 #[derive(Clone, Default)]
 pub(crate) struct CanvasContext {
     pub(crate) draws: RefCell<Vec<Rc<dyn Draw>>>,
+    pub(crate) decoration: Decoration,
     pub(crate) pending_listeners: Rc<RefCell<Vec<ListenerFn>>>,
+    pub(crate) focus_handles: Rc<RefCell<Vec<FocusHandle>>>,
+    pub(crate) ime_handles: Rc<RefCell<Vec<ImeHandle>>>,
+    pub(crate) drag_payload: Option<Rc<RefCell<DragPayload>>>,
+    pub(crate) drop_accepts: Rc<RefCell<Vec<AcceptsFn>>>,
+    pub(crate) accessibility: Rc<RefCell<Accessibility>>,
+    pub(crate) transitions: Vec<Rc<Cell<TransitionEffect>>>,
+    pub(crate) is_focusable: bool,
 }
 
-type DrawFn<'a> = Box<dyn Fn(Layout, &mut Scene) + 'a>;
+type DrawFn<'a> = Box<dyn Fn(Layout, &mut dyn Renderer) + 'a>;
+
+type PointerFn<'a> = Box<dyn Fn(Event) + 'a>;
+
+type DynMeasureFn<'a> = dyn Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32> + 'a;
 
 /// Canvas composable.
-#[derive(Data)]
+///
+/// The draw function of a canvas is called with its computed layout each time the canvas changes,
+/// and draws with a [`Renderer`] positioned at the canvas' origin.
+/// This can be used to draw custom widgets, such as charts.
 #[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
 pub struct Canvas<'a> {
     style: Style,
     f: DrawFn<'a>,
+    on_pointer: PointerFn<'a>,
+    has_on_pointer: bool,
+    measure: Option<Box<DynMeasureFn<'a>>>,
+    accessibility: Option<Accessibility>,
 }
 
 impl<'a> Canvas<'a> {
     /// Create a new canvas from its style and draw function.
-    pub fn new(style: Style, draw_fn: impl Fn(Layout, &mut Scene) + 'a) -> Self {
+    pub fn new(style: Style, draw_fn: impl Fn(Layout, &mut dyn Renderer) + 'a) -> Self {
         Self {
             style,
             f: Box::new(draw_fn),
+            on_pointer: Box::new(|_| {}),
+            has_on_pointer: false,
+            measure: None,
+            accessibility: None,
         }
     }
+
+    /// Set the measure function of this canvas.
+    ///
+    /// The intrinsic size of the canvas is measured with the dimensions already known from its style
+    /// and the space available in its parent, such as to wrap text at the available width.
+    pub fn with_measure(
+        mut self,
+        measure: impl Fn(Size<Option<f32>>, Size<AvailableSpace>) -> Size<f32> + 'a,
+    ) -> Self {
+        self.measure = Some(Box::new(measure));
+        self
+    }
+
+    /// Set the pointer event handler of this canvas.
+    ///
+    /// Mouse events are sent to `on_pointer` with positions relative to the canvas' origin.
+    pub fn on_pointer(mut self, on_pointer: impl Fn(Event) + 'a) -> Self {
+        self.on_pointer = Box::new(on_pointer);
+        self.has_on_pointer = true;
+        self
+    }
+
+    /// Set the accessibility properties of this canvas.
+    ///
+    /// Properties set by modifiers on this canvas take priority.
+    pub fn with_accessibility(mut self, accessibility: Accessibility) -> Self {
+        self.accessibility = Some(accessibility);
+        self
+    }
 }
 
+unsafe impl Data for Canvas<'_> {}
+
 impl Compose for Canvas<'_> {
     fn compose(cx: Scope<Self>) -> impl Compose {
         let canvas_cx = use_context::<CanvasContext>(&cx).unwrap();
@@ -40,9 +99,87 @@ impl Compose for Canvas<'_> {
 
         let (key, layout) = use_layout(&cx, cx.me().style.clone());
 
+        let on_pointer = use_ref(&cx, || {
+            let f: Rc<dyn Fn(Event) + '_> = Rc::new(move |event| {
+                let (transform, _) = renderer_cx.transform(key);
+                (cx.me().on_pointer)(to_local(event, transform));
+            });
+
+            // Safety: `f` is removed from `listeners` on drop.
+            let f: ListenerFn = unsafe { mem::transmute(f) };
+            f
+        });
+
+        // Register the listeners and handles of this canvas each time it's composed,
+        // as its modifiers may have changed.
+        let mut listeners = canvas_cx.pending_listeners.borrow().clone();
+        if cx.me().has_on_pointer {
+            listeners.push(on_pointer.clone());
+        }
+        renderer_cx.listeners.borrow_mut().insert(key, listeners);
+
+        // Only nodes with a focus handle or keyboard input can be focused,
+        // so pointer-only views are skipped when moving the focus with the keyboard.
+        let focus_handles = canvas_cx.focus_handles.borrow();
+        let ime_handles = canvas_cx.ime_handles.borrow();
+        if canvas_cx.is_focusable || !focus_handles.is_empty() || !ime_handles.is_empty() {
+            renderer_cx.focus.focusable.borrow_mut().insert(key);
+        } else if renderer_cx.focus.is_focusable(key) {
+            renderer_cx.focus.remove(key);
+        }
+        for handle in &*focus_handles {
+            handle.key.set(Some(key));
+        }
+
+        if ime_handles.is_empty() {
+            renderer_cx.ime.remove(key);
+        }
+        for handle in &*ime_handles {
+            handle.key.set(Some(key));
+            renderer_cx
+                .ime
+                .inputs
+                .borrow_mut()
+                .insert(key, handle.cursor_area.clone());
+        }
+
+        if let Some(payload) = &canvas_cx.drag_payload {
+            renderer_cx
+                .drag
+                .payloads
+                .borrow_mut()
+                .insert(key, payload.clone());
+        } else {
+            renderer_cx.drag.payloads.borrow_mut().remove(&key);
+        }
+        let drop_accepts = canvas_cx.drop_accepts.borrow();
+        if drop_accepts.is_empty() {
+            renderer_cx.drag.drop_targets.borrow_mut().remove(&key);
+        } else {
+            renderer_cx
+                .drag
+                .drop_targets
+                .borrow_mut()
+                .insert(key, drop_accepts.clone());
+        }
+
         use_ref(&cx, || {
-            let listeners = canvas_cx.pending_listeners.borrow().clone();
-            renderer_cx.listeners.borrow_mut().insert(key, listeners);
+            if cx.me().measure.is_some() {
+                let f: Rc<DynMeasureFn> = Rc::new(move |known_dimensions, available_space| {
+                    cx.me().measure.as_ref().map_or(Size::ZERO, |measure| {
+                        measure(known_dimensions, available_space)
+                    })
+                });
+
+                // Safety: `f` is removed with the layout node of this canvas on drop.
+                let f: MeasureFn = unsafe { mem::transmute(f) };
+
+                renderer_cx
+                    .taffy
+                    .borrow_mut()
+                    .set_node_context(key, Some(f))
+                    .unwrap();
+            }
 
             let f: Box<dyn Fn()> = Box::new(move || {
                 cx.set_changed();
@@ -54,16 +191,40 @@ impl Compose for Canvas<'_> {
             renderer_cx.canvas_update_fns.borrow_mut().insert(key, f);
         });
 
+        let mut accessibility = canvas_cx.accessibility.borrow().clone();
+        if let Some(own_accessibility) = &cx.me().accessibility {
+            accessibility.merge(own_accessibility);
+        }
+        if matches!(accessibility.role, Role::GenericContainer | Role::Label)
+            && accessibility.actions.contains(&Action::Click)
+        {
+            accessibility.role = Role::Button;
+        }
+        if renderer_cx.accessibility.borrow().get(&key) != Some(&accessibility) {
+            renderer_cx
+                .accessibility
+                .borrow_mut()
+                .insert(key, accessibility);
+            renderer_cx.is_changed.set(true);
+        }
+
         // Safety: We must remove `f` here to make the above valid.
         use_drop(&cx, move || {
             renderer_cx.canvas_update_fns.borrow_mut().remove(&key);
+            renderer_cx.fragments.borrow_mut().remove(&key);
         });
 
-        let scene = use_ref(&cx, || RefCell::new(Scene::new()));
-
-        let mut parent_scene = renderer_cx.scene.borrow_mut();
-
         if cx.is_parent_changed() {
+            // Measure this canvas again in the next layout.
+            if cx.me().measure.is_some() {
+                renderer_cx.taffy.borrow_mut().mark_dirty(key).unwrap();
+                renderer_cx.is_layout_changed.set(true);
+            }
+
+            // Record this canvas again after the layout is computed.
+            if let Some(fragment) = renderer_cx.fragments.borrow_mut().get_mut(&key) {
+                fragment.is_dirty = true;
+            }
             renderer_cx.is_changed.set(true);
             return;
         }
@@ -77,23 +238,130 @@ impl Compose for Canvas<'_> {
             }
         }
 
-        scene.borrow_mut().reset();
-        for draw in &*canvas_cx.draws.borrow() {
-            draw.pre_process(&layout, &mut scene.borrow_mut());
+        // Record this canvas into its fragment, which the window appends at this canvas' position.
+        record_fragment(renderer_cx, canvas_cx, key, &layout, |renderer| {
+            (cx.me().f)(layout, renderer)
+        });
+    }
+}
+
+/// Use the draw modifiers and decorations of a container with the layout node `key`,
+/// recording them into its own fragment instead of the fragment of each canvas in its content.
+pub(crate) fn use_container_fragment(cx: ScopeState, key: NodeId, layout: Layout) {
+    let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+    let window_cx = use_context::<WindowContext>(cx).unwrap();
+
+    use_provider(cx, || CanvasContext {
+        draws: RefCell::default(),
+        decoration: Decoration::default(),
+        ..(*canvas_cx).clone()
+    });
+
+    let is_drawn = !canvas_cx.draws.borrow().is_empty() || !canvas_cx.decoration.is_empty();
+
+    use_ref(cx, || {
+        if is_drawn {
+            let f: Box<dyn Fn() + '_> = Box::new(move || cx.set_changed());
+
+            // Safety: `f` is removed from `canvas_update_fns` on drop.
+            let f: Box<dyn Fn()> = unsafe { mem::transmute(f) };
+
+            window_cx.canvas_update_fns.borrow_mut().insert(key, f);
         }
+    });
+
+    use_drop(cx, move || {
+        window_cx.canvas_update_fns.borrow_mut().remove(&key);
+        window_cx.fragments.borrow_mut().remove(&key);
+    });
 
-        (cx.me().f)(layout, &mut scene.borrow_mut());
+    let last_layout = use_ref(cx, || Cell::new(None));
 
-        for draw in &*canvas_cx.draws.borrow() {
-            draw.post_process(&layout, &mut scene.borrow_mut());
+    if !is_drawn {
+        return;
+    }
+
+    if cx.is_parent_changed() {
+        // Record this container again after the layout is computed.
+        if let Some(fragment) = window_cx.fragments.borrow_mut().get_mut(&key) {
+            fragment.is_dirty = true;
         }
+        window_cx.is_changed.set(true);
+        return;
+    }
+
+    if last_layout.replace(Some(layout)).is_none() {
+        window_cx.is_changed.set(true);
+        return;
+    }
+
+    record_fragment(window_cx, canvas_cx, key, &layout, |_| {});
+}
+
+/// Record the fragment of the layout node `key`,
+/// drawing with `f` between the decorations and draw modifiers of `canvas_cx`.
+fn record_fragment(
+    window_cx: &WindowContext,
+    canvas_cx: &CanvasContext,
+    key: NodeId,
+    layout: &Layout,
+    f: impl FnOnce(&mut dyn Renderer),
+) {
+    let mut recording = window_cx
+        .fragments
+        .borrow_mut()
+        .remove(&key)
+        .map(|fragment| fragment.recording)
+        .unwrap_or_default();
+    recording.begin_frame();
+
+    canvas_cx.decoration.pre_process(layout, &mut recording);
+    for draw in &*canvas_cx.draws.borrow() {
+        draw.pre_process(layout, &mut recording);
+    }
+
+    f(&mut recording);
+
+    for draw in &*canvas_cx.draws.borrow() {
+        draw.post_process(layout, &mut recording);
+    }
+    canvas_cx.decoration.post_process(layout, &mut recording);
+    recording.end_frame();
+
+    window_cx.fragments.borrow_mut().insert(
+        key,
+        Fragment {
+            recording,
+            size: layout.size,
+            corner_radius: canvas_cx.decoration.corner_radius,
+            transitions: canvas_cx.transitions.clone(),
+            is_dirty: false,
+        },
+    );
+    window_cx.is_changed.set(true);
+}
+
+/// Map the position of a mouse `event` from window coordinates to local coordinates,
+/// where `transform` maps local coordinates to window coordinates.
+pub(crate) fn to_local(event: Event, transform: Affine) -> Event {
+    let inverse = transform.inverse();
+    let local = |pos: Vec2| (inverse * pos.to_point()).to_vec2();
 
-        parent_scene.append(
-            &scene.borrow(),
-            Some(Affine::translate(Vec2::new(
-                layout.location.x as _,
-                layout.location.y as _,
-            ))),
-        );
+    match event {
+        Event::MouseInput { button, state, pos } => Event::MouseInput {
+            button,
+            state,
+            pos: local(pos),
+        },
+        Event::MouseMove { pos } => Event::MouseMove { pos: local(pos) },
+        Event::DragStart { pos } => Event::DragStart { pos: local(pos) },
+        Event::DragMove { pos, delta } => Event::DragMove {
+            pos: local(pos),
+            delta: local(pos) - local(pos - delta),
+        },
+        Event::DragEnd { pos } => Event::DragEnd { pos: local(pos) },
+        Event::Tap { pos } => Event::Tap { pos: local(pos) },
+        Event::DoubleTap { pos } => Event::DoubleTap { pos: local(pos) },
+        event => event,
     }
 }
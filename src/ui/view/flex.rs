@@ -1,6 +1,6 @@
 use crate::{
     prelude::*,
-    ui::{use_layout, LayoutContext},
+    ui::{use_direction, use_layout, view::canvas::use_container_fragment, LayoutContext},
 };
 use taffy::{FlexDirection, Style};
 
@@ -41,12 +41,18 @@ impl<C> Flex<C> {
     }
 }
 
-
 impl<C: Compose> Compose for Flex<C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
-        let (id, _layout) = use_layout(&cx, cx.me().style.clone());
-
-        use_provider(&cx, || LayoutContext { parent_id: id });
+        // Rows are mirrored in right-to-left layouts.
+        let direction = use_direction(&cx);
+        let style = Style {
+            flex_direction: direction.flex_direction(cx.me().style.flex_direction),
+            ..cx.me().style.clone()
+        };
+        let (id, layout) = use_layout(&cx, style);
+
+        use_provider(&cx, || LayoutContext::new(id));
+        use_container_fragment(&cx, id, layout);
 
         Ref::map(cx.me(), |me| &me.content)
     }
@@ -0,0 +1,53 @@
+use crate::{
+    prelude::*,
+    ui::{use_layout, view::canvas::use_container_fragment, LayoutContext},
+};
+use taffy::{Display, Style, TrackSizingFunction};
+
+/// Grid composable.
+///
+/// Children can be placed in this grid with [`View::grid_area`](crate::ui::view::View::grid_area).
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Grid<C> {
+    style: Style,
+    content: C,
+}
+
+impl<C> Grid<C> {
+    /// Create a new grid from its style and the given `content`.
+    ///
+    /// The style's `display` is always set to [`Display::Grid`].
+    pub fn new(style: Style, content: C) -> Self {
+        Self {
+            style: Style {
+                display: Display::Grid,
+                ..style
+            },
+            content,
+        }
+    }
+
+    /// Set the column tracks of this grid.
+    pub fn grid_template_columns(mut self, columns: Vec<TrackSizingFunction>) -> Self {
+        self.style.grid_template_columns = columns;
+        self
+    }
+
+    /// Set the row tracks of this grid.
+    pub fn grid_template_rows(mut self, rows: Vec<TrackSizingFunction>) -> Self {
+        self.style.grid_template_rows = rows;
+        self
+    }
+}
+
+impl<C: Compose> Compose for Grid<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let (id, layout) = use_layout(&cx, cx.me().style.clone());
+
+        use_provider(&cx, || LayoutContext::new(id));
+        use_container_fragment(&cx, id, layout);
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
@@ -0,0 +1,201 @@
+use crate::prelude::*;
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+    thread,
+};
+use taffy::{Dimension, Size, Style};
+use tokio::sync::{mpsc, oneshot};
+use vello::{
+    kurbo::{Affine, Rect, Vec2},
+    peniko::{Blob, Format, Image as ImageBrush},
+};
+
+/// Source of an [`Image`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageSource {
+    /// Path to an image file.
+    Path(PathBuf),
+    /// Encoded image data.
+    Bytes(Arc<[u8]>),
+}
+
+impl ImageSource {
+    /// Load and decode this image.
+    pub fn load(&self) -> Result<ImageBrush, image::ImageError> {
+        let image = match self {
+            ImageSource::Path(path) => image::open(path)?,
+            ImageSource::Bytes(bytes) => image::load_from_memory(bytes)?,
+        };
+
+        let rgba = image.into_rgba8();
+        let (width, height) = rgba.dimensions();
+        Ok(ImageBrush::new(
+            Blob::new(Arc::new(rgba.into_raw())),
+            Format::Rgba8,
+            width,
+            height,
+        ))
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(value: PathBuf) -> Self {
+        ImageSource::Path(value)
+    }
+}
+
+impl From<&Path> for ImageSource {
+    fn from(value: &Path) -> Self {
+        ImageSource::Path(value.to_owned())
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(value: &str) -> Self {
+        ImageSource::Path(value.into())
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(value: Vec<u8>) -> Self {
+        ImageSource::Bytes(value.into())
+    }
+}
+
+impl From<&'static [u8]> for ImageSource {
+    fn from(value: &'static [u8]) -> Self {
+        ImageSource::Bytes(value.into())
+    }
+}
+
+/// Fit mode of an [`Image`] inside its layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ImageFit {
+    /// Scale the image to fit inside its layout, preserving its aspect ratio.
+    #[default]
+    Contain,
+    /// Scale the image to cover its layout, preserving its aspect ratio and clipping any overflow.
+    Cover,
+    /// Stretch the image to fill its layout.
+    Fill,
+}
+
+impl ImageFit {
+    fn transform(self, image_size: Vec2, size: Vec2) -> Affine {
+        let scale = Vec2::new(size.x / image_size.x, size.y / image_size.y);
+        let scale = match self {
+            ImageFit::Contain => Vec2::new(scale.x.min(scale.y), scale.x.min(scale.y)),
+            ImageFit::Cover => Vec2::new(scale.x.max(scale.y), scale.x.max(scale.y)),
+            ImageFit::Fill => scale,
+        };
+
+        let offset = (size - Vec2::new(image_size.x * scale.x, image_size.y * scale.y)) / 2.;
+        Affine::translate(offset) * Affine::scale_non_uniform(scale.x, scale.y)
+    }
+}
+
+/// Create a new image view from the given `source`.
+///
+/// The image is decoded on a separate thread, so large images don't block composition.
+/// Until the image is loaded, nothing is drawn.
+pub fn image(source: impl Into<ImageSource>) -> Image {
+    Image::new(Style::default(), source)
+}
+
+/// Image composable.
+///
+/// This can be created with [`image`].
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Image {
+    style: Style,
+    source: ImageSource,
+    fit: ImageFit,
+}
+
+impl Image {
+    /// Create a new image view from its style and `source`.
+    ///
+    /// If the style's size is `auto`, the size of the loaded image is used instead.
+    pub fn new(style: Style, source: impl Into<ImageSource>) -> Self {
+        Self {
+            style,
+            source: source.into(),
+            fit: ImageFit::default(),
+        }
+    }
+
+    /// Set the fit mode of this image.
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+}
+
+impl Compose for Image {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let image = use_mut(&cx, || None::<Rc<ImageBrush>>);
+
+        let channel = use_ref(&cx, || {
+            let (tx, rx) = mpsc::unbounded_channel::<ImageSource>();
+            (tx, RefCell::new(Some(rx)))
+        });
+
+        use_memo(&cx, cx.me().source.clone(), || {
+            channel.0.send(cx.me().source.clone()).unwrap();
+        });
+
+        use_local_task(&cx, move || {
+            let mut rx = channel.1.borrow_mut().take().unwrap();
+
+            async move {
+                while let Some(source) = rx.recv().await {
+                    let (tx, rx) = oneshot::channel();
+                    thread::spawn(move || {
+                        let _ = tx.send(source.load());
+                    });
+
+                    match rx.await {
+                        Ok(Ok(loaded)) => Mut::set(image, Some(Rc::new(loaded))),
+                        Ok(Err(_error)) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!("Failed to load image: {}", _error);
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+        });
+
+        let mut style = cx.me().style.clone();
+        if let Some(image) = &*image {
+            if style.size.width == Dimension::Auto && style.size.height == Dimension::Auto {
+                style.size = Size::from_lengths(image.width as _, image.height as _);
+            }
+        }
+
+        Canvas::new(style, move |layout, renderer| {
+            let Some(image) = &*image else {
+                return;
+            };
+
+            let size = Vec2::new(layout.size.width as _, layout.size.height as _);
+            let transform = cx
+                .me()
+                .fit
+                .transform(Vec2::new(image.width as _, image.height as _), size);
+
+            if cx.me().fit == ImageFit::Cover {
+                let clip = Rect::new(0., 0., size.x, size.y);
+                renderer.push_clip(Affine::IDENTITY, clip.into());
+                renderer.draw_image(image, transform);
+                renderer.pop_clip();
+            } else {
+                renderer.draw_image(image, transform);
+            }
+        })
+    }
+}
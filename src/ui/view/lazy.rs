@@ -0,0 +1,265 @@
+use crate::{
+    compose::AnyCompose,
+    prelude::*,
+    ui::{use_layout, view::scroll::ScrollOffset, LayoutContext, WindowContext},
+    ScopeData,
+};
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+};
+use taffy::{Dimension, FlexDirection, NodeId, Overflow, Point, Size, Style};
+
+/// Create a virtualized column of `len` rows, composing each visible row with `f`.
+///
+/// See [`LazyColumn`] for more.
+pub fn lazy_column<'a, C: Compose>(len: usize, f: impl Fn(usize) -> C + 'a) -> LazyColumn<'a, C> {
+    LazyColumn {
+        len,
+        item_height: 32.,
+        overscan: 2,
+        f: Box::new(f),
+    }
+}
+
+/// Virtualized column composable, created with [`lazy_column`].
+///
+/// Only the rows intersecting the viewport (plus a few rows of overscan) are composed and laid out.
+/// The height of the rows outside of the viewport is estimated from [`LazyColumn::item_height`].
+///
+/// Rows scrolled out of the viewport are recycled for the rows scrolled into it,
+/// so state stored in a row's hooks may be re-used by a row at a different index.
+/// Per-item state should be stored outside of the list.
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct LazyColumn<'a, C> {
+    len: usize,
+    item_height: f32,
+    overscan: usize,
+    f: Box<dyn Fn(usize) -> C + 'a>,
+}
+
+unsafe impl<C: Data> Data for LazyColumn<'_, C> {}
+
+impl<C> LazyColumn<'_, C> {
+    /// Set the estimated height of each row (default: 32).
+    pub fn item_height(mut self, item_height: f32) -> Self {
+        self.item_height = item_height;
+        self
+    }
+
+    /// Set the number of rows to compose before and after the viewport (default: 2).
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Get the range of rows visible in a viewport of `height` scrolled to `offset`.
+    fn visible_range(&self, offset: f64, height: f64) -> (usize, usize) {
+        let item_height = self.item_height.max(1.) as f64;
+
+        let start = ((offset / item_height).floor() as usize).saturating_sub(self.overscan);
+        let end = (((offset + height) / item_height).ceil() as usize + self.overscan).min(self.len);
+        (start.min(end), end)
+    }
+}
+
+/// State of a composed row.
+struct Row {
+    // The scope is declared first, so it's dropped before the content it borrows.
+    scope: ScopeData<'static>,
+    index: Rc<Cell<usize>>,
+    key: Rc<Cell<Option<NodeId>>>,
+    compose: Box<dyn AnyCompose>,
+}
+
+// Rows are boxed, so their scopes aren't moved when these vectors reallocate.
+#[allow(clippy::vec_box)]
+#[derive(Default)]
+struct Rows {
+    /// Rows in the visible range, sorted by index.
+    visible: Vec<Box<Row>>,
+    /// Rows scrolled out of the visible range, ready to be recycled.
+    pool: Vec<Box<Row>>,
+}
+
+/// Spacer style for the estimated height of `count` rows.
+fn spacer_style(count: usize, item_height: f32) -> Style {
+    Style {
+        size: Size {
+            width: Dimension::Auto,
+            height: Dimension::Length(count as f32 * item_height),
+        },
+        flex_shrink: 0.,
+        ..Default::default()
+    }
+}
+
+impl<C: Compose> Compose for LazyColumn<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let window_cx = use_context::<WindowContext>(&cx).unwrap();
+
+        let (key, layout) = use_layout(
+            &cx,
+            Style {
+                flex_direction: FlexDirection::Column,
+                overflow: Point {
+                    x: Overflow::Visible,
+                    y: Overflow::Scroll,
+                },
+                ..Default::default()
+            },
+        );
+
+        use_provider(&cx, || LayoutContext::new(key));
+
+        // Spacers before and after the visible rows, sized to the estimated height of the rows they replace.
+        let (top, bottom) = *use_ref(&cx, || {
+            let mut taffy = window_cx.taffy.borrow_mut();
+            let top = taffy.new_leaf(spacer_style(0, 0.)).unwrap();
+            let bottom = taffy.new_leaf(spacer_style(0, 0.)).unwrap();
+            (top, bottom)
+        });
+
+        let last_range = use_ref(&cx, || Cell::new((0, 0)));
+
+        // Recompose this list when its visible range changes.
+        let on_scroll: Rc<dyn Fn(ScrollOffset) + '_> = Rc::new(move |offset| {
+            let height = window_cx.taffy.borrow().layout(key).unwrap().size.height;
+            if cx.me().visible_range(offset.offset.y, height as _) != last_range.get() {
+                cx.set_changed();
+            }
+        });
+
+        // Safety: `on_scroll` is removed from `scrolls` on drop.
+        let on_scroll: Rc<dyn Fn(ScrollOffset)> = unsafe { mem::transmute(on_scroll) };
+        window_cx
+            .scrolls
+            .borrow_mut()
+            .entry(key)
+            .or_default()
+            .on_scroll = Some(on_scroll);
+
+        use_drop(&cx, move || {
+            window_cx.scrolls.borrow_mut().remove(&key);
+
+            let mut taffy = window_cx.taffy.borrow_mut();
+            taffy.remove(top).unwrap();
+            taffy.remove(bottom).unwrap();
+        });
+
+        let offset = window_cx
+            .scrolls
+            .borrow()
+            .get(&key)
+            .map(|scroll| scroll.offset.y)
+            .unwrap_or_default();
+        let (start, end) = cx.me().visible_range(offset, layout.size.height as _);
+        last_range.set((start, end));
+
+        let rows = use_ref(&cx, || RefCell::new(Rows::default()));
+        let mut rows = rows.borrow_mut();
+
+        // Move rows scrolled out of the visible range to the pool.
+        let (visible, scrolled_out): (Vec<_>, Vec<_>) = mem::take(&mut rows.visible)
+            .into_iter()
+            .partition(|row| (start..end).contains(&row.index.get()));
+        rows.visible = visible;
+        rows.pool.extend(scrolled_out);
+
+        for index in start..end {
+            if rows.visible.iter().any(|row| row.index.get() == index) {
+                continue;
+            }
+
+            let row = if let Some(row) = rows.pool.pop() {
+                row.index.set(index);
+                row.scope.is_changed.set(true);
+                row
+            } else {
+                let index = Rc::new(Cell::new(index));
+                let row_key = Rc::new(Cell::new(None));
+
+                let item = LazyItem {
+                    list: cx.me().value,
+                    index: index.clone(),
+                    key: row_key.clone(),
+                };
+                let compose: Box<dyn AnyCompose + '_> = Box::new(item);
+
+                // Safety: Rows are dropped with this composable's scope.
+                let compose: Box<dyn AnyCompose> = unsafe { mem::transmute(compose) };
+
+                Box::new(Row {
+                    scope: ScopeData::default(),
+                    index,
+                    key: row_key,
+                    compose,
+                })
+            };
+            rows.visible.push(row);
+        }
+        rows.visible.sort_by_key(|row| row.index.get());
+
+        // Keep enough rows to refill the viewport.
+        let pool_len = end - start;
+        rows.pool.truncate(pool_len);
+
+        for row in &rows.visible {
+            row.scope.inherit_contexts(&cx);
+
+            row.scope.is_parent_changed.set(cx.is_parent_changed.get());
+
+            unsafe { row.compose.any_compose(&row.scope) }
+        }
+
+        // Order the layout nodes of the visible rows between the spacers.
+        let mut taffy = window_cx.taffy.borrow_mut();
+
+        let mut children = vec![top];
+        children.extend(rows.visible.iter().filter_map(|row| row.key.get()));
+        children.push(bottom);
+        if taffy.children(key).unwrap() != children {
+            taffy.set_children(key, &children).unwrap();
+            window_cx.is_layout_changed.set(true);
+        }
+
+        let item_height = cx.me().item_height;
+        for (spacer, count) in [(top, start), (bottom, cx.me().len - end)] {
+            let style = spacer_style(count, item_height);
+            if *taffy.style(spacer).unwrap() != style {
+                taffy.set_style(spacer, style).unwrap();
+                window_cx.is_layout_changed.set(true);
+            }
+        }
+    }
+}
+
+/// Row of a [`LazyColumn`].
+struct LazyItem<'a, C> {
+    list: &'a LazyColumn<'a, C>,
+    index: Rc<Cell<usize>>,
+    key: Rc<Cell<Option<NodeId>>>,
+}
+
+unsafe impl<C: Data> Data for LazyItem<'_, C> {}
+
+impl<C: Compose> Compose for LazyItem<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let (key, _layout) = use_layout(
+            &cx,
+            Style {
+                flex_direction: FlexDirection::Column,
+                flex_shrink: 0.,
+                ..Default::default()
+            },
+        );
+        cx.me().key.set(Some(key));
+
+        use_provider(&cx, || LayoutContext::new(key));
+
+        (cx.me().list.f)(cx.me().index.get())
+    }
+}
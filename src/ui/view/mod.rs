@@ -1,4 +1,10 @@
-use super::{draw::BackgroundColor, Event};
+use super::{
+    draw::{Background, Border, Shadow},
+    Accessibility, Action, DragPayload, Event, FocusHandle, ImeHandle, KeyboardInput,
+    LayoutContext, StyleFn,
+};
+#[cfg(feature = "menu")]
+use crate::{event_loop::use_window, menu::Menu};
 use crate::{
     prelude::*,
     ui::view::{
@@ -6,9 +12,20 @@ use crate::{
         text::{IntoFontStack, TextContext},
     },
 };
-use parley::FontStack;
-use std::{borrow::Cow, cell::RefCell, mem, rc::Rc};
-use winit::event::{ElementState, MouseButton};
+use parley::{FontFamily, FontStack, FontWeight};
+use std::{
+    any::Any,
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    mem,
+    path::Path,
+    rc::Rc,
+};
+use taffy::Point;
+use winit::event::{ElementState, Ime, MouseButton};
+
+mod button;
+pub use self::button::{button, Button};
 
 pub(crate) mod canvas;
 pub use self::canvas::Canvas;
@@ -16,11 +33,59 @@ pub use self::canvas::Canvas;
 mod flex;
 pub use self::flex::Flex;
 
+mod grid;
+pub use self::grid::Grid;
+
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+mod image;
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+pub use self::image::{image, Image, ImageFit, ImageSource};
+
+mod lazy;
+pub use self::lazy::{lazy_column, LazyColumn};
+
+mod popup;
+pub use self::popup::{Modal, Popup};
+
+mod progress;
+pub use self::progress::{progress_bar, ProgressBar};
+
+pub(crate) mod scroll;
+pub use self::scroll::{Scroll, ScrollOffset};
+
+mod select;
+pub use self::select::{select, Select};
+
+mod slider;
+pub use self::slider::{slider, Slider};
+
+mod split;
+pub use self::split::{split, Split};
+
+mod tabs;
+pub use self::tabs::{tabs, Tabs};
+
+mod table;
+pub use self::table::{table, SortOrder, Table, TableColumn};
+
 /// Text composable.
 pub mod text;
-pub use self::text::Text;
+pub use self::text::{rich_text, span, RichText, Span, Text};
 
-mod window;
+mod toggle;
+pub use self::toggle::{checkbox, radio_group, switch, Checkbox, RadioGroup, Switch};
+
+mod tooltip;
+pub use self::tooltip::Tooltip;
+
+pub(crate) mod transition;
+pub use self::transition::{Transition, TransitionKind};
+
+pub(crate) mod widget;
+
+pub(crate) mod window;
 pub use self::window::Window;
 
 /// Composable view modifiers.
@@ -56,13 +121,238 @@ pub trait View: Compose {
         self.on_event(Clickable::new(on_click))
     }
 
+    /// Add an event handler for key-down events to this view.
+    fn on_key_down<'a>(
+        self,
+        on_key_down: impl Fn(&KeyboardInput) + 'a,
+    ) -> Modified<OnEvent<OnKeyDown<'a>>, Self> {
+        self.on_event(OnKeyDown::new(on_key_down))
+    }
+
+    /// Add an event handler for key-up events to this view.
+    fn on_key_up<'a>(
+        self,
+        on_key_up: impl Fn(&KeyboardInput) + 'a,
+    ) -> Modified<OnEvent<OnKeyUp<'a>>, Self> {
+        self.on_event(OnKeyUp::new(on_key_up))
+    }
+
+    /// Add an event handler for focus events to this view.
+    fn on_focus<'a>(self, on_focus: impl Fn() + 'a) -> Modified<OnEvent<OnFocus<'a>>, Self> {
+        self.on_event(OnFocus::new(on_focus))
+    }
+
+    /// Add an event handler for blur events to this view.
+    fn on_blur<'a>(self, on_blur: impl Fn() + 'a) -> Modified<OnEvent<OnBlur<'a>>, Self> {
+        self.on_event(OnBlur::new(on_blur))
+    }
+
+    /// Add an event handler for input method (IME) events to this view.
+    ///
+    /// These events are only sent while this view accepts text input with [`View::ime`].
+    fn on_ime<'a>(self, on_ime: impl Fn(&Ime) + 'a) -> Modified<OnEvent<OnIme<'a>>, Self> {
+        self.on_event(OnIme::new(on_ime))
+    }
+
+    /// Make this view draggable, dropping `payload` on the drop target it's released over.
+    ///
+    /// Drop targets accepting the payload can be added with [`View::on_drop`].
+    fn draggable(self, payload: impl Any) -> Modified<Draggable, Self> {
+        self.modify(Draggable {
+            payload: DragPayload::new(payload),
+        })
+    }
+
+    /// Make this view a drop target, accepting dragged payloads of type `T`.
+    ///
+    /// Drag enter and leave events are sent to this view while an accepted payload is dragged over it.
+    fn on_drop<'a, T: 'static>(self, on_drop: impl Fn(&T) + 'a) -> Modified<OnDrop<'a, T>, Self> {
+        self.modify(OnDrop::new(on_drop))
+    }
+
+    /// Add an event handler for files dropped on this view from the OS.
+    fn on_file_drop<'a>(
+        self,
+        on_file_drop: impl Fn(&Path) + 'a,
+    ) -> Modified<OnEvent<OnFileDrop<'a>>, Self> {
+        self.on_event(OnFileDrop::new(on_file_drop))
+    }
+
+    /// Show a native context menu when this view is right-clicked,
+    /// building the menu with the provided function.
+    #[cfg(feature = "menu")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "menu")))]
+    fn on_context_menu<'a>(
+        self,
+        menu: impl Fn() -> Menu<'a> + 'a,
+    ) -> Modified<OnContextMenu<'a>, Self> {
+        self.modify(OnContextMenu::new(menu))
+    }
+
+    /// Make this view focusable, attaching it to the provided [`FocusHandle`].
+    ///
+    /// The handle can be created with [`use_focus`](crate::ui::use_focus).
+    fn focusable(self, handle: FocusHandle) -> Modified<Focusable, Self> {
+        self.modify(Focusable { handle })
+    }
+
+    /// Accept text input from the window's input method (IME) while this view is focused,
+    /// attaching it to the provided [`ImeHandle`].
+    ///
+    /// The handle can be created with [`use_ime`](crate::ui::use_ime).
+    fn ime(self, handle: ImeHandle) -> Modified<ImeInput, Self> {
+        self.modify(ImeInput { handle })
+    }
+
+    /// Set the accessibility properties of this view, read by assistive technologies.
+    fn accessibility(self, accessibility: Accessibility) -> Modified<Accessible, Self> {
+        self.modify(Accessible { accessibility })
+    }
+
+    /// Modify the layout style of this view with the provided function.
+    fn layout_style(self, f: impl Fn(&mut Style) + 'static) -> Modified<LayoutStyle, Self> {
+        self.modify(LayoutStyle::new(f))
+    }
+
+    /// Set the flex grow factor of this view.
+    fn flex_grow(self, flex_grow: f32) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.flex_grow = flex_grow)
+    }
+
+    /// Set the flex shrink factor of this view.
+    fn flex_shrink(self, flex_shrink: f32) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.flex_shrink = flex_shrink)
+    }
+
+    /// Set the initial main axis size of this view.
+    fn flex_basis(self, flex_basis: Dimension) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.flex_basis = flex_basis)
+    }
+
+    /// Set how the children of this view are aligned on the cross axis.
+    fn align_items(self, align_items: AlignItems) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.align_items = Some(align_items))
+    }
+
+    /// Set how this view is aligned on the cross axis of its parent.
+    fn align_self(self, align_self: AlignSelf) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.align_self = Some(align_self))
+    }
+
+    /// Set how the children of this view are aligned on the main axis.
+    fn justify_content(self, justify_content: JustifyContent) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.justify_content = Some(justify_content))
+    }
+
+    /// Set the gap between the children of this view.
+    fn gap(self, gap: Size<LengthPercentage>) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.gap = gap)
+    }
+
+    /// Set the padding of this view.
+    fn padding(self, padding: Rect<LengthPercentage>) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.padding = padding)
+    }
+
+    /// Set the margin of this view.
+    fn margin(self, margin: Rect<LengthPercentageAuto>) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.margin = margin)
+    }
+
+    /// Set how content overflowing this view is handled on both axes.
+    ///
+    /// Content is clipped to this view's bounds (and its rounded corners) with [`Overflow::Hidden`] or [`Overflow::Clip`].
+    fn overflow(self, overflow: Overflow) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| {
+            style.overflow = Point {
+                x: overflow,
+                y: overflow,
+            }
+        })
+    }
+
+    /// Clip content overflowing this view to its bounds.
+    ///
+    /// This is the same as [`View::overflow`] with [`Overflow::Hidden`].
+    fn clip(self) -> Modified<LayoutStyle, Self> {
+        self.overflow(Overflow::Hidden)
+    }
+
+    /// Set the opacity of this view and its content, composited together.
+    fn opacity(self, opacity: f32) -> Modified<Effect, Self> {
+        self.modify(Effect::Opacity(opacity))
+    }
+
+    /// Rotate this view and its content by `angle` (in radians) around its center.
+    fn rotate(self, angle: f64) -> Modified<Effect, Self> {
+        self.modify(Effect::Rotate(angle))
+    }
+
+    /// Scale this view and its content by `x` and `y` around its center.
+    fn scale(self, x: f64, y: f64) -> Modified<Effect, Self> {
+        self.modify(Effect::Scale(x, y))
+    }
+
+    /// Translate this view and its content by `x` and `y`, without changing its layout.
+    fn translate(self, x: f64, y: f64) -> Modified<Effect, Self> {
+        self.modify(Effect::Translate(x, y))
+    }
+
+    /// Set the row lines this view is placed between in a [`Grid`].
+    fn grid_row(self, grid_row: Line<GridPlacement>) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.grid_row = grid_row)
+    }
+
+    /// Set the column lines this view is placed between in a [`Grid`].
+    fn grid_column(self, grid_column: Line<GridPlacement>) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| style.grid_column = grid_column)
+    }
+
+    /// Set the area of a [`Grid`] this view is placed in, from its row and column lines.
+    fn grid_area(
+        self,
+        grid_row: Line<GridPlacement>,
+        grid_column: Line<GridPlacement>,
+    ) -> Modified<LayoutStyle, Self> {
+        self.layout_style(move |style| {
+            style.grid_row = grid_row;
+            style.grid_column = grid_column;
+        })
+    }
+
     /// Set the font for this view.
+    ///
+    /// This is the same as [`View::font_family`].
     fn font(self, font_stack: impl IntoFontStack<'static>) -> Modified<Font, Self> {
+        self.font_family(font_stack)
+    }
+
+    /// Set the font family (or stack of families) for this view.
+    ///
+    /// Custom fonts can be loaded with [`use_font`](text::use_font) or [`use_font_file`](text::use_font_file).
+    fn font_family(self, font_stack: impl IntoFontStack<'static>) -> Modified<Font, Self> {
         self.modify(Font {
             font_stack: font_stack.into_font_stack(),
         })
     }
 
+    /// Set the font weight for this view.
+    fn font_weight(self, font_weight: FontWeight) -> Modified<Weight, Self> {
+        self.modify(Weight { font_weight })
+    }
+
+    /// Set the font families to fall back to for characters missing from this view's font, in order.
+    ///
+    /// This defaults to the system emoji font.
+    fn font_fallback<'f>(
+        self,
+        fallback: impl IntoIterator<Item = FontFamily<'f>>,
+    ) -> Modified<FontFallback, Self> {
+        self.modify(FontFallback {
+            fallback: fallback.into_iter().map(text::to_owned_family).collect(),
+        })
+    }
+
     /// Set the text color for this view.
     fn color(self, color: Color) -> Modified<FontColor, Self> {
         self.modify(FontColor { color })
@@ -78,9 +368,53 @@ pub trait View: Compose {
         self.modify(DrawModifier::new(draw))
     }
 
+    /// Set the background for this view, filling it with a solid color, gradient, or image pattern.
+    ///
+    /// Gradients and image patterns are positioned relative to the view's origin.
+    fn background(self, brush: impl Into<Brush>) -> Modified<DrawModifier<Background>, Self> {
+        self.draw(Background {
+            brush: brush.into(),
+        })
+    }
+
     /// Set the background color for this view.
-    fn background_color(self, color: Color) -> Modified<DrawModifier<BackgroundColor>, Self> {
-        self.draw(BackgroundColor { color })
+    ///
+    /// This is the same as [`View::background`] with a solid color.
+    fn background_color(self, color: Color) -> Modified<DrawModifier<Background>, Self> {
+        self.background(color)
+    }
+
+    /// Draw a border of `width` inside the edges of this view.
+    fn border(self, width: f64, color: Color) -> Modified<Bordered, Self> {
+        self.modify(Bordered {
+            border: Border { width, color },
+        })
+    }
+
+    /// Round the corners of this view's background and border with `radius`,
+    /// clipping its drawing to the rounded corners.
+    fn corner_radius(self, radius: f64) -> Modified<Rounded, Self> {
+        self.modify(Rounded { radius })
+    }
+
+    /// Draw a shadow below this view, moved by `offset` and blurred by `blur`.
+    ///
+    /// Multiple shadows can be added to the same view.
+    fn shadow(self, offset: Vec2, blur: f64, color: Color) -> Modified<Shadowed, Self> {
+        self.modify(Shadowed {
+            shadow: Shadow {
+                offset,
+                blur,
+                color,
+            },
+        })
+    }
+
+    /// Show a tooltip with the provided text after the cursor hovers over this view.
+    ///
+    /// The returned [`Tooltip`] can be configured with a custom delay and placement.
+    fn tooltip(self, text: impl Into<String>) -> Tooltip<Self> {
+        Tooltip::new(text, self)
     }
 }
 
@@ -136,6 +470,17 @@ pub trait Handler {
 
     /// Handle an event with the current state.
     fn handle(&self, state: &mut Self::State, event: Event);
+
+    /// Accessibility actions supported by this handler.
+    fn actions(&self) -> Vec<Action> {
+        Vec::new()
+    }
+
+    /// Returns `true` if this handler handles keyboard input,
+    /// making its view focusable so it can receive key events.
+    fn is_keyboard(&self) -> bool {
+        false
+    }
 }
 
 impl<F: Fn(Event)> Handler for F {
@@ -183,14 +528,159 @@ impl<H: Handler> Modify for OnEvent<H> {
             let mut pending_listeners = canvas_cx.pending_listeners.borrow().clone();
             pending_listeners.push(f);
 
+            let on_event = self.on_event.borrow();
+            let mut accessibility = canvas_cx.accessibility.borrow().clone();
+            for action in on_event.actions() {
+                if !accessibility.actions.contains(&action) {
+                    accessibility.actions.push(action);
+                }
+            }
+
             CanvasContext {
-                draws: canvas_cx.draws.clone(),
                 pending_listeners: Rc::new(RefCell::new(pending_listeners)),
+                accessibility: Rc::new(RefCell::new(accessibility)),
+                is_focusable: canvas_cx.is_focusable || on_event.is_keyboard(),
+                ..(*canvas_cx).clone()
             }
         });
     }
 }
 
+/// Focusable modifier.
+#[derive(Data)]
+pub struct Focusable {
+    handle: FocusHandle,
+}
+
+impl Modify for Focusable {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        use_provider(cx, || {
+            let mut focus_handles = canvas_cx.focus_handles.borrow().clone();
+            focus_handles.push(self.handle.clone());
+
+            CanvasContext {
+                focus_handles: Rc::new(RefCell::new(focus_handles)),
+                ..(*canvas_cx).clone()
+            }
+        });
+    }
+}
+
+/// Draggable modifier.
+#[derive(Data)]
+pub struct Draggable {
+    payload: DragPayload,
+}
+
+impl Modify for Draggable {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        let cell = use_ref(cx, || Rc::new(RefCell::new(self.payload.clone())));
+        *cell.borrow_mut() = self.payload.clone();
+
+        use_provider(cx, || CanvasContext {
+            drag_payload: Some(cell.clone()),
+            ..(*canvas_cx).clone()
+        });
+    }
+}
+
+/// Drop target modifier.
+pub struct OnDrop<'a, T> {
+    on_drop: Box<dyn Fn(&T) + 'a>,
+}
+
+impl<'a, T> OnDrop<'a, T> {
+    /// Create a new drop target modifier from the provided function.
+    pub fn new(on_drop: impl Fn(&T) + 'a) -> Self {
+        Self {
+            on_drop: Box::new(on_drop),
+        }
+    }
+}
+
+unsafe impl<T: 'static> Data for OnDrop<'_, T> {}
+
+impl<T: 'static> Modify for OnDrop<'_, T> {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        use_provider(cx, || {
+            let f: Rc<dyn Fn(Event) + '_> = Rc::new(move |event| {
+                if let Event::Drop(payload) = event {
+                    if let Some(value) = payload.downcast_ref() {
+                        (self.on_drop)(value)
+                    }
+                }
+            });
+
+            // Safety: `f` is removed from `listeners` on drop.
+            let f: Rc<dyn Fn(Event)> = unsafe { mem::transmute(f) };
+
+            let mut pending_listeners = canvas_cx.pending_listeners.borrow().clone();
+            pending_listeners.push(f);
+
+            let mut drop_accepts = canvas_cx.drop_accepts.borrow().clone();
+            drop_accepts.push(Rc::new(|payload: &DragPayload| payload.is::<T>()));
+
+            CanvasContext {
+                pending_listeners: Rc::new(RefCell::new(pending_listeners)),
+                drop_accepts: Rc::new(RefCell::new(drop_accepts)),
+                ..(*canvas_cx).clone()
+            }
+        });
+    }
+}
+
+/// Input method (IME) modifier.
+#[derive(Data)]
+pub struct ImeInput {
+    handle: ImeHandle,
+}
+
+impl Modify for ImeInput {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        use_provider(cx, || {
+            let mut ime_handles = canvas_cx.ime_handles.borrow().clone();
+            ime_handles.push(self.handle.clone());
+
+            CanvasContext {
+                ime_handles: Rc::new(RefCell::new(ime_handles)),
+                ..(*canvas_cx).clone()
+            }
+        });
+    }
+}
+
+/// Accessibility modifier.
+#[derive(Data)]
+pub struct Accessible {
+    accessibility: Accessibility,
+}
+
+impl Modify for Accessible {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        let cell = use_ref(cx, || Rc::new(RefCell::new(Accessibility::default())));
+
+        // Keep the parent's actions, overriding its role and label.
+        let mut accessibility = self.accessibility.clone();
+        accessibility.merge(&canvas_cx.accessibility.borrow());
+        *cell.borrow_mut() = accessibility;
+
+        use_provider(cx, || CanvasContext {
+            accessibility: cell.clone(),
+            ..(*canvas_cx).clone()
+        });
+    }
+}
+
 /// Mouse-in event handler.
 #[derive(Data)]
 pub struct OnMouseIn<'a> {
@@ -289,6 +779,317 @@ impl Handler for Clickable<'_> {
             }
         }
     }
+
+    fn actions(&self) -> Vec<Action> {
+        vec![Action::Click]
+    }
+}
+
+/// Focus event handler.
+#[derive(Data)]
+pub struct OnFocus<'a> {
+    on_focus: Box<dyn Fn() + 'a>,
+}
+
+impl<'a> OnFocus<'a> {
+    /// Create a new focus event handler from the provided function.
+    pub fn new(on_focus: impl Fn() + 'a) -> Self {
+        Self {
+            on_focus: Box::new(on_focus),
+        }
+    }
+}
+
+impl Handler for OnFocus<'_> {
+    type State = ();
+
+    fn build(&self) -> Self::State {}
+
+    fn handle(&self, state: &mut Self::State, event: Event) {
+        let _ = state;
+
+        if let Event::Focus = event {
+            (self.on_focus)()
+        }
+    }
+}
+
+/// Blur event handler.
+#[derive(Data)]
+pub struct OnBlur<'a> {
+    on_blur: Box<dyn Fn() + 'a>,
+}
+
+impl<'a> OnBlur<'a> {
+    /// Create a new blur event handler from the provided function.
+    pub fn new(on_blur: impl Fn() + 'a) -> Self {
+        Self {
+            on_blur: Box::new(on_blur),
+        }
+    }
+}
+
+impl Handler for OnBlur<'_> {
+    type State = ();
+
+    fn build(&self) -> Self::State {}
+
+    fn handle(&self, state: &mut Self::State, event: Event) {
+        let _ = state;
+
+        if let Event::Blur = event {
+            (self.on_blur)()
+        }
+    }
+}
+
+/// Key-down event handler.
+#[derive(Data)]
+pub struct OnKeyDown<'a> {
+    on_key_down: Box<dyn Fn(&KeyboardInput) + 'a>,
+}
+
+impl<'a> OnKeyDown<'a> {
+    /// Create a new key-down event handler from the provided function.
+    pub fn new(on_key_down: impl Fn(&KeyboardInput) + 'a) -> Self {
+        Self {
+            on_key_down: Box::new(on_key_down),
+        }
+    }
+}
+
+impl Handler for OnKeyDown<'_> {
+    type State = ();
+
+    fn build(&self) -> Self::State {}
+
+    fn handle(&self, state: &mut Self::State, event: Event) {
+        let _ = state;
+
+        if let Event::KeyboardInput(input) = event {
+            if input.state == ElementState::Pressed {
+                (self.on_key_down)(&input)
+            }
+        }
+    }
+
+    fn is_keyboard(&self) -> bool {
+        true
+    }
+}
+
+/// Key-up event handler.
+#[derive(Data)]
+pub struct OnKeyUp<'a> {
+    on_key_up: Box<dyn Fn(&KeyboardInput) + 'a>,
+}
+
+impl<'a> OnKeyUp<'a> {
+    /// Create a new key-up event handler from the provided function.
+    pub fn new(on_key_up: impl Fn(&KeyboardInput) + 'a) -> Self {
+        Self {
+            on_key_up: Box::new(on_key_up),
+        }
+    }
+}
+
+impl Handler for OnKeyUp<'_> {
+    type State = ();
+
+    fn build(&self) -> Self::State {}
+
+    fn handle(&self, state: &mut Self::State, event: Event) {
+        let _ = state;
+
+        if let Event::KeyboardInput(input) = event {
+            if input.state == ElementState::Released {
+                (self.on_key_up)(&input)
+            }
+        }
+    }
+
+    fn is_keyboard(&self) -> bool {
+        true
+    }
+}
+
+/// Input method (IME) event handler.
+#[derive(Data)]
+pub struct OnIme<'a> {
+    on_ime: Box<dyn Fn(&Ime) + 'a>,
+}
+
+impl<'a> OnIme<'a> {
+    /// Create a new IME event handler from the provided function.
+    pub fn new(on_ime: impl Fn(&Ime) + 'a) -> Self {
+        Self {
+            on_ime: Box::new(on_ime),
+        }
+    }
+}
+
+impl Handler for OnIme<'_> {
+    type State = ();
+
+    fn build(&self) -> Self::State {}
+
+    fn handle(&self, state: &mut Self::State, event: Event) {
+        let _ = state;
+
+        if let Event::Ime(ime) = event {
+            (self.on_ime)(&ime)
+        }
+    }
+
+    fn is_keyboard(&self) -> bool {
+        true
+    }
+}
+
+/// File drop event handler.
+#[derive(Data)]
+pub struct OnFileDrop<'a> {
+    on_file_drop: Box<dyn Fn(&Path) + 'a>,
+}
+
+impl<'a> OnFileDrop<'a> {
+    /// Create a new file drop event handler from the provided function.
+    pub fn new(on_file_drop: impl Fn(&Path) + 'a) -> Self {
+        Self {
+            on_file_drop: Box::new(on_file_drop),
+        }
+    }
+}
+
+impl Handler for OnFileDrop<'_> {
+    type State = ();
+
+    fn build(&self) -> Self::State {}
+
+    fn handle(&self, state: &mut Self::State, event: Event) {
+        let _ = state;
+
+        if let Event::FileDrop(path) = event {
+            (self.on_file_drop)(&path)
+        }
+    }
+}
+
+/// Context menu modifier.
+#[cfg(feature = "menu")]
+#[cfg_attr(docsrs, doc(cfg(feature = "menu")))]
+pub struct OnContextMenu<'a> {
+    menu: Box<dyn Fn() -> Menu<'a> + 'a>,
+}
+
+#[cfg(feature = "menu")]
+impl<'a> OnContextMenu<'a> {
+    /// Create a new context menu modifier from a function to build the menu.
+    pub fn new(menu: impl Fn() -> Menu<'a> + 'a) -> Self {
+        Self {
+            menu: Box::new(menu),
+        }
+    }
+}
+
+#[cfg(feature = "menu")]
+unsafe impl Data for OnContextMenu<'_> {}
+
+#[cfg(feature = "menu")]
+impl Modify for OnContextMenu<'_> {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+        let window = use_window(cx);
+
+        let owner = *use_ref(cx, crate::menu::next_owner);
+        use_drop(cx, move || crate::menu::unregister(owner));
+
+        use_provider(cx, || {
+            // Safety: `f` is removed from `canvas_update_fns` on drop,
+            // and the shown menu is unregistered on drop.
+            let f: Rc<dyn Fn(Event)> = Rc::new(move |event| {
+                if let Event::MouseInput {
+                    button: MouseButton::Right,
+                    state: ElementState::Pressed,
+                    ..
+                } = event
+                {
+                    unsafe { crate::menu::show_context_menu(owner, &window, (self.menu)()) }
+                }
+            });
+            let f: Rc<dyn Fn(Event)> = unsafe { mem::transmute(f) };
+
+            let mut pending_listeners = canvas_cx.pending_listeners.borrow().clone();
+            pending_listeners.push(f);
+
+            CanvasContext {
+                pending_listeners: Rc::new(RefCell::new(pending_listeners)),
+                ..(*canvas_cx).clone()
+            }
+        });
+    }
+}
+
+/// Layout style modifier.
+///
+/// This modifies the style of the next layout node created by its content.
+#[derive(Data)]
+pub struct LayoutStyle {
+    f: StyleFn,
+}
+
+impl LayoutStyle {
+    /// Create a new layout style modifier from a function to modify a [`Style`].
+    pub fn new(f: impl Fn(&mut Style) + 'static) -> Self {
+        Self { f: Rc::new(f) }
+    }
+}
+
+impl Modify for LayoutStyle {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let layout_cx = use_context::<LayoutContext>(cx).unwrap();
+
+        let cell = use_ref(cx, || Rc::new(RefCell::new(self.f.clone())));
+        *cell.borrow_mut() = self.f.clone();
+
+        use_provider(cx, || {
+            let mut layout_cx = (*layout_cx).clone();
+            layout_cx.styles.push(cell.clone());
+            layout_cx
+        });
+    }
+}
+
+/// Visual effect modifier.
+///
+/// Like [`LayoutStyle`], this applies to the next layout node created by its content,
+/// and is drawn with every node inside it.
+/// Hit testing follows the transformed geometry.
+#[derive(Clone, Copy, Debug, PartialEq, Data)]
+pub enum Effect {
+    /// Multiply the opacity of the view.
+    Opacity(f32),
+    /// Rotate the view by an angle (in radians) around its center.
+    Rotate(f64),
+    /// Scale the view around its center.
+    Scale(f64, f64),
+    /// Translate the view.
+    Translate(f64, f64),
+}
+
+impl Modify for Effect {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let layout_cx = use_context::<LayoutContext>(cx).unwrap();
+
+        let cell = use_ref(cx, || Rc::new(Cell::new(*self)));
+        cell.set(*self);
+
+        use_provider(cx, || {
+            let mut layout_cx = (*layout_cx).clone();
+            layout_cx.effects.push(cell.clone());
+            layout_cx
+        });
+    }
 }
 
 /// Font color modifier.
@@ -304,8 +1105,7 @@ impl Modify for FontColor {
 
         use_provider(cx, || TextContext {
             color: self.color,
-            font_size: text_cx.font_size,
-            font_stack: text_cx.font_stack.clone(),
+            ..text_cx.clone()
         });
     }
 }
@@ -322,9 +1122,8 @@ impl Modify for FontSize {
         let text_cx = use_context::<TextContext>(cx).unwrap();
 
         use_provider(cx, || TextContext {
-            color: text_cx.color,
             font_size: self.font_size,
-            font_stack: text_cx.font_stack.clone(),
+            ..text_cx.clone()
         });
     }
 }
@@ -341,9 +1140,44 @@ impl Modify for Font {
         let text_cx = use_context::<TextContext>(cx).unwrap();
 
         use_provider(cx, || TextContext {
-            color: text_cx.color,
-            font_size: text_cx.font_size,
             font_stack: self.font_stack.clone(),
+            ..text_cx.clone()
+        });
+    }
+}
+
+/// Font weight modifier.
+#[derive(Data)]
+pub struct Weight {
+    /// Font weight.
+    pub font_weight: FontWeight,
+}
+
+impl Modify for Weight {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let text_cx = use_context::<TextContext>(cx).unwrap();
+
+        use_provider(cx, || TextContext {
+            font_weight: self.font_weight,
+            ..text_cx.clone()
+        });
+    }
+}
+
+/// Font fallback modifier.
+#[derive(Data)]
+pub struct FontFallback {
+    /// Font families to fall back to, in order.
+    pub fallback: Vec<FontFamily<'static>>,
+}
+
+impl Modify for FontFallback {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let text_cx = use_context::<TextContext>(cx).unwrap();
+
+        use_provider(cx, || TextContext {
+            fallback: self.fallback.clone(),
+            ..text_cx.clone()
         });
     }
 }
@@ -376,3 +1210,60 @@ impl<T: Draw + 'static> Modify for DrawModifier<T> {
         });
     }
 }
+
+/// Border modifier.
+#[derive(Data)]
+pub struct Bordered {
+    /// Border.
+    pub border: Border,
+}
+
+impl Modify for Bordered {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        use_provider(cx, || {
+            let mut canvas_cx = (*canvas_cx).clone();
+            canvas_cx.decoration.border = Some(self.border);
+            canvas_cx
+        });
+    }
+}
+
+/// Corner radius modifier.
+#[derive(Data)]
+pub struct Rounded {
+    /// Corner radius.
+    pub radius: f64,
+}
+
+impl Modify for Rounded {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        use_provider(cx, || {
+            let mut canvas_cx = (*canvas_cx).clone();
+            canvas_cx.decoration.corner_radius = self.radius;
+            canvas_cx
+        });
+    }
+}
+
+/// Shadow modifier.
+#[derive(Data)]
+pub struct Shadowed {
+    /// Shadow.
+    pub shadow: Shadow,
+}
+
+impl Modify for Shadowed {
+    fn use_state<'a>(&'a self, cx: ScopeState<'a>) {
+        let canvas_cx = use_context::<CanvasContext>(cx).unwrap();
+
+        use_provider(cx, || {
+            let mut canvas_cx = (*canvas_cx).clone();
+            canvas_cx.decoration.shadows.push(self.shadow);
+            canvas_cx
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    prelude::*,
+    ui::{use_layer, view::canvas::CanvasContext, LayerKind, LayoutContext, Placement},
+};
+use std::rc::Rc;
+
+/// Popup composable.
+///
+/// The content of a popup is drawn in a layer above the window's content,
+/// positioned next to its anchor: the layout node containing this composable (such as a [`Flex`]).
+/// Popups opened later are drawn above earlier ones.
+///
+/// Pressing outside of the popup and its anchor, or pressing the escape key,
+/// calls the dismiss handler of the topmost popup.
+/// This can be used for dropdowns, tooltips, and menus.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Popup<'a, C> {
+    content: C,
+    placement: Placement,
+    on_dismiss: Box<dyn Fn() + 'a>,
+}
+
+impl<'a, C> Popup<'a, C> {
+    /// Create a new popup from its content.
+    pub fn new(content: C) -> Self {
+        Self {
+            content,
+            placement: Placement::default(),
+            on_dismiss: Box::new(|| {}),
+        }
+    }
+
+    /// Set the placement of this popup relative to its anchor (default: [`Placement::Below`]).
+    ///
+    /// The popup is flipped to the opposite side if it would overflow the window.
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Set the function to call when this popup is dismissed.
+    pub fn on_dismiss(mut self, on_dismiss: impl Fn() + 'a) -> Self {
+        self.on_dismiss = Box::new(on_dismiss);
+        self
+    }
+}
+
+impl<C: Compose> Compose for Popup<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let anchor = use_context::<LayoutContext>(&cx).unwrap().parent_id;
+
+        let kind = LayerKind::Popup {
+            anchor,
+            placement: cx.me().placement,
+        };
+        let root = use_layer(&cx, kind, || Rc::new(move || (cx.me().on_dismiss)()));
+
+        use_provider(&cx, || LayoutContext::new(root));
+        use_provider(&cx, CanvasContext::default);
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+/// Modal dialog composable.
+///
+/// The content of a modal dialog is drawn centered in a layer above the window's content,
+/// over a scrim with the current [`Theme`](crate::ui::Theme)'s scrim color.
+/// While it's open, input to the content below it is blocked,
+/// and keyboard focus is kept within it.
+///
+/// Pressing outside of the dialog, or pressing the escape key, calls its dismiss handler.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Modal<'a, C> {
+    content: C,
+    on_dismiss: Box<dyn Fn() + 'a>,
+}
+
+impl<'a, C> Modal<'a, C> {
+    /// Create a new modal dialog from its content.
+    pub fn new(content: C) -> Self {
+        Self {
+            content,
+            on_dismiss: Box::new(|| {}),
+        }
+    }
+
+    /// Set the function to call when this dialog is dismissed.
+    pub fn on_dismiss(mut self, on_dismiss: impl Fn() + 'a) -> Self {
+        self.on_dismiss = Box::new(on_dismiss);
+        self
+    }
+}
+
+impl<C: Compose> Compose for Modal<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let scrim = use_theme(&cx).colors.scrim;
+
+        let root = use_layer(&cx, LayerKind::Modal { scrim }, || {
+            Rc::new(move || (cx.me().on_dismiss)())
+        });
+
+        use_provider(&cx, || LayoutContext::new(root));
+        use_provider(&cx, CanvasContext::default);
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
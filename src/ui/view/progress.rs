@@ -0,0 +1,173 @@
+use crate::{
+    prelude::*,
+    ui::{use_frame, view::widget::SurfaceStyle, Accessibility, Role},
+};
+use std::time::Duration;
+use taffy::Layout;
+use vello::kurbo::{Affine, Rect};
+
+/// Height of a progress bar.
+const HEIGHT: f32 = 4.;
+
+/// Duration of one sweep of an indeterminate progress bar.
+const PERIOD: Duration = Duration::from_millis(1500);
+
+/// Create a new progress bar from its progress, from 0 to 1.
+///
+/// If `progress` is `None`, the progress bar is indeterminate.
+pub fn progress_bar(progress: impl Into<Option<f64>>) -> ProgressBar {
+    ProgressBar::new(progress)
+}
+
+/// Progress bar composable.
+///
+/// A determinate progress bar fills its track up to its progress, animating each change.
+/// An indeterminate progress bar sweeps a segment across its track on each frame
+/// to show activity of an unknown duration.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct ProgressBar {
+    progress: Option<f64>,
+    label: Option<String>,
+}
+
+impl ProgressBar {
+    /// Create a new progress bar from its progress, from 0 to 1.
+    ///
+    /// If `progress` is `None`, the progress bar is indeterminate.
+    pub fn new(progress: impl Into<Option<f64>>) -> Self {
+        Self {
+            progress: progress.into().map(|progress| progress.clamp(0., 1.)),
+            label: None,
+        }
+    }
+
+    /// Set the accessibility label of this progress bar.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Get the accessibility properties of this progress bar.
+    fn to_accessibility(&self) -> Accessibility {
+        let accessibility = Accessibility {
+            role: Role::ProgressIndicator,
+            label: self.label.clone(),
+            ..Default::default()
+        };
+
+        match self.progress {
+            Some(progress) => accessibility.numeric_value(progress, 0.0..=1.),
+            None => accessibility,
+        }
+    }
+}
+
+impl Compose for ProgressBar {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let accessibility = cx.me().to_accessibility();
+
+        (
+            cx.me().progress.map(|progress| DeterminateBar {
+                progress,
+                accessibility: accessibility.clone(),
+            }),
+            cx.me()
+                .progress
+                .is_none()
+                .then_some(IndeterminateBar { accessibility }),
+        )
+    }
+}
+
+fn bar_style() -> Style {
+    Style {
+        size: Size {
+            width: percent(1.),
+            height: length(HEIGHT),
+        },
+        ..Default::default()
+    }
+}
+
+/// Draw the track of a progress bar with `renderer`, returning its shape.
+fn draw_track(renderer: &mut dyn Renderer, theme: &Theme, layout: &Layout) -> Rect {
+    let track = Rect::new(0., 0., layout.size.width as _, layout.size.height as _);
+    SurfaceStyle {
+        background: theme.colors.text_secondary.multiply_alpha(0.3),
+        corner_radius: track.height() / 2.,
+        ..Default::default()
+    }
+    .draw(renderer, track);
+    track
+}
+
+#[derive(Data)]
+struct DeterminateBar {
+    progress: f64,
+    accessibility: Accessibility,
+}
+
+impl Compose for DeterminateBar {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let progress: f64 = *use_animated(&cx, cx.me().progress, AnimationSpec::default());
+
+        Canvas::new(bar_style(), move |layout, renderer| {
+            let track = draw_track(renderer, theme, &layout);
+
+            SurfaceStyle {
+                background: theme.colors.primary,
+                corner_radius: track.height() / 2.,
+                ..Default::default()
+            }
+            .draw(
+                renderer,
+                track.with_size((track.width() * progress, track.height())),
+            );
+        })
+        .with_accessibility(cx.me().accessibility.clone())
+    }
+}
+
+#[derive(Data)]
+struct IndeterminateBar {
+    accessibility: Accessibility,
+}
+
+impl Compose for IndeterminateBar {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+
+        // Position of the sweep, from 0 to 1.
+        let phase = use_mut(&cx, || 0.);
+        use_frame(&cx, move |dt| {
+            Mut::update(phase, move |phase| {
+                *phase = (*phase + dt.as_secs_f64() / PERIOD.as_secs_f64()).fract()
+            })
+        });
+
+        let phase = *phase;
+        Canvas::new(bar_style(), move |layout, renderer| {
+            let track = draw_track(renderer, theme, &layout);
+            let shape = track.to_rounded_rect(track.height() / 2.);
+
+            // The segment enters from the left and leaves on the right.
+            let segment_width = track.width() * 0.3;
+            let x = phase * (track.width() + segment_width) - segment_width;
+
+            renderer.push_clip(Affine::IDENTITY, shape.into());
+            SurfaceStyle {
+                background: theme.colors.primary,
+                corner_radius: track.height() / 2.,
+                ..Default::default()
+            }
+            .draw(
+                renderer,
+                Rect::new(x, track.y0, x + segment_width, track.y1),
+            );
+            renderer.pop_clip();
+        })
+        .with_accessibility(cx.me().accessibility.clone())
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    prelude::*,
+    ui::{use_layout, LayoutContext, LayoutTree, WindowContext},
+};
+use std::{mem, rc::Rc};
+use taffy::{FlexDirection, NodeId, Overflow, Point, Style};
+use vello::kurbo::Vec2;
+
+/// Scroll position of a [`Scroll`] view.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollOffset {
+    /// Current scroll offset.
+    pub offset: Vec2,
+    /// Maximum scroll offset, measured from the overflow of the content.
+    pub max_offset: Vec2,
+}
+
+type ScrollFn<'a> = Box<dyn Fn(ScrollOffset) + 'a>;
+
+#[derive(Default)]
+pub(crate) struct ScrollState {
+    pub(crate) offset: Vec2,
+    last: Option<ScrollOffset>,
+    pub(crate) on_scroll: Option<Rc<dyn Fn(ScrollOffset)>>,
+}
+
+impl WindowContext {
+    /// Scroll the nearest scroll container containing `key` by `delta`.
+    ///
+    /// Returns `true` if a scroll offset was changed.
+    pub(crate) fn scroll_by(&self, key: NodeId, delta: Vec2) -> bool {
+        let taffy = self.taffy.borrow();
+        let mut scrolls = self.scrolls.borrow_mut();
+
+        let mut node = Some(key);
+        while let Some(key) = node {
+            if let Some(scroll) = scrolls.get_mut(&key) {
+                let max_offset = max_offset(&taffy, key);
+                let offset = Vec2::new(
+                    (scroll.offset.x + delta.x).clamp(0., max_offset.x),
+                    (scroll.offset.y + delta.y).clamp(0., max_offset.y),
+                );
+
+                if offset != scroll.offset {
+                    scroll.offset = offset;
+                    self.is_changed.set(true);
+                    return true;
+                }
+            }
+
+            node = taffy.parent(key);
+        }
+
+        false
+    }
+
+    /// Get the node to scroll by dragging from a left button press on `target`.
+    ///
+    /// Views with listeners can handle drags themselves (for example, sliders, text selections, or drag sources),
+    /// so pressing them doesn't scroll their container.
+    pub(crate) fn scroll_drag_target(&self, target: Option<NodeId>) -> Option<NodeId> {
+        let listeners = self.listeners.borrow();
+        target.filter(|key| listeners.get(key).is_none_or(Vec::is_empty))
+    }
+
+    /// Clamp each scroll offset to the current layout and report any changes.
+    pub(crate) fn update_scrolls(&self) {
+        let taffy = self.taffy.borrow();
+
+        let mut changed = Vec::new();
+        for (key, scroll) in self.scrolls.borrow_mut().iter_mut() {
+            let max_offset = max_offset(&taffy, *key);
+            let offset = Vec2::new(
+                scroll.offset.x.clamp(0., max_offset.x),
+                scroll.offset.y.clamp(0., max_offset.y),
+            );
+            if offset != scroll.offset {
+                scroll.offset = offset;
+                self.is_changed.set(true);
+            }
+
+            let scroll_offset = ScrollOffset { offset, max_offset };
+            if scroll.last != Some(scroll_offset) {
+                scroll.last = Some(scroll_offset);
+
+                if let Some(on_scroll) = &scroll.on_scroll {
+                    changed.push((on_scroll.clone(), scroll_offset));
+                }
+            }
+        }
+
+        for (on_scroll, scroll_offset) in changed {
+            on_scroll(scroll_offset);
+        }
+    }
+}
+
+fn max_offset(taffy: &LayoutTree, key: NodeId) -> Vec2 {
+    let layout = taffy.layout(key).unwrap();
+    Vec2::new(
+        (layout.content_size.width - layout.size.width).max(0.) as _,
+        (layout.content_size.height - layout.size.height).max(0.) as _,
+    )
+}
+
+/// Scroll composable.
+///
+/// Content that overflows this view is clipped, and can be scrolled with the mouse wheel
+/// or by dragging content without pointer listeners.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Scroll<'a, C> {
+    style: Style,
+    on_scroll: ScrollFn<'a>,
+    content: C,
+}
+
+impl<'a, C> Scroll<'a, C> {
+    /// Create a new scroll view from its style and the given `content`.
+    ///
+    /// The style's `overflow` determines which axes can be scrolled.
+    pub fn new(style: Style, content: C) -> Self {
+        Self {
+            style,
+            on_scroll: Box::new(|_| {}),
+            content,
+        }
+    }
+
+    /// Create a new vertical scroll view from the given `content`.
+    pub fn column(content: C) -> Self {
+        Self::new(
+            Style {
+                flex_direction: FlexDirection::Column,
+                overflow: Point {
+                    x: Overflow::Visible,
+                    y: Overflow::Scroll,
+                },
+                ..Default::default()
+            },
+            content,
+        )
+    }
+
+    /// Create a new horizontal scroll view from the given `content`.
+    pub fn row(content: C) -> Self {
+        Self::new(
+            Style {
+                flex_direction: FlexDirection::Row,
+                overflow: Point {
+                    x: Overflow::Scroll,
+                    y: Overflow::Visible,
+                },
+                ..Default::default()
+            },
+            content,
+        )
+    }
+
+    /// Set the function to call when the scroll offset or content overflow changes.
+    pub fn on_scroll(mut self, on_scroll: impl Fn(ScrollOffset) + 'a) -> Self {
+        self.on_scroll = Box::new(on_scroll);
+        self
+    }
+}
+
+impl<C: Compose> Compose for Scroll<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let window_cx = use_context::<WindowContext>(&cx).unwrap();
+
+        let (key, _layout) = use_layout(&cx, cx.me().style.clone());
+
+        use_provider(&cx, || LayoutContext::new(key));
+
+        let on_scroll = &cx.me().on_scroll;
+        let f: Rc<dyn Fn(ScrollOffset)> = Rc::new(on_scroll);
+
+        // Safety: `f` is removed from `scrolls` on drop.
+        let f: Rc<dyn Fn(ScrollOffset)> = unsafe { mem::transmute(f) };
+        window_cx
+            .scrolls
+            .borrow_mut()
+            .entry(key)
+            .or_default()
+            .on_scroll = Some(f);
+
+        use_drop(&cx, move || {
+            window_cx.scrolls.borrow_mut().remove(&key);
+        });
+
+        Ref::map(cx.me(), |me| &me.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Scroll, ScrollOffset};
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::Cell, rc::Rc};
+    use taffy::{prelude::length, FlexDirection, Overflow, Point, Size, Style};
+    use vello::kurbo::Vec2;
+
+    #[derive(Data)]
+    struct Content {
+        has_listener: bool,
+        offset: Rc<Cell<ScrollOffset>>,
+    }
+
+    impl Compose for Content {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let canvas = Canvas::new(
+                Style {
+                    size: Size {
+                        width: length(100.),
+                        height: length(300.),
+                    },
+                    flex_shrink: 0.,
+                    ..Default::default()
+                },
+                |_, _| {},
+            );
+            let canvas = if cx.me().has_listener {
+                canvas.on_pointer(|_| {})
+            } else {
+                canvas
+            };
+
+            let offset = cx.me().offset.clone();
+            Scroll::new(
+                Style {
+                    size: Size {
+                        width: length(100.),
+                        height: length(100.),
+                    },
+                    flex_direction: FlexDirection::Column,
+                    overflow: Point {
+                        x: Overflow::Visible,
+                        y: Overflow::Scroll,
+                    },
+                    ..Default::default()
+                },
+                canvas,
+            )
+            .on_scroll(move |scroll_offset| offset.set(scroll_offset))
+        }
+    }
+
+    fn drag_offset(has_listener: bool) -> Vec2 {
+        let offset = Rc::new(Cell::new(ScrollOffset::default()));
+        let mut window = TestWindow::new(Content {
+            has_listener,
+            offset: offset.clone(),
+        });
+        window.frame();
+
+        window.drag(Vec2::new(50., 80.), Vec2::new(50., 20.));
+        window.frame();
+
+        offset.get().offset
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_scrolls_by_dragging_content() {
+        assert_eq!(drag_offset(false), Vec2::new(0., 60.));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_skips_drag_scrolling_for_views_with_listeners() {
+        assert_eq!(drag_offset(true), Vec2::ZERO);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::{
+    compose,
+    prelude::*,
+    ui::{
+        draw::Border,
+        view::{
+            widget::{use_interaction, Interactive, Surface, SurfaceStyle},
+            Popup,
+        },
+        Accessibility, Role,
+    },
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+use vello::kurbo::{Affine, BezPath, Stroke, Vec2};
+use web_time::Instant;
+use winit::{
+    event::ElementState,
+    keyboard::{Key, NamedKey},
+};
+
+/// Time after the last key press of a type-ahead search to start a new search.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Create a new select from its options and the index of the selected option.
+pub fn select<'a, I>(options: I, selected: impl Into<Option<usize>>) -> Select<'a>
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    Select::new(options, selected)
+}
+
+/// Select composable.
+///
+/// A select shows its selected option in a field that opens a [`Popup`] list of its options when pressed.
+/// Pressing an option selects it and closes the list,
+/// and pressing outside of the list or the escape key closes it without changing the selection.
+///
+/// While the field is focused, the up and down arrow keys open the list and move its highlighted option,
+/// and the space or enter key selects the highlighted option.
+/// Typing the start of an option's label highlights it, or selects it while the list is closed.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Select<'a> {
+    options: Vec<String>,
+    selected: Option<usize>,
+    placeholder: String,
+    label: Option<String>,
+    on_select: Box<dyn Fn(usize) + 'a>,
+}
+
+impl<'a> Select<'a> {
+    /// Create a new select from its options and the index of the selected option.
+    pub fn new<I>(options: I, selected: impl Into<Option<usize>>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: selected.into(),
+            placeholder: String::new(),
+            label: None,
+            on_select: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the placeholder shown while no option is selected.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set the accessibility label of this select.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the function to call with the index of an option when it's selected.
+    pub fn on_select(mut self, on_select: impl Fn(usize) + 'a) -> Self {
+        self.on_select = Box::new(on_select);
+        self
+    }
+
+    /// Bind this select to a form `field`, showing its selected option and setting and touching it when selected.
+    pub fn with_field(mut self, field: &Field<Option<usize>>) -> Self {
+        let field = field.clone();
+        self.selected = field.get();
+        self.on_select = Box::new(move |index| {
+            field.set(Some(index));
+            field.touch();
+        });
+        self
+    }
+
+    /// Find the next option after `start` (or at `start` if `is_inclusive` is `true`)
+    /// whose label starts with `prefix`, ignoring case and wrapping around the options.
+    fn find_prefix(&self, prefix: &str, start: usize, is_inclusive: bool) -> Option<usize> {
+        let prefix = prefix.to_lowercase();
+        let len = self.options.len();
+        let offset = if is_inclusive { 0 } else { 1 };
+
+        (0..len)
+            .map(|idx| (start + offset + idx) % len)
+            .find(|idx| self.options[*idx].to_lowercase().starts_with(&prefix))
+    }
+}
+
+/// Type-ahead search of a [`Select`].
+#[derive(Default)]
+struct TypeAhead {
+    query: RefCell<String>,
+    last: Cell<Option<Instant>>,
+}
+
+impl TypeAhead {
+    /// Add `text` to the query, starting a new query if the last key press timed out,
+    /// and return the query.
+    fn push(&self, text: &str) -> String {
+        let now = Instant::now();
+        let is_new = self
+            .last
+            .replace(Some(now))
+            .is_none_or(|last| now.duration_since(last) > TYPE_AHEAD_TIMEOUT);
+
+        let mut query = self.query.borrow_mut();
+        if is_new {
+            query.clear();
+        }
+        query.push_str(text);
+
+        query.clone()
+    }
+}
+
+impl Compose for Select<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let is_open = use_mut(&cx, || false);
+        let highlighted = use_mut(&cx, || None);
+        let type_ahead = use_ref(&cx, TypeAhead::default);
+
+        let open = move || {
+            Mut::set(highlighted, cx.me().selected);
+            Mut::set(is_open, true);
+        };
+        let close = move || Mut::set(is_open, false);
+        let pick = move |idx: usize| {
+            close();
+            focus.focus();
+            if cx.me().selected != Some(idx) {
+                (cx.me().on_select)(idx);
+            }
+        };
+
+        let surface = use_ref(&cx, || Rc::new(Cell::new(SurfaceStyle::default())));
+        surface.set(SurfaceStyle {
+            background: theme.colors.surface,
+            overlay: interaction.overlay(theme.colors.text),
+            border: Some(interaction.focus_ring(theme).unwrap_or(Border {
+                width: 1.,
+                color: theme.colors.text_secondary,
+            })),
+            corner_radius: 6.,
+        });
+
+        let me = cx.me();
+        let (text, text_color) = match me.selected.and_then(|idx| me.options.get(idx)) {
+            Some(option) => (option.clone(), theme.colors.text),
+            None => (me.placeholder.clone(), theme.colors.text_secondary),
+        };
+
+        let chevron_color = theme.colors.text;
+        let chevron = Canvas::new(
+            Style {
+                size: Size {
+                    width: length(10.),
+                    height: length(6.),
+                },
+                flex_shrink: 0.,
+                ..Default::default()
+            },
+            move |layout, renderer| {
+                let (width, height) = (layout.size.width as f64, layout.size.height as f64);
+                let mut path = BezPath::new();
+                path.move_to((1., 1.));
+                path.line_to((width / 2., height - 1.));
+                path.line_to((width - 1., 1.));
+                renderer.stroke(
+                    &Stroke::new(1.5),
+                    Affine::IDENTITY,
+                    chevron_color.into(),
+                    (&path).into(),
+                );
+            },
+        );
+
+        let list = is_open.then(|| {
+            Popup::new(
+                Flex::column(compose::from_iter(
+                    (0..me.options.len()).collect::<Vec<usize>>(),
+                    move |idx| SelectOption {
+                        label: cx.me().options[*idx].clone(),
+                        is_selected: cx.me().selected == Some(*idx),
+                        is_highlighted: *highlighted == Some(*idx),
+                        on_pick: Box::new(move || pick(*idx)),
+                        on_highlight: Box::new(move |is_highlighted| {
+                            Mut::set(highlighted, is_highlighted.then_some(*idx))
+                        }),
+                    },
+                ))
+                .background_color(theme.colors.surface)
+                .border(1., theme.colors.text_secondary)
+                .corner_radius(6.)
+                .shadow(Vec2::new(0., 2.), 8., theme.colors.scrim)
+                .padding(Rect {
+                    left: zero(),
+                    right: zero(),
+                    top: length(theme.spacing.small),
+                    bottom: length(theme.spacing.small),
+                })
+                .margin(Rect {
+                    left: zero(),
+                    right: zero(),
+                    top: length(theme.spacing.small),
+                    bottom: length(theme.spacing.small),
+                }),
+            )
+            .on_dismiss(close)
+        });
+
+        Flex::row((
+            Text::new(text)
+                .color(text_color)
+                .accessibility(Accessibility {
+                    role: Role::ComboBox,
+                    label: me.label.clone(),
+                    ..Default::default()
+                })
+                .flex_grow(1.),
+            chevron,
+            list,
+        ))
+        .align_items(AlignItems::Center)
+        .gap(Size {
+            width: length(theme.spacing.medium),
+            height: zero(),
+        })
+        .padding(Rect {
+            left: length(theme.spacing.medium),
+            right: length(theme.spacing.medium),
+            top: length(theme.spacing.small),
+            bottom: length(theme.spacing.small),
+        })
+        .draw(Surface {
+            style: surface.clone(),
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, move || {
+            if !*is_open {
+                open()
+            } else if let Some(idx) = *highlighted {
+                pick(idx)
+            } else {
+                close()
+            }
+        }))
+        .on_key_down(move |input| {
+            if input.state != ElementState::Pressed {
+                return;
+            }
+
+            let me = cx.me();
+            let len = me.options.len();
+            if len == 0 {
+                return;
+            }
+
+            let current = if *is_open { *highlighted } else { me.selected };
+            let next = match &input.key {
+                Key::Named(NamedKey::ArrowDown) => current.map_or(0, |idx| (idx + 1).min(len - 1)),
+                Key::Named(NamedKey::ArrowUp) => current.map_or(0, |idx| idx.saturating_sub(1)),
+                Key::Named(NamedKey::Home) => 0,
+                Key::Named(NamedKey::End) => len - 1,
+                Key::Character(text) => {
+                    let query = type_ahead.push(text);
+                    let start = current.unwrap_or(len - 1);
+
+                    // Repeating the same letter cycles between the options starting with it.
+                    let mut chars = query.chars();
+                    let first = chars.next().unwrap_or_default();
+                    let idx = if chars.all(|c| c == first) {
+                        me.find_prefix(&first.to_string(), start, false)
+                    } else {
+                        me.find_prefix(&query, start, true)
+                    };
+                    let Some(idx) = idx else {
+                        return;
+                    };
+                    if !*is_open {
+                        pick(idx);
+                        return;
+                    }
+                    idx
+                }
+                _ => return,
+            };
+
+            if !*is_open
+                && matches!(
+                    input.key,
+                    Key::Named(NamedKey::ArrowDown | NamedKey::ArrowUp)
+                )
+            {
+                open();
+            } else {
+                Mut::set(highlighted, Some(next));
+            }
+        })
+    }
+}
+
+/// Option in the list of a [`Select`].
+#[derive(Data)]
+struct SelectOption<'a> {
+    label: String,
+    is_selected: bool,
+    is_highlighted: bool,
+    on_pick: Box<dyn Fn() + 'a>,
+    on_highlight: Box<dyn Fn(bool) + 'a>,
+}
+
+impl Compose for SelectOption<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let surface = use_ref(&cx, || Rc::new(Cell::new(SurfaceStyle::default())));
+        surface.set(SurfaceStyle {
+            background: if cx.me().is_selected {
+                theme.colors.primary.multiply_alpha(0.16)
+            } else {
+                Color::TRANSPARENT
+            },
+            overlay: (cx.me().is_highlighted || interaction.is_focused)
+                .then(|| theme.colors.text.multiply_alpha(0.08)),
+            ..Default::default()
+        });
+
+        Flex::row(
+            Text::new(cx.me().label.clone())
+                .accessibility(Accessibility {
+                    role: Role::ListBoxOption,
+                    toggled: Some(cx.me().is_selected),
+                    ..Default::default()
+                })
+                .padding(Rect {
+                    left: length(theme.spacing.medium),
+                    right: length(theme.spacing.medium),
+                    top: length(theme.spacing.small),
+                    bottom: length(theme.spacing.small),
+                }),
+        )
+        .draw(Surface {
+            style: surface.clone(),
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, move || (cx.me().on_pick)()))
+        .on_mouse_in(move || (cx.me().on_highlight)(true))
+        .on_mouse_out(move || (cx.me().on_highlight)(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::select;
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::RefCell, rc::Rc};
+    use winit::keyboard::{Key, NamedKey};
+
+    #[derive(Data)]
+    struct Fruits {
+        selects: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Compose for Fruits {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let selected = use_mut(&cx, || None);
+
+            select(["Apple", "Banana", "Blueberry", "Cherry"], *selected)
+                .with_label("Fruit")
+                .on_select(move |index| {
+                    cx.me().selects.borrow_mut().push(index);
+                    Mut::set(selected, Some(index));
+                })
+        }
+    }
+
+    fn is_open(window: &TestWindow) -> bool {
+        !window.window_cx().layers.roots().is_empty()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_selects_options_with_the_keyboard() {
+        let selects = Rc::new(RefCell::new(Vec::new()));
+        let mut window = TestWindow::new(Fruits {
+            selects: selects.clone(),
+        });
+        window.frame();
+
+        // Pressing the field opens the list.
+        window.click_node(window.find_by_label("Fruit").unwrap());
+        window.frame();
+        assert!(is_open(&window));
+
+        window.key_press(Key::Named(NamedKey::ArrowDown));
+        window.frame();
+        window.key_press(Key::Named(NamedKey::ArrowDown));
+        window.frame();
+        window.key_press(Key::Named(NamedKey::Enter));
+        window.frame();
+        assert!(!is_open(&window));
+
+        // Typing while the list is closed selects the next matching option.
+        window.key_press(Key::Character("b".into()));
+        window.frame();
+        // Repeating the same letter cycles between the options starting with it.
+        window.key_press(Key::Character("b".into()));
+        window.frame();
+
+        // The arrow keys open the list at the selected option.
+        window.key_press(Key::Named(NamedKey::ArrowUp));
+        window.frame();
+        assert!(is_open(&window));
+        window.key_press(Key::Named(NamedKey::ArrowUp));
+        window.frame();
+        window.key_press(Key::Named(NamedKey::Enter));
+        window.frame();
+        assert!(!is_open(&window));
+
+        assert_eq!(*selects.borrow(), [1, 2, 1, 0]);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    prelude::*,
+    ui::{
+        draw::Border,
+        view::widget::{use_interaction, SurfaceStyle},
+        Accessibility, Action, Event, Role,
+    },
+};
+use std::{cell::Cell, ops::RangeInclusive};
+use vello::kurbo::{Affine, Circle, Rect, Stroke};
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, NamedKey},
+};
+
+/// Radius of a slider's thumb.
+const THUMB_RADIUS: f64 = 8.;
+
+/// Height of a slider's track.
+const TRACK_HEIGHT: f64 = 4.;
+
+/// Create a new slider from its value and range.
+pub fn slider<'a>(value: f64, range: RangeInclusive<f64>) -> Slider<'a> {
+    Slider::new(value, range)
+}
+
+/// Slider composable.
+///
+/// Pressing or dragging the slider's track moves its thumb to the cursor.
+/// While focused, the arrow keys adjust the slider by one step, the page up and page down keys by ten steps,
+/// and the home and end keys move it to the start or end of its range.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Slider<'a> {
+    value: f64,
+    range: RangeInclusive<f64>,
+    step: Option<f64>,
+    label: Option<String>,
+    on_change: Box<dyn Fn(f64) + 'a>,
+}
+
+impl<'a> Slider<'a> {
+    /// Create a new slider from its value and range.
+    pub fn new(value: f64, range: RangeInclusive<f64>) -> Self {
+        Self {
+            value,
+            range,
+            step: None,
+            label: None,
+            on_change: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the step of this slider.
+    ///
+    /// Values are rounded to the nearest step from the start of the range.
+    /// Without a step, values are continuous and the keyboard adjusts them by 1% of the range.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step).filter(|step| *step > 0.);
+        self
+    }
+
+    /// Set the accessibility label of this slider.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the function to call with the new value when this slider changes.
+    pub fn on_change(mut self, on_change: impl Fn(f64) + 'a) -> Self {
+        self.on_change = Box::new(on_change);
+        self
+    }
+
+    /// Get the fraction of the range at `value`, from 0 to 1.
+    fn fraction(&self, value: f64) -> f64 {
+        let len = self.range.end() - self.range.start();
+        if len > 0. {
+            ((value - self.range.start()) / len).clamp(0., 1.)
+        } else {
+            0.
+        }
+    }
+
+    /// Get the value at `fraction` of the range, rounded to the nearest step.
+    fn value_at(&self, fraction: f64) -> f64 {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let value = start + fraction.clamp(0., 1.) * (end - start);
+
+        match self.step {
+            Some(step) => (start + ((value - start) / step).round() * step).clamp(start, end),
+            None => value,
+        }
+    }
+
+    /// Get the value `steps` steps from the current value.
+    fn value_by(&self, steps: f64) -> f64 {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let step = self.step.unwrap_or((end - start) / 100.);
+
+        self.value_at(self.fraction(self.value + steps * step))
+    }
+
+    /// Change the value of this slider to `value`, if it's different from the current value.
+    fn change(&self, value: f64) {
+        if value != self.value {
+            (self.on_change)(value)
+        }
+    }
+}
+
+impl Compose for Slider<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        // Width of the track, from the last time the slider was drawn.
+        let track_width = use_ref(&cx, || Cell::new(0.));
+        let is_dragging = use_ref(&cx, || Cell::new(false));
+
+        let fraction_at = move |x: f64| {
+            let width = track_width.get();
+            if width > 0. {
+                (x - THUMB_RADIUS) / width
+            } else {
+                0.
+            }
+        };
+
+        let value = (*cx.me()).value;
+        let fraction = cx.me().fraction(value);
+        let state = *interaction;
+        let style = Style {
+            size: Size {
+                width: percent(1.),
+                height: length(THUMB_RADIUS as f32 * 2.),
+            },
+            min_size: Size {
+                width: length(THUMB_RADIUS as f32 * 8.),
+                height: auto(),
+            },
+            ..Default::default()
+        };
+
+        Canvas::new(style, move |layout, renderer| {
+            let width = (layout.size.width as f64 - THUMB_RADIUS * 2.).max(0.);
+            track_width.set(width);
+
+            let center_y = layout.size.height as f64 / 2.;
+            let track = Rect::new(
+                THUMB_RADIUS,
+                center_y - TRACK_HEIGHT / 2.,
+                THUMB_RADIUS + width,
+                center_y + TRACK_HEIGHT / 2.,
+            );
+            let thumb_x = THUMB_RADIUS + fraction * width;
+
+            SurfaceStyle {
+                background: theme.colors.text_secondary,
+                corner_radius: TRACK_HEIGHT / 2.,
+                ..Default::default()
+            }
+            .draw(renderer, track);
+            SurfaceStyle {
+                background: theme.colors.primary,
+                corner_radius: TRACK_HEIGHT / 2.,
+                ..Default::default()
+            }
+            .draw(
+                renderer,
+                track.with_size((thumb_x - track.x0, track.height())),
+            );
+
+            let thumb = Circle::new((thumb_x, center_y), THUMB_RADIUS);
+            if let Some(overlay) = state.overlay(theme.colors.primary) {
+                renderer.fill(
+                    Affine::IDENTITY,
+                    overlay.into(),
+                    Circle::new(thumb.center, THUMB_RADIUS * 1.5).into(),
+                );
+            }
+            renderer.fill(Affine::IDENTITY, theme.colors.primary.into(), thumb.into());
+
+            if let Some(Border { width, color }) = state.focus_ring(theme) {
+                renderer.stroke(
+                    &Stroke::new(width),
+                    Affine::IDENTITY,
+                    color.into(),
+                    Circle::new(thumb.center, THUMB_RADIUS + width / 2.).into(),
+                );
+            }
+        })
+        .on_pointer(move |event| {
+            let me = cx.me();
+
+            match event {
+                Event::MouseIn => Mut::update(interaction, |state| state.is_hovered = true),
+                Event::MouseOut => Mut::update(interaction, |state| state.is_hovered = false),
+                Event::MouseInput {
+                    button: MouseButton::Left,
+                    state,
+                    pos,
+                } => {
+                    let is_pressed = state == ElementState::Pressed;
+                    is_dragging.set(is_pressed);
+                    Mut::update(interaction, move |state| state.is_pressed = is_pressed);
+
+                    if is_pressed {
+                        focus.focus();
+                        me.change(me.value_at(fraction_at(pos.x)));
+                    }
+                }
+                Event::DragMove { pos, .. } if is_dragging.get() => {
+                    me.change(me.value_at(fraction_at(pos.x)))
+                }
+                Event::DragEnd { .. } => {
+                    is_dragging.set(false);
+                    Mut::update(interaction, |state| state.is_pressed = false);
+                }
+                Event::KeyboardInput(input) if input.state == ElementState::Pressed => {
+                    let value = match input.key {
+                        Key::Named(NamedKey::ArrowRight | NamedKey::ArrowUp) => me.value_by(1.),
+                        Key::Named(NamedKey::ArrowLeft | NamedKey::ArrowDown) => me.value_by(-1.),
+                        Key::Named(NamedKey::PageUp) => me.value_by(10.),
+                        Key::Named(NamedKey::PageDown) => me.value_by(-10.),
+                        Key::Named(NamedKey::Home) => *me.range.start(),
+                        Key::Named(NamedKey::End) => *me.range.end(),
+                        _ => return,
+                    };
+                    me.change(value);
+                }
+                Event::Focus => Mut::update(interaction, |state| state.is_focused = true),
+                Event::Blur => Mut::update(interaction, |state| state.is_focused = false),
+                _ => {}
+            }
+        })
+        .with_accessibility(
+            Accessibility {
+                role: Role::Slider,
+                label: cx.me().label.clone(),
+                actions: vec![Action::Increment, Action::Decrement],
+                ..Default::default()
+            }
+            .numeric_value(value, cx.me().range.clone()),
+        )
+        .focusable(focus.clone())
+    }
+}
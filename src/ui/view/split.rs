@@ -0,0 +1,277 @@
+use crate::{
+    prelude::*,
+    ui::{
+        use_layout,
+        view::{canvas::use_container_fragment, widget::use_interaction},
+        Accessibility, Event, LayoutContext, Role, WindowContext,
+    },
+};
+use std::cell::Cell;
+use vello::kurbo::{Affine, Rect as KurboRect};
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, NamedKey},
+};
+
+/// Thickness of the divider between the panes of a split.
+const DIVIDER_SIZE: f32 = 8.;
+
+/// Fraction of the split the divider is moved by each arrow key press while it's focused.
+const STEP: f32 = 0.05;
+
+/// Create a new split of `first` and `second`.
+///
+/// If `is_horizontal` is `true`, the panes are placed side by side. Otherwise, `first` is placed above `second`.
+pub fn split<'a, A, B>(is_horizontal: bool, first: A, second: B) -> Split<'a, A, B> {
+    Split::new(is_horizontal, first, second)
+}
+
+/// Split composable.
+///
+/// A split divides its space between two panes, with a divider between them.
+/// Dragging the divider moves it to the cursor, and while it's focused,
+/// the arrow keys move it by 5% of the split and the home and end keys move it to the start or end of the split.
+///
+/// The position of the divider is stored in the split's state as a ratio of the first pane's size to the split's size,
+/// and is clamped so each pane keeps its minimum size.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Split<'a, A, B> {
+    is_horizontal: bool,
+    first: A,
+    second: B,
+    ratio: f32,
+    min_sizes: (f32, f32),
+    on_change: Box<dyn Fn(f32) + 'a>,
+}
+
+impl<'a, A, B> Split<'a, A, B> {
+    /// Create a new split of `first` and `second`.
+    ///
+    /// If `is_horizontal` is `true`, the panes are placed side by side. Otherwise, `first` is placed above `second`.
+    pub fn new(is_horizontal: bool, first: A, second: B) -> Self {
+        Self {
+            is_horizontal,
+            first,
+            second,
+            ratio: 0.5,
+            min_sizes: (0., 0.),
+            on_change: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the initial ratio of the first pane's size to the split's size, from 0 to 1 (default: 0.5).
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.clamp(0., 1.);
+        self
+    }
+
+    /// Set the minimum size of the first and second panes along the split (default: 0).
+    pub fn with_min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.min_sizes = (first.max(0.), second.max(0.));
+        self
+    }
+
+    /// Set the function to call with the new ratio when the divider is moved.
+    pub fn on_change(mut self, on_change: impl Fn(f32) + 'a) -> Self {
+        self.on_change = Box::new(on_change);
+        self
+    }
+
+    /// Clamp `ratio` so both panes keep their minimum size in a split of `size`, including the divider.
+    fn clamp_ratio(&self, ratio: f32, size: f32) -> f32 {
+        let available = size - DIVIDER_SIZE;
+        if available <= 0. {
+            return ratio.clamp(0., 1.);
+        }
+
+        let (min_first, min_second) = self.min_sizes;
+        let start = (min_first / available).min(1.);
+        let end = (1. - min_second / available).max(start);
+        ratio.clamp(0., 1.).clamp(start, end)
+    }
+
+    /// Get the style of a pane taking up `ratio` of this split.
+    fn pane_style(&self, ratio: f32, min_size: f32) -> Style {
+        let min_size = if self.is_horizontal {
+            Size {
+                width: length(min_size),
+                height: auto(),
+            }
+        } else {
+            Size {
+                width: auto(),
+                height: length(min_size),
+            }
+        };
+
+        Style {
+            flex_direction: if self.is_horizontal {
+                FlexDirection::Row
+            } else {
+                FlexDirection::Column
+            },
+            // Both panes shrink by the size of the divider in proportion to their basis.
+            flex_basis: percent(ratio),
+            flex_grow: 0.,
+            flex_shrink: 1.,
+            min_size,
+            overflow: taffy::Point {
+                x: Overflow::Hidden,
+                y: Overflow::Hidden,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl<A: Compose, B: Compose> Compose for Split<'_, A, B> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let window_cx = use_context::<WindowContext>(&cx).unwrap();
+        let theme = use_theme(&cx);
+
+        let is_horizontal = cx.me().is_horizontal;
+        let (key, layout) = use_layout(
+            &cx,
+            Style {
+                flex_direction: if is_horizontal {
+                    FlexDirection::Row
+                } else {
+                    FlexDirection::Column
+                },
+                flex_grow: 1.,
+                ..Default::default()
+            },
+        );
+
+        use_provider(&cx, || LayoutContext::new(key));
+        use_container_fragment(&cx, key, layout);
+
+        let ratio = use_mut(&cx, || cx.me().ratio);
+        let (interaction, focus) = use_interaction(&cx);
+
+        // Ratio following the cursor while dragging, before it's clamped.
+        let drag_ratio = use_ref(&cx, || Cell::new(0.));
+
+        // Get the size of this split along its panes.
+        let size = move || {
+            let layout = window_cx
+                .taffy
+                .borrow()
+                .layout(key)
+                .copied()
+                .unwrap_or_default();
+            if is_horizontal {
+                layout.size.width
+            } else {
+                layout.size.height
+            }
+        };
+
+        let change = move |next: f32| {
+            let next = cx.me().clamp_ratio(next, size());
+            if next != *ratio {
+                Mut::set(ratio, next);
+                (cx.me().on_change)(next);
+            }
+        };
+
+        let current = cx.me().clamp_ratio(*ratio, size());
+        let (min_first, min_second) = cx.me().min_sizes;
+
+        let state = *interaction;
+        let color = if state.is_pressed || state.is_focused {
+            theme.colors.primary
+        } else if state.is_hovered {
+            theme.colors.text_secondary
+        } else {
+            theme.colors.border
+        };
+        let line_width = if state == Default::default() { 1. } else { 2. };
+
+        let divider_style = Style {
+            size: if is_horizontal {
+                Size {
+                    width: length(DIVIDER_SIZE),
+                    height: auto(),
+                }
+            } else {
+                Size {
+                    width: auto(),
+                    height: length(DIVIDER_SIZE),
+                }
+            },
+            flex_shrink: 0.,
+            ..Default::default()
+        };
+
+        let divider = Canvas::new(divider_style, move |layout, renderer| {
+            let (width, height) = (layout.size.width as f64, layout.size.height as f64);
+            let line = if is_horizontal {
+                let x = (width - line_width) / 2.;
+                KurboRect::new(x, 0., x + line_width, height)
+            } else {
+                let y = (height - line_width) / 2.;
+                KurboRect::new(0., y, width, y + line_width)
+            };
+            renderer.fill(Affine::IDENTITY, color.into(), line.into());
+        })
+        .on_pointer(move |event| match event {
+            Event::MouseIn => Mut::update(interaction, |state| state.is_hovered = true),
+            Event::MouseOut => Mut::update(interaction, |state| state.is_hovered = false),
+            Event::MouseInput {
+                button: MouseButton::Left,
+                state,
+                ..
+            } => {
+                let is_pressed = state == ElementState::Pressed;
+                Mut::update(interaction, move |state| state.is_pressed = is_pressed);
+
+                if is_pressed {
+                    focus.focus();
+                    drag_ratio.set(*ratio);
+                }
+            }
+            Event::DragMove { delta, .. } => {
+                let available = size() - DIVIDER_SIZE;
+                if available > 0. {
+                    let delta = if is_horizontal { delta.x } else { delta.y };
+                    drag_ratio.set(drag_ratio.get() + delta as f32 / available);
+                    change(drag_ratio.get());
+                }
+            }
+            Event::DragEnd { .. } => Mut::update(interaction, |state| state.is_pressed = false),
+            Event::KeyboardInput(input) if input.state == ElementState::Pressed => {
+                let next = match (input.key, is_horizontal) {
+                    (Key::Named(NamedKey::ArrowRight), true)
+                    | (Key::Named(NamedKey::ArrowDown), false) => *ratio + STEP,
+                    (Key::Named(NamedKey::ArrowLeft), true)
+                    | (Key::Named(NamedKey::ArrowUp), false) => *ratio - STEP,
+                    (Key::Named(NamedKey::Home), _) => 0.,
+                    (Key::Named(NamedKey::End), _) => 1.,
+                    _ => return,
+                };
+                change(next);
+            }
+            Event::Focus => Mut::update(interaction, |state| state.is_focused = true),
+            Event::Blur => Mut::update(interaction, |state| state.is_focused = false),
+            _ => {}
+        })
+        .with_accessibility(
+            Accessibility::new(Role::Splitter).numeric_value(current as f64, 0.0..=1.),
+        )
+        .focusable(focus.clone());
+
+        (
+            Flex::new(
+                cx.me().pane_style(current, min_first),
+                Ref::map(cx.me(), |me| &me.first),
+            ),
+            divider,
+            Flex::new(
+                cx.me().pane_style(1. - current, min_second),
+                Ref::map(cx.me(), |me| &me.second),
+            ),
+        )
+    }
+}
@@ -0,0 +1,605 @@
+use crate::{
+    compose,
+    prelude::*,
+    ui::{
+        view::{
+            lazy_column,
+            widget::{use_interaction, Interactive, Surface, SurfaceStyle},
+        },
+        Accessibility, Event, Role,
+    },
+};
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    rc::Rc,
+};
+use vello::kurbo::{Affine, BezPath, Rect as KurboRect};
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, NamedKey},
+};
+
+/// Width of the resize handle at the end of a resizable column's header.
+const HANDLE_WIDTH: f32 = 8.;
+
+/// Distance a column is resized by each arrow key press while its resize handle is focused.
+const RESIZE_STEP: f32 = 8.;
+
+/// Create a new table of `len` rows from its columns.
+pub fn table<'a, C>(
+    len: usize,
+    columns: impl IntoIterator<Item = TableColumn<'a, C>>,
+) -> Table<'a, C> {
+    Table::new(len, columns)
+}
+
+/// Sort order of a [`Table`] column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Data)]
+pub enum SortOrder {
+    /// Sort rows from the smallest to the largest.
+    Ascending,
+    /// Sort rows from the largest to the smallest.
+    Descending,
+}
+
+type SortFn<'a> = dyn Fn(Option<(usize, SortOrder)>) + 'a;
+
+/// Column of a [`Table`].
+///
+/// A column composes the cell of each row by calling its cell function with the row's index.
+#[must_use = "Columns do nothing unless added to a `Table`"]
+pub struct TableColumn<'a, C> {
+    title: String,
+    width: f32,
+    min_width: f32,
+    is_resizable: bool,
+    cell: Box<dyn Fn(usize) -> C + 'a>,
+    compare: Option<Box<dyn Fn(usize, usize) -> Ordering + 'a>>,
+}
+
+unsafe impl<C: Data> Data for TableColumn<'_, C> {}
+
+impl<'a, C> TableColumn<'a, C> {
+    /// Create a new column from its title and a function to compose the cell of the row at an index.
+    pub fn new(title: impl Into<String>, cell: impl Fn(usize) -> C + 'a) -> Self {
+        Self {
+            title: title.into(),
+            width: 120.,
+            min_width: 32.,
+            is_resizable: true,
+            cell: Box::new(cell),
+            compare: None,
+        }
+    }
+
+    /// Set the initial width of this column (default: 120).
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the minimum width this column can be resized to (default: 32).
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Set whether this column can be resized by dragging the end of its header (default: `true`).
+    pub fn with_resizable(mut self, is_resizable: bool) -> Self {
+        self.is_resizable = is_resizable;
+        self
+    }
+
+    /// Make this column sortable, comparing the rows at two indices with `compare`.
+    ///
+    /// Pressing the header of a sortable column sorts the table by it in ascending order,
+    /// then in descending order, and then restores the original order.
+    pub fn with_sort(mut self, compare: impl Fn(usize, usize) -> Ordering + 'a) -> Self {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+}
+
+/// Table composable.
+///
+/// A table shows a header row with the title of each column above its rows.
+/// Rows are virtualized with a [`LazyColumn`](crate::ui::view::LazyColumn),
+/// so only the rows intersecting the viewport are composed.
+///
+/// Columns can be resized by dragging the handle at the end of their header,
+/// or by pressing the left and right arrow keys while the handle is focused.
+/// Sortable columns are sorted by pressing their header,
+/// which reorders the rows without changing the indices passed to each cell function.
+///
+/// Columns with different types of cells can use [`DynCompose`](crate::compose::DynCompose).
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Table<'a, C> {
+    len: usize,
+    columns: Vec<TableColumn<'a, C>>,
+    row_height: f32,
+    on_sort: Box<SortFn<'a>>,
+}
+
+impl<'a, C> Table<'a, C> {
+    /// Create a new table of `len` rows from its columns.
+    pub fn new(len: usize, columns: impl IntoIterator<Item = TableColumn<'a, C>>) -> Self {
+        Self {
+            len,
+            columns: columns.into_iter().collect(),
+            row_height: 32.,
+            on_sort: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the height of each row (default: 32).
+    pub fn with_row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// Set the function to call with the index and order of the sorted column when the sort changes,
+    /// or `None` when the original order is restored.
+    pub fn on_sort(mut self, on_sort: impl Fn(Option<(usize, SortOrder)>) + 'a) -> Self {
+        self.on_sort = Box::new(on_sort);
+        self
+    }
+
+    /// Get the width of the column at `idx`, from its resized width in `widths` or its initial width.
+    fn column_width(&self, widths: &[Option<f32>], idx: usize) -> f32 {
+        widths
+            .get(idx)
+            .copied()
+            .flatten()
+            .unwrap_or(self.columns[idx].width)
+            .max(self.columns[idx].min_width)
+    }
+
+    /// Get the order of the rows sorted by `sort`, as the index of the row at each position.
+    fn sorted_rows(&self, sort: Option<(usize, SortOrder)>) -> Vec<usize> {
+        let mut rows: Vec<usize> = (0..self.len).collect();
+
+        let compare = sort.and_then(|(idx, order)| {
+            let compare = self.columns.get(idx)?.compare.as_ref()?;
+            Some((compare, order))
+        });
+        if let Some((compare, order)) = compare {
+            rows.sort_by(|a, b| match order {
+                SortOrder::Ascending => compare(*a, *b),
+                SortOrder::Descending => compare(*b, *a),
+            });
+        }
+
+        rows
+    }
+}
+
+impl<C: Compose> Compose for Table<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+
+        let widths = use_mut(&cx, Vec::<Option<f32>>::new);
+        let sort = use_mut(&cx, || None::<(usize, SortOrder)>);
+
+        // Rows are sorted again on each compose, as their data may have changed.
+        let rows = use_ref(&cx, || RefCell::new(Vec::new()));
+        *rows.borrow_mut() = cx.me().sorted_rows(*sort);
+
+        let toggle_sort = move |idx: usize| {
+            let next = match *sort {
+                Some((last, SortOrder::Ascending)) if last == idx => {
+                    Some((idx, SortOrder::Descending))
+                }
+                Some((last, SortOrder::Descending)) if last == idx => None,
+                _ => Some((idx, SortOrder::Ascending)),
+            };
+            Mut::set(sort, next);
+            (cx.me().on_sort)(next);
+        };
+
+        let resize = move |idx: usize, width: f32| {
+            Mut::update(widths, move |widths| {
+                if widths.len() <= idx {
+                    widths.resize(idx + 1, None);
+                }
+                widths[idx] = Some(width);
+            })
+        };
+
+        let columns: Vec<usize> = (0..cx.me().columns.len()).collect();
+
+        // The table fills its parent, and shrinks below the height of its rows so only the visible rows are composed.
+        let style = Style {
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.,
+            min_size: Size {
+                width: auto(),
+                height: zero(),
+            },
+            ..Default::default()
+        };
+
+        Flex::new(
+            style,
+            (
+                Flex::row(compose::from_iter(columns, move |idx| TableHeader {
+                    table: cx.me(),
+                    index: *idx,
+                    widths,
+                    sort,
+                    on_sort: Box::new(move || toggle_sort(*idx)),
+                    on_resize: Box::new(move |width| resize(*idx, width)),
+                }))
+                .background_color(theme.colors.surface),
+                lazy_column(cx.me().len, move |idx| TableRow {
+                    table: cx.me(),
+                    index: rows.borrow()[idx],
+                    widths,
+                })
+                .item_height(cx.me().row_height)
+                .overscan(4)
+                .flex_grow(1.),
+            ),
+        )
+    }
+}
+
+/// Header of a [`Table`] column.
+#[derive(Data)]
+struct TableHeader<'a, C> {
+    table: Ref<'a, Table<'a, C>>,
+    index: usize,
+    widths: Mut<'a, Vec<Option<f32>>>,
+    sort: Mut<'a, Option<(usize, SortOrder)>>,
+    on_sort: Box<dyn Fn() + 'a>,
+    on_resize: Box<dyn Fn(f32) + 'a>,
+}
+
+impl<C: Compose> Compose for TableHeader<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let surface = use_ref(&cx, || Rc::new(Cell::new(SurfaceStyle::default())));
+        surface.set(SurfaceStyle {
+            overlay: interaction.overlay(theme.colors.text),
+            border: interaction.focus_ring(theme),
+            ..Default::default()
+        });
+
+        let me = cx.me();
+        let column = &me.table.columns[me.index];
+        let width = me.table.column_width(&me.widths, me.index);
+        let order = me
+            .sort
+            .filter(|(idx, _)| *idx == me.index)
+            .map(|(_, order)| order);
+
+        let title = || HeaderTitle {
+            title: column.title.clone(),
+            order,
+        };
+        let is_sortable = column.compare.is_some();
+
+        Flex::new(
+            Style {
+                size: Size {
+                    width: length(width),
+                    height: auto(),
+                },
+                flex_shrink: 0.,
+                ..Default::default()
+            },
+            (
+                is_sortable.then(|| {
+                    title()
+                        .flex_grow(1.)
+                        .draw(Surface {
+                            style: surface.clone(),
+                        })
+                        .focusable(focus.clone())
+                        .on_event(Interactive::new(interaction, move || (cx.me().on_sort)()))
+                }),
+                (!is_sortable).then(|| title().flex_grow(1.)),
+                column.is_resizable.then(|| ResizeHandle {
+                    width,
+                    min_width: column.min_width,
+                    on_resize: Box::new(move |width| (cx.me().on_resize)(width)),
+                }),
+            ),
+        )
+    }
+}
+
+/// Title of a [`Table`] column in its header, followed by an arrow if the table is sorted by it.
+#[derive(Data)]
+struct HeaderTitle {
+    title: String,
+    order: Option<SortOrder>,
+}
+
+impl Compose for HeaderTitle {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+
+        let arrow_color = theme.colors.text_secondary;
+        let arrow = cx.me().order.map(|order| {
+            Canvas::new(
+                Style {
+                    size: Size {
+                        width: length(8.),
+                        height: length(5.),
+                    },
+                    flex_shrink: 0.,
+                    ..Default::default()
+                },
+                move |layout, renderer| {
+                    let (width, height) = (layout.size.width as f64, layout.size.height as f64);
+                    let (tip, base) = match order {
+                        SortOrder::Ascending => (0., height),
+                        SortOrder::Descending => (height, 0.),
+                    };
+
+                    let mut path = BezPath::new();
+                    path.move_to((0., base));
+                    path.line_to((width / 2., tip));
+                    path.line_to((width, base));
+                    path.close_path();
+                    renderer.fill(Affine::IDENTITY, arrow_color.into(), (&path).into());
+                },
+            )
+        });
+
+        Flex::row((
+            Text::new(cx.me().title.clone())
+                .font_weight(FontWeight::BOLD)
+                .accessibility(Accessibility {
+                    role: Role::ColumnHeader,
+                    label: Some(cx.me().title.clone()),
+                    ..Default::default()
+                }),
+            arrow,
+        ))
+        .align_items(AlignItems::Center)
+        .gap(Size {
+            width: length(theme.spacing.small),
+            height: zero(),
+        })
+        .padding(Rect {
+            left: length(theme.spacing.medium),
+            right: length(theme.spacing.medium),
+            top: length(theme.spacing.small),
+            bottom: length(theme.spacing.small),
+        })
+        .clip()
+    }
+}
+
+/// Handle at the end of a resizable [`Table`] column's header.
+#[derive(Data)]
+struct ResizeHandle<'a> {
+    width: f32,
+    min_width: f32,
+    on_resize: Box<dyn Fn(f32) + 'a>,
+}
+
+impl ResizeHandle<'_> {
+    /// Resize the column to `width`, clamped to its minimum width.
+    fn resize(&self, width: f32) {
+        let width = width.max(self.min_width);
+        if width != self.width {
+            (self.on_resize)(width)
+        }
+    }
+}
+
+impl Compose for ResizeHandle<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        // Width of the column following the cursor, before it's clamped to the minimum width.
+        let drag_width = use_ref(&cx, || Cell::new(0.));
+
+        let color = if interaction.is_pressed || interaction.is_focused {
+            theme.colors.primary
+        } else if interaction.is_hovered {
+            theme.colors.text_secondary
+        } else {
+            theme.colors.border
+        };
+        let line_width = if *interaction == Default::default() {
+            1.
+        } else {
+            2.
+        };
+
+        Canvas::new(
+            Style {
+                size: Size {
+                    width: length(HANDLE_WIDTH),
+                    height: auto(),
+                },
+                flex_shrink: 0.,
+                ..Default::default()
+            },
+            move |layout, renderer| {
+                let x = (layout.size.width as f64 - line_width) / 2.;
+                renderer.fill(
+                    Affine::IDENTITY,
+                    color.into(),
+                    KurboRect::new(x, 0., x + line_width, layout.size.height as _).into(),
+                );
+            },
+        )
+        .on_pointer(move |event| {
+            let me = cx.me();
+
+            match event {
+                Event::MouseIn => Mut::update(interaction, |state| state.is_hovered = true),
+                Event::MouseOut => Mut::update(interaction, |state| state.is_hovered = false),
+                Event::MouseInput {
+                    button: MouseButton::Left,
+                    state,
+                    ..
+                } => {
+                    let is_pressed = state == ElementState::Pressed;
+                    Mut::update(interaction, move |state| state.is_pressed = is_pressed);
+
+                    if is_pressed {
+                        drag_width.set(me.width);
+                    }
+                }
+                Event::DragMove { delta, .. } => {
+                    drag_width.set(drag_width.get() + delta.x as f32);
+                    me.resize(drag_width.get());
+                }
+                Event::DragEnd { .. } => Mut::update(interaction, |state| state.is_pressed = false),
+                Event::KeyboardInput(input) if input.state == ElementState::Pressed => {
+                    match input.key {
+                        Key::Named(NamedKey::ArrowRight) => me.resize(me.width + RESIZE_STEP),
+                        Key::Named(NamedKey::ArrowLeft) => me.resize(me.width - RESIZE_STEP),
+                        _ => {}
+                    }
+                }
+                Event::Focus => Mut::update(interaction, |state| state.is_focused = true),
+                Event::Blur => Mut::update(interaction, |state| state.is_focused = false),
+                _ => {}
+            }
+        })
+        .focusable(focus.clone())
+    }
+}
+
+/// Row of a [`Table`].
+#[derive(Data)]
+struct TableRow<'a, C> {
+    table: Ref<'a, Table<'a, C>>,
+    index: usize,
+    widths: Mut<'a, Vec<Option<f32>>>,
+}
+
+impl<C: Compose> Compose for TableRow<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let columns: Vec<usize> = (0..cx.me().table.columns.len()).collect();
+
+        Flex::new(
+            Style {
+                size: Size {
+                    width: auto(),
+                    height: length(cx.me().table.row_height),
+                },
+                align_items: Some(AlignItems::Center),
+                flex_shrink: 0.,
+                ..Default::default()
+            },
+            compose::from_iter(columns, move |idx| TableCell {
+                row: cx.me(),
+                column: *idx,
+            }),
+        )
+    }
+}
+
+/// Cell of a [`Table`] row.
+#[derive(Data)]
+struct TableCell<'a, C> {
+    row: Ref<'a, TableRow<'a, C>>,
+    column: usize,
+}
+
+impl<C: Compose> Compose for TableCell<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+
+        let me = cx.me();
+        let table = &me.row.table;
+        let width = table.column_width(&me.row.widths, me.column);
+
+        Flex::new(
+            Style {
+                size: Size {
+                    // Cells line up with the headers, including their resize handles.
+                    width: length(width),
+                    height: auto(),
+                },
+                flex_shrink: 0.,
+                padding: Rect {
+                    left: length(theme.spacing.medium),
+                    right: length(theme.spacing.medium),
+                    top: zero(),
+                    bottom: zero(),
+                },
+                overflow: taffy::Point {
+                    x: Overflow::Hidden,
+                    y: Overflow::Hidden,
+                },
+                ..Default::default()
+            },
+            (table.columns[me.column].cell)(me.row.index),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{table, SortOrder, TableColumn};
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::RefCell, rc::Rc};
+
+    const NAMES: [&str; 3] = ["Banana", "Cherry", "Apple"];
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_sorts_rows_when_pressing_headers() {
+        let sorts = Rc::new(RefCell::new(Vec::new()));
+
+        let sorts_ref = sorts.clone();
+        let mut window = TestWindow::new(
+            table(
+                NAMES.len(),
+                [TableColumn::new("Name", |idx| Text::new(NAMES[idx]))
+                    .with_sort(|a, b| NAMES[a].cmp(NAMES[b]))],
+            )
+            .on_sort(move |sort| sorts_ref.borrow_mut().push(sort)),
+        );
+        window.frame();
+        window.frame();
+
+        let row_y = |window: &TestWindow| {
+            NAMES.map(|name| window.position(window.find_by_label(name).unwrap()).y)
+        };
+
+        // Rows are shown in their original order below the header.
+        let [banana, cherry, apple] = row_y(&window);
+        let header = window.find_by_label("Name").unwrap();
+        assert!(window.position(header).y < banana);
+        assert!(banana < cherry && cherry < apple);
+
+        window.click_node(header);
+        window.frame();
+        let [banana, cherry, apple] = row_y(&window);
+        assert!(apple < banana && banana < cherry);
+
+        window.click_node(header);
+        window.frame();
+        let [banana, cherry, apple] = row_y(&window);
+        assert!(cherry < banana && banana < apple);
+
+        // Pressing the header a third time restores the original order.
+        window.click_node(header);
+        window.frame();
+        let [banana, cherry, apple] = row_y(&window);
+        assert!(banana < cherry && cherry < apple);
+
+        assert_eq!(
+            *sorts.borrow(),
+            [
+                Some((0, SortOrder::Ascending)),
+                Some((0, SortOrder::Descending)),
+                None
+            ]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{
+    compose::{self, AnyCompose},
+    prelude::*,
+    ui::{
+        view::widget::{use_interaction, Interactive, Surface, SurfaceStyle},
+        Accessibility, FocusHandle, Role,
+    },
+    ScopeData,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use vello::kurbo::{Affine, Rect as KurboRect};
+use winit::{
+    event::ElementState,
+    keyboard::{Key, NamedKey},
+};
+
+/// Height of the indicator below the selected tab.
+const INDICATOR_HEIGHT: f32 = 2.;
+
+/// Create a new tab view from its tabs, as pairs of labels and content.
+pub fn tabs<'a, L, C>(tabs: impl IntoIterator<Item = (L, C)>) -> Tabs<'a, C>
+where
+    L: Into<String>,
+{
+    Tabs::new(tabs)
+}
+
+/// Tab view composable.
+///
+/// A tab view shows a row of tab labels above the content of the selected tab.
+/// Pressing a label selects its tab, and an indicator slides below the selected label.
+/// While a label is focused, the left and right arrow keys select and focus the previous or next tab.
+///
+/// By default, inactive tabs are kept alive: their content stays composed (and keeps its hook state)
+/// but is hidden from the window. With [`Tabs::with_keep_alive`], inactive tabs can instead be torn down.
+///
+/// Tabs with different types of content can use [`DynCompose`](crate::compose::DynCompose).
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Tabs<'a, C> {
+    tabs: Vec<(String, C)>,
+    initial: usize,
+    is_keep_alive: bool,
+    on_change: Box<dyn Fn(usize) + 'a>,
+}
+
+impl<'a, C> Tabs<'a, C> {
+    /// Create a new tab view from its tabs, as pairs of labels and content.
+    pub fn new<L>(tabs: impl IntoIterator<Item = (L, C)>) -> Self
+    where
+        L: Into<String>,
+    {
+        Self {
+            tabs: tabs
+                .into_iter()
+                .map(|(label, content)| (label.into(), content))
+                .collect(),
+            initial: 0,
+            is_keep_alive: true,
+            on_change: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the index of the tab selected when this tab view is first composed (default: 0).
+    pub fn with_selected(mut self, idx: usize) -> Self {
+        self.initial = idx;
+        self
+    }
+
+    /// Set whether inactive tabs are kept alive (default: `true`).
+    ///
+    /// If `false`, the content of a tab is dropped when another tab is selected,
+    /// and composed again from scratch when it's selected again.
+    pub fn with_keep_alive(mut self, is_keep_alive: bool) -> Self {
+        self.is_keep_alive = is_keep_alive;
+        self
+    }
+
+    /// Set the function to call with the index of a tab when it's selected.
+    pub fn on_change(mut self, on_change: impl Fn(usize) + 'a) -> Self {
+        self.on_change = Box::new(on_change);
+        self
+    }
+}
+
+impl<C: Compose> Compose for Tabs<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+
+        let len = cx.me().tabs.len();
+        let selected = use_mut(&cx, || cx.me().initial);
+        if len > 0 && *selected >= len {
+            Mut::set(selected, len - 1);
+        }
+
+        let focus_handles = use_ref(&cx, || RefCell::new(Vec::<Option<FocusHandle>>::new()));
+        focus_handles.borrow_mut().resize(len, None);
+
+        let select = move |idx: usize| {
+            if idx != *selected {
+                Mut::set(selected, idx);
+                (cx.me().on_change)(idx);
+            }
+        };
+
+        // Select and focus the tab `offset` places after the selected tab, wrapping around the tabs.
+        let select_next = move |offset: isize| {
+            let len = cx.me().tabs.len() as isize;
+            if len == 0 {
+                return;
+            }
+
+            let next = (*selected as isize + offset).rem_euclid(len) as usize;
+            select(next);
+
+            if let Some(Some(handle)) = focus_handles.borrow().get(next) {
+                handle.focus();
+            }
+        };
+
+        let position: f64 = *use_animated(&cx, *selected as f64, AnimationSpec::default());
+        let indicator_color = theme.colors.primary;
+        let indicator = Canvas::new(
+            Style {
+                size: Size {
+                    width: percent(1.),
+                    height: length(INDICATOR_HEIGHT),
+                },
+                flex_shrink: 0.,
+                ..Default::default()
+            },
+            move |layout, renderer| {
+                if len == 0 {
+                    return;
+                }
+
+                let width = layout.size.width as f64 / len as f64;
+                let x = position * width;
+                renderer.fill(
+                    Affine::IDENTITY,
+                    indicator_color.into(),
+                    KurboRect::new(x, 0., x + width, layout.size.height as _).into(),
+                );
+            },
+        );
+
+        let indices: Vec<usize> = (0..len).collect();
+        Flex::column((
+            Flex::row(compose::from_iter(indices.clone(), move |idx| TabLabel {
+                tabs: cx.me(),
+                index: *idx,
+                selected,
+                on_select: Box::new(move || select(*idx)),
+                on_move: Box::new(select_next),
+                on_focus_handle: Box::new(move |handle| {
+                    if let Some(slot) = focus_handles.borrow_mut().get_mut(*idx) {
+                        *slot = Some(handle);
+                    }
+                }),
+            })),
+            indicator,
+            Flex::column(compose::from_iter(indices, move |idx| TabPanel {
+                tabs: cx.me(),
+                index: *idx,
+                selected,
+            }))
+            .flex_grow(1.),
+        ))
+    }
+}
+
+/// Label of a tab in a [`Tabs`] view.
+#[derive(Data)]
+struct TabLabel<'a, C> {
+    tabs: Ref<'a, Tabs<'a, C>>,
+    index: usize,
+    selected: Mut<'a, usize>,
+    on_select: Box<dyn Fn() + 'a>,
+    on_move: Box<dyn Fn(isize) + 'a>,
+    on_focus_handle: Box<dyn Fn(FocusHandle) + 'a>,
+}
+
+impl<C: Compose> Compose for TabLabel<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        use_ref(&cx, || (cx.me().on_focus_handle)(focus.clone()));
+
+        let me = cx.me();
+        let is_selected = *me.selected == me.index;
+        let label = me.tabs.tabs[me.index].0.clone();
+
+        let surface = use_ref(&cx, || Rc::new(Cell::new(SurfaceStyle::default())));
+        surface.set(SurfaceStyle {
+            overlay: interaction.overlay(theme.colors.text),
+            border: interaction.focus_ring(theme),
+            ..Default::default()
+        });
+
+        Flex::row(
+            Text::new(label.clone())
+                .color(if is_selected {
+                    theme.colors.primary
+                } else {
+                    theme.colors.text_secondary
+                })
+                .accessibility(Accessibility {
+                    role: Role::Tab,
+                    label: Some(label),
+                    toggled: Some(is_selected),
+                    ..Default::default()
+                })
+                .padding(Rect {
+                    left: length(theme.spacing.medium),
+                    right: length(theme.spacing.medium),
+                    top: length(theme.spacing.small),
+                    bottom: length(theme.spacing.small),
+                }),
+        )
+        .justify_content(JustifyContent::Center)
+        .flex_grow(1.)
+        .flex_basis(zero())
+        .draw(Surface {
+            style: surface.clone(),
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, move || (cx.me().on_select)()))
+        .on_key_down(move |input| {
+            if input.state != ElementState::Pressed {
+                return;
+            }
+
+            match input.key {
+                Key::Named(NamedKey::ArrowRight) => (cx.me().on_move)(1),
+                Key::Named(NamedKey::ArrowLeft) => (cx.me().on_move)(-1),
+                _ => {}
+            }
+        })
+    }
+}
+
+/// Panel of a tab in a [`Tabs`] view, containing its content.
+///
+/// Inactive panels are hidden from the window if their tab is kept alive, or dropped otherwise.
+#[derive(Data)]
+struct TabPanel<'a, C> {
+    tabs: Ref<'a, Tabs<'a, C>>,
+    index: usize,
+    selected: Mut<'a, usize>,
+}
+
+impl<'a, C: Compose> Compose for TabPanel<'a, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let me = cx.me();
+        let is_active = *me.selected == me.index;
+        if !is_active && !me.tabs.is_keep_alive {
+            return None;
+        }
+
+        let style = Style {
+            display: if is_active {
+                Display::Flex
+            } else {
+                Display::None
+            },
+            flex_direction: FlexDirection::Column,
+            flex_grow: 1.,
+            ..Default::default()
+        };
+
+        Some(Flex::new(
+            style,
+            TabContent {
+                tabs: me.tabs,
+                index: me.index,
+            },
+        ))
+    }
+}
+
+/// Content of a tab in a [`Tabs`] view.
+#[derive(Data)]
+struct TabContent<'a, C> {
+    tabs: Ref<'a, Tabs<'a, C>>,
+    index: usize,
+}
+
+impl<C: Compose> Compose for TabContent<'_, C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let state = use_ref(&cx, ScopeData::default);
+        state.inherit_contexts(&cx);
+        state.is_parent_changed.set(cx.is_parent_changed.get());
+
+        // Safety: The content is looked up from the tab view on every compose,
+        // so it's valid while it's composed.
+        let me = cx.me();
+        unsafe { me.tabs.tabs[me.index].1.any_compose(state) }
+    }
+}
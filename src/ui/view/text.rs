@@ -1,24 +1,86 @@
-use super::Canvas;
-use crate::prelude::*;
+use super::{canvas::to_local, Canvas};
+use crate::{
+    prelude::*,
+    ui::{
+        render::{Glyph, GlyphRun},
+        use_direction,
+        Accessibility, Event, KeyboardInput, Role, Shortcut, WindowContext},
+};
 use parley::{
-    Alignment, FontFamily, FontStack, GenericFamily, LayoutContext, PositionedLayoutItem,
-    StyleProperty,
+    layout::Selection, Alignment, FontFamily, FontStack, FontStyle, FontWeight, GenericFamily,
+    LayoutContext, PositionedLayoutItem, RangedBuilder, StyleProperty,
 };
-use std::{cell::RefCell, fmt};
-use taffy::{Size, Style};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt, fs, io, ops::Range, path::Path, rc::Rc};
+use taffy::{AvailableSpace, Size, Style};
+use thiserror::Error;
 use vello::{
-    self,
-    kurbo::Affine,
-    peniko::{Color, Fill},
-    Glyph,
+    kurbo::{Affine, Rect, Vec2},
+    peniko::Color,
 };
+use winit::event::{ElementState, MouseButton};
 
-#[derive(Default)]
+/// Font collection and layout caches, shared by each window.
+#[derive(Clone)]
 pub(crate) struct FontContext {
-    inner: RefCell<parley::FontContext>,
+    inner: Rc<RefCell<parley::FontContext>>,
+    layout_cx: Rc<RefCell<LayoutContext<Color>>>,
 }
 
-/// Use a new font.
+impl Default for FontContext {
+    fn default() -> Self {
+        let font_cx = Self {
+            inner: Rc::default(),
+            layout_cx: Rc::default(),
+        };
+
+        // System fonts aren't available on the web, so register a bundled font for each generic family.
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mut inner = font_cx.inner.borrow_mut();
+            let families = inner
+                .collection
+                .register_fonts(include_bytes!("../../../assets/fonts/DejaVuSans.ttf").to_vec());
+
+            for &generic in GenericFamily::all() {
+                inner
+                    .collection
+                    .set_generic_families(generic, families.iter().map(|(id, _)| *id));
+            }
+        }
+
+        font_cx
+    }
+}
+
+impl FontContext {
+    /// Register the fonts in `data`, returning `true` if any font was found.
+    fn register(&self, data: Vec<u8>) -> bool {
+        !self
+            .inner
+            .borrow_mut()
+            .collection
+            .register_fonts(data)
+            .is_empty()
+    }
+}
+
+/// Error loading a font.
+#[derive(Debug, Error)]
+pub enum FontError {
+    /// Error reading the font file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The font data doesn't contain any supported fonts.
+    #[error("No supported fonts found in the font data")]
+    NoFonts,
+}
+
+/// Use a new font from its data.
+///
+/// The font's families can then be used by name with [`View::font_family`](super::View::font_family).
 pub fn use_font<R>(cx: ScopeState, make_font: impl FnOnce() -> R)
 where
     R: Into<Vec<u8>>,
@@ -26,14 +88,27 @@ where
     let font_cx = use_context::<FontContext>(cx).unwrap();
 
     use_ref(cx, || {
-        font_cx
-            .inner
-            .borrow_mut()
-            .collection
-            .register_fonts(make_font().into());
+        font_cx.register(make_font().into());
     });
 }
 
+/// Use a new font from the file at `path`, returning an error if it couldn't be loaded.
+///
+/// The font's families can then be used by name with [`View::font_family`](super::View::font_family).
+pub fn use_font_file(cx: ScopeState<'_>, path: impl AsRef<Path>) -> Result<(), &FontError> {
+    let font_cx = use_context::<FontContext>(cx).unwrap();
+
+    let result = use_ref(cx, || {
+        let data = fs::read(path)?;
+        if font_cx.register(data) {
+            Ok(())
+        } else {
+            Err(FontError::NoFonts)
+        }
+    });
+    result.as_ref().copied()
+}
+
 /// Convert a type to a [`FontStack`].
 pub trait IntoFontStack<'a> {
     /// Convert this type to a [`FontStack`].
@@ -63,6 +138,36 @@ pub(crate) struct TextContext {
     pub color: Color,
     pub font_size: f32,
     pub font_stack: FontStack<'static>,
+    pub font_weight: FontWeight,
+    /// Font families to fall back to for characters missing from `font_stack`.
+    pub fallback: Vec<FontFamily<'static>>,
+}
+
+impl TextContext {
+    /// Get the font stack of this context, followed by its fallback families.
+    fn resolved_font_stack(&self) -> FontStack<'static> {
+        let mut families: Vec<_> = match &self.font_stack {
+            FontStack::Source(source) => FontFamily::parse_list(source)
+                .map(to_owned_family)
+                .collect(),
+            FontStack::Single(family) => vec![family.clone()],
+            FontStack::List(families) => families.to_vec(),
+        };
+        for family in &self.fallback {
+            if !families.contains(family) {
+                families.push(family.clone());
+            }
+        }
+        FontStack::List(families.into())
+    }
+
+    /// Push the default styles of this context to a layout `builder`.
+    fn push_defaults(&self, builder: &mut RangedBuilder<Color>) {
+        builder.push_default(StyleProperty::Brush(self.color));
+        builder.push_default(StyleProperty::FontSize(self.font_size));
+        builder.push_default(StyleProperty::FontWeight(self.font_weight));
+        builder.push_default(self.resolved_font_stack());
+    }
 }
 
 impl Default for TextContext {
@@ -73,6 +178,8 @@ impl Default for TextContext {
             font_stack: FontStack::Single(FontFamily::Generic(
                 parley::style::GenericFamily::SansSerif,
             )),
+            font_weight: FontWeight::NORMAL,
+            fallback: vec![FontFamily::Generic(GenericFamily::Emoji)],
         }
     }
 }
@@ -82,12 +189,28 @@ impl Default for TextContext {
 #[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
 pub struct Text<T> {
     content: T,
+    is_selectable: bool,
 }
 
 impl<T> Text<T> {
     /// Create a new text from the given `content`.
     pub fn new(content: T) -> Self {
-        Self { content }
+        Self {
+            content,
+            is_selectable: false,
+        }
+    }
+
+    /// Set whether this text can be selected (defaults to `false`).
+    ///
+    /// Selectable text is selected by dragging over it, double-clicking a word,
+    /// or pressing `Ctrl+A` (`Cmd+A` on macOS) while it's focused.
+    /// With the `clipboard` feature, the selection is copied with `Ctrl+C` (`Cmd+C` on macOS);
+    /// without it, `Ctrl+C` is ignored.
+    /// Clicks on selectable text are not sent to its parent views.
+    pub fn with_selectable(mut self, is_selectable: bool) -> Self {
+        self.is_selectable = is_selectable;
+        self
     }
 }
 
@@ -96,78 +219,665 @@ where
     T: Data + fmt::Display,
 {
     fn compose(cx: Scope<Self>) -> impl Compose {
-        let font_cx = use_context::<FontContext>(&cx).unwrap();
+        let window_cx = use_context::<WindowContext>(&cx).unwrap();
+        let font_cx = &window_cx.font_cx;
         let text_cx = use_context::<TextContext>(&cx).unwrap();
         let content = format!("{}", cx.me().content);
 
         let text_layout = use_memo(&cx, (content.clone(), text_cx.clone()), || {
-            let mut font_cx = font_cx.inner.borrow_mut();
-
-            let mut layout_cx = LayoutContext::<Color>::new();
-            let mut text_layout = layout_cx.ranged_builder(&mut font_cx, &content, 1.);
-            text_layout.push_default(StyleProperty::Brush(text_cx.color));
-            text_layout.push_default(StyleProperty::FontSize(text_cx.font_size));
-            text_layout.push_default(text_cx.font_stack.clone());
-
-            let mut layout = text_layout.build(&content);
-            layout.break_all_lines(None);
-            layout.align(None, Alignment::Start);
-            layout
+            RefCell::new(build_text_layout(font_cx, text_cx, &content))
         });
 
+        let selection = use_text_selection(&cx, text_layout);
+
+        let content_offset = use_ref(&cx, Cell::default);
+
+        let selection_color = use_theme(&cx).colors.primary.multiply_alpha(0.3);
+        let direction = use_direction(&cx);
+        let canvas = text_canvas(
+            text_layout,
+            Mut::as_ref(selection.selection),
+            selection_color,
+            content_offset,
+            direction.text_alignment(),
+        )
+        .with_accessibility(Accessibility::new(Role::Label).label(content.clone()));
+
+        let canvas = if cx.me().is_selectable {
+            canvas.on_pointer(move |event| {
+                let event = to_local(event, Affine::translate(content_offset.get()));
+                handle_selection(&text_layout.borrow(), selection, &event, &content)
+            })
+        } else {
+            canvas
+        };
+
         Memo::new(
-            content.clone(),
-            Canvas::new(
-                Style {
-                    size: Size::from_lengths(text_layout.full_width(), text_layout.height()),
-                    ..Default::default()
-                },
-                move |_layout, scene| {
-                    for line in text_layout.lines() {
-                        for item in line.items() {
-                            let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
-                                continue;
-                            };
-                            let mut x = glyph_run.offset();
-                            let y = glyph_run.baseline();
-                            let run = glyph_run.run();
-                            let font = run.font();
-                            let font_size = run.font_size();
-                            let synthesis = run.synthesis();
-                            let glyph_xform = synthesis
-                                .skew()
-                                .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
-                            let coords = run
-                                .normalized_coords()
-                                .iter()
-                                .map(|coord| {
-                                    vello::skrifa::instance::NormalizedCoord::from_bits(*coord)
-                                })
-                                .collect::<Vec<_>>();
-                            scene
-                                .draw_glyphs(font)
-                                .brush(text_cx.color)
-                                .hint(true)
-                                .glyph_transform(glyph_xform)
-                                .font_size(font_size)
-                                .normalized_coords(&coords)
-                                .draw(
-                                    Fill::NonZero,
-                                    glyph_run.glyphs().map(|glyph| {
-                                        let gx = x + glyph.x;
-                                        let gy = y - glyph.y;
-                                        x += glyph.advance;
-                                        Glyph {
-                                            id: glyph.id as _,
-                                            x: gx,
-                                            y: gy,
-                                        }
-                                    }),
-                                );
-                        }
-                    }
-                },
+            (
+                text_layout.memoized(),
+                selection.selection.memoized(),
+                direction,
             ),
+            canvas,
         )
     }
 }
+
+/// Styled span of text in a [`RichText`].
+///
+/// This can be created with [`span`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    content: String,
+    font_weight: Option<FontWeight>,
+    is_italic: bool,
+    font_size: Option<f32>,
+    color: Option<Color>,
+    is_underlined: bool,
+    is_strikethrough: bool,
+    link: Option<String>,
+}
+
+impl Span {
+    /// Create a new span from its text `content`.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            font_weight: None,
+            is_italic: false,
+            font_size: None,
+            color: None,
+            is_underlined: false,
+            is_strikethrough: false,
+            link: None,
+        }
+    }
+
+    /// Set the font weight of this span to bold.
+    pub fn bold(self) -> Self {
+        self.font_weight(FontWeight::BOLD)
+    }
+
+    /// Set the font weight of this span.
+    pub fn font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = Some(font_weight);
+        self
+    }
+
+    /// Set the font style of this span to italic.
+    pub fn italic(mut self) -> Self {
+        self.is_italic = true;
+        self
+    }
+
+    /// Set the font size of this span.
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Set the text color of this span.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Underline this span.
+    pub fn underline(mut self) -> Self {
+        self.is_underlined = true;
+        self
+    }
+
+    /// Strike through this span.
+    pub fn strikethrough(mut self) -> Self {
+        self.is_strikethrough = true;
+        self
+    }
+
+    /// Make this span an underlined link to `url`.
+    ///
+    /// Clicking the span calls the link handler of its [`RichText`] with `url`.
+    pub fn link(mut self, url: impl Into<String>) -> Self {
+        self.link = Some(url.into());
+        self.is_underlined = true;
+        self
+    }
+}
+
+/// Create a new [`Span`] from its text `content`.
+pub fn span(content: impl Into<String>) -> Span {
+    Span::new(content)
+}
+
+/// Rich text composable.
+///
+/// Rich text is made of [`Span`]s with their own weight, size, color, and decorations,
+/// laid out together as a single text.
+///
+/// ```no_run
+/// use actuate::{prelude::*, ui::view::{rich_text, span}};
+///
+/// #[derive(Data)]
+/// struct App;
+///
+/// impl Compose for App {
+///     fn compose(cx: Scope<Self>) -> impl Compose {
+///         Window::new(
+///             rich_text([
+///                 span("Read the "),
+///                 span("docs").bold().link("https://docs.rs/actuate"),
+///                 span(" to get started."),
+///             ])
+///             .on_link(|url| println!("Open {url}")),
+///         )
+///     }
+/// }
+/// ```
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct RichText<'a> {
+    spans: Vec<Span>,
+    on_link: Box<dyn Fn(&str) + 'a>,
+    is_selectable: bool,
+}
+
+impl<'a> RichText<'a> {
+    /// Create a new rich text from its spans.
+    pub fn new(spans: impl IntoIterator<Item = Span>) -> Self {
+        Self {
+            spans: spans.into_iter().collect(),
+            on_link: Box::new(|_| {}),
+            is_selectable: false,
+        }
+    }
+
+    /// Set whether this text can be selected (defaults to `false`).
+    ///
+    /// For more see [`Text::with_selectable`].
+    pub fn with_selectable(mut self, is_selectable: bool) -> Self {
+        self.is_selectable = is_selectable;
+        self
+    }
+
+    /// Set the function to call with the URL of a link span when it's clicked.
+    pub fn on_link(mut self, on_link: impl Fn(&str) + 'a) -> Self {
+        self.on_link = Box::new(on_link);
+        self
+    }
+}
+
+unsafe impl Data for RichText<'_> {}
+
+impl Compose for RichText<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let window_cx = use_context::<WindowContext>(&cx).unwrap();
+        let font_cx = &window_cx.font_cx;
+        let text_cx = use_context::<TextContext>(&cx).unwrap();
+        let spans = cx.me().spans.clone();
+
+        let text_layout = use_memo(&cx, (spans.clone(), text_cx.clone()), || {
+            RefCell::new(build_rich_text_layout(font_cx, text_cx, &spans))
+        });
+
+        let content: String = spans.iter().map(|span| span.content.as_str()).collect();
+        let has_links = spans.iter().any(|span| span.link.is_some());
+        let is_selectable = cx.me().is_selectable;
+
+        let selection = use_text_selection(&cx, text_layout);
+
+        let content_offset = use_ref(&cx, Cell::default);
+
+        let selection_color = use_theme(&cx).colors.primary.multiply_alpha(0.3);
+        let direction = use_direction(&cx);
+        let canvas = text_canvas(
+            text_layout,
+            Mut::as_ref(selection.selection),
+            selection_color,
+            content_offset,
+            direction.text_alignment(),
+        )
+        .with_accessibility(Accessibility::new(Role::Label).label(content.clone()));
+
+        // Only handle pointer events for links and selection, so clicks on plain rich text reach its parent.
+        let canvas = if has_links || is_selectable {
+            canvas.on_pointer(move |event| {
+                let event = to_local(event, Affine::translate(content_offset.get()));
+                if is_selectable {
+                    handle_selection(&text_layout.borrow(), selection, &event, &content);
+                }
+
+                let Event::Tap { pos } = event else {
+                    return;
+                };
+
+                let Some(idx) = text_index_at(&text_layout.borrow(), pos) else {
+                    return;
+                };
+                let me = cx.me();
+                if let Some(url) = span_at(&me.spans, idx).and_then(|span| span.link.as_deref()) {
+                    (me.on_link)(url);
+                }
+            })
+        } else {
+            canvas
+        };
+
+        Memo::new(
+            (
+                text_layout.memoized(),
+                selection.selection.memoized(),
+                direction,
+            ),
+            canvas,
+        )
+    }
+}
+
+/// Create a new [`RichText`] from its spans.
+pub fn rich_text<'a>(spans: impl IntoIterator<Item = Span>) -> RichText<'a> {
+    RichText::new(spans)
+}
+
+/// Find the span containing the byte index `idx` of the concatenated content of `spans`.
+fn span_at(spans: &[Span], idx: usize) -> Option<&Span> {
+    let mut start = 0;
+    spans.iter().find(|span| {
+        let range = start..start + span.content.len();
+        start = range.end;
+        range.contains(&idx)
+    })
+}
+
+/// Selection of a text.
+#[derive(Clone, Copy)]
+struct TextSelection<'a> {
+    selection: Mut<'a, Option<Selection>>,
+
+    /// Latest selection from events, as updates to `selection` are only applied on the next composition
+    /// (so a press and drag sent before then extend the pressed selection).
+    latest: &'a Cell<Option<Selection>>,
+}
+
+/// Use the selection of a text, cleared when its layout is rebuilt.
+fn use_text_selection<'a>(
+    cx: ScopeState<'a>,
+    text_layout: Ref<'a, RefCell<parley::Layout<Color>>>,
+) -> TextSelection<'a> {
+    let selection = use_mut(cx, || None);
+    let latest = use_ref(cx, || Cell::new(None));
+
+    use_memo(cx, text_layout, || {
+        latest.set(None);
+        if selection.is_some() {
+            Mut::set(selection, None);
+        }
+    });
+
+    TextSelection { selection, latest }
+}
+
+/// Update the `selection` of a selectable text from a pointer or keyboard `event`,
+/// copying the selected text of `content` with `Ctrl+C` (`Cmd+C` on macOS)
+/// if the `clipboard` feature is enabled.
+#[cfg_attr(not(feature = "clipboard"), allow(unused_variables))]
+fn handle_selection(
+    layout: &parley::Layout<Color>,
+    selection: TextSelection,
+    event: &Event,
+    content: &str,
+) {
+    let TextSelection { selection, latest } = selection;
+
+    let next = match event {
+        Event::MouseInput {
+            button: MouseButton::Left,
+            state: ElementState::Pressed,
+            pos,
+        } => Some(Selection::from_point(layout, pos.x as _, pos.y as _)),
+        Event::DragMove { pos, .. } | Event::DragEnd { pos } => {
+            let Some(selection) = latest.get() else {
+                return;
+            };
+            Some(selection.extend_to_point(layout, pos.x as _, pos.y as _))
+        }
+        Event::DoubleTap { pos } => {
+            Some(Selection::word_from_point(layout, pos.x as _, pos.y as _))
+        }
+        Event::KeyboardInput(input)
+            if input.state == ElementState::Pressed && is_shortcut(input, "CmdOrCtrl+A") =>
+        {
+            Some(Selection::from_point(layout, 0., 0.).extend_to_point(layout, f32::MAX, f32::MAX))
+        }
+        #[cfg(feature = "clipboard")]
+        Event::KeyboardInput(input)
+            if input.state == ElementState::Pressed && is_shortcut(input, "CmdOrCtrl+C") =>
+        {
+            if let Some(selection) = latest.get().filter(|selection| !selection.is_collapsed()) {
+                copy(&content[selection.text_range()]);
+            }
+            return;
+        }
+        // The selection is cleared when another view is focused.
+        Event::Blur => None,
+        _ => return,
+    };
+
+    if latest.replace(next) != next {
+        Mut::set(selection, next);
+    }
+}
+
+/// Returns `true` if `input` is the key press of `shortcut`.
+fn is_shortcut(input: &KeyboardInput, shortcut: &str) -> bool {
+    shortcut
+        .parse::<Shortcut>()
+        .is_ok_and(|shortcut| shortcut.matches(&input.key, input.modifiers))
+}
+
+/// Copy `text` to the platform clipboard.
+#[cfg(feature = "clipboard")]
+fn copy(text: &str) {
+    if let Err(_error) = crate::clipboard::Clipboard::default().set_text(text) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Failed to copy selected text: {_error}");
+    }
+}
+
+/// Find the byte index of the text under `pos` in `layout`, by hit-testing its glyph runs.
+fn text_index_at(layout: &parley::Layout<Color>, pos: Vec2) -> Option<usize> {
+    let (x, y) = (pos.x as f32, pos.y as f32);
+
+    for line in layout.lines() {
+        let metrics = line.metrics();
+        if y < metrics.min_coord || y > metrics.max_coord {
+            continue;
+        }
+
+        for item in line.items() {
+            let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                continue;
+            };
+            let start = glyph_run.offset();
+            if x < start || x > start + glyph_run.advance() {
+                continue;
+            }
+
+            let run = glyph_run.run();
+            let mut offset = start;
+            for cluster in run.visual_clusters() {
+                offset += cluster.advance();
+                if x <= offset {
+                    return Some(cluster.text_range().start);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Create a canvas drawing `text_layout` with its `selection` highlighted in `selection_color`,
+/// measured by wrapping its lines at the available width and aligning them with `alignment`.
+///
+/// The offset of the text inside the canvas is stored in `content_offset` when it's drawn,
+/// so pointer positions can be mapped to positions in `text_layout`.
+fn text_canvas<'a>(
+    text_layout: Ref<'a, RefCell<parley::Layout<Color>>>,
+    selection: Ref<'a, Option<Selection>>,
+    selection_color: Color,
+    content_offset: &'a Cell<Vec2>,
+    alignment: Alignment,
+) -> Canvas<'a> {
+    Canvas::new(Style::default(), move |layout, renderer| {
+        // Text is drawn inside the padding and border of the canvas.
+        let left = layout.padding.left + layout.border.left;
+        let top = layout.padding.top + layout.border.top;
+        let width = layout.size.width - left - layout.padding.right - layout.border.right;
+        content_offset.set(Vec2::new(left as f64, top as f64));
+        let transform = Affine::translate(content_offset.get());
+
+        let mut text_layout = text_layout.borrow_mut();
+        break_lines(&mut text_layout, Some(width), alignment);
+
+        if let Some(selection) = selection.filter(|selection| !selection.is_collapsed()) {
+            // Lines may have been broken again since this selection was made.
+            let selection = selection.refresh(&text_layout);
+            for rect in selection.geometry(&text_layout) {
+                renderer.fill(
+                    transform,
+                    selection_color.into(),
+                    Rect::new(rect.x0, rect.y0, rect.x1, rect.y1).into(),
+                );
+            }
+        }
+
+        draw_text_layout(renderer, &text_layout, transform)
+    })
+    .with_measure(move |known_dimensions, available_space| {
+        let max_advance = known_dimensions.width.or(match available_space.width {
+            AvailableSpace::Definite(width) => Some(width),
+            AvailableSpace::MinContent => Some(0.),
+            AvailableSpace::MaxContent => None,
+        });
+
+        let mut text_layout = text_layout.borrow_mut();
+        break_lines(&mut text_layout, max_advance, alignment);
+
+        // Sizes are rounded up so rounding the layout never wraps the text again when it's drawn.
+        let width = if max_advance.is_some() {
+            text_layout.width()
+        } else {
+            text_layout.full_width()
+        };
+        Size {
+            width: known_dimensions.width.unwrap_or(width.ceil()),
+            height: known_dimensions
+                .height
+                .unwrap_or(text_layout.height().ceil()),
+        }
+    })
+}
+
+/// Break the lines of `layout` at `max_advance`, or only at explicit line breaks if `None`,
+/// and align them with `alignment`.
+fn break_lines(
+    layout: &mut parley::Layout<Color>,
+    max_advance: Option<f32>,
+    alignment: Alignment,
+) {
+    layout.break_all_lines(max_advance);
+    layout.align(max_advance, alignment);
+}
+
+/// Draw the glyphs and decorations of a text `layout` with `renderer`.
+pub(crate) fn draw_text_layout(
+    renderer: &mut dyn Renderer,
+    layout: &parley::Layout<Color>,
+    transform: Affine,
+) {
+    for line in layout.lines() {
+        for item in line.items() {
+            let PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                continue;
+            };
+            let mut x = glyph_run.offset();
+            let y = glyph_run.baseline();
+            let run = glyph_run.run();
+            let style = glyph_run.style();
+            let font = run.font();
+            let font_size = run.font_size();
+            let synthesis = run.synthesis();
+            let glyph_xform = synthesis
+                .skew()
+                .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0));
+            let glyphs = glyph_run
+                .glyphs()
+                .map(|glyph| {
+                    let gx = x + glyph.x;
+                    let gy = y - glyph.y;
+                    x += glyph.advance;
+                    Glyph {
+                        id: glyph.id as _,
+                        x: gx,
+                        y: gy,
+                    }
+                })
+                .collect::<Vec<_>>();
+            renderer.draw_glyphs(GlyphRun {
+                font,
+                font_size,
+                normalized_coords: run.normalized_coords(),
+                transform,
+                glyph_transform: glyph_xform,
+                brush: style.brush.into(),
+                glyphs: &glyphs,
+            });
+
+            let metrics = run.metrics();
+            let decorations = [
+                style
+                    .underline
+                    .as_ref()
+                    .map(|deco| (deco, metrics.underline_offset, metrics.underline_size)),
+                style.strikethrough.as_ref().map(|deco| {
+                    (
+                        deco,
+                        metrics.strikethrough_offset,
+                        metrics.strikethrough_size,
+                    )
+                }),
+            ];
+            for (deco, offset, size) in decorations.into_iter().flatten() {
+                let top = y - deco.offset.unwrap_or(offset);
+                let x = glyph_run.offset();
+                renderer.fill(
+                    transform,
+                    deco.brush.into(),
+                    Rect::new(
+                        x as _,
+                        top as _,
+                        (x + glyph_run.advance()) as _,
+                        (top + deco.size.unwrap_or(size)) as _,
+                    )
+                    .into(),
+                );
+            }
+        }
+    }
+}
+
+/// Build a single-line text layout of `content`.
+pub(crate) fn build_text_layout(
+    font_cx: &FontContext,
+    text_cx: &TextContext,
+    content: &str,
+) -> parley::Layout<Color> {
+    let mut inner = font_cx.inner.borrow_mut();
+    let mut layout_cx = font_cx.layout_cx.borrow_mut();
+
+    let mut text_layout = layout_cx.ranged_builder(&mut inner, content, 1.);
+    text_cx.push_defaults(&mut text_layout);
+
+    let mut layout = text_layout.build(content);
+    layout.break_all_lines(None);
+    layout.align(None, Alignment::Start);
+    layout
+}
+
+/// Build a single-line text layout of `spans`, with the style of each span on top of `text_cx`.
+fn build_rich_text_layout(
+    font_cx: &FontContext,
+    text_cx: &TextContext,
+    spans: &[Span],
+) -> parley::Layout<Color> {
+    let content: String = spans.iter().map(|span| span.content.as_str()).collect();
+    let mut inner = font_cx.inner.borrow_mut();
+    let mut layout_cx = font_cx.layout_cx.borrow_mut();
+
+    let mut text_layout = layout_cx.ranged_builder(&mut inner, &content, 1.);
+    text_cx.push_defaults(&mut text_layout);
+
+    let mut start = 0;
+    for span in spans {
+        let range: Range<usize> = start..start + span.content.len();
+        start = range.end;
+
+        if let Some(font_weight) = span.font_weight {
+            text_layout.push(StyleProperty::FontWeight(font_weight), range.clone());
+        }
+        if span.is_italic {
+            text_layout.push(StyleProperty::FontStyle(FontStyle::Italic), range.clone());
+        }
+        if let Some(font_size) = span.font_size {
+            text_layout.push(StyleProperty::FontSize(font_size), range.clone());
+        }
+        if let Some(color) = span.color {
+            text_layout.push(StyleProperty::Brush(color), range.clone());
+        }
+        if span.is_underlined {
+            text_layout.push(StyleProperty::Underline(true), range.clone());
+        }
+        if span.is_strikethrough {
+            text_layout.push(StyleProperty::Strikethrough(true), range);
+        }
+    }
+
+    let mut layout = text_layout.build(&content);
+    layout.break_all_lines(None);
+    layout.align(None, Alignment::Start);
+    layout
+}
+
+/// Convert a font family borrowing its name to one that owns it.
+pub(crate) fn to_owned_family(family: FontFamily) -> FontFamily<'static> {
+    match family {
+        FontFamily::Named(name) => FontFamily::Named(Cow::Owned(name.into_owned())),
+        FontFamily::Generic(generic) => FontFamily::Generic(generic),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rich_text, span};
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::Cell, rc::Rc};
+    use vello::kurbo::Vec2;
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_hit_tests_text_inside_padding() {
+        let clicked = Rc::new(Cell::new(false));
+
+        let clicked_ref = clicked.clone();
+        let mut window = TestWindow::new(
+            rich_text([span("Link").link("https://example.com")])
+                .on_link(move |_| clicked_ref.set(true))
+                .padding(Rect::length(40.)),
+        );
+        // Draw the text after its layout is computed.
+        window.frame();
+        window.frame();
+
+        let key = window.find_by_label("Link").unwrap();
+        window.click(window.position(key) + Vec2::new(42., 42.));
+        assert!(clicked.get());
+    }
+
+    #[test]
+    #[cfg(feature = "software")]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_selects_dragged_text() {
+        let mut window = TestWindow::new(Text::new("Selectable").with_selectable(true));
+        window.frame();
+        window.frame();
+        let unselected = window.render(200, 40).unwrap();
+
+        let key = window.find_by_label("Selectable").unwrap();
+        let pos = window.position(key);
+        window.drag(pos + Vec2::new(1., 5.), pos + Vec2::new(150., 5.));
+        window.frame();
+
+        // The selection is drawn behind the text.
+        let selected = window.render(200, 40).unwrap();
+        assert!(selected.diff(&unselected, 0) > 0);
+    }
+}
@@ -0,0 +1,585 @@
+use crate::{
+    compose,
+    prelude::*,
+    ui::{
+        draw::Border,
+        view::widget::{use_interaction, Interactive, SurfaceStyle},
+        Accessibility, FocusHandle, Role,
+    },
+};
+use std::{
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+};
+use taffy::Layout;
+use vello::kurbo::{Affine, BezPath, Circle, Rect, Stroke};
+use winit::{
+    event::ElementState,
+    keyboard::{Key, NamedKey},
+};
+
+/// Style of a fixed-size toggle control.
+fn control_style(width: f32, height: f32) -> Style {
+    Style {
+        size: Size {
+            width: length(width),
+            height: length(height),
+        },
+        flex_shrink: 0.,
+        ..Default::default()
+    }
+}
+
+/// Row of a toggle control followed by its label.
+fn control_row<'a>(
+    theme: &Theme,
+    control: impl Compose + 'a,
+    label: Option<String>,
+) -> impl Compose + 'a {
+    Flex::row((control, label.map(Text::new)))
+        .align_items(AlignItems::Center)
+        .gap(Size {
+            width: length(theme.spacing.medium),
+            height: zero(),
+        })
+}
+
+/// Accessibility properties of a toggle control.
+fn control_accessibility(role: Role, label: Option<&str>, is_toggled: bool) -> Accessibility {
+    let mut accessibility = Accessibility::new(role).toggled(is_toggled);
+    accessibility.label = label.map(str::to_owned);
+    accessibility
+}
+
+/// Create a new checkbox from its checked state.
+pub fn checkbox<'a>(is_checked: bool) -> Checkbox<'a> {
+    Checkbox::new(is_checked)
+}
+
+/// Checkbox composable.
+///
+/// Clicking the checkbox, or pressing the space or enter key while it's focused,
+/// calls its toggle handler with the new checked state.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Checkbox<'a> {
+    is_checked: bool,
+    label: Option<String>,
+    on_toggle: Box<dyn Fn(bool) + 'a>,
+}
+
+impl<'a> Checkbox<'a> {
+    /// Create a new checkbox from its checked state.
+    pub fn new(is_checked: bool) -> Self {
+        Self {
+            is_checked,
+            label: None,
+            on_toggle: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the label shown next to this checkbox.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the function to call with the new checked state when this checkbox is toggled.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool) + 'a) -> Self {
+        self.on_toggle = Box::new(on_toggle);
+        self
+    }
+
+    /// Bind this checkbox to a form `field`, showing its value and setting and touching it when toggled.
+    pub fn with_field(mut self, field: &Field<bool>) -> Self {
+        let field = field.clone();
+        self.is_checked = field.get();
+        self.on_toggle = Box::new(move |is_checked| {
+            field.set(is_checked);
+            field.touch();
+        });
+        self
+    }
+}
+
+impl Compose for Checkbox<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let is_checked = cx.me().is_checked;
+        let on_toggle = move || (cx.me().on_toggle)(!cx.me().is_checked);
+
+        let state = *interaction;
+        let colors = theme.colors.clone();
+        let control = Canvas::new(control_style(18., 18.), move |layout, renderer| {
+            let style = SurfaceStyle {
+                background: if is_checked {
+                    colors.primary
+                } else {
+                    Color::TRANSPARENT
+                },
+                overlay: state.overlay(colors.text),
+                border: Some(Border {
+                    width: 2.,
+                    color: if state.is_focused {
+                        colors.text
+                    } else if is_checked {
+                        colors.primary
+                    } else {
+                        colors.text_secondary
+                    },
+                }),
+                corner_radius: 4.,
+            };
+            style.draw(renderer, layout_rect(&layout));
+
+            if is_checked {
+                let (width, height) = (layout.size.width as f64, layout.size.height as f64);
+                let mut check = BezPath::new();
+                check.move_to((width * 0.25, height * 0.5));
+                check.line_to((width * 0.42, height * 0.68));
+                check.line_to((width * 0.75, height * 0.32));
+                renderer.stroke(
+                    &Stroke::new(2.),
+                    Affine::IDENTITY,
+                    colors.on_primary.into(),
+                    (&check).into(),
+                );
+            }
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, on_toggle))
+        .accessibility(control_accessibility(
+            Role::CheckBox,
+            cx.me().label.as_deref(),
+            is_checked,
+        ));
+
+        control_row(theme, control, cx.me().label.clone())
+    }
+}
+
+/// Create a new switch from its state.
+pub fn switch<'a>(is_on: bool) -> Switch<'a> {
+    Switch::new(is_on)
+}
+
+/// Switch composable.
+///
+/// Clicking the switch, or pressing the space or enter key while it's focused,
+/// calls its toggle handler with the new state.
+/// The switch's knob slides to its new position as the state changes.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Switch<'a> {
+    is_on: bool,
+    label: Option<String>,
+    on_toggle: Box<dyn Fn(bool) + 'a>,
+}
+
+impl<'a> Switch<'a> {
+    /// Create a new switch from its state.
+    pub fn new(is_on: bool) -> Self {
+        Self {
+            is_on,
+            label: None,
+            on_toggle: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the label shown next to this switch.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the function to call with the new state when this switch is toggled.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool) + 'a) -> Self {
+        self.on_toggle = Box::new(on_toggle);
+        self
+    }
+
+    /// Bind this switch to a form `field`, showing its value and setting and touching it when toggled.
+    pub fn with_field(mut self, field: &Field<bool>) -> Self {
+        let field = field.clone();
+        self.is_on = field.get();
+        self.on_toggle = Box::new(move |is_on| {
+            field.set(is_on);
+            field.touch();
+        });
+        self
+    }
+}
+
+impl Compose for Switch<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let is_on = cx.me().is_on;
+        let on_toggle = move || (cx.me().on_toggle)(!cx.me().is_on);
+
+        let target = if is_on { 1. } else { 0. };
+        let progress: f64 = *use_animated(&cx, target, AnimationSpec::default());
+
+        let state = *interaction;
+        let colors = theme.colors.clone();
+        let control = Canvas::new(control_style(36., 20.), move |layout, renderer| {
+            let rect = layout_rect(&layout);
+            let radius = rect.height() / 2.;
+
+            let style = SurfaceStyle {
+                background: if is_on {
+                    colors.primary
+                } else {
+                    colors.text_secondary
+                },
+                overlay: state.overlay(colors.text),
+                border: state.is_focused.then_some(Border {
+                    width: 2.,
+                    color: colors.text,
+                }),
+                corner_radius: radius,
+            };
+            style.draw(renderer, rect);
+
+            let knob_x = radius + progress * (rect.width() - radius * 2.);
+            renderer.fill(
+                Affine::IDENTITY,
+                colors.on_primary.into(),
+                Circle::new((knob_x, radius), radius - 3.).into(),
+            );
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, on_toggle))
+        .accessibility(control_accessibility(
+            Role::Switch,
+            cx.me().label.as_deref(),
+            is_on,
+        ));
+
+        control_row(theme, control, cx.me().label.clone())
+    }
+}
+
+/// Create a new radio group from its options and the index of the selected option.
+pub fn radio_group<'a, I>(options: I, selected: impl Into<Option<usize>>) -> RadioGroup<'a>
+where
+    I: IntoIterator,
+    I::Item: Into<String>,
+{
+    RadioGroup::new(options, selected)
+}
+
+type SelectFn = Rc<dyn Fn(usize)>;
+
+/// State of a [`RadioGroup`], shared with each of its options.
+#[derive(Default)]
+struct RadioGroupContext {
+    options: RefCell<Vec<String>>,
+    selected: Cell<Option<usize>>,
+    on_select: RefCell<Option<SelectFn>>,
+    focus_handles: RefCell<Vec<Option<FocusHandle>>>,
+}
+
+impl RadioGroupContext {
+    /// Select the option at `index`.
+    fn select(&self, index: usize) {
+        let on_select = self.on_select.borrow().clone();
+        if let Some(on_select) = on_select {
+            on_select(index)
+        }
+    }
+
+    /// Select and focus the option `offset` places after `index`, wrapping around the group.
+    fn select_next(&self, index: usize, offset: isize) {
+        let len = self.options.borrow().len() as isize;
+        if len == 0 {
+            return;
+        }
+
+        let next = (index as isize + offset).rem_euclid(len) as usize;
+        self.select(next);
+
+        if let Some(Some(handle)) = self.focus_handles.borrow().get(next) {
+            handle.focus();
+        }
+    }
+}
+
+/// Radio group composable.
+///
+/// A radio group shows a column of options, where only one can be selected.
+/// Clicking an option, or pressing the space or enter key while it's focused, calls the group's select handler.
+/// The arrow keys select and focus the next or previous option.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct RadioGroup<'a> {
+    options: Vec<String>,
+    selected: Option<usize>,
+    on_select: Box<dyn Fn(usize) + 'a>,
+}
+
+impl<'a> RadioGroup<'a> {
+    /// Create a new radio group from its options and the index of the selected option.
+    pub fn new<I>(options: I, selected: impl Into<Option<usize>>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: selected.into(),
+            on_select: Box::new(|_| {}),
+        }
+    }
+
+    /// Set the function to call with the index of an option when it's selected.
+    pub fn on_select(mut self, on_select: impl Fn(usize) + 'a) -> Self {
+        self.on_select = Box::new(on_select);
+        self
+    }
+
+    /// Bind this radio group to a form `field`, showing its selected option and setting and touching it when selected.
+    pub fn with_field(mut self, field: &Field<Option<usize>>) -> Self {
+        let field = field.clone();
+        self.selected = field.get();
+        self.on_select = Box::new(move |index| {
+            field.set(Some(index));
+            field.touch();
+        });
+        self
+    }
+}
+
+impl Compose for RadioGroup<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+
+        let group = use_provider(&cx, || {
+            let f: Rc<dyn Fn(usize) + '_> = Rc::new(move |index| (cx.me().on_select)(index));
+
+            // Safety: `f` is only called by the options of this group, which are dropped before it.
+            let f: SelectFn = unsafe { mem::transmute(f) };
+
+            RadioGroupContext {
+                on_select: RefCell::new(Some(f)),
+                ..Default::default()
+            }
+        });
+        group.options.replace(cx.me().options.clone());
+        group.selected.set(cx.me().selected);
+
+        let indices: Vec<usize> = (0..cx.me().options.len()).collect();
+        Flex::column(compose::from_iter(indices, |index| RadioOption {
+            index: *index,
+        }))
+        .gap(Size {
+            width: zero(),
+            height: length(theme.spacing.small),
+        })
+    }
+}
+
+/// Option of a [`RadioGroup`].
+#[derive(Data)]
+struct RadioOption {
+    index: usize,
+}
+
+impl Compose for RadioOption {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let group = use_context::<RadioGroupContext>(&cx).unwrap();
+        let theme = use_theme(&cx);
+        let (interaction, focus) = use_interaction(&cx);
+
+        let index = cx.me().index;
+        use_ref(&cx, || {
+            let mut focus_handles = group.focus_handles.borrow_mut();
+            if focus_handles.len() <= index {
+                focus_handles.resize(index + 1, None);
+            }
+            focus_handles[index] = Some(focus.clone());
+        });
+        use_drop(&cx, move || {
+            if let Some(handle) = group.focus_handles.borrow_mut().get_mut(index) {
+                *handle = None;
+            }
+        });
+
+        let is_selected = group.selected.get() == Some(index);
+        let label = group.options.borrow().get(index).cloned();
+        let on_select = move || group.select(index);
+
+        let state = *interaction;
+        let colors = theme.colors.clone();
+        let control = Canvas::new(control_style(18., 18.), move |layout, renderer| {
+            let rect = layout_rect(&layout);
+            let style = SurfaceStyle {
+                background: Color::TRANSPARENT,
+                overlay: state.overlay(colors.text),
+                border: Some(Border {
+                    width: 2.,
+                    color: if state.is_focused {
+                        colors.text
+                    } else if is_selected {
+                        colors.primary
+                    } else {
+                        colors.text_secondary
+                    },
+                }),
+                corner_radius: rect.width() / 2.,
+            };
+            style.draw(renderer, rect);
+
+            if is_selected {
+                renderer.fill(
+                    Affine::IDENTITY,
+                    colors.primary.into(),
+                    Circle::new(rect.center(), rect.width() / 4.).into(),
+                );
+            }
+        })
+        .focusable(focus.clone())
+        .on_event(Interactive::new(interaction, on_select))
+        .on_key_down(move |input| {
+            if input.state != ElementState::Pressed {
+                return;
+            }
+
+            match input.key {
+                Key::Named(NamedKey::ArrowDown | NamedKey::ArrowRight) => {
+                    group.select_next(index, 1)
+                }
+                Key::Named(NamedKey::ArrowUp | NamedKey::ArrowLeft) => group.select_next(index, -1),
+                _ => {}
+            }
+        })
+        .accessibility(control_accessibility(
+            Role::RadioButton,
+            label.as_deref(),
+            is_selected,
+        ));
+
+        control_row(theme, control, label)
+    }
+}
+
+/// Get the rectangle of a `layout`, at its origin.
+fn layout_rect(layout: &Layout) -> Rect {
+    Rect::new(0., 0., layout.size.width as _, layout.size.height as _)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checkbox, radio_group, switch};
+    use crate::{prelude::*, ui::testing::TestWindow};
+    use std::{cell::RefCell, rc::Rc};
+    use winit::keyboard::{Key, NamedKey};
+
+    #[derive(Data)]
+    struct Toggles {
+        toggles: Rc<RefCell<Vec<(&'static str, bool)>>>,
+    }
+
+    impl Compose for Toggles {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let is_checked = use_mut(&cx, || false);
+            let is_on = use_mut(&cx, || false);
+
+            Flex::column((
+                checkbox(*is_checked)
+                    .with_label("Checkbox")
+                    .on_toggle(move |value| {
+                        cx.me().toggles.borrow_mut().push(("Checkbox", value));
+                        Mut::set(is_checked, value);
+                    }),
+                switch(*is_on).with_label("Switch").on_toggle(move |value| {
+                    cx.me().toggles.borrow_mut().push(("Switch", value));
+                    Mut::set(is_on, value);
+                }),
+            ))
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_toggles_on_click_and_key_press() {
+        let toggles = Rc::new(RefCell::new(Vec::new()));
+        let mut window = TestWindow::new(Toggles {
+            toggles: toggles.clone(),
+        });
+        window.frame();
+
+        window.click_node(window.find_by_label("Checkbox").unwrap());
+        window.frame();
+        window.click_node(window.find_by_label("Checkbox").unwrap());
+        window.frame();
+
+        let switch = window.find_by_label("Switch").unwrap();
+        window.click_node(switch);
+        window.frame();
+        assert_eq!(window.focused(), Some(switch));
+        window.key_press(Key::Named(NamedKey::Space));
+        window.frame();
+        window.key_press(Key::Named(NamedKey::Enter));
+        window.frame();
+
+        assert_eq!(
+            *toggles.borrow(),
+            [
+                ("Checkbox", true),
+                ("Checkbox", false),
+                ("Switch", true),
+                ("Switch", false),
+                ("Switch", true),
+            ]
+        );
+    }
+
+    #[derive(Data)]
+    struct Radios {
+        selects: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl Compose for Radios {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let selected = use_mut(&cx, || None);
+
+            radio_group(["A", "B", "C"], *selected).on_select(move |index| {
+                cx.me().selects.borrow_mut().push(index);
+                Mut::set(selected, Some(index));
+            })
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "loads system fonts")]
+    fn it_selects_radio_options_with_arrow_keys() {
+        let selects = Rc::new(RefCell::new(Vec::new()));
+        let mut window = TestWindow::new(Radios {
+            selects: selects.clone(),
+        });
+        window.frame();
+
+        window.click_node(window.find_by_label("B").unwrap());
+        window.frame();
+
+        window.key_press(Key::Named(NamedKey::ArrowDown));
+        window.frame();
+        assert_eq!(window.focused(), window.find_by_label("C"));
+
+        // Arrow keys wrap around the group.
+        window.key_press(Key::Named(NamedKey::ArrowDown));
+        window.frame();
+        assert_eq!(window.focused(), window.find_by_label("A"));
+
+        window.key_press(Key::Named(NamedKey::ArrowUp));
+        window.frame();
+        assert_eq!(window.focused(), window.find_by_label("C"));
+
+        assert_eq!(*selects.borrow(), [1, 2, 0, 2]);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    prelude::*,
+    ui::{use_frame, use_layer, view::canvas::CanvasContext, LayerKind, LayoutContext, Placement},
+};
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+/// Tooltip composable.
+///
+/// A tooltip shows its text in a layer next to its content
+/// after the cursor hovers over the content for the tooltip's delay.
+/// The tooltip is flipped to the opposite side of its content if it would overflow the window,
+/// and is hidden when the cursor leaves the content, or when the window is pressed or scrolled.
+///
+/// The content is wrapped in a [`Flex`] that the tooltip is positioned next to.
+/// This can be created with [`View::tooltip`](crate::ui::view::View::tooltip).
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Tooltip<C> {
+    text: String,
+    content: C,
+    delay: Duration,
+    placement: Placement,
+}
+
+impl<C> Tooltip<C> {
+    /// Create a new tooltip from its text and the given `content`.
+    pub fn new(text: impl Into<String>, content: C) -> Self {
+        Self {
+            text: text.into(),
+            content,
+            delay: Duration::from_millis(500),
+            placement: Placement::Below,
+        }
+    }
+
+    /// Set the time the cursor must hover over the content before showing this tooltip (default: 500ms).
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the placement of this tooltip relative to its content (default: [`Placement::Below`]).
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+}
+
+impl<C: Compose> Compose for Tooltip<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let is_hovered = use_mut(&cx, || false);
+        let is_shown = use_mut(&cx, || false);
+
+        let hide = move || Mut::set(is_shown, false);
+
+        Flex::new(
+            Style::default(),
+            (
+                Ref::map(cx.me(), |me| &me.content),
+                (*is_hovered && !*is_shown).then(|| TooltipDelay {
+                    delay: cx.me().delay,
+                    on_elapsed: Box::new(move || Mut::set(is_shown, true)),
+                }),
+                is_shown.then(|| TooltipLayer {
+                    text: cx.me().text.clone(),
+                    placement: cx.me().placement,
+                    on_dismiss: Box::new(move || {
+                        Mut::set(is_hovered, false);
+                        hide();
+                    }),
+                }),
+            ),
+        )
+        .on_mouse_in(move || Mut::set(is_hovered, true))
+        .on_mouse_out(move || {
+            Mut::set(is_hovered, false);
+            hide();
+        })
+    }
+}
+
+/// Timer that calls `on_elapsed` after `delay`, measured with the window's frame clock.
+#[derive(Data)]
+struct TooltipDelay<'a> {
+    delay: Duration,
+    on_elapsed: Box<dyn Fn() + 'a>,
+}
+
+impl Compose for TooltipDelay<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let elapsed = use_ref(&cx, || Cell::new(Duration::ZERO));
+
+        use_frame(&cx, move |dt| {
+            let last = elapsed.get();
+            elapsed.set(last + dt);
+
+            if last < cx.me().delay && last + dt >= cx.me().delay {
+                (cx.me().on_elapsed)()
+            }
+        });
+    }
+}
+
+/// Layer showing the text of a [`Tooltip`].
+#[derive(Data)]
+struct TooltipLayer<'a> {
+    text: String,
+    placement: Placement,
+    on_dismiss: Box<dyn Fn() + 'a>,
+}
+
+impl Compose for TooltipLayer<'_> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let theme = use_theme(&cx);
+        let anchor = use_context::<LayoutContext>(&cx).unwrap().parent_id;
+
+        let kind = LayerKind::Tooltip {
+            anchor,
+            placement: cx.me().placement,
+        };
+        let root = use_layer(&cx, kind, || Rc::new(move || (cx.me().on_dismiss)()));
+
+        use_provider(&cx, || LayoutContext::new(root));
+        use_provider(&cx, CanvasContext::default);
+
+        Flex::row(
+            Text::new(cx.me().text.clone())
+                .color(theme.colors.background)
+                .font_size(theme.typography.caption),
+        )
+        .background_color(theme.colors.text)
+        .corner_radius(4.)
+        .padding(Rect {
+            left: length(theme.spacing.small * 2.),
+            right: length(theme.spacing.small * 2.),
+            top: length(theme.spacing.small),
+            bottom: length(theme.spacing.small),
+        })
+        .margin(Rect {
+            left: zero(),
+            right: zero(),
+            top: length(theme.spacing.small),
+            bottom: length(theme.spacing.small),
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    compose::AnyCompose,
+    prelude::*,
+    ui::{view::canvas::CanvasContext, WindowContext},
+    ScopeData,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use vello::kurbo::Vec2;
+
+/// Kind of animation played by a [`Transition`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TransitionKind {
+    /// Fade the content in and out.
+    #[default]
+    Fade,
+    /// Slide the content in from, and out to, the given offset.
+    Slide(Vec2),
+    /// Fade and slide the content from the given offset.
+    FadeSlide(Vec2),
+}
+
+impl TransitionKind {
+    fn effect(self, progress: f32) -> TransitionEffect {
+        let opacity = progress.clamp(0., 1.);
+        let offset = |from: Vec2| from * (1. - progress as f64);
+
+        match self {
+            TransitionKind::Fade => TransitionEffect {
+                opacity,
+                offset: Vec2::ZERO,
+            },
+            TransitionKind::Slide(from) => TransitionEffect {
+                opacity: 1.,
+                offset: offset(from),
+            },
+            TransitionKind::FadeSlide(from) => TransitionEffect {
+                opacity,
+                offset: offset(from),
+            },
+        }
+    }
+}
+
+/// Effect applied by a transition to each canvas it contains.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TransitionEffect {
+    pub(crate) opacity: f32,
+    pub(crate) offset: Vec2,
+}
+
+impl Default for TransitionEffect {
+    fn default() -> Self {
+        Self {
+            opacity: 1.,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+/// Transition composable.
+///
+/// This mounts its content when it becomes visible and plays an enter animation.
+/// When it becomes hidden, an exit animation is played before the content is removed.
+#[derive(Data)]
+#[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
+pub struct Transition<C> {
+    is_visible: bool,
+    kind: TransitionKind,
+    spec: AnimationSpec,
+    content: C,
+}
+
+impl<C> Transition<C> {
+    /// Create a new transition from its visibility, kind, and the given `content`.
+    pub fn new(is_visible: bool, kind: TransitionKind, content: C) -> Self {
+        Self {
+            is_visible,
+            kind,
+            spec: AnimationSpec::default(),
+            content,
+        }
+    }
+
+    /// Create a new fading transition from its visibility and the given `content`.
+    pub fn fade(is_visible: bool, content: C) -> Self {
+        Self::new(is_visible, TransitionKind::Fade, content)
+    }
+
+    /// Create a new sliding transition from its visibility, offset, and the given `content`.
+    pub fn slide(is_visible: bool, offset: Vec2, content: C) -> Self {
+        Self::new(is_visible, TransitionKind::Slide(offset), content)
+    }
+
+    /// Set the animation spec of this transition.
+    pub fn spec(mut self, spec: AnimationSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+}
+
+impl<C: Compose> Compose for Transition<C> {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        cx.is_container.set(true);
+
+        let window_cx = use_context::<WindowContext>(&cx).unwrap();
+        let canvas_cx = use_context::<CanvasContext>(&cx).unwrap();
+
+        let target: f32 = if cx.me().is_visible { 1. } else { 0. };
+        let progress = *use_animated(&cx, target, cx.me().spec);
+
+        let effect = use_ref(&cx, || Rc::new(Cell::new(TransitionEffect::default())));
+        let next_effect = cx.me().kind.effect(progress);
+        if effect.get() != next_effect {
+            effect.set(next_effect);
+            window_cx.is_changed.set(true);
+        }
+
+        use_provider(&cx, || {
+            let mut transitions = canvas_cx.transitions.clone();
+            transitions.push(effect.clone());
+
+            CanvasContext {
+                transitions,
+                ..(*canvas_cx).clone()
+            }
+        });
+
+        let child_state = use_ref(&cx, || RefCell::new(None::<ScopeData>));
+        let mut child_state = child_state.borrow_mut();
+
+        // Keep the content mounted until its exit animation is complete.
+        if cx.me().is_visible || progress > 0. {
+            let state = child_state.get_or_insert_with(ScopeData::default);
+
+            state.inherit_contexts(&cx);
+
+            state.is_parent_changed.set(cx.is_parent_changed.get());
+
+            unsafe { cx.me().content.any_compose(state) }
+        } else if child_state.is_some() {
+            // Drop the content's state in place, as child scopes point to it.
+            *child_state = None;
+
+            window_cx.is_changed.set(true);
+            window_cx.is_layout_changed.set(true);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    prelude::*,
+    ui::{draw::Border, use_focus, view::Handler, Action, Event, FocusHandle},
+};
+use std::{cell::Cell, rc::Rc};
+use taffy::Layout;
+use vello::kurbo::{Affine, Rect, Stroke};
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, NamedKey},
+};
+
+/// Interaction state of a widget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Data)]
+pub(crate) struct Interaction {
+    pub(crate) is_hovered: bool,
+    pub(crate) is_pressed: bool,
+    pub(crate) is_focused: bool,
+}
+
+impl Interaction {
+    /// Get the color of the state layer drawn over a widget with content of `color`,
+    /// or `None` if the widget is idle.
+    pub(crate) fn overlay(&self, color: Color) -> Option<Color> {
+        if self.is_pressed {
+            Some(color.multiply_alpha(0.16))
+        } else if self.is_hovered {
+            Some(color.multiply_alpha(0.08))
+        } else {
+            None
+        }
+    }
+
+    /// Get the focus ring of a widget, if it's focused.
+    pub(crate) fn focus_ring(&self, theme: &Theme) -> Option<Border> {
+        self.is_focused.then_some(Border {
+            width: 2.,
+            color: theme.colors.text,
+        })
+    }
+}
+
+/// Event handler of a widget.
+///
+/// This tracks the widget's [`Interaction`] state, and activates it when clicked
+/// or when the space or enter key is pressed while it's focused.
+#[derive(Data)]
+pub(crate) struct Interactive<'a> {
+    interaction: Mut<'a, Interaction>,
+    on_activate: Box<dyn Fn() + 'a>,
+}
+
+impl<'a> Interactive<'a> {
+    pub(crate) fn new(interaction: Mut<'a, Interaction>, on_activate: impl Fn() + 'a) -> Self {
+        Self {
+            interaction,
+            on_activate: Box::new(on_activate),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Interaction) + 'static) {
+        Mut::update(self.interaction, f)
+    }
+}
+
+impl Handler for Interactive<'_> {
+    /// `true` while the left mouse button is pressed on the widget.
+    type State = bool;
+
+    fn build(&self) -> Self::State {
+        false
+    }
+
+    fn handle(&self, is_pressed: &mut Self::State, event: Event) {
+        match event {
+            Event::MouseIn => self.update(|interaction| interaction.is_hovered = true),
+            Event::MouseOut => {
+                *is_pressed = false;
+                self.update(|interaction| {
+                    interaction.is_hovered = false;
+                    interaction.is_pressed = false;
+                });
+            }
+            Event::MouseInput {
+                button: MouseButton::Left,
+                state,
+                ..
+            } => {
+                if state == ElementState::Released && *is_pressed {
+                    (self.on_activate)();
+                }
+
+                *is_pressed = state == ElementState::Pressed;
+                let is_pressed = *is_pressed;
+                self.update(move |interaction| interaction.is_pressed = is_pressed);
+            }
+            Event::KeyboardInput(input)
+                if input.state == ElementState::Pressed
+                    && !input.repeat
+                    && is_activation_key(&input.key) =>
+            {
+                (self.on_activate)()
+            }
+            Event::Focus => self.update(|interaction| interaction.is_focused = true),
+            Event::Blur => self.update(|interaction| interaction.is_focused = false),
+            _ => {}
+        }
+    }
+
+    fn actions(&self) -> Vec<Action> {
+        vec![Action::Click]
+    }
+
+    fn is_keyboard(&self) -> bool {
+        true
+    }
+}
+
+/// Returns `true` if `key` activates the focused widget.
+pub(crate) fn is_activation_key(key: &Key) -> bool {
+    matches!(key, Key::Named(NamedKey::Space | NamedKey::Enter))
+}
+
+/// Style of a widget's surface, drawn below its content.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SurfaceStyle {
+    pub(crate) background: Color,
+    pub(crate) overlay: Option<Color>,
+    pub(crate) border: Option<Border>,
+    pub(crate) corner_radius: f64,
+}
+
+impl Default for SurfaceStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::TRANSPARENT,
+            overlay: None,
+            border: None,
+            corner_radius: 0.,
+        }
+    }
+}
+
+impl SurfaceStyle {
+    /// Draw this surface with `renderer`, filling `rect`.
+    pub(crate) fn draw(&self, renderer: &mut dyn Renderer, rect: Rect) {
+        let shape = rect.to_rounded_rect(self.corner_radius);
+        renderer.fill(Affine::IDENTITY, self.background.into(), shape.into());
+
+        if let Some(overlay) = self.overlay {
+            renderer.fill(Affine::IDENTITY, overlay.into(), shape.into());
+        }
+
+        if let Some(border) = self.border {
+            let inset = border.width / 2.;
+            renderer.stroke(
+                &Stroke::new(border.width),
+                Affine::IDENTITY,
+                border.color.into(),
+                rect.inset(-inset)
+                    .to_rounded_rect((self.corner_radius - inset).max(0.))
+                    .into(),
+            );
+        }
+    }
+}
+
+/// Drawable surface of a widget, drawn below its content.
+///
+/// The style is shared with the widget, so it can change without replacing this modifier.
+#[derive(Data)]
+pub(crate) struct Surface {
+    pub(crate) style: Rc<Cell<SurfaceStyle>>,
+}
+
+impl Draw for Surface {
+    fn pre_process(&self, layout: &Layout, renderer: &mut dyn Renderer) {
+        let rect = Rect::new(0., 0., layout.size.width as _, layout.size.height as _);
+        self.style.get().draw(renderer, rect);
+    }
+}
+
+/// Use the interaction state and focus handle of a widget.
+pub(crate) fn use_interaction(cx: ScopeState<'_>) -> (Mut<'_, Interaction>, &FocusHandle) {
+    (use_mut(cx, Interaction::default), use_focus(cx))
+}
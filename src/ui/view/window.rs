@@ -1,35 +1,110 @@
 use crate::prelude::*;
+#[cfg(feature = "devtools")]
+use crate::ui::Inspector;
 use crate::{
-    event_loop,
-    ui::{Event, LayoutContext, WindowContext},
+    composer::Runtime,
+    event_loop::{self, EventLoopContext},
+    ui::{
+        self,
+        accessibility::{ActionForwarder, InitialTreeHandler, NoopDeactivationHandler},
+        view::text::FontContext,
+        Event, KeyboardInput, LayoutContext, RendererContext, SafeArea, ScaleFactor, WindowContext,
+    },
 };
-use parley::Rect;
+use accesskit_winit::Adapter;
+#[cfg(target_arch = "wasm32")]
+use std::rc::Rc;
+#[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+use crate::ui::render::SoftwareRenderer;
+#[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+use std::num::NonZeroU32;
 use std::{
     cell::{Cell, RefCell},
     mem,
-    num::NonZeroUsize,
-    rc::Rc,
+    sync::mpsc,
 };
-use taffy::{prelude::TaffyMaxContent, FlexDirection, NodeId, Size, Style, TaffyTree};
+use taffy::NodeId;
 use vello::{
     self,
     kurbo::{Affine, Vec2},
-    peniko::{Color, Fill},
+    peniko::Color,
     util::{RenderContext, RenderSurface},
-    wgpu, AaConfig, RenderParams, Renderer, RendererOptions, Scene,
+    wgpu, AaConfig, RenderParams, Renderer, Scene,
 };
-use wgpu::PresentMode;
+use web_time::Instant;
 use winit::{
-    event::{Event as WinitEvent, WindowEvent},
+    dpi::PhysicalSize,
+    event::{ElementState, Event as WinitEvent, MouseButton, MouseScrollDelta, WindowEvent},
+    keyboard::{Key, NamedKey},
     window::WindowAttributes,
 };
 
-struct State {
-    renderer: Renderer,
-    render_surface: RenderSurface<'static>,
+/// Distance to scroll for each line of a mouse wheel.
+const LINE_HEIGHT: f64 = 20.;
+
+enum State {
+    /// Rendering with vello on the GPU.
+    Gpu {
+        renderer: Box<Renderer>,
+        render_surface: RenderSurface<'static>,
+    },
+    /// Rendering on the CPU, if no GPU can render this window.
+    #[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+    Software(SoftwareSurface),
+}
+
+/// Surface of a window drawn on the CPU with a [`SoftwareRenderer`].
+#[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+struct SoftwareSurface {
+    surface: softbuffer::Surface<&'static winit::window::Window, &'static winit::window::Window>,
+    renderer: SoftwareRenderer,
+}
+
+#[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+impl SoftwareSurface {
+    fn new(window: &winit::window::Window) -> Result<Self, softbuffer::SoftBufferError> {
+        // Safety: The surface is dropped with the window's state, before the window.
+        let window: &'static winit::window::Window = unsafe { mem::transmute(window) };
+
+        let context = softbuffer::Context::new(window)?;
+        let surface = softbuffer::Surface::new(&context, window)?;
+        let size = event_loop::surface_size(window);
+        let mut software_surface = Self {
+            surface,
+            renderer: SoftwareRenderer::new(
+                size.width.max(1),
+                size.height.max(1),
+                Color::BLACK,
+            ),
+        };
+        software_surface.resize(size)?;
+        Ok(software_surface)
+    }
+
+    fn resize(&mut self, size: PhysicalSize<u32>) -> Result<(), softbuffer::SoftBufferError> {
+        let (Some(width), Some(height)) = (NonZeroU32::new(size.width), NonZeroU32::new(size.height))
+        else {
+            return Ok(());
+        };
+
+        self.surface.resize(width, height)?;
+        self.renderer.resize(size.width, size.height);
+        Ok(())
+    }
+
+    /// Copy the last frame drawn by the renderer to the window.
+    fn present(&mut self) -> Result<(), softbuffer::SoftBufferError> {
+        let mut buffer = self.surface.buffer_mut()?;
+        self.renderer.copy_to(&mut buffer);
+        buffer.present()
+    }
 }
 
 /// Window composable.
+///
+/// With the `devtools` feature, pressing `Ctrl+Shift+I` toggles an inspector overlay showing the bounds
+/// of each layout node. Clicking a node while inspecting logs its composable path, layout, and style
+/// at the debug level (with the `tracing` feature).
 #[derive(Data)]
 #[must_use = "Composables do nothing unless composed with `actuate::run` or returned from other composables"]
 pub struct Window<C> {
@@ -37,124 +112,224 @@ pub struct Window<C> {
     pub attributes: WindowAttributes,
     /// Composable content.
     pub content: C,
-    /// Background color.
-    pub background_color: Color,
+    /// Background color, or `None` to use the background color of the current [`Theme`](crate::ui::Theme).
+    pub background_color: Option<Color>,
+    /// Target frame rate while animating, or `None` to draw frames at the display's refresh rate.
+    pub frame_rate: Option<f64>,
 }
 
 impl<C> Window<C> {
     /// Create a new window from its content.
+    ///
+    /// On the web, the window's canvas is appended to the page.
     pub fn new(content: C) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let attributes = WindowAttributes::default();
+
+        #[cfg(target_arch = "wasm32")]
+        let attributes = winit::platform::web::WindowAttributesExtWebSys::with_append(
+            WindowAttributes::default(),
+            true,
+        );
+
         Self {
-            attributes: WindowAttributes::default(),
+            attributes,
             content,
-            background_color: Color::WHITE,
+            background_color: None,
+            frame_rate: None,
         }
     }
+
+    /// Set the target frame rate of this window while animating.
+    ///
+    /// By default, frames are drawn at the display's refresh rate.
+    pub fn with_frame_rate(mut self, frame_rate: f64) -> Self {
+        self.frame_rate = Some(frame_rate);
+        self
+    }
 }
 
 impl<C: Compose> Compose for Window<C> {
     fn compose(cx: Scope<Self>) -> impl Compose {
+        let font_cx = use_context::<FontContext>(&cx).unwrap();
+
         let mut root_key_cell = None;
         let window_cx = use_provider(&cx, || {
-            let mut taffy = TaffyTree::new();
-            let root_key = taffy
-                .new_leaf(Style {
-                    flex_direction: FlexDirection::Column,
-                    ..Default::default()
-                })
-                .unwrap();
+            let (window_cx, root_key) = WindowContext::new(font_cx.clone());
             root_key_cell = Some(root_key);
+            window_cx
+        });
 
-            let mut scene = Scene::new();
-            scene.fill(
-                Fill::NonZero,
-                Affine::default(),
-                Color::BLACK,
-                None,
-                &Rect::new(0., 0., 500., 500.),
-            );
+        let theme = use_theme(&cx);
+        let base_color = cx.me().background_color.unwrap_or(theme.colors.background);
+        if window_cx.base_color.replace(base_color) != base_color {
+            window_cx.is_changed.set(true);
+        }
 
-            WindowContext {
-                scene: RefCell::new(scene),
-                taffy: RefCell::new(taffy),
-                is_changed: Cell::new(false),
-                is_layout_changed: Cell::new(false),
-                canvas_update_fns: RefCell::default(),
-                listeners: Rc::default(),
-                base_color: Cell::new(Color::WHITE),
-            }
-        });
+        let layout_cx = use_provider(&cx, || LayoutContext::new(root_key_cell.unwrap()));
 
-        let layout_cx = use_provider(&cx, || LayoutContext {
-            parent_id: root_key_cell.unwrap(),
-        });
+        let scale_factor = use_mut(&cx, || 1.);
+        use_memo_provider(&cx, *scale_factor, || ScaleFactor(*scale_factor));
+
+        let safe_area = use_mut(&cx, SafeArea::default);
+        use_memo_provider(&cx, *safe_area, || *safe_area);
+
+        // Each window shares the same devices from the root render context.
+        #[cfg(not(target_arch = "wasm32"))]
+        let renderer_cx = use_context::<RendererContext>(&cx).unwrap();
+
+        // On the web, each canvas has its own WebGL context (and so, its own devices).
+        #[cfg(target_arch = "wasm32")]
+        let renderer_cx = use_ref(&cx, RendererContext::default);
 
-        let render_cx = use_ref(&cx, || RefCell::new(RenderContext::new()));
+        // Surfaces are created asynchronously on the web, and handled with the next event once they're ready.
+        #[cfg(target_arch = "wasm32")]
+        let pending_surface = use_ref(&cx, || Rc::new(RefCell::new(None)));
+        let render_cx = &renderer_cx.render_cx;
 
         let cursor_pos = use_ref(&cx, RefCell::default);
         let target = use_ref(&cx, || Cell::new(None));
+        let scroll_drag = use_ref(&cx, || Cell::new(None));
 
         let state = use_ref(&cx, || RefCell::new(None));
+        let content_scene = use_ref(&cx, || RefCell::new(Scene::new()));
 
         let is_first = use_ref(&cx, || Cell::new(true));
 
+        let event_loop_cx = use_context::<EventLoopContext>(&cx).unwrap();
+        let frame_rate = cx.me().frame_rate;
+
+        let adapter = use_ref(&cx, || RefCell::new(None));
+        let action_channel = use_ref(&cx, mpsc::channel);
+        let last_focus = use_ref(&cx, || Cell::new(None));
+
+        // Duration of the last layout pass, recorded in the next frame's span.
+        #[cfg(feature = "tracing")]
+        let layout_time = use_ref(&cx, || Cell::new(std::time::Duration::ZERO));
+
         event_loop::Window::new(
-            WindowAttributes::default(),
+            // The window is shown after creating the accessibility adapter.
+            cx.me().attributes.clone().with_visible(false),
             move |window, event| {
                 if is_first.get() {
-                    window_cx.scene.borrow_mut().fill(
-                        Fill::NonZero,
-                        Affine::default(),
-                        window_cx.base_color.get(),
-                        None,
-                        &Rect::new(
-                            0.,
-                            0.,
-                            window.inner_size().width as _,
-                            window.inner_size().height as _,
-                        ),
-                    );
+                    *adapter.borrow_mut() = Some(Adapter::with_direct_handlers(
+                        window,
+                        InitialTreeHandler,
+                        ActionForwarder {
+                            tx: action_channel.0.clone(),
+                            sender: Runtime::current().update_sender(),
+                        },
+                        NoopDeactivationHandler,
+                    ));
+                    window.set_visible(true);
+
+                    window_cx.scale_factor.set(window.scale_factor());
+                    Mut::set(scale_factor, window.scale_factor());
+
                     is_first.set(false);
                 }
 
+                // Android doesn't notify the app when its content rect changes,
+                // so the safe area is checked with each event.
+                let new_safe_area =
+                    window_safe_area(window, event_loop_cx, window_cx.scale_factor.get());
+                if *safe_area != new_safe_area {
+                    Mut::set(safe_area, new_safe_area);
+                }
+
+                #[cfg(target_arch = "wasm32")]
+                if let Some(surface) = pending_surface.borrow_mut().take() {
+                    set_state(render_cx, state, window, surface);
+                    window_cx.is_changed.set(true);
+                }
+
+                if let WinitEvent::WindowEvent { event, .. } = event {
+                    if let Some(adapter) = &mut *adapter.borrow_mut() {
+                        adapter.process_event(window, event);
+                    }
+                }
+
+                while let Ok(request) = action_channel.1.try_recv() {
+                    window_cx.handle_action(request);
+                }
+
                 match event {
+                    // Draw the next frame while any animation or frame callback is active.
+                    WinitEvent::AboutToWait if window_cx.needs_frame() => {
+                        match window_cx.next_frame(frame_rate) {
+                            Some(next_frame) if next_frame > Instant::now() => {
+                                event_loop_cx.wake_at(next_frame);
+                            }
+                            _ => window.request_redraw(),
+                        }
+                    }
                     WinitEvent::Resumed => {
-                        let surface: RenderSurface<'_> =
-                            pollster::block_on(render_cx.borrow_mut().create_surface(
-                                window,
-                                window.inner_size().width,
-                                window.inner_size().height,
-                                PresentMode::AutoVsync,
-                            ))
-                            .unwrap();
-
-                        let renderer = Renderer::new(
-                            &render_cx.borrow().devices[surface.dev_id].device,
-                            RendererOptions {
-                                surface_format: Some(surface.format),
-                                use_cpu: false,
-                                antialiasing_support: vello::AaSupport::all(),
-                                num_init_threads: NonZeroUsize::new(1),
-                            },
-                        )
-                        .unwrap();
-
-                        // Safety: render_surface is valid for the lifetime of the window.
-                        let render_surface: RenderSurface<'static> =
-                            unsafe { mem::transmute(surface) };
-                        *state.borrow_mut() = Some(State {
-                            render_surface,
-                            renderer,
-                        })
+                        #[cfg(not(target_arch = "wasm32"))]
+                        set_state(render_cx, state, window, renderer_cx.create_surface(window));
+
+                        #[cfg(target_arch = "wasm32")]
+                        {
+                            let pending_surface = pending_surface.clone();
+                            let sender = Runtime::current().update_sender();
+                            renderer_cx.spawn_surface(window, move |surface| {
+                                *pending_surface.borrow_mut() = Some(surface);
+
+                                // Wake the event loop to handle the new surface.
+                                sender.wake();
+                            });
+                        }
                     }
+                    // The native window may be destroyed while suspended (such as on Android),
+                    // so its surface is dropped and created again when resumed.
+                    WinitEvent::Suspended => *state.borrow_mut() = None,
                     WinitEvent::WindowEvent { event, .. } => match event {
+                        #[cfg(feature = "devtools")]
+                        WindowEvent::KeyboardInput { event, .. }
+                            if event.state == ElementState::Pressed
+                                && Inspector::is_toggle(
+                                    &event.logical_key,
+                                    window_cx.modifiers.get(),
+                                ) =>
+                        {
+                            window_cx.inspector.toggle();
+                            window_cx.is_changed.set(true);
+                        }
+                        #[cfg(feature = "devtools")]
+                        WindowEvent::CursorMoved { position, .. }
+                            if window_cx.inspector.is_enabled.get() =>
+                        {
+                            *cursor_pos.borrow_mut() =
+                                Vec2::new(position.x, position.y) / window_cx.scale_factor.get();
+
+                            let hovered = hit_test(window_cx, *cursor_pos.borrow(), layout_cx);
+                            if window_cx.inspector.hovered.replace(hovered) != hovered {
+                                window_cx.is_changed.set(true);
+                            }
+                        }
+                        #[cfg(feature = "devtools")]
+                        WindowEvent::MouseInput { state, .. }
+                            if window_cx.inspector.is_enabled.get() =>
+                        {
+                            // Clicks are not forwarded to views while inspecting.
+                            let key = hit_test(window_cx, *cursor_pos.borrow(), layout_cx)
+                                .filter(|_| *state == ElementState::Pressed);
+                            if let Some(key) = key {
+                                window_cx.inspector.log(window_cx, key);
+                            }
+                        }
                         WindowEvent::CursorMoved { position, .. } => {
-                            *cursor_pos.borrow_mut() = Vec2::new(position.x, position.y);
+                            *cursor_pos.borrow_mut() =
+                                Vec2::new(position.x, position.y) / window_cx.scale_factor.get();
 
                             let pos = *cursor_pos.borrow();
-                            let taffy = window_cx.taffy.borrow();
 
-                            if let Some(id) = hit_test(&taffy, pos, layout_cx) {
+                            if let Some((key, last_pos)) = scroll_drag.get() {
+                                window_cx.scroll_by(key, last_pos - pos);
+                                scroll_drag.set(Some((key, pos)));
+                            }
+
+                            let hit = hit_test(window_cx, pos, layout_cx);
+                            if let Some(id) = hit {
                                 if let Some(last_id) = target.replace(Some(id)) {
                                     if last_id != id {
                                         if let Some(listeners) =
@@ -180,130 +355,252 @@ impl<C: Compose> Compose for Window<C> {
                                         f(Event::MouseIn)
                                     }
                                 }
+                            }
 
-                                if let Some(listeners) = window_cx.listeners.borrow().get(&id) {
-                                    for f in listeners {
-                                        f(Event::MouseMove { pos })
-                                    }
+                            // Move events are only sent to the captured view while the pointer is captured.
+                            if !window_cx.pointer_move(pos, hit) {
+                                if let Some(id) = hit {
+                                    window_cx.send(id, Event::MouseMove { pos });
                                 }
                             }
                         }
                         WindowEvent::MouseInput { button, state, .. } => {
                             let pos = *cursor_pos.borrow();
-                            let taffy = window_cx.taffy.borrow();
-
-                            let mut keys = vec![(Vec2::default(), layout_cx.parent_id)];
-
-                            let mut target = None;
-
-                            while let Some((parent_pos, key)) = keys.pop() {
-                                let layout = taffy.layout(key).unwrap();
-                                if pos.x >= parent_pos.x + layout.location.x as f64
-                                    && pos.y >= parent_pos.y + layout.location.y as f64
-                                    && pos.x
-                                        <= parent_pos.x
-                                            + layout.location.x as f64
-                                            + layout.size.width as f64
-                                    && pos.y
-                                        <= parent_pos.y
-                                            + layout.location.y as f64
-                                            + layout.size.height as f64
-                                {
-                                    target = Some(key);
-
-                                    keys.extend(taffy.children(key).unwrap().into_iter().map(
-                                        |key| {
-                                            (
-                                                parent_pos
-                                                    + Vec2::new(
-                                                        layout.location.x as _,
-                                                        layout.location.y as _,
-                                                    ),
-                                                key,
-                                            )
-                                        },
-                                    ));
+                            let target = hit_test(window_cx, pos, layout_cx);
+
+                            if *button == MouseButton::Left {
+                                if *state == ElementState::Pressed {
+                                    scroll_drag.set(
+                                        window_cx.scroll_drag_target(target).map(|key| (key, pos)),
+                                    );
+                                } else {
+                                    scroll_drag.set(None);
                                 }
                             }
 
+                            if *state == ElementState::Pressed {
+                                window_cx.dismiss_outside(target);
+                            }
+
                             if let Some(key) = target {
+                                if *state == ElementState::Pressed {
+                                    let focus_key = if window_cx.focus.is_focusable(key) {
+                                        Some(key)
+                                    } else {
+                                        None
+                                    };
+                                    window_cx
+                                        .focus
+                                        .set(focus_key, &window_cx.listeners.borrow());
+                                }
+                            }
+
+                            window_cx.pointer_input(target, *button, *state, pos, Instant::now());
+                        }
+                        WindowEvent::MouseWheel { delta, .. } => {
+                            let delta = match delta {
+                                MouseScrollDelta::LineDelta(x, y) => {
+                                    Vec2::new(*x as _, *y as _) * LINE_HEIGHT
+                                }
+                                MouseScrollDelta::PixelDelta(pos) => {
+                                    Vec2::new(pos.x, pos.y) / window_cx.scale_factor.get()
+                                }
+                            };
+
+                            window_cx.dismiss_tooltips();
+
+                            if let Some(key) = hit_test(window_cx, *cursor_pos.borrow(), layout_cx)
+                            {
+                                window_cx.scroll_by(key, -delta);
+                            }
+                        }
+                        WindowEvent::ModifiersChanged(modifiers) => {
+                            window_cx.modifiers.set(modifiers.state());
+                        }
+                        WindowEvent::KeyboardInput { event, .. } => {
+                            if event.state == ElementState::Pressed
+                                && event.logical_key == Key::Named(NamedKey::Tab)
+                            {
+                                window_cx.focus.traverse(
+                                    &window_cx.taffy.borrow(),
+                                    &window_cx.focus_roots(layout_cx.parent_id),
+                                    window_cx.modifiers.get().shift_key(),
+                                    &window_cx.listeners.borrow(),
+                                );
+                            } else if event.state == ElementState::Pressed
+                                && event.logical_key == Key::Named(NamedKey::Escape)
+                                && window_cx.dismiss_top_layer()
+                            {
+                                // Escape dismisses the topmost layer instead of being sent to the focused view.
+                            } else if event.state == ElementState::Pressed
+                                && window_cx.handle_shortcut(&event.logical_key)
+                            {
+                                // Keys pressed for a shortcut are not sent to the focused view.
+                            } else if let Some(key) = window_cx.focus.focused() {
+                                let input = KeyboardInput {
+                                    key: event.logical_key.clone(),
+                                    physical_key: event.physical_key,
+                                    state: event.state,
+                                    repeat: event.repeat,
+                                    modifiers: window_cx.modifiers.get(),
+                                };
+
                                 if let Some(listeners) = window_cx.listeners.borrow().get(&key) {
                                     for f in listeners {
-                                        f(Event::MouseInput {
-                                            button: *button,
-                                            state: *state,
-                                            pos: *cursor_pos.borrow(),
-                                        })
+                                        f(Event::KeyboardInput(input.clone()))
                                     }
                                 }
                             }
                         }
+                        WindowEvent::DroppedFile(path) => {
+                            if let Some(key) = hit_test(window_cx, *cursor_pos.borrow(), layout_cx)
+                            {
+                                window_cx.send(key, Event::FileDrop(path.clone()));
+                            }
+                        }
+                        WindowEvent::Ime(ime) => {
+                            window_cx.send_ime(ime.clone());
+                        }
+                        WindowEvent::ScaleFactorChanged {
+                            scale_factor: new_scale_factor,
+                            ..
+                        } => {
+                            // Layout is in logical pixels, so the window's content is laid out again
+                            // and rendered with the new scale to a surface of the new physical size.
+                            window_cx.scale_factor.set(*new_scale_factor);
+                            Mut::set(scale_factor, *new_scale_factor);
+
+                            resize_surface(render_cx, state, event_loop::surface_size(window));
+                            window_cx.is_changed.set(true);
+                        }
+                        WindowEvent::Resized(size) => {
+                            // The root layout node is resized to the window below.
+                            resize_surface(render_cx, state, *size);
+                            window_cx.is_changed.set(true);
+                        }
                         WindowEvent::RedrawRequested => {
                             #[cfg(feature = "tracing")]
-                            tracing::trace!("Redraw");
+                            let frame_span = tracing::trace_span!(
+                                "frame",
+                                layout_time = ?layout_time.get(),
+                                render_time = tracing::field::Empty,
+                            )
+                            .entered();
 
                             let Some(state) = &mut *state.borrow_mut() else {
                                 return;
                             };
+                            match state {
+                                State::Gpu {
+                                    renderer,
+                                    render_surface,
+                                } => {
+                                    let texture =
+                                        render_surface.surface.get_current_texture().unwrap();
+
+                                    // Build the scene from the recorded fragment of each canvas, in logical pixels.
+                                    let mut content_scene = content_scene.borrow_mut();
+                                    window_cx.draw(&mut *content_scene, layout_cx.parent_id);
+
+                                    // Scale the scene to physical pixels.
+                                    let mut scene = window_cx.scene.borrow_mut();
+                                    scene.reset();
+                                    scene.append(
+                                        &content_scene,
+                                        Some(Affine::scale(window_cx.scale_factor.get())),
+                                    );
+
+                                    let device_handle =
+                                        &render_cx.borrow().devices[render_surface.dev_id];
+
+                                    #[cfg(feature = "tracing")]
+                                    let render_start = Instant::now();
+
+                                    renderer
+                                        .render_to_surface(
+                                            &device_handle.device,
+                                            &device_handle.queue,
+                                            &scene,
+                                            &texture,
+                                            &RenderParams {
+                                                base_color: Color::BLACK,
+                                                width: render_surface.config.width,
+                                                height: render_surface.config.height,
+                                                antialiasing_method: AaConfig::Msaa16,
+                                            },
+                                        )
+                                        .unwrap();
+
+                                    texture.present();
+                                    device_handle.device.poll(wgpu::Maintain::Poll);
+
+                                    #[cfg(feature = "tracing")]
+                                    frame_span.record(
+                                        "render_time",
+                                        tracing::field::debug(render_start.elapsed()),
+                                    );
+                                }
+                                #[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+                                State::Software(surface) => {
+                                    // Draw the recorded fragment of each canvas on the CPU, scaled to physical pixels.
+                                    surface
+                                        .renderer
+                                        .set_transform(Affine::scale(window_cx.scale_factor.get()));
+                                    window_cx.draw(&mut surface.renderer, layout_cx.parent_id);
+                            
+                                    if let Err(_error) = surface.present() {
+                                        #[cfg(feature = "tracing")]
+                                        tracing::error!("Failed to present software frame: {_error}");
+                                    }
+                                }
+                            }
 
-                            let texture =
-                                state.render_surface.surface.get_current_texture().unwrap();
-                            let mut scene = window_cx.scene.borrow_mut();
-                            let device_handle =
-                                &render_cx.borrow().devices[state.render_surface.dev_id];
-
-                            state
-                                .renderer
-                                .render_to_surface(
-                                    &device_handle.device,
-                                    &device_handle.queue,
-                                    &scene,
-                                    &texture,
-                                    &RenderParams {
-                                        base_color: Color::BLACK,
-                                        width: window.inner_size().width,
-                                        height: window.inner_size().height,
-                                        antialiasing_method: AaConfig::Msaa16,
-                                    },
-                                )
-                                .unwrap();
-
-                            texture.present();
-                            device_handle.device.poll(wgpu::Maintain::Poll);
-
-                            scene.reset();
-                            scene.fill(
-                                Fill::NonZero,
-                                Affine::default(),
-                                window_cx.base_color.get(),
-                                None,
-                                &Rect::new(
-                                    0.,
-                                    0.,
-                                    window.inner_size().width as _,
-                                    window.inner_size().height as _,
-                                ),
-                            );
+                            window_cx.tick(Instant::now());
                         }
                         _ => {}
                     },
                     _ => {}
                 }
 
+                let size = event_loop::surface_size(window)
+                    .to_logical::<f64>(window_cx.scale_factor.get());
+                window_cx.set_size(Vec2::new(size.width, size.height));
+
+                window_cx.focus.apply_pending(&window_cx.listeners.borrow());
+
+                window_cx.update_scrolls();
+
+                let is_accessibility_changed = window_cx.is_changed.get()
+                    || window_cx.is_layout_changed.get()
+                    || last_focus.replace(window_cx.focus.focused()) != window_cx.focus.focused();
+
+                if window_cx.is_layout_changed.take() {
+                    #[cfg(feature = "tracing")]
+                    let _span = tracing::trace_span!("layout").entered();
+                    #[cfg(feature = "tracing")]
+                    let layout_start = Instant::now();
+
+                    window_cx.compute_layout(layout_cx.parent_id);
+
+                    #[cfg(feature = "tracing")]
+                    layout_time.set(layout_start.elapsed());
+                }
+                window_cx.update_layers();
+
                 if window_cx.is_changed.take() {
                     window.request_redraw();
 
-                    for f in window_cx.canvas_update_fns.borrow().values() {
-                        f()
-                    }
+                    window_cx.update_fragments();
                 }
 
-                if window_cx.is_layout_changed.take() {
-                    window_cx
-                        .taffy
-                        .borrow_mut()
-                        .compute_layout(layout_cx.parent_id, Size::MAX_CONTENT)
-                        .unwrap();
+                window_cx.update_ime(window);
+
+                if is_accessibility_changed {
+                    if let Some(adapter) = &mut *adapter.borrow_mut() {
+                        adapter.update_if_active(|| {
+                            window_cx.accessibility_update(layout_cx.parent_id)
+                        });
+                    }
                 }
             },
             Ref::map(cx.me(), |me| &me.content),
@@ -311,26 +608,218 @@ impl<C: Compose> Compose for Window<C> {
     }
 }
 
-fn hit_test(taffy: &TaffyTree, pos: Vec2, layout_cx: &LayoutContext) -> Option<NodeId> {
-    let mut keys = vec![(Vec2::default(), layout_cx.parent_id)];
+/// Create a renderer for a new render `surface`, storing both in `state`.
+///
+/// If no device can render `window`, it's drawn on the CPU with the `software` feature,
+/// and otherwise keeps running without drawing.
+#[cfg_attr(
+    not(all(feature = "software", not(target_arch = "wasm32"))),
+    allow(unused_variables)
+)]
+fn set_state(
+    render_cx: &RefCell<RenderContext>,
+    state: &RefCell<Option<State>>,
+    window: &winit::window::Window,
+    surface: Result<RenderSurface<'_>, vello::Error>,
+) {
+    let result = surface.and_then(|surface| {
+        let renderer = ui::create_renderer(
+            &render_cx.borrow().devices[surface.dev_id].device,
+            Some(surface.format),
+            vello::AaSupport::all(),
+        )?;
+        Ok((surface, renderer))
+    });
+
+    let (surface, renderer) = match result {
+        Ok(state) => state,
+        Err(_error) => {
+            #[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+            match SoftwareSurface::new(window) {
+                Ok(surface) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        "Failed to create GPU renderer ({_error}), falling back to software rendering"
+                    );
+
+                    *state.borrow_mut() = Some(State::Software(surface));
+                    return;
+                }
+                Err(_software_error) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("Failed to create software surface: {_software_error}");
+                }
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::error!("Failed to create renderer: {_error}");
+
+            return;
+        }
+    };
+
+    // Safety: render_surface is valid for the lifetime of the window.
+    let render_surface: RenderSurface<'static> = unsafe { mem::transmute(surface) };
+    *state.borrow_mut() = Some(State::Gpu {
+        render_surface,
+        renderer: Box::new(renderer),
+    })
+}
+
+/// Get the safe area insets of `window`, in logical pixels.
+#[cfg_attr(not(target_os = "android"), allow(unused_variables))]
+fn window_safe_area(
+    window: &winit::window::Window,
+    event_loop_cx: &EventLoopContext,
+    scale_factor: f64,
+) -> SafeArea {
+    // The inner rectangle of a window on iOS is its safe area.
+    #[cfg(target_os = "ios")]
+    {
+        let (Ok(inner_pos), Ok(outer_pos)) = (window.inner_position(), window.outer_position())
+        else {
+            return SafeArea::default();
+        };
+        let inner_size = window.inner_size();
+        let outer_size = window.outer_size();
+
+        let left = (inner_pos.x - outer_pos.x) as f64;
+        let top = (inner_pos.y - outer_pos.y) as f64;
+        SafeArea {
+            top: top / scale_factor,
+            right: (outer_size.width as f64 - inner_size.width as f64 - left) / scale_factor,
+            bottom: (outer_size.height as f64 - inner_size.height as f64 - top) / scale_factor,
+            left: left / scale_factor,
+        }
+    }
+
+    // The content rectangle of an Android app excludes the system bars.
+    #[cfg(target_os = "android")]
+    {
+        let Some(app) = event_loop_cx.android_app() else {
+            return SafeArea::default();
+        };
+        let rect = app.content_rect();
+        let size = window.inner_size();
+
+        // The content rectangle is empty until the window is shown.
+        if rect.right <= rect.left || rect.bottom <= rect.top {
+            return SafeArea::default();
+        }
+
+        SafeArea {
+            top: rect.top as f64 / scale_factor,
+            right: (size.width as f64 - rect.right as f64) / scale_factor,
+            bottom: (size.height as f64 - rect.bottom as f64) / scale_factor,
+            left: rect.left as f64 / scale_factor,
+        }
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    SafeArea::default()
+}
+
+/// Reconfigure the render surface of `state` (if created) to the physical `size` of its window.
+fn resize_surface(
+    render_cx: &RefCell<RenderContext>,
+    state: &RefCell<Option<State>>,
+    size: PhysicalSize<u32>,
+) {
+    // Surfaces can't be configured with a zero size, such as while the window is minimized.
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+
+    match &mut *state.borrow_mut() {
+        Some(State::Gpu { render_surface, .. })
+            if (render_surface.config.width, render_surface.config.height)
+                != (size.width, size.height) =>
+        {
+            render_cx
+                .borrow()
+                .resize_surface(render_surface, size.width, size.height);
+        }
+        #[cfg(all(feature = "software", not(target_arch = "wasm32")))]
+        Some(State::Software(surface)) => {
+            if let Err(_error) = surface.resize(size) {
+                #[cfg(feature = "tracing")]
+                tracing::error!("Failed to resize software surface: {_error}");
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Find the deepest node containing `pos`, in the topmost layer containing it.
+///
+/// While a modal layer is open, the content below it is never hit.
+pub(crate) fn hit_test(
+    window_cx: &WindowContext,
+    pos: Vec2,
+    layout_cx: &LayoutContext,
+) -> Option<NodeId> {
+    for (root, origin, is_modal) in window_cx.layers.hit_order() {
+        if let Some(key) = hit_test_tree(window_cx, pos, root, origin) {
+            return Some(key);
+        }
+
+        if is_modal {
+            return Some(root);
+        }
+    }
+
+    hit_test_tree(window_cx, pos, layout_cx.parent_id, Vec2::ZERO)
+}
+
+/// Find the deepest node containing `pos` in the tree starting at `root`, positioned at `origin`.
+///
+/// Nodes are tested in their local coordinates, so transformed nodes are hit where they're drawn.
+fn hit_test_tree(
+    window_cx: &WindowContext,
+    pos: Vec2,
+    root: NodeId,
+    origin: Vec2,
+) -> Option<NodeId> {
+    let taffy = window_cx.taffy.borrow();
+    let scrolls = window_cx.scrolls.borrow();
+    let effects = window_cx.effects.borrow();
+
+    let mut keys = vec![(Affine::translate(origin), root)];
 
     let mut target = None;
 
-    while let Some((parent_pos, key)) = keys.pop() {
+    while let Some((parent_transform, key)) = keys.pop() {
+        if taffy.style(key).unwrap().display == Display::None {
+            continue;
+        }
+
         let layout = taffy.layout(key).unwrap();
-        if pos.x >= parent_pos.x + layout.location.x as f64
-            && pos.y >= parent_pos.y + layout.location.y as f64
-            && pos.x <= parent_pos.x + layout.location.x as f64 + layout.size.width as f64
-            && pos.y <= parent_pos.y + layout.location.y as f64 + layout.size.height as f64
+
+        let mut transform = parent_transform
+            * Affine::translate((layout.location.x as f64, layout.location.y as f64));
+        if let Some(effect) = effects.get(&key) {
+            transform *= effect.local_transform(layout.size);
+        }
+
+        let local_pos = transform.inverse() * pos.to_point();
+        if local_pos.x >= 0.
+            && local_pos.y >= 0.
+            && local_pos.x <= layout.size.width as f64
+            && local_pos.y <= layout.size.height as f64
         {
             target = Some(key);
 
-            keys.extend(taffy.children(key).unwrap().into_iter().map(|key| {
-                (
-                    parent_pos + Vec2::new(layout.location.x as _, layout.location.y as _),
-                    key,
-                )
-            }));
+            if let Some(scroll) = scrolls.get(&key) {
+                transform *= Affine::translate(-scroll.offset);
+            }
+
+            keys.extend(
+                taffy
+                    .children(key)
+                    .unwrap()
+                    .into_iter()
+                    .map(|key| (transform, key)),
+            );
         }
     }
 
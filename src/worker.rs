@@ -0,0 +1,173 @@
+use crate::{use_drop, use_memo, use_ref, use_signal, Memoize, Ref, ScopeState};
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex, OnceLock,
+    },
+    thread,
+};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Get the sender of jobs to the shared pool of worker threads, starting the pool on first use.
+fn pool() -> &'static mpsc::Sender<Job> {
+    static POOL: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+    POOL.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+
+        let len = thread::available_parallelism().map_or(4, |len| len.get());
+        for idx in 0..len {
+            let rx = rx.clone();
+            thread::Builder::new()
+                .name(format!("actuate-worker-{idx}"))
+                .spawn(move || loop {
+                    let Ok(job) = rx.lock().unwrap().recv() else {
+                        break;
+                    };
+
+                    // Keep this thread alive if a job panics, dropping its result.
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                })
+                .unwrap();
+        }
+
+        tx
+    })
+}
+
+/// Use the result of running `compute` with `input` on a background thread.
+///
+/// `compute` runs on a shared pool of worker threads whenever `input` changes (compared with [`Memoize`]),
+/// and its result is set as a state update, triggering an update to this composable.
+/// The returned value is `None` until the first result is ready,
+/// and keeps the last result while a new one is computed.
+///
+/// Computations superseded by a newer `input` are skipped if they haven't started,
+/// and their results are discarded otherwise.
+pub fn use_worker<'a, T, R>(
+    cx: ScopeState<'a>,
+    input: T,
+    compute: impl FnOnce(T) -> R + Send + 'static,
+) -> Ref<'a, Option<R>>
+where
+    T: Memoize + Clone + Send + 'static,
+    R: Send + 'static,
+{
+    let result = use_signal(cx, || None);
+    let generation = use_ref(cx, || Arc::new(AtomicU64::new(0)));
+    let last_input = use_ref(cx, || RefCell::new(None));
+
+    let memoized = input.clone().memoized();
+    if last_input.borrow().as_ref() != Some(&memoized) {
+        *last_input.borrow_mut() = Some(memoized);
+
+        let job_generation = generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let generation = generation.clone();
+        let result = result.clone();
+
+        pool()
+            .send(Box::new(move || {
+                if generation.load(Ordering::SeqCst) != job_generation {
+                    return;
+                }
+
+                let output = compute(input);
+                let is_current = move || generation.load(Ordering::SeqCst) == job_generation;
+                if is_current() {
+                    result.update(move |result| {
+                        if is_current() {
+                            *result = Some(output);
+                        }
+                    });
+                }
+            }))
+            .unwrap();
+    }
+
+    // Skip pending computations once this composable is dropped.
+    use_drop(cx, || {
+        generation.fetch_add(1, Ordering::SeqCst);
+    });
+
+    use_memo(cx, result.clone(), || result.lock().take())
+}
+
+#[cfg(all(test, feature = "rt"))]
+mod tests {
+    use crate::{
+        composer::{testing::ChannelUpdater, Composer},
+        prelude::*,
+    };
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+        sync::{mpsc, Arc, Mutex},
+        time::Duration,
+    };
+
+    #[test]
+    fn it_computes_on_workers() {
+        #[derive(Data)]
+        struct A {
+            input: Rc<Cell<u64>>,
+            gate: Arc<Mutex<mpsc::Receiver<()>>>,
+            values: Rc<RefCell<Vec<Option<u64>>>>,
+        }
+
+        impl Compose for A {
+            fn compose(cx: Scope<Self>) -> impl Compose {
+                let gate = cx.me().gate.clone();
+                let output = use_worker(&cx, cx.me().input.get(), move |input| {
+                    // Wait for the test to let this computation finish.
+                    gate.lock().unwrap().recv().unwrap();
+                    input * 2
+                });
+                cx.me().values.borrow_mut().push(*output);
+
+                cx.set_changed();
+            }
+        }
+
+        let input = Rc::new(Cell::new(1));
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let (gate_tx, gate_rx) = mpsc::channel();
+        let (updater, rx) = ChannelUpdater::new();
+        let mut composer = Composer::with_updater(
+            A {
+                input: input.clone(),
+                gate: Arc::new(Mutex::new(gate_rx)),
+                values: values.clone(),
+            },
+            updater,
+        );
+
+        // Apply each update from the workers until the expected result is composed.
+        let compose_until = |composer: &mut Composer, value| {
+            while values.borrow().last() != Some(&Some(value)) {
+                let update = rx
+                    .recv_timeout(Duration::from_secs(10))
+                    .expect("worker result");
+                update.apply();
+                composer.compose();
+            }
+        };
+
+        composer.compose();
+        gate_tx.send(()).unwrap();
+        compose_until(&mut composer, 2);
+
+        // Supersede the computation for `2` before letting it finish.
+        input.set(2);
+        composer.compose();
+        input.set(3);
+        composer.compose();
+        gate_tx.send(()).unwrap();
+        gate_tx.send(()).unwrap();
+        compose_until(&mut composer, 6);
+        assert!(!values.borrow().contains(&Some(4)));
+    }
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "rt")]
+
+use actuate::{composer::Composer, prelude::*};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[test]
+fn it_composes_derived_enums() {
+    #[allow(dead_code)]
+    #[derive(Data)]
+    enum A<'a, T>
+    where
+        T: Clone,
+    {
+        Count { x: Rc<Cell<i32>>, label: &'a str },
+        Value(T),
+        Empty,
+    }
+
+    impl<T> Compose for A<'_, T>
+    where
+        T: Clone + Data,
+    {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            if let A::Count { x, label } = &*cx.me() {
+                x.set(x.get() + label.len() as i32);
+            }
+        }
+    }
+
+    #[derive(Data)]
+    struct Wrap(Rc<Cell<i32>>);
+
+    impl Compose for Wrap {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let a: A<i32> = A::Count {
+                x: cx.me().0.clone(),
+                label: "count",
+            };
+            a
+        }
+    }
+
+    let x = Rc::new(Cell::new(0));
+    let mut composer = Composer::new(Wrap(x.clone()));
+
+    composer.compose();
+    assert_eq!(x.get(), 5);
+}
+
+#[test]
+fn it_skips_recomposes_with_unchanged_memo_fields() {
+    #[memo]
+    #[derive(Data)]
+    struct B {
+        x: i32,
+        count: Rc<Cell<i32>>,
+    }
+
+    impl Compose for B {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            cx.me().count.set(cx.me().count.get() + 1);
+        }
+    }
+
+    #[derive(Data)]
+    struct A {
+        x: Rc<Cell<i32>>,
+        count: Rc<Cell<i32>>,
+    }
+
+    impl Compose for A {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            cx.set_changed();
+
+            B {
+                x: cx.me().x.get(),
+                count: cx.me().count.clone(),
+            }
+        }
+    }
+
+    let x = Rc::new(Cell::new(0));
+    let count = Rc::new(Cell::new(0));
+    let mut composer = Composer::new(A {
+        x: x.clone(),
+        count: count.clone(),
+    });
+
+    composer.compose();
+    assert_eq!(count.get(), 1);
+
+    composer.compose();
+    assert_eq!(count.get(), 1);
+
+    x.set(1);
+    composer.compose();
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn it_composes_function_composables() {
+    #[composable]
+    fn counter(cx: ScopeState, start: i32, values: Rc<RefCell<Vec<i32>>>) -> impl Compose {
+        let count = use_mut(cx, || start);
+        values.borrow_mut().push(*count);
+
+        if *count < start + 2 {
+            Mut::update(count, |count| *count += 1);
+        }
+    }
+
+    #[composable]
+    fn app(_cx: ScopeState, values: Rc<RefCell<Vec<i32>>>) -> impl Compose {
+        Counter { start: 1, values }
+    }
+
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let mut composer = Composer::new(App {
+        values: values.clone(),
+    });
+
+    composer.compose();
+    composer.compose();
+    composer.compose();
+    assert_eq!(*values.borrow(), [1, 2, 3]);
+}
+
+#[test]
+fn it_clones_bindings_into_callbacks() {
+    #[derive(Data)]
+    struct A {
+        values: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl Compose for A {
+        fn compose(cx: Scope<Self>) -> impl Compose {
+            let values = cx.me().values.clone();
+            let prefix = String::from("x");
+
+            let push = clone!(values, prefix as p => move |value: i32| {
+                values.borrow_mut().push(format!("{p}{value}"))
+            });
+            push(1);
+
+            let f = callback!(&cx, values, prefix => move |value: i32| {
+                values.borrow_mut().push(format!("{prefix}{value}"))
+            });
+            f(2);
+
+            // The original bindings are still available.
+            values.borrow_mut().push(prefix);
+        }
+    }
+
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let mut composer = Composer::new(A {
+        values: values.clone(),
+    });
+
+    composer.compose();
+    assert_eq!(*values.borrow(), ["x1", "x2", "x"]);
+}
@@ -0,0 +1,8 @@
+use actuate::prelude::*;
+
+#[derive(Data)]
+struct Escape<'a> {
+    f: Box<dyn Fn(&'a str) + 'a>,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use actuate::prelude::*;
+
+// Shadow `Cell` so diagnostics print the full path of `std::cell::Cell` with any set of features.
+#[allow(dead_code)]
+struct Cell;
+
+#[derive(Data)]
+struct A;
+
+impl Compose for A {
+    fn compose(cx: Scope<Self>) -> impl Compose {
+        let count = use_mut(&cx, || 0);
+
+        std::thread::scope(|s| {
+            s.spawn(move || Mut::update(count, |count| *count += 1));
+        });
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actuate::prelude::*;
+use std::rc::Rc;
+
+struct NotData;
+
+#[derive(Data)]
+struct Escape<'a> {
+    value: &'a Rc<NotData>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use actuate::prelude::*;
+use std::cell::RefCell;
+
+#[derive(Data)]
+struct Escape<'a> {
+    cell: RefCell<&'a str>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use actuate::prelude::*;
+use std::borrow::Cow as StdCow;
+
+#[derive(Data)]
+struct Label<'a> {
+    text: &'a str,
+    title: StdCow<'a, str>,
+    items: &'a [String],
+}
+
+#[derive(Data)]
+struct Wrap<'a, C> {
+    content: Map<'a, C>,
+    cow: Cow<'a, String>,
+}
+
+fn main() {}